@@ -0,0 +1,55 @@
+//! 占空比精度基准：在1个核心上以目标使用率运行一小段时间，统计工作线程的实际计算时间与目标的偏差
+//!
+//! 每次迭代通过库接口启动一次负载，运行 [`WINDOW`] 后停止；报告的“时间”是每个窗口内计算时间与
+//! `窗口 × 目标使用率` 之差的绝对值，越小说明占空比越贴近目标。每个目标固定运行 [`ITERATIONS`] 次：
+//!
+//! ```text
+//! cargo bench --bench duty_cycle
+//! ```
+//!
+//! 迭代次数是固定的，Criterion 提示无法在目标时间内完成采样的警告可以忽略。
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use criterion::{BenchmarkId, Criterion, SamplingMode, criterion_group, criterion_main};
+use enhance_cpu_memory::LoadConfig;
+
+/// 每次迭代运行负载的时长
+const WINDOW: Duration = Duration::from_millis(200);
+
+/// 每个目标使用率的迭代次数
+const ITERATIONS: usize = 100;
+
+/// 运行一个窗口，返回计算时间与目标的偏差
+fn window_error(percent: f64) -> Duration {
+    let config = LoadConfig::new().cores(1).unwrap().cpu_percent(percent).unwrap().build().unwrap();
+    let handle = config.start().expect("无法启动负载");
+    let started = Instant::now();
+    thread::sleep(WINDOW);
+    // 停止请求发出后工作线程不再计算，等待其余线程退出的时间不计入窗口
+    let elapsed = started.elapsed();
+    let stats = handle.stop();
+    let target = elapsed.as_secs_f64() * percent / 100.0;
+    Duration::from_secs_f64((stats.total_cpu_seconds - target).abs())
+}
+
+fn duty_cycle(c: &mut Criterion) {
+    let mut group = c.benchmark_group("duty_cycle");
+    // 返回的是偏差而不是耗时，不能让 Criterion 按耗时估算迭代次数：
+    // 预热和测量时间取最小值，使每个样本只迭代一次、共 ITERATIONS 个样本
+    group
+        .sampling_mode(SamplingMode::Flat)
+        .sample_size(ITERATIONS)
+        .warm_up_time(Duration::from_nanos(1))
+        .measurement_time(Duration::from_nanos(1));
+    for percent in [20.0, 50.0, 80.0] {
+        group.bench_with_input(BenchmarkId::from_parameter(percent), &percent, |b, &percent| {
+            b.iter_custom(|iters| (0..iters).map(|_| window_error(percent)).sum());
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, duty_cycle);
+criterion_main!(benches);
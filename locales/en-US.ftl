@@ -0,0 +1,789 @@
+# English messages, grouped by module; keep the IDs in sync with zh-CN.ftl
+
+## parse
+
+parse-invalid-duration = invalid duration: { $value }
+parse-invalid-duration-unit = invalid duration unit: { $unit }
+parse-duration-not-positive = duration must be greater than 0
+parse-invalid-number = invalid number: { $value }
+parse-fraction-out-of-range = value must be between 0.0 and 1.0
+parse-invalid-thread-override = invalid thread override: { $entry } (expected THREAD:PERCENT)
+parse-invalid-thread-index = invalid thread index: { $value }
+parse-invalid-percent = invalid percentage: { $value }
+parse-percent-out-of-range = percentage must be between 0 and 100: { $value }
+parse-duplicate-thread = thread { $index } is specified more than once
+parse-invalid-size = invalid size: { $value }
+parse-invalid-rate = invalid rate: { $value }
+parse-invalid-regex = invalid regular expression: { $error }
+parse-invalid-schedule = invalid load schedule entry: { $entry } (expected work=<duration>, pause=<duration> or repeat=<count>)
+parse-schedule-incomplete = load schedule needs both work and pause: { $value }
+parse-cpu-base-zero = --cpu-base must not be 0; use -c 0 for a memory-only load
+cpu-base-clamped = Warning: --cpu-base { $value } exceeds 100, using 100
+
+## commands
+
+stop-stopping = Stopping the CPU load process (PID: { $pid })...
+stop-stopped = CPU load stopped (PID: { $pid }, terminated with { $how }, exit confirmed)
+stop-not-running = No running CPU load process found
+ctl-connect-failed = cannot connect to control socket { $path }: { $error }
+ctl-unsupported = the control socket is only supported on Unix
+sync-server-failed = sync server failed: { $error }
+start-already-running = An instance is already running (PID: { $pid }). Use the 'stop' command to stop it, or pass a different --instance-name to run another instance alongside it
+pid-save-failed = Warning: cannot save the PID file: { $error }
+instance-name-invalid = Invalid instance name "{ $name }": only letters, digits, -, _ and . are allowed, and it must not start with .
+list-empty = No instances found
+list-column-name = NAME
+list-column-state = STATE
+list-state-running = running
+list-state-stale = exited (stale PID file)
+list-state-corrupted = corrupted PID file
+stop-stale = Warning: process { $pid } recorded in the PID file no longer exists (it may have exited or its PID was reused), removed the PID file
+stop-force-kill = Warning: process { $pid } did not exit within { $secs } seconds, killing it
+stop-timed-out = process { $pid } did not exit within { $secs } seconds, kept the PID file; use --force to kill it or --timeout to wait longer
+stop-foreign = Warning: PID { $pid } is not an instance of this program (invalid PID file or PID reused), not signalling it and removing the PID file
+signal-refused-pid = refusing to signal PID { $pid }
+
+## start
+
+start-alloc-fail-injection = Allocation failure injection enabled: probability { $rate }, only for allocations larger than { $size } bytes
+error-prefixed = Error: { $error }
+start-stopping = Stopping the system load...
+start-workload-only = Starting { $workload } load without compute threads
+start-auto-cores = Starting CPU load with automatic core count (up to { $cores }), target system CPU usage { $target }%
+start-cores = Starting CPU load on { $cores } cores
+start-hybrid-warning = Warning: CPUs { $efficiency } on this hybrid CPU are efficiency cores; using { $cores } cores exceeds the { $performance } performance cores, so the load will be uneven
+start-target = Target CPU usage: { $target }%
+start-target-noise = Target CPU usage: { $target }% ±{ $noise }%
+disk-file-created = Created disk fill file: { $path }
+disk-file-create-failed = Warning: failed to create the disk fill file: { $error }
+disk-invalid-size = Warning: invalid disk size, no file will be created
+memory-percent-of-total = Using { $percent }% of total system memory
+memory-allocating = Allocating memory: { $size }
+memory-oom = memory allocation failed: requested { $requested } GiB but only { $available } GiB is available
+memory-invalid-size = invalid memory size: { $error }
+notify-fd-unsupported = Warning: --notify-fd is only supported on Unix and will be ignored
+background-starting = Running in the background; use the 'stop' command to stop it
+background-pipe-failed = cannot create the readiness pipe: { $error }
+background-ready = Background process is ready
+background-exited = Error: the background process exited before becoming ready
+background-timed-out = Error: the background process was not ready within { $secs } seconds
+background-fork-failed = cannot fork to run in the background
+background-pid-save-failed = Warning: cannot save the PID file in the background process: { $error }
+background-parent-exit = Parent exiting, child (PID: { $pid }) runs in the background
+background-windows-unsupported = Warning: background mode (-b) is not supported on this platform; continuing in the foreground.
+mlock-locked = Locked { $locked } of { $requested } requested
+mlock-failed = Warning: cannot lock memory (RLIMIT_MEMLOCK may be exceeded): { $error }
+thread-overrides-unused = Warning: thread indices { $threads } exceed the worker count ({ $cores }); those overrides are ignored
+rate-limit = Rate limit: { $rate } iterations/s in total, { $per_thread }/s per thread
+perf-disabled = Warning: hardware performance counters will not be collected
+worker-perf-failed = Warning: worker thread { $thread } cannot open hardware performance counters (CAP_PERFMON may be required): { $error }
+worker-started-target = Started worker thread { $thread } (target { $percent }%)
+worker-started = Started worker thread { $thread }
+worker-rt-failed = Warning: worker thread { $thread } cannot use a real-time scheduling policy and runs at normal priority: { $error }
+worker-affinity-failed = Warning: worker thread { $thread } cannot be pinned to CPU { $cpu }: { $error }
+sync-wait-failed = Error: waiting for the sync start signal failed: { $error }
+burst-mode = Burst mode: { $count } cycles, each { $on }s of load and { $off }s idle
+control-started = Control socket listening on { $path }
+control-start-failed = Warning: cannot start the control socket { $path }: { $error }
+control-unsupported = Warning: --control-socket is only supported on Unix
+compress-settings = zstd compression load: level { $level }, block size { $block }
+udp-start-failed = Warning: cannot start the UDP loopback load: { $error }
+diskio-start-failed = Warning: cannot start the disk I/O load: { $error }
+memstress-prefetch-stride = Detected CPU vendor { $vendor }, using a prefetch-defeating stride of { $stride } bytes
+duration-reached = Load duration reached, stopping the load...
+cycles-reached = All worker threads completed the requested iterations, stopping the load...
+perf-summary = Hardware counter summary: { $metrics }
+perf-none-opened = Warning: no worker thread opened the hardware performance counters
+measurement-started = [POST-LOAD MEASUREMENT] Load stopped, monitoring for another { $secs } seconds
+disk-file-cleanup-failed = Warning: failed to remove the disk fill file: { $error }
+interleave-unsupported = Warning: --cpu-affinity-interleave is only supported on Linux
+interleave-header = Worker thread affinity (interleaved across sockets):
+interleave-entry = thread { $thread } -> CPU { $cpu } (socket { $package }, core { $core })
+ht-siblings-not-found = Warning: no hyper-thread siblings found (SMT disabled or not Linux); --hyper-threads-only will not pin threads
+ht-siblings = Running only on hyper-thread siblings: CPUs { $cpus } ({ $count } in total)
+ht-siblings-shared = Warning: { $workers } worker threads exceed the { $count } hyper-thread siblings, so some siblings will run several threads
+numa-node-invalid = NUMA node { $node } does not exist or has no CPUs
+numa-memory-bound = Memory allocations bound to NUMA node { $node }
+numa-cpus-bound = All threads bound to the CPUs of NUMA node { $node }: { $cpus }
+numa-bind-failed = Warning: failed to bind to NUMA node { $node }: { $error }
+numa-weights-invalid = Invalid NUMA weight: { $value } (expected node:percent, e.g. 0:70)
+numa-weights-sum = --numa-weights percentages sum to { $total }%, must be 100%
+numa-weights-duplicate = NUMA node { $node } appears more than once in --numa-weights
+numa-weights-node-missing = NUMA node { $node } does not exist
+numa-weights-bind-failed = Warning: failed to bind memory to NUMA nodes by weight: { $error }
+numa-weights-report-unavailable = Warning: cannot read /proc/self/numa_maps, per-node allocation not reported
+numa-weights-header = Memory allocation per NUMA node (requested / achieved):
+numa-weights-entry = Node { $node }: { $requested }% / { $achieved }% ({ $size })
+numa-weights-unexpected-node = Warning: node { $node } also holds { $size } of the memory but is not in --numa-weights
+numa-latency-unsupported = Cannot read the NUMA nodes with memory: { $error }
+numa-latency-bind-failed = Failed to bind memory to NUMA node { $node }: { $error }
+numa-latency-same-node = The remote node must differ from the local node
+numa-latency-single-node = This machine has only one NUMA node with memory, nothing to compare
+numa-latency-started = NUMA latency test: local node { $local }, remote node { $remote }, { $size } per node
+numa-latency-local = Local latency (node { $node }): { $ns } ns
+numa-latency-remote = Remote latency (node { $node }): { $ns } ns
+numa-latency-factor = NUMA factor (remote/local): { $factor }
+numa-latency-copy-local = Local-to-local copy bandwidth: { $bandwidth } GB/s
+numa-latency-copy-remote = Remote-to-local copy bandwidth: { $bandwidth } GB/s ({ $percent }% of local)
+numa-coverage-unavailable = Warning: cannot read NUMA nodes or /proc/self/numa_maps; --ensure-numa-coverage has no effect
+numa-coverage-added = NUMA node { $node } had no pages; allocated { $size } on it
+numa-coverage-failed = Warning: failed to allocate memory on NUMA node { $node }: { $error }
+numa-coverage-node = node { $node }: { $percent }%
+numa-coverage-distribution = NUMA page distribution: { $nodes }
+cpu-affinity-invalid = Invalid CPU list: { $value } (e.g. "0,2,4", "4-7")
+cpu-affinity-out-of-range = CPU { $cpu } in --cpu-affinity does not exist ({ $count } logical CPUs)
+cpu-affinity-duplicate = CPU { $cpu } appears more than once in --cpu-affinity
+cpu-affinity-cores-mismatch = --cores { $cores } does not match the { $count } CPUs in --cpu-affinity
+cpu-affinity-unsupported = Warning: --cpu-affinity is not supported on this platform, worker threads are not pinned
+cpu-affinity-pinned = Pinning worker threads to CPUs in order: { $cpus }
+background-spawn-failed = Failed to start background child process: { $error }
+thread-overrides-cores-raised = Warning: --thread-overrides (--core-loads) lists thread indices beyond --cores ({ $cores }); using { $count } worker threads instead
+schedule-mode = Load schedule: { $work }s of load and { $pause }s of pause per cycle, { $repeat } cycles
+schedule-repeat-forever = unlimited
+background-log-file = Background output goes to { $path }
+background-log-open-failed = Warning: cannot open the background log file { $path }; background output will be discarded: { $error }
+control-path-not-socket = { $path } already exists and is not a socket, refusing to remove it
+ctrlc-handler-failed = cannot set the Ctrl-C handler: { $error }
+
+## status
+
+status-system-header = System information:
+status-cpu-header = CPU:
+status-core-count = Total cores: { $count }
+status-core-usage = Socket { $package }, core { $core }: [{ $usages }]
+status-cpu-usage = Core #{ $cpu }: { $usage }%
+status-avg-cpu = Average CPU usage: { $usage }%
+status-memory-header = Memory:
+status-memory-total = Total memory: { $size } GB
+status-memory-used = Used memory: { $size } GB
+status-memory-available = Available memory: { $size } GB
+status-memory-usage = Memory usage: { $percent }%
+status-disk-header = Disk I/O:
+status-no-disks = No matching disks
+status-network-header = Network:
+status-no-networks = No matching network interfaces
+self-monitor-header = Worker duty cycles:
+self-monitor-thread = Thread { $thread }: target { $target }%, actual { $actual }%, deviation { $deviation }, total iterations { $iterations }
+self-monitor-deviation = Warning: the actual duty cycle of thread { $thread } deviates from the target by { $deviation } percentage points
+status-host-name = Hostname: { $name }
+status-os = OS: { $name } { $version }
+status-kernel = Kernel: { $version }
+status-uptime = Uptime: { $uptime } (booted at { $boot })
+status-physical-memory = Physical memory: { $size } GiB
+status-cpu-brand = CPU model: { $brand }
+status-cpu-steal = CPU steal time: { $percent }%
+
+## benchmark
+
+benchmark-header = Benchmark results:
+benchmark-threads = Worker threads: { $threads }, iterations per thread: { $limit }
+benchmark-stopped-early = Warning: the load was stopped early, only { $total } iterations completed
+benchmark-elapsed = Elapsed: { $secs } s
+benchmark-score = Score: { $score } iterations/s
+spawn-failed = Creation failed ({ $method }): { $error }
+spawn-fork-unsupported = --method fork is only supported on Unix
+calibration-running = Measuring { $workload } ({ $secs } s)...
+calibration-unit-iteration = iterations
+calibration-unit-block = blocks
+calibration-unit-op = ops
+calibration-header = Single-thread throughput (by time per unit, highest first; { $baseline } = 1.0×):
+calibration-no-home = Warning: neither HOME nor XDG_CONFIG_HOME is set, calibration not saved
+calibration-saved = Calibration saved to { $path }
+calibration-save-failed = Warning: failed to save calibration to { $path }: { $error }
+
+## autocores
+
+autocores-adjusted = Auto cores: system CPU { $usage }% is { $direction ->
+    [below] below
+   *[above] above
+} the { $target }% ± { $band }% dead band, load { $before } → { $after } cores, { $active } worker threads active at { $percent }% duty cycle
+
+## burst
+
+burst-finished = All { $count } burst cycles completed, stopping the load...
+burst-transition = Burst cycle { $cycle }/{ $total }: { $state ->
+    [on] load started
+   *[off] idle started
+} (target { $percent }%)
+burst-summary-header = Burst summary ({ $completed }/{ $total } cycles completed):
+burst-summary-cycle = Cycle { $cycle }: average CPU { $on } during load, { $off } while idle
+burst-summary-incomplete = (incomplete)
+schedule-phase-changed = Load schedule cycle { $cycle } entered { $phase } (target { $target }%)
+schedule-finished = All { $count } load schedule cycles completed, stopping the load...
+
+## compress
+
+compress-encode-failed = Error: worker thread { $thread } failed to compress: { $error }
+compress-decode-failed = Error: worker thread { $thread } failed to decompress: { $error }
+compress-throughput = Compression: { $rate } MiB/s, ratio { $ratio }
+
+## control
+
+control-accept-failed = Warning: the control socket failed to accept a connection: { $error }
+control-command = Control command: { $request }
+control-stop = Received a stop command on the control socket, stopping the system load...
+
+## cpufreq
+
+cpufreq-read-failed = Warning: cannot read the scaling governor of CPU { $cpu } (there may be no cpufreq driver): { $error }
+cpufreq-switching = CPU scaling governor: current { $current } -> target { $target }
+cpufreq-set-failed = Warning: cannot set the scaling governor of CPU { $cpu } (root is required): { $error }
+cpufreq-unsupported = Warning: --cpufreq-governor is only supported on Linux
+cpufreq-restore-failed = Warning: cannot restore scaling governor { $governor } on CPU { $cpu }: { $error }
+cpufreq-restored = Restored the scaling governor on { $count } CPUs
+turbo-status = Turbo Boost: { $status ->
+    [enabled] enabled
+    [disabled] disabled
+   *[unknown] unknown
+}
+turbo-switched = Turbo Boost { $enabled ->
+    [true] enabled
+   *[false] disabled
+} for the duration of the load
+turbo-not-found = Warning: no Turbo Boost control found (intel_pstate/no_turbo or cpufreq/boost), ignoring --turbo-boost
+turbo-set-failed = Warning: cannot change the Turbo Boost control { $path } (root is required): { $error }
+turbo-unsupported = Warning: --turbo-boost is only supported on Linux
+turbo-restore-failed = Warning: cannot restore the Turbo Boost control { $path }: { $error }
+turbo-restored = Restored the original Turbo Boost setting
+governor-profile-read-failed = Cannot read the governor profile { $file }: { $error }
+governor-profile-invalid = Invalid governor profile { $file }: { $error }
+governor-profile-not-object = the top level must be an object keyed by core index
+governor-profile-bad-core = "{ $core }" is not a valid core index
+governor-profile-bad-entry = the settings for core { $core } must be an object
+governor-profile-bad-freq = { $field } for core { $core } must be a positive integer (kHz)
+governor-profile-bad-governor = governor for core { $core } must be a string
+governor-profile-min-above-max = min_freq { $min } is greater than max_freq { $max } for core { $core }
+governor-profile-write-failed = Warning: cannot write { $path } (root and a cpufreq driver are required): { $error }
+governor-profile-applied = Applied the governor profile to { $cores } cores, { $files } settings written
+governor-profile-unsupported = Warning: --cpu-governor-profile is only supported on Linux
+governor-profile-restore-failed = Warning: cannot restore { $path } to { $value }: { $error }
+governor-profile-restored = Restored { $files } frequency settings
+freqtrace-started = Tracing CPU frequency changes on { $count } cores
+freqtrace-start-failed = Warning: cannot enable CPU frequency tracing (requires root and tracefs): { $error }
+freqtrace-unsupported = Warning: --cpufreq-report is only supported on Linux
+freqtrace-change = [{ $time }s] CPU { $cpu }: { $old } MHz -> { $new } MHz
+freqtrace-summary-header = CPU frequency summary:
+freqtrace-summary-core = CPU { $cpu }: avg { $avg } MHz, min { $min } MHz, max { $max } MHz, { $changes } changes
+freqtrace-summary-core-unknown = CPU { $cpu }: frequency unknown (scaling_cur_freq unreadable and no frequency events)
+freqtrace-no-events = No frequency change events were recorded; drivers with hardware-managed P-states such as intel_pstate may not emit cpu_frequency events
+freqtrace-histogram-header = Frequency distribution (share of core time):
+irq-needs-force = --irq-balance-disable moves all interrupts onto one CPU and may disrupt network performance on multi-queue NICs; pass --force to confirm
+irq-needs-root = Warning: --irq-balance-disable requires root, ignored
+irq-unsupported = Warning: --irq-balance-disable is only supported on Linux, ignored
+irq-no-free-cpu = Warning: every CPU runs load, none is free to handle interrupts; use fewer cores with CPU pinning
+irq-read-failed = Warning: failed to read /proc/irq: { $error }
+irq-nic-warning = Warning: all interrupts will be handled by a single CPU, network performance on multi-queue NICs may drop significantly
+irq-irqbalance-stopped = Stopped irqbalance
+irq-irqbalance-stop-failed = Warning: failed to stop irqbalance, interrupts may be redistributed
+irq-pinned = Pinned { $count } interrupts to CPU { $cpu } (mask { $mask }), { $skipped } could not be changed
+irq-restored = Restored affinity of { $count } interrupts, { $failed } failed
+irq-irqbalance-started = Restarted irqbalance
+irq-irqbalance-start-failed = Warning: failed to restart irqbalance, run systemctl start irqbalance manually
+status-core-out-of-range = --status-core { $core } is out of range, this machine has { $count } logical CPUs
+status-core-no-free-cpu = This machine has only 1 logical CPU and cannot reserve one for the status thread; add --allow-status-core-sharing
+status-core-async = Warning: with the async engine status refresh is a timer task and cannot be pinned, ignoring --status-core
+status-core-unsupported = Warning: CPU pinning is not supported on this platform, ignoring --status-core
+status-core-pinned = Status thread pinned to CPU { $cpu }
+status-core-failed = Warning: failed to pin the status thread to CPU { $cpu }: { $error }
+governor-aware-not-performance = Warning: CPU frequency governor is '{ $governor }'. CPU load measurements may be lower than expected. Consider using --cpufreq-governor performance.
+governor-aware-freq-range = Warning: CPU minimum frequency { $min } MHz is less than half of the maximum { $max } MHz; the CPU may run at a lower frequency during the load
+governor-aware-unavailable = Warning: cannot read the CPU frequency governor (no cpufreq driver?), skipping the --cpu-governor-aware check
+governor-aware-unsupported = Warning: --cpu-governor-aware is only supported on Linux
+
+## diskio
+
+diskio-stats = Disk I/O (depth { $depth }): { $iops } IOPS, latency p50/p95/p99 = { $p50 }/{ $p95 }/{ $p99 } µs, mean { $mean } µs, max { $max } µs
+diskio-failed = Warning: disk I/O failed, the I/O thread exits: { $error }
+diskio-started = Starting disk I/O load: { $path } (queue depth { $depth })
+diskio-summary = Disk I/O summary - { $stats }
+io-stress-started = Starting I/O stress: directory { $path }, { $threads } threads, pattern { $pattern }, block size { $block }, { $size } per file
+io-stress-create-failed = Warning: cannot create the I/O stress temp file in { $path }: { $error }
+io-stress-failed = Warning: I/O stress failed, the thread exits: { $error }
+io-stress-summary = I/O stress summary - { $ops } operations, average { $stats }
+io-stress-block-too-large = --block-size { $block } must be greater than 0 and no larger than --file-size { $size }
+
+## engine
+
+engine-tokio = Using the tokio async load engine
+engine-tokio-failed = Warning: cannot create the tokio runtime, falling back to system threads: { $error }
+engine-tokio-missing = Warning: --async requires building with the tokio feature (cargo build --features tokio), falling back to system threads
+
+## growth
+
+growth-started-rate = Memory growth: limit { $limit }, max rate { $rate }/s
+growth-started = Memory growth: limit { $limit }, unthrottled
+growth-paused = Warning: available system memory { $available } is below the safety margin { $margin }, pausing memory growth
+growth-resumed = Available system memory recovered to { $available }, resuming memory growth
+growth-alloc-failed = Warning: memory growth failed to allocate { $size }, stopping growth
+growth-progress = Memory growth: { $grown } grown, { $rate }/s
+growth-limit-reached = Memory growth reached the limit of { $limit }
+
+## hooks
+
+hook-running = Running { $name } hook: { $cmd }
+hook-spawn-failed = Warning: cannot run the { $name } hook: { $error }
+hook-timed-out = Warning: the { $name } hook ran longer than { $secs } seconds and was killed
+hook-wait-failed = Warning: waiting for the { $name } hook failed: { $error }
+hook-finished = The { $name } hook finished
+hook-failed = Warning: the { $name } hook exited with { $status }
+
+## json
+
+json-trailing-content = unexpected trailing content at byte { $pos }
+json-error-at = { $message } (at { $pos })
+json-invalid-literal = invalid literal
+json-unexpected-end = unexpected end of input
+json-invalid-number = invalid number
+json-unterminated-string = unterminated string
+json-invalid-escape = invalid escape sequence
+json-invalid-utf8 = string is not valid UTF-8
+json-array-expected = expected ',' or ']' in array
+json-key-not-string = object keys must be strings
+json-colon-expected = expected ':' in object
+json-object-expected = expected ',' or '{"}"}' in object
+json-too-deep = nesting deeper than { $max } levels
+
+## logger
+
+syslog-unsupported = Warning: --syslog is not supported on this platform, logs continue to go to the console
+log-file-open-failed = Warning: cannot open log file { $path }: { $error }
+log-file-write-failed = Warning: failed to write the log file: { $error }
+eventlog-open-failed = Warning: cannot open the Windows event log: { $error }, logs will keep going to the console
+syslog-open-failed = Warning: cannot connect to syslog: { $error }, logs will keep going to the console
+
+## memory
+
+memory-size-empty = memory size must not be empty
+memory-size-invalid-number = invalid number: { $value }
+memory-size-invalid-unit = invalid unit: { $unit } (supported: B, K, M, G, T, KiB, MiB, GiB, TiB or %)
+memory-size-percent-out-of-range = percentage must be greater than 0 and at most 100: { $percent }%
+memory-draining = Draining memory ({ $size } GiB left)...
+memory-drain-timed-out = Memory drain timed out, freeing the remaining { $size } GiB at once
+memory-prefault-progress = Prefaulting memory 
+memory-prefault-sequential = sequential writes
+memory-prefaulted = all pages prefaulted via { $method } in { $ms } ms
+memory-first-access = first access { $first } µs/page, second access { $again } µs/page, page faults cost about { $fault } µs/page
+objects-size-invalid = --memory-object-size is { $size }; it must be greater than 0 and at most the total allocation of { $total }
+objects-bench-header = Allocator overhead (average of { $count } consecutive allocations per size):
+objects-bench-size = { $size }: { $ns } ns per allocation
+objects-bench-failed = Warning: allocator benchmark allocation failed for { $size } objects; skipped
+objects-allocating = Allocating { $count } objects of { $size }...
+objects-allocated = Objects allocated in { $ms } ms
+compaction-needs-root = Warning: --memory-compaction requires root to write /proc/sys/vm/compact_memory; skipped
+compaction-unsupported = Warning: --memory-compaction is only supported on Linux
+compaction-failed = Warning: failed to trigger memory compaction: { $error }
+compaction-done = Memory compaction finished in { $ms } ms
+compaction-before = before
+compaction-during = during
+compaction-after = after
+compaction-phase = { $phase } ({ $count } probes): allocation mean/p99/max { $alloc_mean }/{ $alloc_p99 }/{ $alloc_max } µs, access mean/p99/max { $access_mean }/{ $access_p99 }/{ $access_max } µs
+compaction-fragmentation = Huge page fragmentation index: before { $before }, after { $after } (0-1, higher is more fragmented)
+compaction-thp = THP coverage: before { $before }%, after { $after }%
+config-no-cores = Core count must be at least 1
+config-percent-out-of-range = CPU percent must be in (0, 100], got { $percent }
+config-empty-memory = Memory size must be greater than 0
+config-zero-duration = Duration must be greater than 0
+config-too-many-cores = Core count { $cores } exceeds the { $cpus } CPUs on this machine
+config-memory-exceeds-total = Memory size { $memory } exceeds the total physical memory { $total }
+dealloc-allocating = Allocating { $count } chunks of { $size }, to be freed in { $pattern } order on exit
+dealloc-chunks-too-many = Chunk count { $count } is too large: { $total } is less than one byte per chunk
+dealloc-report = Freed { $count } chunks in { $pattern } order in { $ms } ms, per chunk mean { $mean } µs, p50/p99 = { $p50 }/{ $p99 } µs, max { $max } µs
+fragmentation-allocating = Allocating { $size } as random 64 B-4 KiB fragments, reallocating 10% of them every second
+fragmentation-allocated = Allocated { $count } fragments in { $ms } ms
+
+## memstress
+
+memstress-started = Memory access pattern: { $pattern }, stride { $stride } bytes, { $count } access threads{ $shared ->
+    [yes] {" "}(sharing the whole buffer)
+   *[no] {""}
+}
+memstress-rate = Memory access: { $rate } M/s, cache line bandwidth { $bandwidth } GB/s
+memstress-per-thread = Per-thread bandwidth (GB/s): { $bandwidths }
+memstress-prefetch = Prefetch training: stride { $train_stride } bytes { $train } GB/s, stride { $prime_stride } bytes { $prime } GB/s, ratio { $ratio }
+memstress-latency = synthetic latency calibration: requested { $requested } ns, effective { $effective } ns per access (including the access itself and timing overhead)
+bandwidth-started = Memory bandwidth test started: mode { $mode }, { $count } threads
+bandwidth-calibration = Peak calibration: read-only { $read } GB/s, write-only { $write } GB/s
+bandwidth-rate = Memory bandwidth ({ $mode }): { $bandwidth } GB/s
+bandwidth-efficiency = Memory controller efficiency: { $efficiency }% (theoretical peak { $peak } GB/s)
+
+## mlock
+
+mlock-unsupported = mlock is not supported on this platform
+
+## notify
+
+notify-sent = Sent the readiness notification on file descriptor { $fd }
+notify-failed = Warning: cannot write the readiness notification to file descriptor { $fd }: { $error }
+
+## pattern
+
+spike-period-too-short = the spike period ({ $every }) must be longer than the hold time plus the ramps ({ $spike })
+spike-started = CPU spike mode started: phase { $phase } (target { $percent }%)
+spike-phase-changed = CPU spike phase: { $from } -> { $to } (target { $percent }%)
+spike-trigger-missing = --cpu-spike-to requires --cpu-spike-every or --spike-on-signal
+spike-signal-ready = Signal spike ready: send SIGUSR1 to process { $pid } to trigger a spike
+spike-signal-triggered = Triggering spike #{ $count } (SIGUSR1 received at { $received }): target { $percent }%, hold { $hold }
+spike-signal-finished = Spike #{ $count } finished, back to { $percent }%
+spike-signal-queued = SIGUSR1 received during an active spike, queued ({ $queued } pending)
+spike-signal-discarded = Warning: { $max } spikes already queued, discarding this SIGUSR1
+spike-signal-install-failed = Warning: failed to install the SIGUSR1 handler, signal spikes are unavailable: { $error }
+spike-signal-unsupported = Warning: --spike-on-signal is only supported on Unix, ignored
+thermal-unavailable = Warning: cannot read the CPU temperature, thermal protection disabled
+thermal-started = Thermal protection enabled: lowering the target above { $max }°C, restoring below { $resume }°C
+thermal-throttled = Warning: CPU temperature { $temp }°C above the limit, target lowered to { $percent }%
+thermal-restored = CPU temperature back down to { $temp }°C, target restored to { $percent }%
+signal-adjust-ready = Signal adjustment enabled: kill -USR1 { $pid } to raise the load, kill -USR2 { $pid } to lower it, { $step } per signal
+signal-adjust-percent = Signal received, target CPU usage is now { $percent }%
+signal-adjust-cores = Signal received, active worker threads are now { $cores }/{ $max }
+signal-adjust-install-failed = Warning: cannot install SIGUSR1/SIGUSR2 handlers, signal adjustment disabled: { $error }
+signal-adjust-unsupported = Warning: --signal-adjust is only supported on Unix and is ignored
+signal-adjust-interruption-conflict = --signal-adjust cannot be combined with --workload interruption-test: both use SIGUSR2
+
+## perf
+
+perf-unknown-event = unknown hardware event: { $name } (supported: { $supported })
+perf-llc-miss-rate = LLC miss rate { $rate }%
+perf-branch-miss-rate = branch miss rate { $rate }%
+perf-paranoid-unreadable = Warning: cannot read /proc/sys/kernel/perf_event_paranoid
+perf-paranoid-unrestricted = unrestricted, all events allowed
+perf-paranoid-0 = CPU-wide events allowed, raw tracepoint data denied
+perf-paranoid-1 = CPU-wide events denied, per-process kernel and user events allowed
+perf-paranoid-2 = kernel profiling denied, only per-process user events allowed
+perf-paranoid-restricted = perf events denied to unprivileged users
+perf-paranoid-level = kernel.perf_event_paranoid = { $level } ({ $meaning })
+perf-paranoid-denied = Error: unprivileged users cannot open perf events at this level; run `sudo sysctl -w kernel.perf_event_paranoid=2` or grant CAP_PERFMON and retry
+perf-feature-missing = Warning: hardware performance counters require building with the perf feature (cargo build --features perf); counters will not be collected
+perf-unsupported = Warning: hardware performance counters are only supported on Linux; counters will not be collected
+
+## residency
+
+residency-checked = Memory residency: { $resident } / { $total } pages resident ({ $percent }%)
+residency-low = Warning: memory residency { $percent }% is below { $min }%, some pages may have been swapped out
+residency-failed = Warning: the memory residency check failed, stopping checks: { $error }
+residency-unsupported = mincore is not supported on this platform
+residency-willneed = Called madvise(MADV_WILLNEED) to prefetch memory pages
+residency-willneed-failed = Warning: madvise(MADV_WILLNEED) failed: { $error }
+
+## sched
+
+sched-needs-root = Warning: real-time scheduling requires root (or CAP_SYS_NICE); the current user probably cannot set the { $policy } policy
+sched-policy = Worker threads will use the real-time policy { $policy } at priority { $priority }
+sched-starvation = Warning: fully loaded real-time threads can starve normal processes on the same core
+sched-unsupported = Warning: real-time scheduling is not supported on this platform; worker threads run at normal priority
+
+## reset
+
+reset-force-kill = Warning: process { $pid } did not exit within { $secs } seconds, killing it
+reset-remove-failed = Warning: cannot remove { $path }: { $error }
+reset-done = Stopped { $stopped } processes gracefully, killed { $killed }, removed { $removed } PID files
+reset-skip-foreign = Warning: PID { $pid } is not an instance of this program (invalid PID file or PID reused), skipping it
+
+## status-diff
+
+status-diff-header = System changes (over { $secs } seconds):
+status-diff-cores = Per core: { $sparkline }
+status-diff-memory-stable = Memory is essentially unchanged
+status-diff-memory-growing = Memory growing at { $rate } MiB/s
+status-diff-memory-shrinking = Memory shrinking at { $rate } MiB/s
+status-watch-header = { $time }  sample { $sample }, refreshing every { $secs }s, press Ctrl+C to exit
+status-watch-memory = Memory: { $used } / { $total } GiB ({ $percent }%)
+status-watch-swap = Swap: { $used } / { $total } GiB ({ $percent }%)
+status-watch-no-swap = Swap: not enabled
+status-watch-handler-failed = Warning: failed to set the Ctrl-C handler: { $error }
+status-json-failed = Warning: failed to produce JSON output: { $error }
+
+## summary
+
+summary-no-samples = no samples
+summary-samples = Samples: { $count }
+summary-avg-cpu = Average CPU usage: { $usage }%
+summary-cpu-range = Min/max CPU usage: { $min }% / { $max }%
+summary-avg-memory = Average memory used: { $size }GB
+summary-header = Run summary:
+summary-load-phase = [LOAD]
+summary-histogram-header = CPU usage distribution:
+summary-cpu-cv = coefficient of variation: { $cv } (stddev { $stddev }%); lower means a steadier load
+summary-cpu-cv-idle = coefficient of variation: n/a (mean usage is 0)
+summary-avg-steal = Average CPU steal: { $percent }%
+summary-steal-inaccurate = Warning: average CPU steal { $percent }%; this run is possibly inaccurate due to high steal time
+
+## swap
+
+swap-disabled = Warning: swap is not enabled, --swap-pressure can only drop pages and cannot test swap-in or swap-out
+swap-too-small = Warning: free swap ({ $free }MB) is smaller than the memory to swap out ({ $size }MB); some pages cannot be swapped out
+swap-started = Swap pressure: { $pages } pages written, forcing them out
+swap-pageout-failed = Warning: madvise(MADV_PAGEOUT) failed ({ $error }), falling back to MADV_DONTNEED
+swap-dontneed-failed = Warning: madvise(MADV_DONTNEED) failed, stopping swap pressure: { $error }
+swap-bandwidth = Swap pressure: swap-in bandwidth { $rate } MB/s
+swap-vmstat = , si { $si } pages/s, so { $so } pages/s
+swap-unsupported = forcing pages out is not supported on this platform
+swap-exceeds-capacity = Warning: the requested memory ({ $size }) exceeds physical memory plus swap ({ $capacity }); the OOM killer may be triggered
+swap-alloc-progress = Swap pressure: allocated { $allocated } / { $total }
+swap-alloc-stopped = Warning: allocation failed, stopping at { $allocated } / { $total }
+
+## sync
+
+sync-server-started = Sync server listening on { $addr }, waiting for { $count } clients
+sync-client-ready = Client { $addr } is ready ({ $ready }/{ $count })
+sync-invalid-client-message = Warning: ignoring an invalid message from client { $addr }: { $message }
+sync-broadcast-failed = Warning: failed to send the start signal to client { $addr }: { $error }
+sync-broadcast = Broadcast the start signal to { $count } clients
+sync-connect-retry = Warning: cannot connect to the sync server { $addr } ({ $error }), retrying
+sync-connected = Connected to the sync server { $addr }, waiting for the start signal...
+sync-server-closed = the sync server closed the connection
+sync-unexpected-message = unexpected message: { $message }
+sync-go-received-latency = Received the start signal, sync latency { $latency } ms (waited { $waited } s)
+sync-go-received = Received the start signal (waited { $waited } s)
+sync-accept-failed = Warning: failed to accept a client connection: { $error }
+sync-client-read-failed = Warning: ignoring client { $addr }, failed to read its ready message: { $error }
+sync-client-disconnected = Warning: client { $addr } disconnected, waiting for another client
+
+## syscalls
+
+syscalls-rates = syscalls/s: { $syscalls }, context switches/s: { $switches }
+syscalls-perf-disabled = Warning: system calls will not be counted; context switches are read from /proc/self/task instead
+syscalls-trace-failed = Warning: cannot count system calls (a readable tracefs and CAP_PERFMON are required): { $error }
+syscalls-switches-failed = Warning: cannot open the context switch counter ({ $error }), reading /proc/self/task instead
+syscalls-unsupported = Warning: --trace-syscalls is only supported on Linux
+syscalls-invalid-tracepoint = invalid tracepoint ID
+
+## udpflood
+
+udp-started-rate = UDP loopback load: { $from } -> { $to }, limited to { $pps } packets/s
+udp-started = UDP loopback load: { $from } -> { $to }, unthrottled
+udp-stats = UDP: sent { $sent } packets/s, received { $received } packets/s, loss { $loss }%, mean one-way latency { $latency } µs
+
+## diskstats
+
+diskstats-rates = { $name } read { $read }MiB/s write { $write }MiB/s util { $utilization }%
+diskstats-wait = wait { $wait }ms
+
+## netstats
+
+netstats-errors = errors:{ $errors }
+netstats-detailed = { $rates } packets/s rx:{ $rx } tx:{ $tx }
+
+## output
+
+output-cpu-usage-target-noise = Current CPU usage: { $usage }% (target: { $target }% ±{ $noise }%)
+output-cpu-usage-target = Current CPU usage: { $usage }% (target: { $target }%)
+output-cpu-usage = Current CPU usage: { $usage }%
+output-burst-cycle = burst cycle { $cycle }
+output-disk = DISK
+output-memory-usage = Current memory usage: { $used }GB / { $total }GB (allocated: { $allocated }GB)
+output-memory-locked = (locked: { $locked }GB)
+output-table-phase = Phase
+output-table-cpu = CPU usage
+output-table-target = Target usage
+output-table-burst-cycle = Burst cycle
+output-table-perf = Hardware counters
+output-table-syscalls = System calls
+output-table-disk = Disk I/O
+output-table-network = Network
+output-table-memory = Memory usage
+output-table-allocated = Allocated memory
+output-table-locked = Locked memory
+output-table-pressure = Memory pressure
+output-thp-coverage = THP coverage: { $coverage }%
+output-table-thp = THP coverage
+output-cpu-steal = Steal: { $percent }%
+output-table-steal = CPU steal
+output-remaining = { $remaining } remaining
+output-table-remaining = Time remaining
+output-table-ramp = Ramp phase
+output-schedule-remaining = { $count } cycles left
+output-schedule-forever = repeating forever
+output-table-schedule = Schedule
+output-io-stress = { $iops } IOPS, { $mb } MB/s
+output-table-io-stress = I/O stress
+
+## main
+
+lang-unsupported = Warning: unsupported language { $lang } (supported: { $supported }), using the detected language
+random-seed = Random seed: { $seed } (rerun with --random-seed { $seed } to reproduce)
+
+## statefile
+
+statefile-write-failed = Warning: cannot write the state file { $path }: { $error }
+statefile-instance = Running instance: PID { $pid }, started { $started }, workload { $profile }, { $cores } cores, target CPU usage { $target }%, memory { $memory }
+
+## phases
+
+phases-length-mismatch = --phase-cores has { $cores } entries but --phase-durations has { $durations }; they must match one to one
+phases-all-idle = at least one phase in --phase-cores must use more than 0 cores
+phases-mode = Phased core mode: { $count } phases, { $workers } worker threads pre-spawned
+phases-transition = Phase { $phase }/{ $total }: { $cores } active worker threads for { $duration }
+phases-completed = All { $total } phases completed, stopping load
+timeline-written = Timeline written to { $path } ({ $count } events)
+timeline-hint = Open it at https://ui.perfetto.dev or chrome://tracing to view
+timeline-write-failed = Warning: failed to write timeline file { $path }: { $error }
+ramp-exceeds-duration = --ramp-up plus --ramp-down must not exceed --duration
+ramp-phase-up = ramping up
+ramp-phase-steady = steady
+ramp-phase-down = ramping down
+ramp-phase-changed = Entering phase: { $phase }
+phases-settings = Target CPU usage { $target }%, memory { $memory }
+phases-memory-failed = Warning: failed to allocate { $size } for the phase; it runs without memory
+
+## error
+
+pid-file-corrupted = PID file { $path } is corrupted; run reset to clean it up
+pid-file-corrupted-overwrite = Warning: PID file { $path } is corrupted and will be overwritten
+
+## balloon
+
+balloon-started = Memory balloon: up to { $max }, period { $period }, { $chunk } per chunk
+balloon-event = Memory balloon cycle { $cycle }: { $event ->
+    [inflate-start] inflation started
+    [inflate-done] inflation finished
+    [deflate-start] deflation started
+   *[deflate-done] deflation finished
+}, currently holding { $size }
+balloon-alloc-failed = Warning: memory balloon failed to allocate { $size }, inflation stopped for this cycle
+
+## profile
+
+profile-duration-zero = duration_secs of phase { $phase } must be greater than 0
+profile-cpu-out-of-range = target_percent of phase { $phase } must be between 0 and 100, got { $value }
+profile-cores-invalid = cores of phase { $phase } must be greater than 0, got { $value }
+profile-memory-invalid = memory of phase { $phase } is invalid: { $error }
+profile-workload-invalid = workload of phase { $phase } is invalid: { $value } (expected one of { $choices })
+profile-no-phases = the profile has no phases ([[phases]]); the total duration must be greater than 0
+profile-invalid = profile { $file } has { $count } errors
+profile-valid = Profile is valid: { $file }
+profile-valid-named = Profile is valid: { $file } ({ $name })
+profile-summary-phases = Phases: { $count }
+profile-summary-duration = Total duration: { $duration }
+profile-summary-cpu = Peak CPU usage: { $percent }%
+profile-summary-memory = Peak memory: { $memory }
+profile-read-failed = cannot read profile { $file }: { $error }
+profile-workload-mixed = Profile phases use different workloads; the workload cannot change while running
+profile-running = Running load profile { $file }
+profile-running-named = Running load profile { $file } ({ $name })
+
+## realtimelog
+
+realtime-log-started = Per-second CPU usage log: { $path } (buffer of { $capacity } entries)
+realtime-log-open-failed = Warning: cannot open the CPU usage log { $path }: { $error }
+realtime-log-write-failed = Warning: failed to write the CPU usage log { $path }: { $error }
+metrics-started = Writing metrics to { $path }
+metrics-open-failed = Cannot create metrics file { $path }: { $error }
+metrics-write-failed = Warning: failed to write metrics file, no longer writing: { $error }
+
+## ipc
+
+ipc-started = IPC stress: { $kind }, { $pairs } producer/consumer pairs, { $size }-byte messages
+ipc-stats = IPC stress ({ $kind }): { $messages } messages/s, { $rate } MiB/s
+ipc-start-failed = Warning: cannot start the IPC stress: { $error }
+ipc-unsupported = Warning: the ipc-stress workload is only supported on Unix
+
+## workload
+
+work-verify-baseline = Busy loop baseline rate: { $rate } iterations/s
+work-verify-optimized = Warning: thread { $thread } runs { $rate } iterations/s, more than 5x the baseline of { $baseline } iterations/s; the busy loop may have been optimized away, switching to volatile computation
+atomic-settings = Atomic workload: array length { $size }, operation { $op }
+atomic-stats = Atomic operations: { $mops } M/s
+atomic-stats-cas = Atomic operations: { $mops } M/s, CAS failure rate { $failed }%
+alloc-settings = Allocation churn: { $min } - { $max } per allocation, { $pool } live blocks per thread
+alloc-stats = Allocated: { $allocated }/s, freed: { $freed }/s, mean live: { $live }
+alloc-size-invalid = Invalid allocation size "{ $value }": { $error }
+alloc-size-zero = the size must be greater than 0
+alloc-size-reversed = The lower bound { $min } of the allocation size is greater than the upper bound { $max }
+work-mix-invalid = Invalid work mix entry: { $value } (expected kind:percent, e.g. matmul:50%)
+work-mix-unknown = Unknown work kind: { $value } (expected trig, math, nop, volatile, matmul or sha256)
+work-mix-sum = --cpu-work-mix percentages sum to { $total }%, must be 100%
+work-mix-assigned = Work kind { $kind } ({ $percent }%): { $threads } threads
+
+## yieldto
+
+yield-watching = Yield watchdog: lowering this process's priority while a process matching { $pattern } with a nice value below { $floor } is running
+yield-started = Found high-priority process { $name } (PID: { $pid }, nice { $nice }), set this process's nice value to { $own }
+yield-restored = The high-priority process has exited, restored nice value { $nice }
+yield-renice-failed = Warning: cannot set the nice value to { $nice } (raising priority requires root): { $error }
+yield-unsupported = Warning: --yield-to is only supported on Linux
+
+## stop
+
+stop-signal-sending = Sending { $signal } to the load process (PID: { $pid })
+signal-out-of-range = Signal number { $value } is not between 1 and 64
+signal-unknown = Unknown signal "{ $value }", expected one of { $names } or a number 1-64
+signal-unsupported = stop --signal is only supported on Unix
+
+## memorypressure
+
+memory-psi = memory pressure some: { $some10 }%/{ $some60 }%, full: { $full10 }%/{ $full60 }%
+memory-pressure-needs-root = Warning: --memory-pressure-test needs root to write /proc/sys/vm/drop_caches; skipping the memory pressure test
+memory-pressure-danger = Warning: the memory pressure test writes { $level } to /proc/sys/vm/drop_caches every 30 seconds, dropping caches for the WHOLE system! Other processes will see I/O latency spikes, and under memory pressure the system may stall or trigger the OOM killer. Do not use this in production
+memory-pressure-unsupported = Warning: the memory pressure test is not supported on this platform (requires Linux)
+memory-pressure-drop-failed = Warning: failed to write /proc/sys/vm/drop_caches, memory pressure test stopped: { $error }
+memory-pressure-baseline = memory pressure test: page faults when re-allocating { $size } before reclaim take { $fault } µs/page
+memory-pressure-cycle = memory pressure test #{ $cycle }: drop_caches={ $level } took { $drop } ms, re-accessing { $size } took { $access } ms, re-allocation faults { $fault } µs/page ({ $ratio }x baseline)
+
+## rayon
+
+rayon-started = Rayon load: pool of { $threads } threads, { $iterations } iterations per work unit
+rayon-stats = Rayon: { $rate } M iterations/s
+rayon-summary = Rayon completed { $total } iterations, { $rate } M/s on average
+rayon-start-failed = Warning: failed to build the Rayon thread pool: { $error }
+
+## fdstress
+
+fd-type-unsupported = { $kind } file descriptors are not supported on this platform
+fd-max-open = at most { $max } file descriptors open at the same time
+fd-limit-reached = Warning: reached the file descriptor limit (RLIMIT_NOFILE soft { $soft }, hard { $hard }); continuing with the number that could be opened. Raise the soft limit up to the hard limit with `ulimit -n <N>`, or raise the nofile hard limit in /etc/security/limits.conf (LimitNOFILE= for systemd services)
+fd-limit-reached-unknown = Warning: reached the file descriptor limit; continuing with the number that could be opened
+fd-started = fd stress: type { $kind }, { $count } per set
+fd-open-failed = Warning: failed to open a file descriptor, fd stress stopped: { $error }
+fd-stats = file descriptors: { $rate } ops/s (open + close), { $open } ms to open and { $close } ms to close a set
+fd-start-failed = Warning: failed to start the fd stress: { $error }
+pagecache-started = Page cache pressure started: file { $path } ({ $size }), { $threads } reader threads
+pagecache-file-too-small = file is smaller than one page ({ $size } bytes)
+pagecache-read-failed = Warning: file read failed, reader thread exiting: { $error }
+pagecache-start-failed = Warning: failed to start page cache pressure: { $error }
+pagecache-stats = Page cache reads: { $throughput } MiB/s ({ $iops } reads/s), hit rate { $hit_rate }, major faults { $faults }
+
+## cachethrash
+
+thrash-settings = cache thrash: shared array of { $size } ({ $lines } cache lines)
+thrash-stats = cache line writes: { $rate } M/s, inferred coherence bandwidth { $bandwidth } GB/s
+cache-alt-settings = Cache alternating: warm phase { $warm } per-thread buffer for { $warm_period }, cold phase shared { $cold } buffer for { $cold_period }
+cache-alt-cold = cold cache
+cache-alt-warm = warm cache
+cache-alt-transition = { $finished } phase finished ({ $throughput } GB/s per thread), switching to { $next } phase
+cache-alt-ratio = Warm:cold throughput ratio { $ratio }
+interrupt-started = Interruption test started: sending { $rate } SIGUSR2 per second to worker threads
+interrupt-stats = Interruptions: { $sent } sent, { $measured } measured during work, overhead mean { $mean } ns, p50/p99 = { $p50 }/{ $p99 } ns, max { $max } ns
+interrupt-summary = Interruption summary - { $stats }
+interrupt-start-failed = Warning: failed to start interruption test: { $error }
+interrupt-unsupported = Warning: the interruption-test workload is only supported on Unix; workers run the plain CPU load
+
+## stepper
+
+step-range-invalid = the step start { $start } must be positive and not greater than the end { $end }
+step-multiplier-invalid = --step-multiplier must be greater than 1
+step-invalid-cores = invalid core count: { $value }
+step-cores = step { $step }/{ $total }: { $cores } cores active
+step-memory = step { $step }/{ $total }: { $size } of memory allocated
+step-memory-failed = Warning: failed to allocate { $size } for the memory step, stepping stopped
+step-holding = reached the last step, holding the current load
+
+## hugepages
+
+thp-low-coverage = Warning: transparent huge pages are set to always, but THP coverage of this process is only { $coverage }%; memory fragmentation may be preventing huge page promotion (try echo 1 > /proc/sys/vm/compact_memory)
+steal-high = Warning: High CPU steal detected ({ $percent }%); load results may be inaccurate.
+steal-compensation-started = Steal compensation enabled: adjusting the duty cycle for steal time every { $secs }s
+steal-compensation-adjusted = Steal time { $steal }%, duty cycle { $target }% -> { $percent }%
+steal-compensation-unavailable = Warning: cannot read steal time from /proc/stat; --cpu-steal-compensation has no effect
+target-file-watching = Watching target file { $path }; write a value from 0 to 100 to change the CPU target
+target-file-updated = Target file: CPU target { $from }% -> { $to }%
+target-file-invalid = Warning: target file content { $value } is not a number from 0 to 100; keeping the current target
+target-file-inotify-failed = Warning: cannot watch the target file with inotify, polling every 500ms instead: { $error }
+
+## socket
+
+socket-started = TCP connection stress: listening on { $addr }, { $acceptors } acceptor threads, target rate { $rate } connections/s
+socket-rate-unlimited = unlimited
+socket-stats = TCP connections: accepted { $accepted }/s, connected { $connected }/s (target: { $target }), { $failed } failed
+socket-softnet = Network softirq: CPU { $softirq }%, softnet processed { $processed } packets/s, dropped { $dropped }
+socket-start-failed = Warning: cannot start the TCP connection stress: { $error }
+socket-unsupported = Warning: the socket-stress workload is only supported on Unix
@@ -0,0 +1,789 @@
+# 简体中文消息，消息ID按模块分组
+
+## parse
+
+parse-invalid-duration = 无效的时长: { $value }
+parse-invalid-duration-unit = 无效的时长单位: { $unit }
+parse-duration-not-positive = 时长必须大于0
+parse-invalid-number = 无效的数值: { $value }
+parse-fraction-out-of-range = 数值必须在0.0到1.0之间
+parse-invalid-thread-override = 无效的线程覆盖项: { $entry }（格式为 线程序号:百分比）
+parse-invalid-thread-index = 无效的线程序号: { $value }
+parse-invalid-percent = 无效的百分比: { $value }
+parse-percent-out-of-range = 百分比必须在0到100之间: { $value }
+parse-duplicate-thread = 线程 { $index } 被重复指定
+parse-invalid-size = 无效的大小: { $value }
+parse-invalid-rate = 无效的速率: { $value }
+parse-invalid-regex = 无效的正则表达式: { $error }
+parse-invalid-schedule = 无效的循环负载项: { $entry }（应为 work=<时长>、pause=<时长> 或 repeat=<次数>）
+parse-schedule-incomplete = 循环负载必须同时指定 work 和 pause: { $value }
+parse-cpu-base-zero = --cpu-base 不能为0；只需内存负载时使用 -c 0
+cpu-base-clamped = 警告：--cpu-base { $value } 超过100，按100处理
+
+## commands
+
+stop-stopping = 正在停止CPU负载进程 (PID: { $pid })...
+stop-stopped = CPU负载已停止（PID: { $pid }，通过 { $how } 终止，已确认进程退出）
+stop-not-running = 没有找到正在运行的CPU负载进程
+ctl-connect-failed = 无法连接控制套接字 { $path }: { $error }
+ctl-unsupported = 控制套接字仅在 Unix 平台上受支持
+sync-server-failed = 同步服务端出错: { $error }
+start-already-running = 已有一个实例正在运行 (PID: { $pid })。如需停止，请使用 'stop' 命令；如需同时运行多个实例，请用 --instance-name 指定不同的名称
+pid-save-failed = 警告：无法保存PID文件: { $error }
+instance-name-invalid = 无效的实例名称 "{ $name }"：只能包含字母、数字、-、_ 和 .，且不能以 . 开头
+list-empty = 没有找到任何实例
+list-column-name = 名称
+list-column-state = 状态
+list-state-running = 运行中
+list-state-stale = 已退出（PID文件残留）
+list-state-corrupted = PID文件损坏
+stop-stale = 警告：PID文件记录的进程 { $pid } 已不存在（可能已退出，或PID已被系统重用），已清理PID文件
+stop-force-kill = 警告：进程 { $pid } 在 { $secs } 秒内未退出，强制终止
+stop-timed-out = 进程 { $pid } 在 { $secs } 秒内未退出，已保留PID文件；可加 --force 强制终止或用 --timeout 延长等待
+stop-foreign = 警告：PID { $pid } 不是本程序的实例（PID文件无效或PID已被其他程序使用），不发送信号并删除PID文件
+signal-refused-pid = 拒绝向PID { $pid } 发送信号
+
+## start
+
+start-alloc-fail-injection = 启用分配失败注入: 概率 { $rate }，仅针对大于 { $size } 字节的分配
+error-prefixed = 错误：{ $error }
+start-stopping = 正在停止系统负载...
+start-workload-only = 启动 { $workload } 负载，不启动计算线程
+start-auto-cores = 启动CPU负载，自动调整核心数（最多 { $cores } 个），目标系统CPU使用率 { $target }%
+start-cores = 启动CPU负载，使用 { $cores } 个核心
+start-hybrid-warning = 警告：此混合架构CPU上 CPU { $efficiency } 是能效核，使用的 { $cores } 个核心超过了 { $performance } 个性能核，负载结果将不均匀
+start-target = 目标CPU使用率: { $target }%
+start-target-noise = 目标CPU使用率: { $target }% ±{ $noise }%
+disk-file-created = 创建硬盘占用文件: { $path }
+disk-file-create-failed = 警告：创建硬盘占用文件失败: { $error }
+disk-invalid-size = 警告：无效的硬盘大小格式，将不会创建文件
+memory-percent-of-total = 按系统总内存的 { $percent }% 计算
+memory-allocating = 分配内存: { $size }
+memory-oom = 内存分配失败：请求 { $requested } GiB，但仅有 { $available } GiB 可用
+memory-invalid-size = 无效的内存大小格式: { $error }
+notify-fd-unsupported = 警告：--notify-fd 仅支持Unix，将被忽略
+background-starting = 程序将在后台运行，使用 'stop' 命令停止
+background-pipe-failed = 无法创建就绪通知管道: { $error }
+background-ready = 后台进程已就绪
+background-exited = 错误：后台进程在就绪前退出
+background-timed-out = 错误：后台进程在 { $secs } 秒内未就绪
+background-fork-failed = 无法 fork 进程以在后台运行
+background-pid-save-failed = 警告：无法在后台进程中保存PID文件: { $error }
+background-parent-exit = 父进程退出，子进程 (PID: { $pid }) 在后台运行
+background-windows-unsupported = 警告：当前平台不支持后台运行模式 (-b)，程序将继续在前台运行。
+mlock-locked = 成功锁定 { $locked } / 请求 { $requested }
+mlock-failed = 警告：无法锁定内存（可能超出 RLIMIT_MEMLOCK）: { $error }
+thread-overrides-unused = 警告：线程序号 { $threads } 超出工作线程数 ({ $cores })，对应的覆盖将被忽略
+rate-limit = 限速: 合计 { $rate } 次迭代/秒，每个线程 { $per_thread } 次/秒
+perf-disabled = 警告：将不统计硬件性能计数器
+worker-perf-failed = 警告：工作线程 { $thread } 无法打开硬件性能计数器（可能需要 CAP_PERFMON）: { $error }
+worker-started-target = 启动工作线程 { $thread } (目标 { $percent }%)
+worker-started = 启动工作线程 { $thread }
+worker-rt-failed = 警告：工作线程 { $thread } 无法设置实时调度策略，将以普通优先级运行: { $error }
+worker-affinity-failed = 警告：工作线程 { $thread } 无法绑定到 CPU { $cpu }: { $error }
+sync-wait-failed = 错误：等待同步开始信号失败: { $error }
+burst-mode = 负载脉冲模式: { $count } 个周期，每个周期负载 { $on } 秒、空闲 { $off } 秒
+control-started = 控制套接字已启动: { $path }
+control-start-failed = 警告：无法启动控制套接字 { $path }: { $error }
+control-unsupported = 警告：--control-socket 仅在 Unix 平台上受支持
+compress-settings = zstd 压缩负载: 级别 { $level }，数据块 { $block }
+udp-start-failed = 警告：无法启动UDP回环压力: { $error }
+diskio-start-failed = 警告：无法启动磁盘I/O负载: { $error }
+memstress-prefetch-stride = 检测到CPU厂商 { $vendor }，使用预取失效步长 { $stride } 字节
+duration-reached = 已达到负载持续时间，正在停止负载...
+cycles-reached = 所有工作线程已完成指定的迭代次数，正在停止负载...
+perf-summary = 硬件计数器汇总: { $metrics }
+perf-none-opened = 警告：没有工作线程成功打开硬件性能计数器
+measurement-started = [POST-LOAD MEASUREMENT] 负载已停止，继续监控 { $secs } 秒
+disk-file-cleanup-failed = 警告：清理硬盘占用文件失败: { $error }
+interleave-unsupported = 警告：--cpu-affinity-interleave 仅在 Linux 上受支持
+interleave-header = 工作线程绑核（按插槽交错）:
+interleave-entry = 线程 { $thread } -> CPU { $cpu } (插槽 { $package }, 核心 { $core })
+ht-siblings-not-found = 警告：没有找到超线程兄弟（未开启超线程或不是 Linux），--hyper-threads-only 不绑核
+ht-siblings = 只在超线程兄弟上运行: CPU { $cpus }（共 { $count } 个）
+ht-siblings-shared = 警告：{ $workers } 个工作线程多于 { $count } 个超线程兄弟，部分兄弟上会运行多个线程
+numa-node-invalid = NUMA节点 { $node } 不存在或没有CPU
+numa-memory-bound = 内存分配绑定到NUMA节点 { $node }
+numa-cpus-bound = 所有线程绑定到NUMA节点 { $node } 的CPU: { $cpus }
+numa-bind-failed = 警告：无法绑定到NUMA节点 { $node }: { $error }
+numa-weights-invalid = 无效的NUMA节点比例：{ $value }（格式为 节点:百分比，例如 0:70）
+numa-weights-sum = --numa-weights 的比例之和为 { $total }%，必须为100%
+numa-weights-duplicate = --numa-weights 中节点 { $node } 出现了多次
+numa-weights-node-missing = NUMA节点 { $node } 不存在
+numa-weights-bind-failed = 警告：无法按比例把内存绑定到各NUMA节点：{ $error }
+numa-weights-report-unavailable = 警告：无法读取 /proc/self/numa_maps，不统计各节点实际分配的内存
+numa-weights-header = 各NUMA节点的内存分配（要求 / 实际）：
+numa-weights-entry = 节点 { $node }：{ $requested }% / { $achieved }%（{ $size }）
+numa-weights-unexpected-node = 警告：节点 { $node } 上也有 { $size } 内存，不在 --numa-weights 中
+numa-latency-unsupported = 无法读取有内存的NUMA节点：{ $error }
+numa-latency-bind-failed = 无法把内存绑定到NUMA节点 { $node }：{ $error }
+numa-latency-same-node = 远端节点不能与本地节点相同
+numa-latency-single-node = 本机只有一个有内存的NUMA节点，无法比较本地与远端
+numa-latency-started = NUMA延迟测试：本地节点 { $local }，远端节点 { $remote }，每个节点 { $size }
+numa-latency-local = 本地延迟（节点 { $node }）：{ $ns } ns
+numa-latency-remote = 远端延迟（节点 { $node }）：{ $ns } ns
+numa-latency-factor = NUMA 系数（远端/本地）：{ $factor }
+numa-latency-copy-local = 本地到本地拷贝带宽：{ $bandwidth } GB/s
+numa-latency-copy-remote = 远端到本地拷贝带宽：{ $bandwidth } GB/s（本地的 { $percent }%）
+numa-coverage-unavailable = 警告：无法读取NUMA节点或 /proc/self/numa_maps，--ensure-numa-coverage 不生效
+numa-coverage-added = NUMA节点 { $node } 上没有分配到页面，已在该节点上补充分配 { $size }
+numa-coverage-failed = 警告：无法在NUMA节点 { $node } 上补充分配内存: { $error }
+numa-coverage-node = 节点{ $node }: { $percent }%
+numa-coverage-distribution = NUMA页面分布: { $nodes }
+cpu-affinity-invalid = 无效的CPU列表：{ $value }（例如："0,2,4"、"4-7"）
+cpu-affinity-out-of-range = --cpu-affinity 中的CPU { $cpu } 不存在（共 { $count } 个逻辑CPU）
+cpu-affinity-duplicate = --cpu-affinity 中的CPU { $cpu } 重复出现
+cpu-affinity-cores-mismatch = --cores { $cores } 与 --cpu-affinity 中的 { $count } 个CPU不一致
+cpu-affinity-unsupported = 警告：当前平台不支持 --cpu-affinity，工作线程不绑核
+cpu-affinity-pinned = 工作线程依次绑定到CPU：{ $cpus }
+background-spawn-failed = 无法启动后台子进程：{ $error }
+thread-overrides-cores-raised = 警告：--thread-overrides（--core-loads）列出的线程序号超出 --cores ({ $cores })，改为使用 { $count } 个工作线程
+schedule-mode = 循环负载模式: 每个周期负载 { $work } 秒、停顿 { $pause } 秒，共 { $repeat } 个周期
+schedule-repeat-forever = 无限
+background-log-file = 后台进程的输出写入 { $path }
+background-log-open-failed = 警告：无法打开后台日志文件 { $path }，后台进程的输出将被丢弃: { $error }
+control-path-not-socket = { $path } 已存在且不是套接字，不会删除
+ctrlc-handler-failed = 无法设置Ctrl-C处理器: { $error }
+
+## status
+
+status-system-header = 系统信息:
+status-cpu-header = CPU信息:
+status-core-count = 总核心数: { $count }
+status-core-usage = 插槽 { $package }, 核心 { $core }: [{ $usages }]
+status-cpu-usage = 核心 #{ $cpu }: { $usage }%
+status-avg-cpu = 平均CPU使用率: { $usage }%
+status-memory-header = 内存信息:
+status-memory-total = 总内存: { $size } GB
+status-memory-used = 已用内存: { $size } GB
+status-memory-available = 可用内存: { $size } GB
+status-memory-usage = 内存使用率: { $percent }%
+status-disk-header = 磁盘I/O:
+status-no-disks = 没有匹配的磁盘
+status-network-header = 网络:
+status-no-networks = 没有匹配的网络接口
+self-monitor-header = 工作线程占空比:
+self-monitor-thread = 线程 { $thread }: 目标 { $target }%, 实际 { $actual }%, 偏差 { $deviation }, 累计迭代 { $iterations }
+self-monitor-deviation = 警告：线程 { $thread } 的实际占空比偏离目标 { $deviation } 个百分点
+status-host-name = 主机名: { $name }
+status-os = 操作系统: { $name } { $version }
+status-kernel = 内核版本: { $version }
+status-uptime = 运行时间: { $uptime }（启动于 { $boot }）
+status-physical-memory = 物理内存: { $size } GiB
+status-cpu-brand = CPU型号: { $brand }
+status-cpu-steal = CPU窃取时间: { $percent }%
+
+## benchmark
+
+benchmark-header = 基准测试结果:
+benchmark-threads = 工作线程: { $threads }，每线程迭代: { $limit }
+benchmark-stopped-early = 警告：负载被提前停止，仅完成 { $total } 次迭代
+benchmark-elapsed = 用时: { $secs } 秒
+benchmark-score = 得分: { $score } 次迭代/秒
+spawn-failed = 创建失败（{ $method }）: { $error }
+spawn-fork-unsupported = --method fork 仅在 Unix 上受支持
+calibration-running = 正在测量 { $workload }（{ $secs } 秒）...
+calibration-unit-iteration = 次迭代
+calibration-unit-block = 块
+calibration-unit-op = 次操作
+calibration-header = 单线程吞吐量（按每单位耗时从高到低，以 { $baseline } 为基准 1.0×）：
+calibration-no-home = 警告：未设置 HOME 或 XDG_CONFIG_HOME，校准结果未保存
+calibration-saved = 校准结果已保存到 { $path }
+calibration-save-failed = 警告：无法保存校准结果到 { $path }：{ $error }
+
+## autocores
+
+autocores-adjusted = 自动核心调整: 系统CPU { $usage }% { $direction ->
+    [below] 低于
+   *[above] 高于
+} { $target }% ± { $band }% 的死区，负载 { $before } → { $after } 核心，启用 { $active } 个工作线程，占空比 { $percent }%
+
+## burst
+
+burst-finished = 已完成全部 { $count } 个脉冲周期，正在停止负载...
+burst-transition = 脉冲周期 { $cycle }/{ $total }: { $state ->
+    [on] 开始负载
+   *[off] 开始空闲
+} (目标 { $percent }%)
+burst-summary-header = 脉冲汇总 (完成 { $completed }/{ $total } 个周期):
+burst-summary-cycle = 周期 { $cycle }: 负载阶段平均CPU { $on }, 空闲阶段平均CPU { $off }
+burst-summary-incomplete = (未完成)
+schedule-phase-changed = 循环负载第 { $cycle } 个周期进入 { $phase } 阶段 (目标 { $target }%)
+schedule-finished = 已完成全部 { $count } 个循环负载周期，正在停止负载...
+
+## compress
+
+compress-encode-failed = 错误：工作线程 { $thread } 压缩失败: { $error }
+compress-decode-failed = 错误：工作线程 { $thread } 解压失败: { $error }
+compress-throughput = 压缩: { $rate } MiB/s, 压缩率 { $ratio }
+
+## control
+
+control-accept-failed = 警告：控制套接字接受连接失败: { $error }
+control-command = 控制命令: { $request }
+control-stop = 收到控制套接字的停止命令，正在停止系统负载...
+
+## cpufreq
+
+cpufreq-read-failed = 警告：无法读取 CPU { $cpu } 的调频策略（可能没有 cpufreq 驱动）: { $error }
+cpufreq-switching = CPU调频策略: 当前 { $current } -> 目标 { $target }
+cpufreq-set-failed = 警告：无法设置 CPU { $cpu } 的调频策略（需要root权限）: { $error }
+cpufreq-unsupported = 警告：--cpufreq-governor 仅在 Linux 上受支持
+cpufreq-restore-failed = 警告：无法恢复 CPU { $cpu } 的调频策略 { $governor }: { $error }
+cpufreq-restored = 已恢复 { $count } 个CPU的调频策略
+turbo-status = 睿频: { $status ->
+    [enabled] 已开启
+    [disabled] 已关闭
+   *[unknown] 未知
+}
+turbo-switched = 已{ $enabled ->
+    [true] 开启
+   *[false] 关闭
+}睿频，负载结束后恢复
+turbo-not-found = 警告：未找到睿频开关（intel_pstate/no_turbo 或 cpufreq/boost），--turbo-boost 不生效
+turbo-set-failed = 警告：无法修改睿频开关 { $path }（需要root权限）: { $error }
+turbo-unsupported = 警告：--turbo-boost 仅在 Linux 上受支持
+turbo-restore-failed = 警告：无法恢复睿频开关 { $path }: { $error }
+turbo-restored = 已恢复原来的睿频设置
+governor-profile-read-failed = 无法读取调频配置文件 { $file }: { $error }
+governor-profile-invalid = 调频配置文件 { $file } 无效: { $error }
+governor-profile-not-object = 顶层必须是以核心序号为键的对象
+governor-profile-bad-core = "{ $core }" 不是有效的核心序号
+governor-profile-bad-entry = 核心 { $core } 的设置必须是对象
+governor-profile-bad-freq = 核心 { $core } 的 { $field } 必须是正整数（kHz）
+governor-profile-bad-governor = 核心 { $core } 的 governor 必须是字符串
+governor-profile-min-above-max = 核心 { $core } 的 min_freq { $min } 大于 max_freq { $max }
+governor-profile-write-failed = 警告：无法写入 { $path }（需要root权限和 cpufreq 驱动）: { $error }
+governor-profile-applied = 已按调频配置设置 { $cores } 个核心，写入 { $files } 项
+governor-profile-unsupported = 警告：--cpu-governor-profile 仅在 Linux 上受支持
+governor-profile-restore-failed = 警告：无法把 { $path } 恢复为 { $value }: { $error }
+governor-profile-restored = 已恢复 { $files } 项调频设置
+freqtrace-started = 开始跟踪 { $count } 个核心的CPU频率变化
+freqtrace-start-failed = 警告：无法启用CPU频率跟踪（需要root权限和 tracefs）：{ $error }
+freqtrace-unsupported = 警告：--cpufreq-report 仅在 Linux 上受支持
+freqtrace-change = [{ $time }s] CPU { $cpu }：{ $old } MHz -> { $new } MHz
+freqtrace-summary-header = CPU频率汇总:
+freqtrace-summary-core = CPU { $cpu }：平均 { $avg } MHz，最低 { $min } MHz，最高 { $max } MHz，变化 { $changes } 次
+freqtrace-summary-core-unknown = CPU { $cpu }：频率未知（无法读取 scaling_cur_freq 且没有频率事件）
+freqtrace-no-events = 运行期间没有记录到频率变化事件；intel_pstate 等由硬件自主调频的驱动可能不产生 cpu_frequency 事件
+freqtrace-histogram-header = 频率分布（按核心时间占比）:
+irq-needs-force = --irq-balance-disable 会把所有中断集中到一个CPU，可能影响多队列网卡的网络性能，确认后请同时指定 --force
+irq-needs-root = 警告：--irq-balance-disable 需要root权限，已忽略
+irq-unsupported = 警告：--irq-balance-disable 仅支持Linux，已忽略
+irq-no-free-cpu = 警告：所有CPU都参与负载，没有可用于处理中断的CPU，请减少核心数并绑核
+irq-read-failed = 警告：无法读取 /proc/irq: { $error }
+irq-nic-warning = 警告：所有中断将集中到一个CPU，多队列网卡的网络性能可能明显下降
+irq-irqbalance-stopped = 已停止 irqbalance
+irq-irqbalance-stop-failed = 警告：无法停止 irqbalance，中断可能被重新分散
+irq-pinned = 已把 { $count } 个中断绑定到CPU { $cpu }（掩码 { $mask }），{ $skipped } 个无法修改
+irq-restored = 已恢复 { $count } 个中断的亲和性，{ $failed } 个恢复失败
+irq-irqbalance-started = 已重新启动 irqbalance
+irq-irqbalance-start-failed = 警告：无法重新启动 irqbalance，请手动执行 systemctl start irqbalance
+status-core-out-of-range = --status-core { $core } 超出范围，本机只有 { $count } 个逻辑CPU
+status-core-no-free-cpu = 本机只有1个逻辑CPU，无法为状态线程保留CPU；请同时指定 --allow-status-core-sharing
+status-core-async = 警告：异步引擎中状态刷新是定时任务，无法绑核，忽略 --status-core
+status-core-unsupported = 警告：当前平台不支持绑核，忽略 --status-core
+status-core-pinned = 状态线程已绑定到CPU { $cpu }
+status-core-failed = 警告：无法把状态线程绑定到CPU { $cpu }：{ $error }
+governor-aware-not-performance = 警告：CPU调频策略为 '{ $governor }'，测得的CPU负载可能低于预期。可以考虑使用 --cpufreq-governor performance
+governor-aware-freq-range = 警告：CPU频率下限 { $min } MHz 不到上限 { $max } MHz 的一半，负载期间CPU可能运行在较低频率
+governor-aware-unavailable = 警告：无法读取CPU调频策略（可能没有 cpufreq 驱动），跳过 --cpu-governor-aware 检查
+governor-aware-unsupported = 警告：--cpu-governor-aware 仅在 Linux 上受支持
+
+## diskio
+
+diskio-stats = 磁盘I/O (深度 { $depth }): { $iops } IOPS, 延迟 p50/p95/p99 = { $p50 }/{ $p95 }/{ $p99 } µs, 平均 { $mean } µs, 最大 { $max } µs
+diskio-failed = 警告：磁盘I/O失败，I/O线程退出: { $error }
+diskio-started = 启动磁盘I/O负载: { $path } (队列深度 { $depth })
+diskio-summary = 磁盘I/O汇总 - { $stats }
+io-stress-started = 启动I/O压力: 目录 { $path }，{ $threads } 个线程，模式 { $pattern }，块大小 { $block }，每个文件 { $size }
+io-stress-create-failed = 警告：无法创建I/O压力的临时文件 { $path }: { $error }
+io-stress-failed = 警告：I/O压力读写失败，线程退出: { $error }
+io-stress-summary = I/O压力汇总 - 共 { $ops } 次操作，平均 { $stats }
+io-stress-block-too-large = --block-size { $block } 必须大于0且不超过 --file-size { $size }
+
+## engine
+
+engine-tokio = 使用 tokio 异步负载引擎
+engine-tokio-failed = 警告：无法创建 tokio 运行时，改用系统线程: { $error }
+engine-tokio-missing = 警告：--async 需要以 tokio 特性编译（cargo build --features tokio），改用系统线程
+
+## growth
+
+growth-started-rate = 内存增长: 上限 { $limit }，最大速率 { $rate }/s
+growth-started = 内存增长: 上限 { $limit }，不限速
+growth-paused = 警告：系统可用内存 { $available } 低于安全余量 { $margin }，暂停内存增长
+growth-resumed = 系统可用内存已恢复到 { $available }，继续内存增长
+growth-alloc-failed = 警告：内存增长分配 { $size } 失败，停止增长
+growth-progress = 内存增长: 已增长 { $grown }，速率 { $rate }/s
+growth-limit-reached = 内存增长已达到上限 { $limit }
+
+## hooks
+
+hook-running = 执行 { $name } 钩子: { $cmd }
+hook-spawn-failed = 警告：无法执行 { $name } 钩子: { $error }
+hook-timed-out = 警告：{ $name } 钩子运行超过 { $secs } 秒，已终止
+hook-wait-failed = 警告：等待 { $name } 钩子结束失败: { $error }
+hook-finished = { $name } 钩子执行完成
+hook-failed = 警告：{ $name } 钩子以 { $status } 退出
+
+## json
+
+json-trailing-content = 第 { $pos } 个字节处有多余内容
+json-error-at = { $message }（位置 { $pos }）
+json-invalid-literal = 无效的字面量
+json-unexpected-end = 意外的输入结尾
+json-invalid-number = 无效的数字
+json-unterminated-string = 字符串未结束
+json-invalid-escape = 无效的转义序列
+json-invalid-utf8 = 字符串不是有效的UTF-8
+json-array-expected = 数组中缺少 ',' 或 ']'
+json-key-not-string = 对象的键必须是字符串
+json-colon-expected = 对象中缺少 ':'
+json-object-expected = 对象中缺少 ',' 或 '{"}"}'
+json-too-deep = 嵌套超过 { $max } 层
+
+## logger
+
+syslog-unsupported = 警告：--syslog 在当前平台不受支持，日志将继续输出到控制台
+log-file-open-failed = 警告：无法打开日志文件 { $path }：{ $error }
+log-file-write-failed = 警告：写入日志文件失败：{ $error }
+eventlog-open-failed = 警告：无法打开Windows事件日志: { $error }，日志将继续输出到控制台
+syslog-open-failed = 警告：无法连接系统日志: { $error }，日志将继续输出到控制台
+
+## memory
+
+memory-size-empty = 内存大小不能为空
+memory-size-invalid-number = 无效的数值: { $value }
+memory-size-invalid-unit = 无效的单位: { $unit }（支持 B、K、M、G、T、KiB、MiB、GiB、TiB 或 %）
+memory-size-percent-out-of-range = 百分比必须大于0且不超过100: { $percent }%
+memory-draining = 正在清空内存（剩余 { $size } GiB）...
+memory-drain-timed-out = 清空内存超时，直接释放剩余的 { $size } GiB
+memory-prefault-progress = 预填充内存 
+memory-prefault-sequential = 逐页写入
+memory-prefaulted = 已通过 { $method } 预填充全部页面，用时 { $ms } ms
+memory-first-access = 首次访问 { $first } µs/页，再次访问 { $again } µs/页，缺页开销约 { $fault } µs/页
+objects-size-invalid = --memory-object-size 为 { $size }，必须大于0且不超过分配总量 { $total }
+objects-bench-header = 分配器开销（每种大小连续分配 { $count } 次的平均耗时）：
+objects-bench-size = { $size }：{ $ns } ns/次
+objects-bench-failed = 警告：{ $size } 对象的分配器基准分配失败，已跳过
+objects-allocating = 正在分配 { $count } 个 { $size } 的对象...
+objects-allocated = 对象分配完成，用时 { $ms } 毫秒
+compaction-needs-root = 警告：--memory-compaction 需要root权限写入 /proc/sys/vm/compact_memory，已跳过
+compaction-unsupported = 警告：--memory-compaction 仅在 Linux 上受支持
+compaction-failed = 警告：触发内存规整失败: { $error }
+compaction-done = 内存规整完成，用时 { $ms } 毫秒
+compaction-before = 规整前
+compaction-during = 规整中
+compaction-after = 规整后
+compaction-phase = { $phase }（{ $count } 次探测）: 分配 平均/P99/最大 { $alloc_mean }/{ $alloc_p99 }/{ $alloc_max } µs, 访问 平均/P99/最大 { $access_mean }/{ $access_p99 }/{ $access_max } µs
+compaction-fragmentation = 大页碎片化指数: 规整前 { $before }, 规整后 { $after }（0-1，越大碎片越严重）
+compaction-thp = THP覆盖率: 规整前 { $before }%, 规整后 { $after }%
+config-no-cores = 核心数必须至少为1
+config-percent-out-of-range = CPU使用率必须在 (0, 100] 范围内，实际为 { $percent }
+config-empty-memory = 内存大小必须大于0
+config-zero-duration = 运行时长必须大于0
+config-too-many-cores = 核心数 { $cores } 超过本机CPU数 { $cpus }
+config-memory-exceeds-total = 内存大小 { $memory } 超过本机物理内存总量 { $total }
+dealloc-allocating = 正在分配 { $count } 块 { $size } 的内存，退出时按 { $pattern } 顺序释放
+dealloc-chunks-too-many = 块数 { $count } 过多：总量 { $total } 不足以每块至少1字节
+dealloc-report = 按 { $pattern } 顺序释放 { $count } 块内存共耗时 { $ms } ms，单块 平均 { $mean } µs，p50/p99 = { $p50 }/{ $p99 } µs，最大 { $max } µs
+fragmentation-allocating = 正在把 { $size } 拆成64B-4KiB的随机大小小块分配，每秒重新分配其中10%...
+fragmentation-allocated = 已分配 { $count } 个小块，用时 { $ms } 毫秒
+
+## memstress
+
+memstress-started = 内存访问模式: { $pattern }，步长 { $stride } 字节，{ $count } 个访问线程{ $shared ->
+    [yes] （共享整块内存）
+   *[no] {""}
+}
+memstress-rate = 内存访问: { $rate } M次/s, 缓存行带宽 { $bandwidth } GB/s
+memstress-per-thread = 各访问线程带宽 (GB/s): { $bandwidths }
+memstress-prefetch = 预取训练: 步长 { $train_stride } 字节 { $train } GB/s，步长 { $prime_stride } 字节 { $prime } GB/s，比值 { $ratio }
+memstress-latency = 合成延迟校准: 请求 { $requested } ns，实际每次访问 { $effective } ns（含内存访问本身和计时开销）
+bandwidth-started = 内存带宽测试启动: 模式 { $mode }，{ $count } 个线程
+bandwidth-calibration = 理论峰值校准: 只读 { $read } GB/s，只写 { $write } GB/s
+bandwidth-rate = 内存带宽（{ $mode }）: { $bandwidth } GB/s
+bandwidth-efficiency = 内存控制器效率: { $efficiency }%（理论峰值 { $peak } GB/s）
+
+## mlock
+
+mlock-unsupported = 当前平台不支持 mlock
+
+## notify
+
+notify-sent = 已通过文件描述符 { $fd } 发送就绪通知
+notify-failed = 警告：无法向文件描述符 { $fd } 写入就绪通知: { $error }
+
+## pattern
+
+spike-period-too-short = 尖峰周期 ({ $every }) 必须大于保持时间加上下过渡时间 ({ $spike })
+spike-started = CPU尖峰模式启动: 阶段 { $phase } (目标 { $percent }%)
+spike-phase-changed = CPU尖峰阶段切换: { $from } -> { $to } (目标 { $percent }%)
+spike-trigger-missing = --cpu-spike-to 需要 --cpu-spike-every 或 --spike-on-signal
+spike-signal-ready = 信号尖峰已就绪: 向进程 { $pid } 发送 SIGUSR1 触发尖峰
+spike-signal-triggered = 触发第 { $count } 次尖峰（SIGUSR1 收到于 { $received }）: 目标 { $percent }%，保持 { $hold }
+spike-signal-finished = 第 { $count } 次尖峰结束，回到 { $percent }%
+spike-signal-queued = 尖峰进行中收到 SIGUSR1，已排队（排队中 { $queued } 个）
+spike-signal-discarded = 警告：排队的尖峰已达上限 { $max } 个，丢弃本次 SIGUSR1
+spike-signal-install-failed = 警告：无法安装 SIGUSR1 处理函数，信号尖峰不可用: { $error }
+spike-signal-unsupported = 警告：--spike-on-signal 仅支持Unix平台，已忽略
+thermal-unavailable = 警告：无法读取CPU温度，温度保护未启用
+thermal-started = 温度保护已启用：超过 { $max }°C 时降低目标使用率，低于 { $resume }°C 时恢复
+thermal-throttled = 警告：CPU温度 { $temp }°C 超过上限，目标使用率降至 { $percent }%
+thermal-restored = CPU温度回落到 { $temp }°C，目标使用率恢复为 { $percent }%
+signal-adjust-ready = 信号调整已启用：kill -USR1 { $pid } 加大负载，kill -USR2 { $pid } 减小负载，每次调整 { $step }
+signal-adjust-percent = 收到信号，目标CPU使用率调整为 { $percent }%
+signal-adjust-cores = 收到信号，启用的工作线程数调整为 { $cores }/{ $max }
+signal-adjust-install-failed = 警告：无法安装 SIGUSR1/SIGUSR2 处理函数，信号调整未启用：{ $error }
+signal-adjust-unsupported = 警告：--signal-adjust 仅支持 Unix，已忽略
+signal-adjust-interruption-conflict = --signal-adjust 不能与 --workload interruption-test 同时使用：两者都使用 SIGUSR2
+
+## perf
+
+perf-unknown-event = 未知的硬件事件: { $name }（支持 { $supported }）
+perf-llc-miss-rate = LLC未命中率 { $rate }%
+perf-branch-miss-rate = 分支预测失败率 { $rate }%
+perf-paranoid-unreadable = 警告：无法读取 /proc/sys/kernel/perf_event_paranoid
+perf-paranoid-unrestricted = 不限制，允许所有事件
+perf-paranoid-0 = 允许CPU级事件，禁止读取原始跟踪点数据
+perf-paranoid-1 = 禁止CPU级事件，只允许进程级的内核和用户态事件
+perf-paranoid-2 = 禁止内核态剖析，只允许进程级的用户态事件
+perf-paranoid-restricted = 禁止普通用户使用 perf 事件
+perf-paranoid-level = kernel.perf_event_paranoid = { $level }（{ $meaning }）
+perf-paranoid-denied = 错误：当前级别下普通用户无法打开 perf 事件，可执行 `sudo sysctl -w kernel.perf_event_paranoid=2` 或授予 CAP_PERFMON 后重试
+perf-feature-missing = 警告：硬件性能计数器需要以 perf 特性编译（cargo build --features perf），将不统计计数器
+perf-unsupported = 警告：硬件性能计数器仅在 Linux 上受支持，将不统计计数器
+
+## residency
+
+residency-checked = 内存常驻检查: { $resident } / { $total } 页常驻 ({ $percent }%)
+residency-low = 警告：内存常驻比例 { $percent }% 低于 { $min }%，部分页面可能已被系统换出
+residency-failed = 警告：内存常驻检查失败，停止检查: { $error }
+residency-unsupported = 当前平台不支持 mincore
+residency-willneed = 已调用 madvise(MADV_WILLNEED) 预取内存页面
+residency-willneed-failed = 警告：madvise(MADV_WILLNEED) 失败: { $error }
+
+## sched
+
+sched-needs-root = 警告：实时调度需要root权限（或 CAP_SYS_NICE），当前用户很可能无法设置 { $policy } 策略
+sched-policy = 工作线程将使用实时调度策略 { $policy }，优先级 { $priority }
+sched-starvation = 警告：实时优先级的满负载线程可能让同一核心上的普通进程长时间得不到调度
+sched-unsupported = 警告：当前平台不支持实时调度，工作线程将以普通优先级运行
+
+## reset
+
+reset-force-kill = 警告：进程 { $pid } 在 { $secs } 秒内未退出，强制终止
+reset-remove-failed = 警告：无法删除 { $path }: { $error }
+reset-done = 已正常停止 { $stopped } 个进程，强制终止 { $killed } 个，删除 { $removed } 个PID文件
+reset-skip-foreign = 警告：PID { $pid } 不是本程序的实例（PID文件无效或PID已被其他程序使用），跳过
+
+## status-diff
+
+status-diff-header = 系统状态变化（间隔 { $secs } 秒）:
+status-diff-cores = 各核心: { $sparkline }
+status-diff-memory-stable = 内存基本保持不变
+status-diff-memory-growing = 内存增长速度: { $rate } MiB/s
+status-diff-memory-shrinking = 内存减少速度: { $rate } MiB/s
+status-watch-header = { $time }  第 { $sample } 次刷新，每 { $secs } 秒刷新一次，按 Ctrl+C 退出
+status-watch-memory = 内存: { $used } / { $total } GiB ({ $percent }%)
+status-watch-swap = 交换分区: { $used } / { $total } GiB ({ $percent }%)
+status-watch-no-swap = 交换分区: 未启用
+status-watch-handler-failed = 警告：无法设置Ctrl-C处理器：{ $error }
+status-json-failed = 警告：无法生成JSON输出：{ $error }
+
+## summary
+
+summary-no-samples = 无采样数据
+summary-samples = 采样次数: { $count }
+summary-avg-cpu = 平均CPU使用率: { $usage }%
+summary-cpu-range = 最低/最高CPU使用率: { $min }% / { $max }%
+summary-avg-memory = 平均内存使用: { $size }GB
+summary-header = 运行汇总:
+summary-load-phase = [负载阶段]
+summary-histogram-header = CPU使用率分布:
+summary-cpu-cv = 变异系数: { $cv }（标准差 { $stddev }%），越小负载越稳定
+summary-cpu-cv-idle = 变异系数: 无（平均使用率为0）
+summary-avg-steal = 平均CPU窃取时间: { $percent }%
+summary-steal-inaccurate = 警告：平均CPU窃取时间 { $percent }%，本次运行结果可能因窃取时间过高而不准确
+
+## swap
+
+swap-disabled = 警告：系统未启用交换分区，--swap-pressure 只能丢弃页面而无法测试换入换出
+swap-too-small = 警告：可用交换空间 ({ $free }MB) 小于要换出的内存 ({ $size }MB)，部分页面将无法换出
+swap-started = 交换压力: 已写入 { $pages } 个页面，开始强制换出
+swap-pageout-failed = 警告：madvise(MADV_PAGEOUT) 失败 ({ $error })，改用 MADV_DONTNEED
+swap-dontneed-failed = 警告：madvise(MADV_DONTNEED) 失败，停止交换压力: { $error }
+swap-bandwidth = 交换压力: 换入带宽 { $rate } MB/s
+swap-vmstat = , si { $si } 页/s, so { $so } 页/s
+swap-unsupported = 当前平台不支持强制换出页面
+swap-exceeds-capacity = 警告：要分配的内存 ({ $size }) 超过物理内存与交换空间之和 ({ $capacity })，可能触发 OOM killer
+swap-alloc-progress = 交换压力: 已分配 { $allocated } / { $total }
+swap-alloc-stopped = 警告：分配失败，停止在 { $allocated } / { $total }
+
+## sync
+
+sync-server-started = 同步服务端已启动: { $addr }，等待 { $count } 个客户端
+sync-client-ready = 客户端 { $addr } 已就绪 ({ $ready }/{ $count })
+sync-invalid-client-message = 警告：忽略客户端 { $addr } 的无效消息: { $message }
+sync-broadcast-failed = 警告：向客户端 { $addr } 发送开始信号失败: { $error }
+sync-broadcast = 已向 { $count } 个客户端广播开始信号
+sync-connect-retry = 警告：无法连接同步服务端 { $addr } ({ $error })，稍后重试
+sync-connected = 已连接同步服务端 { $addr }，等待开始信号...
+sync-server-closed = 同步服务端关闭了连接
+sync-unexpected-message = 意外的消息: { $message }
+sync-go-received-latency = 收到开始信号，同步延迟 { $latency } ms（等待 { $waited } 秒）
+sync-go-received = 收到开始信号（等待 { $waited } 秒）
+sync-accept-failed = 警告：接受客户端连接失败: { $error }
+sync-client-read-failed = 警告：忽略客户端 { $addr }，读取就绪消息失败: { $error }
+sync-client-disconnected = 警告：客户端 { $addr } 已断开，等待新的客户端
+
+## syscalls
+
+syscalls-rates = 系统调用/s: { $syscalls }, 上下文切换/s: { $switches }
+syscalls-perf-disabled = 警告：将不统计系统调用，上下文切换改为读取 /proc/self/task
+syscalls-trace-failed = 警告：无法统计系统调用（需要可读的 tracefs 和 CAP_PERFMON）: { $error }
+syscalls-switches-failed = 警告：无法打开上下文切换计数器 ({ $error })，改为读取 /proc/self/task
+syscalls-unsupported = 警告：--trace-syscalls 仅在 Linux 上受支持
+syscalls-invalid-tracepoint = 无效的跟踪点ID
+
+## udpflood
+
+udp-started-rate = UDP回环压力: { $from } -> { $to }，限速 { $pps } 包/s
+udp-started = UDP回环压力: { $from } -> { $to }，不限速
+udp-stats = UDP: 发送 { $sent } 包/s, 接收 { $received } 包/s, 丢包率 { $loss }%, 平均单向延迟 { $latency } µs
+
+## diskstats
+
+diskstats-rates = { $name } 读 { $read }MiB/s 写 { $write }MiB/s 利用率 { $utilization }%
+diskstats-wait = 等待 { $wait }ms
+
+## netstats
+
+netstats-errors = 错误:{ $errors }
+netstats-detailed = { $rates } 包/s rx:{ $rx } tx:{ $tx }
+
+## output
+
+output-cpu-usage-target-noise = 当前CPU使用率: { $usage }% (目标: { $target }% ±{ $noise }%)
+output-cpu-usage-target = 当前CPU使用率: { $usage }% (目标: { $target }%)
+output-cpu-usage = 当前CPU使用率: { $usage }%
+output-burst-cycle = 脉冲周期 { $cycle }
+output-disk = 磁盘
+output-memory-usage = 当前内存使用: { $used }GB / { $total }GB (已分配: { $allocated }GB)
+output-memory-locked = (已锁定: { $locked }GB)
+output-table-phase = 阶段
+output-table-cpu = CPU使用率
+output-table-target = 目标使用率
+output-table-burst-cycle = 脉冲周期
+output-table-perf = 硬件计数器
+output-table-syscalls = 系统调用
+output-table-disk = 磁盘I/O
+output-table-network = 网络
+output-table-memory = 内存使用
+output-table-allocated = 已分配内存
+output-table-locked = 已锁定内存
+output-table-pressure = 内存压力
+output-thp-coverage = THP覆盖率: { $coverage }%
+output-table-thp = THP覆盖率
+output-cpu-steal = 窃取: { $percent }%
+output-table-steal = CPU窃取
+output-remaining = 剩余 { $remaining }
+output-table-remaining = 剩余时间
+output-table-ramp = 负载阶段
+output-schedule-remaining = 剩余 { $count } 个周期
+output-schedule-forever = 无限循环
+output-table-schedule = 循环负载
+output-io-stress = { $iops } IOPS, { $mb } MB/s
+output-table-io-stress = I/O压力
+
+## main
+
+lang-unsupported = 警告：不支持的语言 { $lang }（支持 { $supported }），将使用自动检测的语言
+random-seed = 随机种子: { $seed }（使用 --random-seed { $seed } 复现本次运行）
+
+## statefile
+
+statefile-write-failed = 警告：无法写入状态文件 { $path }: { $error }
+statefile-instance = 运行中的实例: PID { $pid }，启动于 { $started }，负载 { $profile }，{ $cores } 个核心，目标CPU使用率 { $target }%，内存 { $memory }
+
+## phases
+
+phases-length-mismatch = --phase-cores 有 { $cores } 项，而 --phase-durations 有 { $durations } 项，两者必须一一对应
+phases-all-idle = --phase-cores 中至少要有一个阶段的核心数大于0
+phases-mode = 分阶段核心数模式: 共 { $count } 个阶段，预先启动 { $workers } 个工作线程
+phases-transition = 阶段 { $phase }/{ $total }: 启用 { $cores } 个工作线程，持续 { $duration }
+phases-completed = 已完成全部 { $total } 个阶段，停止负载
+timeline-written = 时间线已写入 { $path }（{ $count } 个事件）
+timeline-hint = 可以在 https://ui.perfetto.dev 或 chrome://tracing 中打开查看
+timeline-write-failed = 警告：无法写入时间线文件 { $path }: { $error }
+ramp-exceeds-duration = --ramp-up 与 --ramp-down 之和不能超过 --duration
+ramp-phase-up = 负载爬升中
+ramp-phase-steady = 负载稳定
+ramp-phase-down = 负载下降中
+ramp-phase-changed = 进入阶段：{ $phase }
+phases-settings = 目标CPU使用率 { $target }%，占用内存 { $memory }
+phases-memory-failed = 警告：阶段内存分配 { $size } 失败，本阶段不占用内存
+
+## error
+
+pid-file-corrupted = PID文件 { $path } 已损坏，可执行 reset 清理
+pid-file-corrupted-overwrite = 警告：PID文件 { $path } 已损坏，将被覆盖
+
+## balloon
+
+balloon-started = 内存气球: 上限 { $max }，周期 { $period }，每块 { $chunk }
+balloon-event = 内存气球 第 { $cycle } 周期: { $event ->
+    [inflate-start] 开始膨胀
+    [inflate-done] 膨胀完成
+    [deflate-start] 开始收缩
+   *[deflate-done] 收缩完成
+}，当前占用 { $size }
+balloon-alloc-failed = 警告：内存气球分配 { $size } 失败，本周期停止膨胀
+
+## profile
+
+profile-duration-zero = 阶段 { $phase } 的 duration_secs 必须大于0
+profile-cpu-out-of-range = 阶段 { $phase } 的 target_percent 必须在0到100之间，实际为 { $value }
+profile-cores-invalid = 阶段 { $phase } 的 cores 必须大于0，实际为 { $value }
+profile-memory-invalid = 阶段 { $phase } 的 memory 无效: { $error }
+profile-workload-invalid = 阶段 { $phase } 的 workload 无效: { $value }（可选 { $choices }）
+profile-no-phases = 配置文件没有任何阶段（[[phases]]），总时长必须大于0
+profile-invalid = 配置文件 { $file } 有 { $count } 个错误
+profile-valid = 配置文件有效: { $file }
+profile-valid-named = 配置文件有效: { $file } ({ $name })
+profile-summary-phases = 阶段数: { $count }
+profile-summary-duration = 总时长: { $duration }
+profile-summary-cpu = 峰值CPU使用率: { $percent }%
+profile-summary-memory = 峰值内存: { $memory }
+profile-read-failed = 无法读取配置文件 { $file }: { $error }
+profile-workload-mixed = 负载配置文件的各阶段使用了不同的 workload，运行中不能切换负载类型
+profile-running = 按负载配置文件 { $file } 运行
+profile-running-named = 按负载配置文件 { $file }（{ $name }）运行
+
+## realtimelog
+
+realtime-log-started = 逐秒CPU使用率日志: { $path }（缓冲区 { $capacity } 条）
+realtime-log-open-failed = 警告：无法打开CPU使用率日志 { $path }: { $error }
+realtime-log-write-failed = 警告：写入CPU使用率日志 { $path } 失败: { $error }
+metrics-started = 指标写入 { $path }
+metrics-open-failed = 无法创建指标文件 { $path }：{ $error }
+metrics-write-failed = 警告：写入指标文件失败，不再写入：{ $error }
+
+## ipc
+
+ipc-started = IPC压力: { $kind }，{ $pairs } 对生产者/消费者线程，每条消息 { $size } 字节
+ipc-stats = IPC压力 ({ $kind }): { $messages } 条消息/s，{ $rate } MiB/s
+ipc-start-failed = 警告：无法启动IPC压力: { $error }
+ipc-unsupported = 警告：ipc-stress 负载仅在 Unix 平台上受支持
+
+## workload
+
+work-verify-baseline = 空转循环基准速率：{ $rate } 次迭代/秒
+work-verify-optimized = 警告：线程 { $thread } 的迭代速率 { $rate } 次/秒超过基准 { $baseline } 次/秒的5倍，空转循环可能已被优化掉，改用 volatile 读写的计算
+atomic-settings = 原子操作负载: 数组长度 { $size }，操作 { $op }
+atomic-stats = 原子操作: { $mops } 百万次/秒
+atomic-stats-cas = 原子操作: { $mops } 百万次/秒, CAS失败率 { $failed }%
+alloc-settings = 分配抖动负载: 每次分配 { $min } - { $max }，每个线程保留 { $pool } 个存活块
+alloc-stats = 分配: { $allocated }/s, 释放: { $freed }/s, 平均存活: { $live }
+alloc-size-invalid = 无效的分配大小 "{ $value }": { $error }
+alloc-size-zero = 大小必须大于0
+alloc-size-reversed = 分配大小的下限 { $min } 大于上限 { $max }
+work-mix-invalid = 无效的计算类型比例：{ $value }（格式为 类型:百分比，例如 matmul:50%）
+work-mix-unknown = 未知的计算类型：{ $value }（可选 trig、math、nop、volatile、matmul、sha256）
+work-mix-sum = --cpu-work-mix 的比例之和为 { $total }%，必须为100%
+work-mix-assigned = 计算类型 { $kind }（{ $percent }%）：{ $threads } 个线程
+
+## yieldto
+
+yield-watching = 让路监控: 名称匹配 { $pattern } 且 nice 值低于 { $floor } 的进程出现时降低本进程的优先级
+yield-started = 发现高优先级进程 { $name } (PID: { $pid }, nice { $nice })，本进程的 nice 值调整为 { $own }
+yield-restored = 高优先级进程已退出，恢复 nice 值 { $nice }
+yield-renice-failed = 警告：无法把 nice 值设为 { $nice }（提高优先级需要root权限）: { $error }
+yield-unsupported = 警告：--yield-to 仅在 Linux 上受支持
+
+## stop
+
+stop-signal-sending = 向负载进程 (PID: { $pid }) 发送信号 { $signal }
+signal-out-of-range = 信号编号 { $value } 不在1到64之间
+signal-unknown = 未知的信号 "{ $value }"，可用: { $names } 或编号1-64
+signal-unsupported = stop --signal 仅在 Unix 上受支持
+
+## memorypressure
+
+memory-psi = 内存压力 some: { $some10 }%/{ $some60 }%, full: { $full10 }%/{ $full60 }%
+memory-pressure-needs-root = 警告：--memory-pressure-test 需要root权限写入 /proc/sys/vm/drop_caches，已跳过内存压力测试
+memory-pressure-danger = 警告：内存压力测试每30秒向 /proc/sys/vm/drop_caches 写入 { $level }，会清空整个系统的缓存！其他进程的I/O延迟会突增，内存紧张时可能导致系统卡顿甚至触发OOM，请勿在生产环境使用
+memory-pressure-unsupported = 警告：当前平台不支持内存压力测试（需要Linux）
+memory-pressure-drop-failed = 警告：写入 /proc/sys/vm/drop_caches 失败，内存压力测试已停止: { $error }
+memory-pressure-baseline = 内存压力测试: 回收前重新分配 { $size } 的缺页耗时为 { $fault } µs/页
+memory-pressure-cycle = 内存压力测试 #{ $cycle }: 写入 drop_caches={ $level } 用时 { $drop } ms，重新访问 { $size } 用时 { $access } ms，重新分配缺页 { $fault } µs/页（基线的 { $ratio } 倍）
+
+## rayon
+
+rayon-started = Rayon 负载: 线程池 { $threads } 个线程，每个工作单元 { $iterations } 次迭代
+rayon-stats = Rayon: { $rate } M次迭代/s
+rayon-summary = Rayon 共完成 { $total } 次迭代，平均 { $rate } M次/s
+rayon-start-failed = 警告：创建 Rayon 线程池失败: { $error }
+
+## fdstress
+
+fd-type-unsupported = 当前平台不支持 { $kind } 类型的文件描述符
+fd-max-open = 同时打开的文件描述符最多 { $max } 个
+fd-limit-reached = 警告：已达到文件描述符上限（RLIMIT_NOFILE 软限制 { $soft }，硬限制 { $hard }），改用实际能打开的数量继续测试；可用 `ulimit -n <N>` 把软限制提高到硬限制以内，或在 /etc/security/limits.conf 中提高 nofile 硬限制（systemd 服务使用 LimitNOFILE=）
+fd-limit-reached-unknown = 警告：已达到文件描述符上限，改用实际能打开的数量继续测试
+fd-started = 文件描述符压力: 类型 { $kind }，每组 { $count } 个
+fd-open-failed = 警告：打开文件描述符失败，文件描述符压力已停止: { $error }
+fd-stats = 文件描述符: { $rate } 次操作/s（打开+关闭），每组打开 { $open } ms，关闭 { $close } ms
+fd-start-failed = 警告：启动文件描述符压力失败: { $error }
+pagecache-started = 页缓存压力启动: 文件 { $path }（{ $size }），{ $threads } 个读取线程
+pagecache-file-too-small = 文件小于一个页面（{ $size } 字节）
+pagecache-read-failed = 警告：读取文件失败，读取线程退出: { $error }
+pagecache-start-failed = 警告：无法启动页缓存压力: { $error }
+pagecache-stats = 页缓存读取: { $throughput } MiB/s（{ $iops } 次/秒），命中率 { $hit_rate }，主缺页 { $faults }
+
+## cachethrash
+
+thrash-settings = 缓存颠簸: 共享数组 { $size }（{ $lines } 条缓存行）
+thrash-stats = 缓存行写入: { $rate } M次/s, 推算一致性带宽 { $bandwidth } GB/s
+cache-alt-settings = 冷热缓存交替：热阶段每线程 { $warm } 缓冲区、持续 { $warm_period }，冷阶段共享 { $cold } 缓冲区、持续 { $cold_period }
+cache-alt-cold = 冷缓存
+cache-alt-warm = 热缓存
+cache-alt-transition = { $finished }阶段结束（每线程吞吐量 { $throughput } GB/s），切换到{ $next }阶段
+cache-alt-ratio = 热:冷吞吐量之比 { $ratio }
+interrupt-started = 信号打断测试已启动：每秒向工作线程发送 { $rate } 次 SIGUSR2
+interrupt-stats = 信号打断: 发送 { $sent } 次，计算中测得 { $measured } 次，开销 平均 { $mean } ns，p50/p99 = { $p50 }/{ $p99 } ns，最大 { $max } ns
+interrupt-summary = 信号打断汇总 - { $stats }
+interrupt-start-failed = 警告：无法启动信号打断测试: { $error }
+interrupt-unsupported = 警告：interruption-test 负载仅支持Unix，工作线程按普通计算负载运行
+
+## stepper
+
+step-range-invalid = 阶梯的起始值 { $start } 必须大于0且不大于终值 { $end }
+step-multiplier-invalid = --step-multiplier 必须大于1
+step-invalid-cores = 无效的核心数: { $value }
+step-cores = 阶梯 { $step }/{ $total }: 启用 { $cores } 个核心
+step-memory = 阶梯 { $step }/{ $total }: 已分配 { $size } 内存
+step-memory-failed = 警告：阶梯内存分配 { $size } 失败，阶梯已停止
+step-holding = 已到达最后一级阶梯，保持当前负载
+
+## hugepages
+
+thp-low-coverage = 警告：透明大页已设为 always，但本进程的THP覆盖率只有 { $coverage }%，内存碎片可能阻止了大页合并（可尝试 echo 1 > /proc/sys/vm/compact_memory）
+steal-high = 警告：CPU窃取时间过高（{ $percent }%），负载结果可能不准确
+steal-compensation-started = 窃取时间补偿已启用：每 { $secs } 秒按窃取时间调整占空比
+steal-compensation-adjusted = 窃取时间 { $steal }%，占空比 { $target }% -> { $percent }%
+steal-compensation-unavailable = 警告：无法读取 /proc/stat 中的窃取时间，--cpu-steal-compensation 不生效
+target-file-watching = 正在监视目标文件 { $path }，写入0-100的数值即可修改CPU目标使用率
+target-file-updated = 目标文件：CPU目标使用率 { $from }% -> { $to }%
+target-file-invalid = 警告：目标文件内容 { $value } 不是0-100的数值，保持当前目标
+target-file-inotify-failed = 警告：无法用 inotify 监视目标文件，改为每500ms读取一次: { $error }
+
+## socket
+
+socket-started = TCP连接压力：监听 { $addr }，{ $acceptors } 个接受线程，目标速率 { $rate } 连接/秒
+socket-rate-unlimited = 不限
+socket-stats = TCP连接: 接受 { $accepted } 次/s, 发起 { $connected } 次/s（目标: { $target }）, 失败 { $failed } 次
+socket-softnet = 网络软中断: CPU { $softirq }%, softnet 处理 { $processed } 包/s, 丢弃 { $dropped } 包
+socket-start-failed = 警告：无法启动TCP连接压力: { $error }
+socket-unsupported = 警告：socket-stress 负载仅在 Unix 平台上受支持
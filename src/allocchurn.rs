@@ -0,0 +1,175 @@
+//! 分配抖动负载：工作线程不断分配随机大小的堆块，只保留固定数量的存活块并释放最旧的块
+//!
+//! 分配和释放的模式与带GC的语言运行时相似，用于观察分配器在持续抖动下的开销和碎片。
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use bytesize::ByteSize;
+use rand::Rng;
+
+use crate::ratelimit::RateLimiter;
+use crate::seed;
+use crate::selfmonitor;
+use crate::state::LoadState;
+
+/// 输出统计的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 每轮执行的分配次数，之后检查运行标志并按占空比休眠
+const BURST_ALLOCS: u64 = 1_000;
+
+/// 分配参数
+#[derive(Clone, Copy, Debug)]
+pub struct ChurnSettings {
+    /// 每次分配的最小和最大字节数
+    pub min_size: usize,
+    pub max_size: usize,
+    /// 每个线程保留的存活块数
+    pub pool_size: usize,
+    /// 每个线程每秒的分配次数，None 表示不限速
+    pub rate: Option<f64>,
+}
+
+/// 所有工作线程共享的累计量
+#[derive(Default)]
+pub struct ChurnStats {
+    allocated_bytes: AtomicU64,
+    freed_bytes: AtomicU64,
+}
+
+impl ChurnStats {
+    /// 当前存活的字节数
+    fn live_bytes(&self) -> u64 {
+        let freed = self.freed_bytes.load(Ordering::Relaxed);
+        self.allocated_bytes.load(Ordering::Relaxed).saturating_sub(freed)
+    }
+}
+
+/// 解析分配大小范围，例如 "64-4K"；单个值表示固定大小
+pub fn parse_size_range(s: &str) -> Result<(usize, usize), String> {
+    let parse = |part: &str| -> Result<usize, String> {
+        let size = part.trim().parse::<ByteSize>().map_err(|e| tr!("alloc-size-invalid", value = part, error = e))?;
+        match size.as_u64() {
+            0 => Err(tr!("alloc-size-invalid", value = part, error = tr!("alloc-size-zero"))),
+            bytes => Ok(bytes as usize),
+        }
+    };
+    let (min, max) = match s.split_once('-') {
+        Some((min, max)) => (parse(min)?, parse(max)?),
+        None => {
+            let size = parse(s)?;
+            (size, size)
+        }
+    };
+    if min > max {
+        return Err(tr!("alloc-size-reversed", min = ByteSize::b(min as u64), max = ByteSize::b(max as u64)));
+    }
+    Ok((min, max))
+}
+
+/// 工作线程主循环，按目标使用率在分配和休眠之间切换，返回完成的分配次数
+pub fn churn_task(
+    state: &LoadState,
+    index: usize,
+    fixed_percent: Option<u8>,
+    limit: Option<u64>,
+    settings: ChurnSettings,
+    stats: &ChurnStats,
+) -> u64 {
+    let mut rng = seed::thread_rng(index);
+    let mut limiter = settings.rate.map(RateLimiter::new);
+    let mut pool: VecDeque<Box<[u8]>> = VecDeque::with_capacity(settings.pool_size);
+    let mut done = 0u64;
+    while state.is_running() {
+        let remaining = limit.map_or(u64::MAX, |limit| limit - done);
+        if remaining == 0 {
+            break;
+        }
+        state.wait_until_active(index);
+        let percent = state.thread_percent(index, fixed_percent);
+        if percent == 0 {
+            thread::sleep(crate::spin::IDLE_POLL_INTERVAL);
+            continue;
+        }
+        let percent = state.with_noise(percent, &mut rng);
+
+        let mut count = remaining.min(BURST_ALLOCS);
+        if let Some(limiter) = &mut limiter {
+            match limiter.take(count) {
+                Ok(granted) => count = granted,
+                Err(wait) => {
+                    thread::sleep(wait.min(crate::spin::IDLE_POLL_INTERVAL));
+                    continue;
+                }
+            }
+        }
+
+        let burst_start = Instant::now();
+        let (mut allocated, mut freed) = (0u64, 0u64);
+        for _ in 0..count {
+            if pool.len() >= settings.pool_size
+                && let Some(oldest) = pool.pop_front() {
+                freed += oldest.len() as u64;
+            }
+            let size = rng.gen_range(settings.min_size..=settings.max_size);
+            // 写入内容使分配的页面真正被使用
+            pool.push_back(vec![0x5A; size].into_boxed_slice());
+            allocated += size as u64;
+        }
+        stats.allocated_bytes.fetch_add(allocated, Ordering::Relaxed);
+        stats.freed_bytes.fetch_add(freed, Ordering::Relaxed);
+        done += count;
+
+        selfmonitor::duty_cycle_sleep(state.thread_stats(index), percent, count, burst_start.elapsed());
+    }
+    let remaining: u64 = pool.iter().map(|chunk| chunk.len() as u64).sum();
+    drop(pool);
+    stats.freed_bytes.fetch_add(remaining, Ordering::Relaxed);
+    done
+}
+
+/// 输出分配参数，并定期输出分配速率、释放速率和期间的平均存活大小
+pub fn spawn_reporter(settings: ChurnSettings, stats: Arc<ChurnStats>, state: Arc<LoadState>) -> JoinHandle<()> {
+    info!(
+        "{}",
+        tr!(
+            "alloc-settings",
+            min = ByteSize::b(settings.min_size as u64),
+            max = ByteSize::b(settings.max_size as u64),
+            pool = settings.pool_size
+        )
+    );
+    thread::spawn(move || {
+        let (mut last_allocated, mut last_freed) = (0u64, 0u64);
+        let (mut live_sum, mut samples) = (0u64, 0u64);
+        let mut last_report = Instant::now();
+        while state.is_running() {
+            thread::sleep(Duration::from_millis(100));
+            live_sum += stats.live_bytes();
+            samples += 1;
+            let elapsed = last_report.elapsed();
+            if elapsed < REPORT_INTERVAL {
+                continue;
+            }
+            let allocated = stats.allocated_bytes.load(Ordering::Relaxed);
+            let freed = stats.freed_bytes.load(Ordering::Relaxed);
+            let per_second = |bytes: u64| ByteSize::b((bytes as f64 / elapsed.as_secs_f64()) as u64);
+            info!(
+                "{}",
+                tr!(
+                    "alloc-stats",
+                    allocated = per_second(allocated - last_allocated),
+                    freed = per_second(freed - last_freed),
+                    live = ByteSize::b(live_sum / samples)
+                )
+            );
+            (last_allocated, last_freed) = (allocated, freed);
+            (live_sum, samples) = (0, 0);
+            last_report = Instant::now();
+        }
+    })
+}
@@ -0,0 +1,168 @@
+//! 原子操作负载：所有工作线程在同一个 `AtomicU64` 数组上随机执行 load、store 或 CAS 自增
+//!
+//! 全部操作使用 `SeqCst`，多个核心争用同一缓存行，用于考验缓存一致性协议（MESI 状态迁移）。
+//! 自增通过 `compare_exchange` 循环实现，失败次数反映争用程度。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use rand::rngs::SmallRng;
+use rand::Rng;
+
+use crate::seed;
+use crate::selfmonitor;
+use crate::state::LoadState;
+
+/// 输出统计的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 每轮执行的操作数，之后检查运行标志并按占空比休眠
+const BURST_OPS: u64 = 10_000;
+
+/// 执行的原子操作
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum AtomicOp {
+    /// 每次随机选择 load、store 或 rmw（默认）
+    #[default]
+    Mix,
+    /// 只读
+    Load,
+    /// 只写
+    Store,
+    /// 只做 compare_exchange 自增
+    Rmw,
+}
+
+impl AtomicOp {
+    fn name(self) -> String {
+        self.to_possible_value().map_or_else(String::new, |v| v.get_name().to_string())
+    }
+}
+
+/// 所有工作线程共享的数组和累计量
+pub struct AtomicStress {
+    array: Vec<AtomicU64>,
+    op: AtomicOp,
+    /// 已完成的操作数
+    ops: AtomicU64,
+    /// compare_exchange 的尝试次数和失败次数
+    cas_attempts: AtomicU64,
+    cas_failures: AtomicU64,
+}
+
+impl AtomicStress {
+    pub fn new(size: usize, op: AtomicOp) -> Self {
+        AtomicStress {
+            array: (0..size.max(1)).map(|_| AtomicU64::new(0)).collect(),
+            op,
+            ops: AtomicU64::new(0),
+            cas_attempts: AtomicU64::new(0),
+            cas_failures: AtomicU64::new(0),
+        }
+    }
+
+    /// 执行一轮操作，返回 compare_exchange 的尝试次数和失败次数
+    fn burst(&self, ops: u64, rng: &mut SmallRng) -> (u64, u64) {
+        let (mut attempts, mut failures) = (0u64, 0u64);
+        let mut sink = 0u64;
+        for _ in 0..ops {
+            let slot = &self.array[rng.gen_range(0..self.array.len())];
+            let op = match self.op {
+                AtomicOp::Mix => [AtomicOp::Load, AtomicOp::Store, AtomicOp::Rmw][rng.gen_range(0..3)],
+                op => op,
+            };
+            match op {
+                AtomicOp::Load => sink = sink.wrapping_add(slot.load(Ordering::SeqCst)),
+                AtomicOp::Store => slot.store(sink, Ordering::SeqCst),
+                _ => {
+                    let mut current = slot.load(Ordering::SeqCst);
+                    loop {
+                        attempts += 1;
+                        match slot.compare_exchange(current, current.wrapping_add(1), Ordering::SeqCst, Ordering::SeqCst) {
+                            Ok(_) => break,
+                            Err(actual) => {
+                                failures += 1;
+                                current = actual;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        std::hint::black_box(sink);
+        (attempts, failures)
+    }
+}
+
+/// 工作线程主循环，按目标使用率在原子操作和休眠之间切换，返回完成的操作数
+pub fn atomic_task(
+    state: &LoadState,
+    index: usize,
+    fixed_percent: Option<u8>,
+    limit: Option<u64>,
+    stress: &AtomicStress,
+) -> u64 {
+    let mut rng = seed::thread_rng(index);
+    let mut done = 0u64;
+    while state.is_running() {
+        let remaining = limit.map_or(u64::MAX, |limit| limit - done);
+        if remaining == 0 {
+            break;
+        }
+        state.wait_until_active(index);
+        let percent = state.thread_percent(index, fixed_percent);
+        if percent == 0 {
+            thread::sleep(crate::spin::IDLE_POLL_INTERVAL);
+            continue;
+        }
+        let percent = state.with_noise(percent, &mut rng);
+
+        let burst_start = Instant::now();
+        let ops = remaining.min(BURST_OPS);
+        // 每轮结束后才汇总到共享计数器，避免计数本身成为争用点
+        let (attempts, failures) = stress.burst(ops, &mut rng);
+        stress.ops.fetch_add(ops, Ordering::Relaxed);
+        stress.cas_attempts.fetch_add(attempts, Ordering::Relaxed);
+        stress.cas_failures.fetch_add(failures, Ordering::Relaxed);
+        done += ops;
+
+        selfmonitor::duty_cycle_sleep(state.thread_stats(index), percent, ops, burst_start.elapsed());
+    }
+    done
+}
+
+/// 输出数组大小和操作类型，并定期输出操作速率和 CAS 失败率
+pub fn spawn_reporter(stress: Arc<AtomicStress>, state: Arc<LoadState>) -> JoinHandle<()> {
+    info!("{}", tr!("atomic-settings", size = stress.array.len(), op = stress.op.name()));
+    thread::spawn(move || {
+        let (mut last_ops, mut last_attempts, mut last_failures) = (0u64, 0u64, 0u64);
+        let mut last_report = Instant::now();
+        while state.is_running() {
+            thread::sleep(Duration::from_millis(100));
+            let elapsed = last_report.elapsed();
+            if elapsed < REPORT_INTERVAL {
+                continue;
+            }
+            let ops = stress.ops.load(Ordering::Relaxed);
+            let attempts = stress.cas_attempts.load(Ordering::Relaxed);
+            let failures = stress.cas_failures.load(Ordering::Relaxed);
+            let mops = format!("{:.1}", (ops - last_ops) as f64 / elapsed.as_secs_f64() / 1e6);
+            match attempts - last_attempts {
+                0 => info!("{}", tr!("atomic-stats", mops = mops)),
+                delta => info!(
+                    "{}",
+                    tr!(
+                        "atomic-stats-cas",
+                        mops = mops,
+                        failed = format!("{:.1}", (failures - last_failures) as f64 * 100.0 / delta as f64)
+                    )
+                ),
+            }
+            (last_ops, last_attempts, last_failures) = (ops, attempts, failures);
+            last_report = Instant::now();
+        }
+    })
+}
@@ -0,0 +1,116 @@
+//! 按系统整体CPU使用率自动调整工作线程数和占空比
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use sysinfo::{CpuExt, System, SystemExt};
+
+use crate::state::LoadState;
+
+/// 控制器的采样间隔
+const CONTROL_TICK: Duration = Duration::from_secs(1);
+
+/// 阻尼时间常数：距上次调整经过 t 秒时修正误差的 t / DAMPING（最多全部修正），避免振荡
+const DAMPING: Duration = Duration::from_secs(5);
+
+/// 控制参数
+#[derive(Clone, Copy, Debug)]
+pub struct ControlSettings {
+    /// 目标系统整体CPU使用率（百分比）
+    pub target: u8,
+    /// 死区宽度（百分点）：误差不超过该值时不调整
+    pub hysteresis: u8,
+    /// 两次调整之间的最短间隔
+    pub damping_time: Duration,
+}
+
+/// 比例控制器，控制量是负载总量（以核心为单位，0 到 `max_workers`）
+struct Controller {
+    settings: ControlSettings,
+    max_workers: usize,
+    /// 系统逻辑CPU数，用于把百分点误差换算为核心数
+    cpus: f64,
+    load: f64,
+    last_change: Option<Instant>,
+}
+
+impl Controller {
+    /// 根据本次采样的系统CPU使用率更新负载总量，发生调整时返回调整前的负载
+    fn update(&mut self, usage: f64, now: Instant) -> Option<f64> {
+        let target = f64::from(self.settings.target);
+        let band = f64::from(self.settings.hysteresis);
+        let error = target - usage;
+        // 死区：实际值在 目标 ± 死区 之内时保持不变
+        if error.abs() <= band {
+            return None;
+        }
+        // 阻尼：距上次调整不足阻尼时间时不调整
+        let since_change = match self.last_change {
+            Some(last) if now.duration_since(last) < self.settings.damping_time => return None,
+            Some(last) => now.duration_since(last),
+            None => CONTROL_TICK,
+        };
+
+        let gain = (since_change.as_secs_f64() / DAMPING.as_secs_f64()).min(1.0);
+        let before = self.load;
+        self.load = (self.load + error / 100.0 * self.cpus * gain).clamp(0.0, self.max_workers as f64);
+        self.last_change = Some(now);
+        (self.load != before).then_some(before)
+    }
+}
+
+/// 启动控制线程，使系统整体平均CPU使用率（包括其他进程）接近目标值
+///
+/// 负载总量换算为启用的工作线程数和每个线程的占空比。
+pub fn spawn_controller(settings: ControlSettings, max_workers: usize, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut sys = System::new();
+        sys.refresh_cpu();
+        let mut controller = Controller {
+            settings,
+            max_workers,
+            cpus: sys.cpus().len().max(1) as f64,
+            load: 0.0,
+            last_change: None,
+        };
+        apply(controller.load, max_workers, &state);
+
+        let target = settings.target;
+        let band = settings.hysteresis;
+        while state.is_running() {
+            thread::sleep(CONTROL_TICK);
+            sys.refresh_cpu();
+            let usage = f64::from(sys.global_cpu_info().cpu_usage());
+
+            if let Some(before) = controller.update(usage, Instant::now()) {
+                let (active, percent) = apply(controller.load, max_workers, &state);
+                let direction = if usage < f64::from(target) { "below" } else { "above" };
+                info!(
+                    "{}",
+                    tr!(
+                        "autocores-adjusted",
+                        usage = format!("{:.1}", usage),
+                        direction = direction,
+                        target = target,
+                        band = band,
+                        before = format!("{:.2}", before),
+                        after = format!("{:.2}", controller.load),
+                        active = active,
+                        percent = percent
+                    )
+                );
+            }
+        }
+    })
+}
+
+/// 将负载总量换算为启用的线程数和占空比并写入共享状态
+fn apply(load: f64, max_workers: usize, state: &LoadState) -> (usize, u8) {
+    let active = (load.ceil() as usize).clamp(1, max_workers);
+    let percent = (load / active as f64 * 100.0).round().clamp(0.0, 100.0) as u8;
+    state.set_active_workers(active);
+    state.target_percent.store(percent, Ordering::Relaxed);
+    (active, percent)
+}
@@ -0,0 +1,114 @@
+//! 内存气球：周期性地逐块分配再释放内存，模拟虚拟化平台的气球驱动回收和归还客户机内存
+//!
+//! 每个周期的前半段从0线性膨胀到上限，后半段从尾部逐块释放回0，分块方式与内存增长模式相同。
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use bytesize::ByteSize;
+
+use crate::growth;
+use crate::state::LoadState;
+use crate::timeutil;
+
+/// 检查进度的间隔
+const TICK: Duration = Duration::from_millis(100);
+
+#[derive(Clone, Copy, Debug)]
+pub struct BalloonSettings {
+    /// 一次膨胀加一次收缩的周期
+    pub period: Duration,
+    /// 膨胀的上限（字节）
+    pub max: u64,
+}
+
+/// 气球所处的阶段
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Inflate,
+    Deflate,
+}
+
+/// 启动内存气球线程，负载停止时释放全部内存
+pub fn spawn_balloon(settings: BalloonSettings, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let half = settings.period / 2;
+        // 按半个周期内膨胀到上限所需的速率分块
+        let rate = (settings.max as f64 / half.as_secs_f64()) as u64;
+        let chunk_size = growth::chunk_size(Some(rate.max(1)));
+        info!(
+            "{}",
+            tr!(
+                "balloon-started",
+                max = ByteSize::b(settings.max),
+                period = format!("{:?}", settings.period),
+                chunk = ByteSize::b(chunk_size)
+            )
+        );
+
+        let mut chunks: Vec<Vec<u8>> = Vec::new();
+        let mut size = 0u64;
+        let mut cycle = 0u32;
+        'cycles: while state.is_running() {
+            cycle += 1;
+            for phase in [Phase::Inflate, Phase::Deflate] {
+                report(phase, false, cycle, size);
+                let start = Instant::now();
+                // 分配失败后本次膨胀不再重试，避免每个检查间隔都输出警告
+                let mut exhausted = false;
+                loop {
+                    if !state.is_running() {
+                        break 'cycles;
+                    }
+                    let progress = (start.elapsed().as_secs_f64() / half.as_secs_f64()).min(1.0);
+                    let target = match phase {
+                        Phase::Inflate => (settings.max as f64 * progress) as u64,
+                        Phase::Deflate => (settings.max as f64 * (1.0 - progress)) as u64,
+                    };
+                    match phase {
+                        Phase::Inflate => {
+                            while !exhausted && size < target {
+                                let len = chunk_size.min(settings.max - size);
+                                let Some(chunk) = growth::alloc_resident(len) else {
+                                    warn!("{}", tr!("balloon-alloc-failed", size = ByteSize::b(len)));
+                                    exhausted = true;
+                                    break;
+                                };
+                                chunks.push(chunk);
+                                size += len;
+                                state.balloon_bytes.store(size, Ordering::Relaxed);
+                            }
+                        }
+                        Phase::Deflate => {
+                            while size > target && let Some(chunk) = chunks.pop() {
+                                size -= chunk.len() as u64;
+                                drop(chunk);
+                                state.balloon_bytes.store(size, Ordering::Relaxed);
+                            }
+                        }
+                    }
+                    if progress >= 1.0 {
+                        break;
+                    }
+                    growth::sleep_while_running(&state, TICK);
+                }
+                report(phase, true, cycle, size);
+            }
+        }
+        state.balloon_bytes.store(0, Ordering::Relaxed);
+        drop(chunks);
+    })
+}
+
+/// 带时间戳输出阶段的开始或结束，以及当前占用的大小
+fn report(phase: Phase, done: bool, cycle: u32, size: u64) {
+    let event = match (phase, done) {
+        (Phase::Inflate, false) => "inflate-start",
+        (Phase::Inflate, true) => "inflate-done",
+        (Phase::Deflate, false) => "deflate-start",
+        (Phase::Deflate, true) => "deflate-done",
+    };
+    info!("[{}] {}", timeutil::now_rfc3339(), tr!("balloon-event", cycle = cycle, event = event, size = ByteSize::b(size)));
+}
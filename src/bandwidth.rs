@@ -0,0 +1,186 @@
+//! 内存带宽模式：只读、只写或拷贝，分别测量内存子系统各部分的带宽
+//!
+//! 默认的内存访问模式对每个字节做读改写，读写混在一起无法区分瓶颈。这里按块顺序扫过已分配的内存，
+//! 只读和只写都通过 `ptr::read_volatile`/`ptr::write_volatile` 逐字进行，防止 LLVM 合并或消除访问。
+//! `copy` 模式把每个线程负责的一段内存分成等大的两半，在两半之间 memcpy；拷贝每个字节都要读一次、
+//! 写一次，理论峰值是只读与只写带宽之和的一半，启动时先分别测量只读和只写带宽，以实际拷贝带宽
+//! 与理论峰值之比作为内存控制器效率。
+
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+
+use crate::state::LoadState;
+
+/// 每次持有内存锁的时长，之后释放锁以便控制接口替换内存
+const LOCK_SLICE: Duration = Duration::from_millis(50);
+
+/// 每次检查时间片是否用完之前处理的字节数
+const BLOCK: usize = 64 * 1024;
+
+/// 输出带宽的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// `copy` 模式启动时测量只读、只写带宽各用的时长
+const CALIBRATION: Duration = Duration::from_millis(500);
+
+/// 内存带宽模式
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum BandwidthMode {
+    /// 只读：逐字读取，不写回
+    ReadOnly,
+    /// 只写：逐字写入0，不读取
+    WriteOnly,
+    /// 在两个等大的缓冲区之间 memcpy
+    Copy,
+}
+
+impl BandwidthMode {
+    fn name(self) -> &'static str {
+        match self {
+            BandwidthMode::ReadOnly => "read-only",
+            BandwidthMode::WriteOnly => "write-only",
+            BandwidthMode::Copy => "copy",
+        }
+    }
+}
+
+/// 只读扫过 `words`，逐字读取
+fn read_block(words: &[u64]) {
+    let mut sum = 0u64;
+    for word in words {
+        // SAFETY: word 是有效且对齐的引用
+        sum ^= unsafe { std::ptr::read_volatile(word) };
+    }
+    std::hint::black_box(sum);
+}
+
+/// 只写填充 `words`，逐字写入0
+fn write_block(words: &mut [u64]) {
+    for word in words {
+        // SAFETY: word 是有效且对齐的引用
+        unsafe { std::ptr::write_volatile(word, 0) };
+    }
+}
+
+/// 在 `deadline` 之前按 `mode` 顺序扫过 `chunk`，从 `offset`（字）继续，返回处理的字节数；
+/// `copy` 模式返回拷贝的字节数（读写各一次）
+fn run_chunk(mode: BandwidthMode, chunk: &mut [u8], offset: &mut usize, deadline: Instant) -> u64 {
+    // SAFETY: 任意字节序列都是合法的 u64，align_to_mut 只取出对齐的中间部分
+    let (_, words, _) = unsafe { chunk.align_to_mut::<u64>() };
+    // copy 模式把这一段分成等大的两半，从前一半拷贝到后一半
+    let half = words.len() / 2;
+    let span = if mode == BandwidthMode::Copy { half } else { words.len() };
+    if span == 0 {
+        return 0;
+    }
+    let mut bytes = 0u64;
+    while Instant::now() < deadline {
+        if *offset >= span {
+            *offset = 0;
+        }
+        let range = *offset..(*offset + BLOCK / 8).min(span);
+        let len = range.len();
+        match mode {
+            BandwidthMode::ReadOnly => read_block(&words[range]),
+            BandwidthMode::WriteOnly => write_block(&mut words[range]),
+            BandwidthMode::Copy => {
+                let (src, dst) = words.split_at_mut(half);
+                dst[range.clone()].copy_from_slice(&src[range.clone()]);
+                std::hint::black_box(&dst[range.start]);
+            }
+        }
+        *offset += len;
+        bytes += len as u64 * 8;
+    }
+    bytes
+}
+
+/// 持有内存锁一个时间片，由 `offsets.len()` 个线程各自扫过等分的一段；内存不足时返回 None
+fn run_slice(mode: BandwidthMode, state: &LoadState, offsets: &mut [usize]) -> Option<(u64, Duration)> {
+    let mut guard = state.memory.lock().unwrap_or_else(|e| e.into_inner());
+    let buffer = guard.as_deref_mut().filter(|b| b.len() >= offsets.len() * 16)?;
+    let segment = buffer.len() / offsets.len();
+    let start = Instant::now();
+    let deadline = start + LOCK_SLICE;
+    let bytes = thread::scope(|scope| {
+        let workers: Vec<_> = buffer
+            .chunks_mut(segment)
+            .zip(offsets.iter_mut())
+            .map(|(chunk, offset)| scope.spawn(move || run_chunk(mode, chunk, offset, deadline)))
+            .collect();
+        workers.into_iter().map(|w| w.join().unwrap_or(0)).sum()
+    });
+    Some((bytes, start.elapsed()))
+}
+
+/// 以 `mode` 持续运行 `duration`，返回带宽（GB/s）；内存不可用时返回 None
+fn measure(mode: BandwidthMode, duration: Duration, state: &LoadState, offsets: &mut [usize]) -> Option<f64> {
+    let (mut bytes, mut time) = (0u64, Duration::ZERO);
+    while time < duration && state.is_running() {
+        let (b, t) = run_slice(mode, state, offsets)?;
+        bytes += b;
+        time += t;
+    }
+    Some(gbps(bytes, time))
+}
+
+fn gbps(bytes: u64, time: Duration) -> f64 {
+    bytes as f64 / time.as_secs_f64().max(f64::EPSILON) / 1e9
+}
+
+/// 启动内存带宽线程，`threads` 个线程同时扫过内存
+pub fn spawn_bandwidth_test(mode: BandwidthMode, threads: usize, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut offsets = vec![0usize; threads.max(1)];
+        info!("{}", tr!("bandwidth-started", mode = mode.name(), count = offsets.len()));
+        // copy 模式的理论峰值：只读与只写带宽之和的一半
+        let peak = if mode == BandwidthMode::Copy {
+            let read = measure(BandwidthMode::ReadOnly, CALIBRATION, &state, &mut offsets);
+            let write = measure(BandwidthMode::WriteOnly, CALIBRATION, &state, &mut offsets);
+            offsets.iter_mut().for_each(|o| *o = 0);
+            match (read, write) {
+                (Some(read), Some(write)) => {
+                    info!(
+                        "{}",
+                        tr!("bandwidth-calibration", read = format!("{:.2}", read), write = format!("{:.2}", write))
+                    );
+                    Some((read + write) / 2.0)
+                }
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        let (mut bytes, mut time) = (0u64, Duration::ZERO);
+        let mut last_report = Instant::now();
+        while state.is_running() {
+            let Some((b, t)) = run_slice(mode, &state, &mut offsets) else {
+                thread::sleep(Duration::from_millis(100));
+                continue;
+            };
+            bytes += b;
+            time += t;
+            if last_report.elapsed() < REPORT_INTERVAL {
+                continue;
+            }
+            let bandwidth = gbps(bytes, time);
+            info!("{}", tr!("bandwidth-rate", mode = mode.name(), bandwidth = format!("{:.2}", bandwidth)));
+            if let Some(peak) = peak.filter(|&p| p > 0.0) {
+                info!(
+                    "  {}",
+                    tr!(
+                        "bandwidth-efficiency",
+                        peak = format!("{:.2}", peak),
+                        efficiency = format!("{:.1}", bandwidth * 100.0 / peak)
+                    )
+                );
+            }
+            (bytes, time) = (0, Duration::ZERO);
+            last_report = Instant::now();
+        }
+    })
+}
@@ -0,0 +1,138 @@
+//! 固定次数的负载脉冲：每个周期先按目标使用率负载，再空闲，完成后自动退出
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use sysinfo::{CpuExt, System, SystemExt};
+
+use crate::json::Json;
+use crate::output::OutputFormat;
+use crate::state::LoadState;
+use crate::timeutil;
+
+/// 检查运行标志的间隔
+const DRIVER_TICK: Duration = Duration::from_millis(100);
+
+/// 测量CPU使用率的采样间隔
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 脉冲配置
+#[derive(Clone, Debug)]
+pub struct BurstPattern {
+    /// 周期数
+    pub count: u32,
+    /// 每个周期的负载时长
+    pub on: Duration,
+    /// 每个周期的空闲时长
+    pub off: Duration,
+    /// 负载阶段的目标使用率
+    pub percent: u8,
+    /// 参与负载的核心数，测量时只统计这些核心
+    pub cores: usize,
+}
+
+/// 一个阶段内测得的平均CPU使用率
+#[derive(Default)]
+struct PhaseAverage {
+    sum: f64,
+    samples: u32,
+}
+
+impl PhaseAverage {
+    fn get(&self) -> Option<f64> {
+        (self.samples > 0).then(|| self.sum / f64::from(self.samples))
+    }
+}
+
+#[derive(Default)]
+struct CycleResult {
+    on: PhaseAverage,
+    off: PhaseAverage,
+}
+
+/// 启动脉冲驱动线程，全部周期完成后停止负载并输出每个周期的汇总
+pub fn spawn_burst_driver(burst: BurstPattern, format: OutputFormat, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut sys = System::new();
+        sys.refresh_cpu();
+        let mut results: Vec<CycleResult> = Vec::new();
+        let mut completed = 0;
+
+        'cycles: for cycle in 1..=burst.count {
+            state.burst_cycle.store(cycle, Ordering::Relaxed);
+            let mut result = CycleResult::default();
+            for (on, len) in [(true, burst.on), (false, burst.off)] {
+                let percent = if on { burst.percent } else { 0 };
+                state.target_percent.store(percent, Ordering::Relaxed);
+                report_transition(format, cycle, burst.count, on, percent);
+
+                let phase = if on { &mut result.on } else { &mut result.off };
+                let start = Instant::now();
+                let mut last_sample = Instant::now();
+                while start.elapsed() < len {
+                    if !state.is_running() {
+                        results.push(result);
+                        break 'cycles;
+                    }
+                    thread::sleep(DRIVER_TICK);
+                    if last_sample.elapsed() >= SAMPLE_INTERVAL {
+                        sys.refresh_cpu();
+                        phase.sum += average_usage(&sys, burst.cores);
+                        phase.samples += 1;
+                        last_sample = Instant::now();
+                    }
+                }
+            }
+            results.push(result);
+            completed += 1;
+        }
+
+        print_summary(&results, completed, burst.count);
+        if state.is_running() {
+            info!("{}", tr!("burst-finished", count = burst.count));
+            state.finish();
+        }
+    })
+}
+
+fn average_usage(sys: &System, cores: usize) -> f64 {
+    let cores = cores.max(1);
+    sys.cpus().iter().take(cores).map(|cpu| f64::from(cpu.cpu_usage())).sum::<f64>() / cores as f64
+}
+
+/// 输出周期切换事件，JSON 输出格式下为单行JSON
+fn report_transition(format: OutputFormat, cycle: u32, total: u32, on: bool, percent: u8) {
+    if format == OutputFormat::Json {
+        let event = Json::object([
+            ("event", "burst_transition".into()),
+            ("timestamp_ms", timeutil::unix_millis().into()),
+            ("cycle", cycle.into()),
+            ("total_cycles", total.into()),
+            ("state", if on { "on" } else { "off" }.into()),
+            ("target_percent", percent.into()),
+        ]);
+        info!("{}", event);
+    } else {
+        info!(
+            "[{}] {}",
+            timeutil::now_rfc3339(),
+            tr!("burst-transition", cycle = cycle, total = total, state = if on { "on" } else { "off" }, percent = percent)
+        );
+    }
+}
+
+/// 输出每个周期的测量结果，`completed` 之后的周期为中途停止的周期
+fn print_summary(results: &[CycleResult], completed: usize, total: u32) {
+    let format = |avg: Option<f64>| avg.map_or_else(|| "-".to_string(), |v| format!("{:.1}%", v));
+    info!("{}", tr!("burst-summary-header", completed = completed, total = total));
+    for (i, result) in results.iter().enumerate() {
+        let line = tr!("burst-summary-cycle", cycle = i + 1, on = format(result.on.get()), off = format(result.off.get()));
+        if i < completed {
+            info!("  {}", line);
+        } else {
+            info!("  {} {}", line, tr!("burst-summary-incomplete"));
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! 冷热缓存交替负载：工作线程在放不进L3的大缓冲区和放得进L2的小缓冲区之间按时间交替顺序读取
+//!
+//! 冷阶段所有线程顺序扫过共享的64MiB缓冲区，每次读取都要从内存取数据；热阶段每个线程反复扫过
+//! 自己的256KiB缓冲区，数据一直留在L2中。两个阶段执行完全相同的计算，只有工作集大小不同，
+//! 热、冷阶段每个线程忙碌时间内的吞吐量之比即为该访问模式下缓存未命中带来的性能落差。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use bytesize::ByteSize;
+
+use crate::selfmonitor;
+use crate::state::LoadState;
+use crate::timeutil;
+
+/// 热阶段每个线程的缓冲区大小，放得进L2
+const WARM_SIZE: usize = 256 * 1024;
+
+/// 冷阶段共享的缓冲区大小，放不进L3
+const COLD_SIZE: usize = 64 * 1024 * 1024;
+
+/// 每轮读取的字节数，之后检查运行标志和当前阶段并按占空比休眠
+const BURST_BYTES: usize = 1024 * 1024;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Phase {
+    Cold,
+    Warm,
+}
+
+impl Phase {
+    fn index(self) -> usize {
+        match self {
+            Phase::Cold => 0,
+            Phase::Warm => 1,
+        }
+    }
+
+    fn label(self) -> String {
+        match self {
+            Phase::Cold => tr!("cache-alt-cold"),
+            Phase::Warm => tr!("cache-alt-warm"),
+        }
+    }
+}
+
+/// 冷阶段的共享缓冲区、阶段时长和每个阶段的累计读取量
+pub struct CacheAlternating {
+    cold: Vec<u64>,
+    cold_period: Duration,
+    warm_period: Duration,
+    start: Instant,
+    /// 每个阶段累计读取的字节数，按 [`Phase::index`] 索引
+    bytes: [AtomicU64; 2],
+    /// 每个阶段所有线程累计的忙碌时间（纳秒）
+    busy_nanos: [AtomicU64; 2],
+}
+
+impl CacheAlternating {
+    /// `enabled` 为 false 时不分配冷缓冲区
+    pub fn new(enabled: bool, cold_period: Duration, warm_period: Duration) -> Self {
+        // 用不同的值填充，避免全零页面被内核映射到同一个物理页
+        let cold = if enabled { (0..(COLD_SIZE / 8) as u64).collect() } else { Vec::new() };
+        CacheAlternating {
+            cold,
+            cold_period,
+            warm_period,
+            start: Instant::now(),
+            bytes: Default::default(),
+            busy_nanos: Default::default(),
+        }
+    }
+
+    /// 从开始运行到现在所处的阶段，先冷后热
+    fn phase(&self) -> Phase {
+        let cycle = self.cold_period + self.warm_period;
+        let offset = self.start.elapsed().as_nanos() % cycle.as_nanos().max(1);
+        if offset < self.cold_period.as_nanos() { Phase::Cold } else { Phase::Warm }
+    }
+}
+
+/// 从 `offset`（字）开始顺序读取 `buffer` 中的 `words` 个字，到末尾后回到开头；
+/// 只做加法，累加之间没有依赖，吞吐量受限于数据供给而不是计算
+fn scan(buffer: &[u64], offset: &mut usize, mut words: usize) -> u64 {
+    let mut acc = 0u64;
+    while words > 0 && !buffer.is_empty() {
+        if *offset >= buffer.len() {
+            *offset = 0;
+        }
+        let end = (*offset + words).min(buffer.len());
+        acc = buffer[*offset..end].iter().fold(acc, |acc, &word| acc.wrapping_add(word));
+        words -= end - *offset;
+        *offset = end;
+    }
+    acc
+}
+
+/// 工作线程主循环，按目标使用率在读取和休眠之间切换，返回完成的轮数
+pub fn alternating_task(
+    state: &LoadState,
+    index: usize,
+    fixed_percent: Option<u8>,
+    limit: Option<u64>,
+    shared: &CacheAlternating,
+) -> u64 {
+    let mut rng = crate::seed::thread_rng(index);
+    let warm: Vec<u64> = (0..(WARM_SIZE / 8) as u64).collect();
+    // 各线程从冷缓冲区的不同位置开始，避免同时读取同一段内存
+    let mut cold_offset = index * BURST_BYTES / 8 % shared.cold.len().max(1);
+    let mut warm_offset = 0usize;
+    let mut done = 0u64;
+    while state.is_running() {
+        if limit.is_some_and(|limit| done >= limit) {
+            break;
+        }
+        state.wait_until_active(index);
+        let percent = state.thread_percent(index, fixed_percent);
+        if percent == 0 {
+            thread::sleep(crate::spin::IDLE_POLL_INTERVAL);
+            continue;
+        }
+        let percent = state.with_noise(percent, &mut rng);
+
+        let phase = shared.phase();
+        let burst_start = Instant::now();
+        let acc = match phase {
+            Phase::Cold => scan(&shared.cold, &mut cold_offset, BURST_BYTES / 8),
+            Phase::Warm => scan(&warm, &mut warm_offset, BURST_BYTES / 8),
+        };
+        std::hint::black_box(acc);
+        let busy = burst_start.elapsed();
+        shared.bytes[phase.index()].fetch_add(BURST_BYTES as u64, Ordering::Relaxed);
+        shared.busy_nanos[phase.index()].fetch_add(busy.as_nanos() as u64, Ordering::Relaxed);
+        done += 1;
+
+        selfmonitor::duty_cycle_sleep(state.thread_stats(index), percent, 1, busy);
+    }
+    done
+}
+
+/// 每线程忙碌时间内的吞吐量（GB/s）
+fn throughput(bytes: u64, busy_nanos: u64) -> Option<f64> {
+    (busy_nanos > 0).then(|| bytes as f64 / busy_nanos as f64)
+}
+
+/// 输出缓冲区大小，并在每次阶段切换时输出上一阶段的吞吐量以及热冷吞吐量之比
+pub fn spawn_reporter(shared: Arc<CacheAlternating>, state: Arc<LoadState>) -> JoinHandle<()> {
+    info!(
+        "{}",
+        tr!(
+            "cache-alt-settings",
+            warm = ByteSize::b(WARM_SIZE as u64),
+            cold = ByteSize::b(COLD_SIZE as u64),
+            warm_period = format!("{:?}", shared.warm_period),
+            cold_period = format!("{:?}", shared.cold_period)
+        )
+    );
+    thread::spawn(move || {
+        let mut phase = shared.phase();
+        let mut last = [(0u64, 0u64); 2];
+        // 最近一个完整阶段的吞吐量，按 Phase::index 索引
+        let mut rates: [Option<f64>; 2] = [None, None];
+        while state.is_running() {
+            thread::sleep(Duration::from_millis(100));
+            let current = shared.phase();
+            if current == phase {
+                continue;
+            }
+            let i = phase.index();
+            let now = (shared.bytes[i].load(Ordering::Relaxed), shared.busy_nanos[i].load(Ordering::Relaxed));
+            rates[i] = throughput(now.0 - last[i].0, now.1 - last[i].1);
+            last[i] = now;
+            let rate = rates[i].map_or_else(|| "-".to_string(), |r| format!("{:.2}", r));
+            info!(
+                "[{}] {}",
+                timeutil::now_rfc3339(),
+                tr!("cache-alt-transition", finished = phase.label(), throughput = rate, next = current.label())
+            );
+            if let [Some(cold), Some(warm)] = rates
+                && cold > 0.0
+            {
+                info!("  {}", tr!("cache-alt-ratio", ratio = format!("{:.2}", warm / cold)));
+            }
+            phase = current;
+        }
+    })
+}
@@ -0,0 +1,118 @@
+//! 缓存颠簸负载：所有工作线程向同一个共享数组中随机的缓存行写入
+//!
+//! 数组默认4MiB，能放进L3但放不进L2。每次写入都要求该缓存行在本核心上处于 Modified 状态，
+//! 其他核心上的副本随之失效（M→I），一致性互连上持续有失效广播和缓存行迁移。
+//! 写入速率乘以缓存行大小即为推算的一致性带宽，不需要硬件计数器。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use bytesize::ByteSize;
+use rand::rngs::SmallRng;
+use rand::Rng;
+
+use crate::seed;
+use crate::selfmonitor;
+use crate::state::LoadState;
+
+/// 输出统计的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 每轮执行的写入次数，之后检查运行标志并按占空比休眠
+const BURST_WRITES: u64 = 10_000;
+
+/// 缓存行大小
+const CACHE_LINE: usize = 64;
+
+/// 按缓存行对齐的一行，保证每次写入只落在一条缓存行上
+#[repr(align(64))]
+struct Line([AtomicU64; CACHE_LINE / 8]);
+
+/// 所有工作线程共享的数组和写入计数
+pub struct CacheThrash {
+    lines: Vec<Line>,
+    writes: AtomicU64,
+}
+
+impl CacheThrash {
+    pub fn new(size: usize) -> Self {
+        CacheThrash {
+            lines: (0..(size / CACHE_LINE).max(1)).map(|_| Line(Default::default())).collect(),
+            writes: AtomicU64::new(0),
+        }
+    }
+
+    /// 向随机的缓存行写入 `writes` 次；用 Relaxed 原子写入避免数据竞争，又不引入额外的内存屏障
+    fn burst(&self, writes: u64, rng: &mut SmallRng) {
+        for i in 0..writes {
+            let line = &self.lines[rng.gen_range(0..self.lines.len())];
+            line.0[0].store(i, Ordering::Relaxed);
+        }
+    }
+}
+
+/// 工作线程主循环，按目标使用率在写入和休眠之间切换，返回完成的写入次数
+pub fn thrash_task(
+    state: &LoadState,
+    index: usize,
+    fixed_percent: Option<u8>,
+    limit: Option<u64>,
+    thrash: &CacheThrash,
+) -> u64 {
+    let mut rng = seed::thread_rng(index);
+    let mut done = 0u64;
+    while state.is_running() {
+        let remaining = limit.map_or(u64::MAX, |limit| limit - done);
+        if remaining == 0 {
+            break;
+        }
+        state.wait_until_active(index);
+        let percent = state.thread_percent(index, fixed_percent);
+        if percent == 0 {
+            thread::sleep(crate::spin::IDLE_POLL_INTERVAL);
+            continue;
+        }
+        let percent = state.with_noise(percent, &mut rng);
+
+        let burst_start = Instant::now();
+        let writes = remaining.min(BURST_WRITES);
+        thrash.burst(writes, &mut rng);
+        // 每轮结束后才汇总到共享计数器，避免计数器本身成为争用最激烈的缓存行
+        thrash.writes.fetch_add(writes, Ordering::Relaxed);
+        done += writes;
+
+        selfmonitor::duty_cycle_sleep(state.thread_stats(index), percent, writes, burst_start.elapsed());
+    }
+    done
+}
+
+/// 输出数组大小，并定期输出合计写入速率和推算的一致性带宽
+pub fn spawn_reporter(thrash: Arc<CacheThrash>, state: Arc<LoadState>) -> JoinHandle<()> {
+    let lines = thrash.lines.len();
+    info!("{}", tr!("thrash-settings", size = ByteSize::b((lines * CACHE_LINE) as u64), lines = lines));
+    thread::spawn(move || {
+        let mut last = 0u64;
+        let mut last_report = Instant::now();
+        while state.is_running() {
+            thread::sleep(Duration::from_millis(100));
+            let elapsed = last_report.elapsed();
+            if elapsed < REPORT_INTERVAL {
+                continue;
+            }
+            let writes = thrash.writes.load(Ordering::Relaxed);
+            let rate = (writes - last) as f64 / elapsed.as_secs_f64();
+            info!(
+                "{}",
+                tr!(
+                    "thrash-stats",
+                    rate = format!("{:.1}", rate / 1e6),
+                    bandwidth = format!("{:.2}", rate * CACHE_LINE as f64 / 1e9)
+                )
+            );
+            last = writes;
+            last_report = Instant::now();
+        }
+    })
+}
@@ -0,0 +1,164 @@
+//! `benchmark all-workloads`：在单个线程上依次满载运行各种计算负载，比较它们在本机上的吞吐量
+//!
+//! 每种 `--busy-loop-type` 以及 zstd-compress、atomic-stress 各运行 `--duration`，按每个工作单位的
+//! 耗时从高到低排列（越靠前，每次迭代占用的CPU时间越多），吞吐量以 math（trig）为基准 1.0× 归一化。
+//! 结果同时写入 `~/.config/enhance-cpu-memory/calibration.json`，供之后按负载类型换算占空比使用。
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+
+use crate::atomicstress::{self, AtomicOp, AtomicStress};
+use crate::compress::{self, CompressSettings, CompressStats};
+use crate::error::LoadError;
+use crate::json::Json;
+use crate::state::LoadState;
+use crate::timeutil;
+use crate::workload::{self, BusyLoop};
+
+/// 每轮空转的迭代次数，与工作线程一致
+const BURST_ITERATIONS: u64 = 20_000;
+
+/// zstd-compress 使用的压缩级别和块大小，与命令行默认值一致
+const COMPRESS_SETTINGS: CompressSettings = CompressSettings { level: 3, block_size: 64 * 1024 };
+
+/// atomic-stress 的数组长度，与命令行默认值一致
+const ATOMIC_ARRAY_SIZE: usize = 1024;
+
+/// 一种负载的测量结果
+struct Measurement {
+    name: String,
+    /// 工作单位的名称，例如"次迭代"
+    unit: String,
+    /// 每秒完成的工作单位数
+    per_sec: f64,
+    /// 每秒处理的数据量（MB/s），只有压缩负载有
+    mb_per_sec: Option<f64>,
+}
+
+impl Measurement {
+    fn nanos_per_unit(&self) -> f64 {
+        1e9 / self.per_sec.max(f64::EPSILON)
+    }
+}
+
+/// 在当前线程上以100%使用率运行 `task`，`duration` 后停止，返回每秒完成的工作单位数
+fn measure_task(duration: Duration, task: impl FnOnce(&LoadState) -> u64) -> f64 {
+    let state = Arc::new(LoadState::new(100, None));
+    let timer = {
+        let state = state.clone();
+        thread::spawn(move || {
+            thread::sleep(duration);
+            state.finish();
+        })
+    };
+    let start = Instant::now();
+    let done = task(&state);
+    let elapsed = start.elapsed();
+    let _ = timer.join();
+    done as f64 / elapsed.as_secs_f64().max(f64::EPSILON)
+}
+
+/// 校准结果的保存位置：`$XDG_CONFIG_HOME` 或 `~/.config` 下的 `enhance-cpu-memory/calibration.json`
+fn calibration_path() -> Option<PathBuf> {
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config.join("enhance-cpu-memory").join("calibration.json"))
+}
+
+/// 依次测量所有负载，输出对比表并保存结果
+pub fn run(duration: Duration) -> Result<(), LoadError> {
+    let mut results = Vec::new();
+    for &kind in BusyLoop::value_variants() {
+        info!("{}", tr!("calibration-running", workload = kind.name(), secs = duration.as_secs_f64()));
+        results.push(Measurement {
+            name: kind.name(),
+            unit: tr!("calibration-unit-iteration"),
+            per_sec: workload::calibrate(kind, duration, BURST_ITERATIONS),
+            mb_per_sec: None,
+        });
+    }
+
+    info!("{}", tr!("calibration-running", workload = "zstd-compress", secs = duration.as_secs_f64()));
+    let stats = CompressStats::default();
+    let per_sec = measure_task(duration, |state| {
+        compress::compress_task(state, 0, Some(100), None, COMPRESS_SETTINGS, &stats)
+    });
+    results.push(Measurement {
+        name: "zstd-compress".to_string(),
+        unit: tr!("calibration-unit-block"),
+        per_sec,
+        mb_per_sec: Some(per_sec * COMPRESS_SETTINGS.block_size as f64 / 1e6),
+    });
+
+    info!("{}", tr!("calibration-running", workload = "atomic-stress", secs = duration.as_secs_f64()));
+    let stress = AtomicStress::new(ATOMIC_ARRAY_SIZE, AtomicOp::Mix);
+    results.push(Measurement {
+        name: "atomic-stress".to_string(),
+        unit: tr!("calibration-unit-op"),
+        per_sec: measure_task(duration, |state| atomicstress::atomic_task(state, 0, Some(100), None, &stress)),
+        mb_per_sec: None,
+    });
+
+    let baseline = results
+        .iter()
+        .find(|m| m.name == BusyLoop::Math.name())
+        .map_or(1.0, |m| m.per_sec)
+        .max(f64::EPSILON);
+    results.sort_by(|a, b| b.nanos_per_unit().total_cmp(&a.nanos_per_unit()));
+
+    info!("{}", tr!("calibration-header", baseline = BusyLoop::Math.name()));
+    for m in &results {
+        let throughput = match m.mb_per_sec {
+            Some(mb) => format!("{:.0} {}/s ({:.1} MB/s)", m.per_sec, m.unit, mb),
+            None => format!("{:.0} {}/s", m.per_sec, m.unit),
+        };
+        info!(
+            "  {:<14} {:>12.1} ns  {:>8.3}×  {}",
+            m.name,
+            m.nanos_per_unit(),
+            m.per_sec / baseline,
+            throughput
+        );
+    }
+
+    let report = Json::object([
+        ("created_at", Json::from(timeutil::now_rfc3339())),
+        ("duration_secs", duration.as_secs_f64().into()),
+        ("baseline", BusyLoop::Math.name().into()),
+        (
+            "workloads",
+            Json::Array(
+                results
+                    .iter()
+                    .map(|m| {
+                        Json::object([
+                            ("name", Json::from(m.name.as_str())),
+                            ("per_sec", m.per_sec.into()),
+                            ("ns_per_unit", m.nanos_per_unit().into()),
+                            ("relative", (m.per_sec / baseline).into()),
+                            ("mb_per_sec", m.mb_per_sec.into()),
+                        ])
+                    })
+                    .collect(),
+            ),
+        ),
+    ]);
+    let Some(path) = calibration_path() else {
+        warn!("{}", tr!("calibration-no-home"));
+        return Ok(());
+    };
+    let saved = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| std::fs::write(&path, format!("{}\n", report)));
+    match saved {
+        Ok(()) => info!("{}", tr!("calibration-saved", path = path.display())),
+        Err(e) => warn!("{}", tr!("calibration-save-failed", path = path.display(), error = e)),
+    }
+    Ok(())
+}
@@ -0,0 +1,3056 @@
+//! 命令行程序：参数定义、子命令分发，以及 `start`/`io-stress` 的完整负载流程（状态输出、PID文件、各类辅助负载）
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use std::sync::{Arc, Barrier, mpsc, atomic::{AtomicBool, Ordering}};
+use std::collections::HashMap;
+use std::thread;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+use sysinfo::{System, SystemExt, CpuExt};
+use std::fs::OpenOptions;
+use std::io::{Write, Seek, SeekFrom};
+use std::path::{PathBuf, Path};
+use std::process;
+use std::str::FromStr;
+use bytesize::ByteSize;
+use rand::RngCore;
+use serde::Serialize;
+
+use crate::{
+    allocchurn, atomicstress, autocores, balloon, bandwidth, burst, cachealternate, cachethrash, calibration,
+    compaction, compress, cpufreq, diskio, diskstats, failalloc, fdstress, growth, hooks, i18n, instances, iostress,
+    irqaffinity, json, logger, memory, memorypressure, memstress, metrics, mlock, netstats, numa, numalatency,
+    objects, output, pagecache, pattern, perf, phases, profile, ramp, rayonpool, realtimelog, reset, residency,
+    sched, schedule, seed, sigadjust, signals, spawnbench, spin, statefile, status, steal, swap, sync,
+    syscalls, targetfile, thermal, timeline, timeutil, topology, udpflood, workload,
+};
+#[cfg(unix)]
+use crate::{control, interruption, ipcstress, notify, socketstress};
+#[cfg(target_os = "linux")]
+use crate::{freqtrace, yieldto};
+
+use crate::allocchurn::{ChurnSettings, ChurnStats};
+use crate::atomicstress::{AtomicOp, AtomicStress};
+use crate::autocores::ControlSettings;
+use crate::balloon::BalloonSettings;
+use crate::bandwidth::BandwidthMode;
+use crate::burst::BurstPattern;
+use crate::cachealternate::CacheAlternating;
+use crate::cachethrash::CacheThrash;
+use crate::compress::{CompressSettings, CompressStats};
+use crate::cpufreq::{Governor, TurboBoost};
+use crate::engine::{Engine, Periodic};
+use crate::config::LoadConfig;
+use crate::error::LoadError;
+use crate::fdstress::FdType;
+use crate::growth::GrowthSettings;
+use crate::hugepages::ThpMonitor;
+use crate::iostress::{IoCounters, IoMonitor, IoStressSettings};
+use crate::instances::{read_pid, remove_pid_file, save_pid};
+#[cfg(unix)]
+use crate::interruption::InterruptionTest;
+use crate::logger::RotationInterval;
+use crate::memory::MemorySpec;
+use crate::memorypressure::{MemoryPsi, PressureLevel};
+use crate::memstress::{AccessThreads, MemoryPattern};
+use crate::objects::DeallocPattern;
+use crate::output::{OutputFormat, RunPhase, Stats};
+use crate::residency::ResidencyCheck;
+use crate::sched::RtPolicy;
+use crate::selfmonitor::SelfMonitor;
+use crate::spin::{SpinSettings, WORK_BURST_ITERATIONS, cpu_intensive_task};
+use crate::spawnbench::SpawnMethod;
+use crate::state::LoadState;
+use crate::status::StatusReport;
+use crate::steal::StealMonitor;
+use crate::stepper::{CoreStepper, MemoryStepper};
+use crate::summary::RunSummary;
+use crate::pattern::{SignalSpike, SpikePattern};
+use crate::phases::PhasePlan;
+use crate::ramp::{RampPhase, RampSettings, RampTarget};
+use crate::perf::{PerfCounters, PerfEvent};
+use crate::workload::{BusyLoop, IpcType, Workload};
+
+#[derive(Parser)]
+#[command(author, version, about = "一个简易的CPU、内存和硬盘负载工具", long_about = None)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Commands>,
+
+    #[command(flatten)]
+    load: LoadArgs,
+
+    /// 静默模式，不向标准输出/标准错误打印任何信息
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// 将日志发送到系统日志（Unix为syslog，Windows为事件日志，隐含 --quiet）
+    #[arg(long, global = true)]
+    syslog: bool,
+
+    /// 同时把日志追加到该文件，每行带时间戳，不受 --quiet 影响；
+    /// 后台运行时进程的标准输出和错误也写入该文件，未指定时写入临时目录中的 `enhancecpu-<实例名称>.log`
+    #[arg(long, global = true)]
+    log_file: Option<PathBuf>,
+
+    /// 日志文件按时间轮转：hourly 每小时、daily 每天零点（本地时区）换到 <文件名>-YYYY-MM-DDTHH.log 或 <文件名>-YYYY-MM-DD.log
+    #[arg(long, global = true, value_enum, default_value_t = RotationInterval::Never, requires = "log_file")]
+    log_rotation_interval: RotationInterval,
+
+    /// 按时间轮转时最多保留的旧日志文件数
+    #[arg(long, global = true, default_value_t = 7, requires = "log_file")]
+    log_keep_files: usize,
+
+    /// 输出语言（zh-CN 或 en-US），默认按 LC_ALL、LC_MESSAGES、LANG 自动检测
+    #[arg(long, global = true, value_name = "LOCALE")]
+    lang: Option<String>,
+}
+
+/// 负载参数，顶层命令与 `start` 子命令共用
+#[derive(Args, Clone)]
+struct LoadArgs {
+    /// 要使用的CPU核心数量，默认为系统核心数的一半（至少为1），指定 --cpu-affinity 时为列表中的CPU数
+    #[arg(short, long)]
+    cores: Option<usize>,
+
+    /// 要占用的内存大小（例如："1G"、"512M"，或系统总内存的百分比如"50%"）
+    #[arg(short, long)]
+    memory: Option<String>,
+
+    /// 要占用的硬盘大小（例如："1G"或"512M"）
+    #[arg(short = 'd', long)]
+    disk: Option<String>,
+
+    /// 状态输出中只统计名称匹配该正则表达式的磁盘（含分区），默认为除 loop、ram 外的整块磁盘
+    #[arg(long, value_parser = diskstats::parse_disk_filter)]
+    disk_filter: Option<regex::Regex>,
+
+    /// 状态输出中只统计名称匹配该正则表达式的网络接口，默认为除回环接口 lo 外的所有接口
+    #[arg(long, value_parser = netstats::parse_net_filter)]
+    net_filter: Option<regex::Regex>,
+
+    /// 硬盘占用文件的存储路径，默认为当前目录
+    #[arg(short = 'p', long, default_value = ".")]
+    path: String,
+
+    /// 是否在后台运行
+    #[arg(short, long)]
+    background: bool,
+
+    /// 实例名称，PID文件为临时目录中的 `enhancecpu-<名称>.pid`；名称不同的实例可以同时运行
+    #[arg(long, default_value = instances::DEFAULT_NAME, value_parser = instances::parse_name)]
+    instance_name: String,
+
+    /// Windows 后台模式重新启动的子进程带有该参数，PID文件中已是自己的PID
+    #[arg(long = "_child", hide = true)]
+    background_child: bool,
+
+    /// 所有工作线程启动后向该文件描述符写入 `\n` 作为就绪通知（仅Unix）
+    #[arg(long)]
+    notify_fd: Option<i32>,
+
+    /// 后台模式下父进程等待子进程就绪的超时时间（秒），超时后以退出码1退出
+    #[arg(long, default_value_t = 10)]
+    notify_timeout: u64,
+
+    /// 在PID文件旁写入二进制状态文件（`enhancecpu-<名称>.state`），记录PID、启动时间和负载参数，
+    /// 负载参数变化时随之更新
+    #[arg(long)]
+    compress_pid_file: bool,
+
+    /// 随机负载（随机访问、alloc-churn、噪声等）的伪随机数种子，用于复现一次运行；默认随机生成并在启动时输出
+    #[arg(long)]
+    random_seed: Option<u64>,
+
+    /// 负载类型
+    #[arg(long, value_enum, default_value_t = Workload::Spin)]
+    workload: Workload,
+
+    /// spin 负载空转时执行的计算类型
+    #[arg(long, alias = "workload-type", value_enum, default_value_t = BusyLoop::Math)]
+    busy_loop_type: BusyLoop,
+
+    /// memory-bound 计算类型每个线程的缓冲区大小（例如："8MiB"），L2、L3 或内存延迟取决于它能否放进对应的缓存
+    #[arg(long, value_parser = parse_byte_size, default_value = "8MiB")]
+    cache_size: ByteSize,
+
+    /// spin 负载每轮计算内两次检查运行标志之间的迭代次数
+    #[arg(long, default_value_t = spin::DEFAULT_WORK_BATCH, value_parser = clap::value_parser!(u64).range(1..))]
+    work_batch: u64,
+
+    /// 启动时测量空转循环的迭代速率，运行中速率超过基准5倍时认为循环被优化掉并切换到更难优化的计算
+    #[arg(long)]
+    cpu_work_verify: bool,
+
+    /// 按比例混合多种计算类型，例如 `trig:25%,matmul:50%,sha256:25%`，比例之和必须为100；
+    /// 线程按比例向下取整分配，余数分给比例最高的一项
+    #[arg(long, value_delimiter = ',', value_parser = workload::parse_mix_entry, conflicts_with_all = ["busy_loop_type", "cpu_work_verify"])]
+    cpu_work_mix: Vec<(BusyLoop, u8)>,
+
+    /// 通过 tracefs 的 power:cpu_frequency 事件记录运行期间每个负载核心的每次调频，
+    /// 结束时输出各核心的平均、最低、最高频率和频率分布直方图（仅Linux，需要root权限）
+    #[arg(long)]
+    cpufreq_report: bool,
+
+    /// 运行结束时在汇总中输出负载阶段CPU使用率的文本直方图和变异系数
+    #[arg(long)]
+    cpu_utilization_histogram: bool,
+
+    /// CPU使用率直方图的区间数（1-100），默认20个区间即每个5%
+    #[arg(long, requires = "cpu_utilization_histogram", default_value_t = 20,
+          value_parser = clap::value_parser!(u8).range(1..=100))]
+    histogram_bins: u8,
+
+    /// 可接受的CPU窃取时间（百分比，仅Linux），状态刷新间隔内超过时告警，负载阶段平均值超过时在汇总中标记结果可能不准确
+    #[arg(long, default_value_t = steal::DEFAULT_MAX_STEAL, value_parser = parse_percent_f64)]
+    max_acceptable_steal: f64,
+
+    /// 每个工作线程记录实际的忙碌和休眠时间，随状态输出目标与实际占空比，偏差超过10个百分点时告警
+    #[arg(long)]
+    self_monitor: bool,
+
+    /// udp-flood 负载的发送速率（包/秒），默认不限速
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    udp_pps: Option<u64>,
+
+    /// socket-stress 负载每秒发起的连接数，默认不限速
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    connection_rate: Option<u64>,
+
+    /// socket-stress 负载的接受线程数（1-256），多个时各自以 SO_REUSEPORT 监听同一端口
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..=256))]
+    acceptor_threads: u16,
+
+    /// page-cache 负载读取的文件，默认在 -p 目录下创建 --file-size 大小的临时文件
+    #[arg(long)]
+    file: Option<PathBuf>,
+
+    /// page-cache 负载创建的临时文件大小（例如："4G"），宜大于可用内存以产生页缓存回收
+    #[arg(long, value_parser = parse_byte_size, default_value = "1G", conflicts_with = "file")]
+    file_size: ByteSize,
+
+    /// page-cache 负载的读取线程数（1-256）
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..=256))]
+    read_threads: u16,
+
+    /// ipc-stress 负载的通道类型
+    #[arg(long, value_enum, default_value_t = IpcType::Pipe)]
+    ipc_type: IpcType,
+
+    /// ipc-stress 负载的生产者/消费者线程对数（1-256）
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..=256))]
+    ipc_threads: u16,
+
+    /// zstd-compress 负载的压缩级别（1-22）
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(i32).range(1..=22))]
+    compress_level: i32,
+
+    /// zstd-compress 负载每次压缩的数据块大小（例如："64K"、"1M"）
+    #[arg(long, value_parser = parse_byte_size, default_value = "64K")]
+    compress_block_size: ByteSize,
+
+    /// atomic-stress 负载共享的 AtomicU64 数组长度
+    #[arg(long, default_value_t = 1024, value_parser = clap::value_parser!(u64).range(1..=1 << 24))]
+    atomic_array_size: u64,
+
+    /// atomic-stress 负载执行的原子操作
+    #[arg(long, value_enum, default_value_t = AtomicOp::Mix)]
+    atomic_op: AtomicOp,
+
+    /// cache-thrash 负载共享数组的大小（例如："4MiB"），宜放得进L3但放不进L2
+    #[arg(long, value_parser = parse_byte_size, default_value = "4MiB")]
+    shared_array_size: ByteSize,
+
+    /// cache-alternating 负载每个冷缓存阶段的时长（例如："10s"）
+    #[arg(long, value_parser = parse_duration, default_value = "10s")]
+    cold_period: Duration,
+
+    /// cache-alternating 负载每个热缓存阶段的时长（例如："10s"）
+    #[arg(long, value_parser = parse_duration, default_value = "10s")]
+    warm_period: Duration,
+
+    /// interruption-test 负载每秒向工作线程发送的 SIGUSR2 总数，轮流发给各线程
+    #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u64).range(1..=1_000_000))]
+    interrupt_rate: u64,
+
+    /// alloc-churn 负载每次分配的大小范围（例如："64-4K"），单个值表示固定大小
+    #[arg(long, value_parser = allocchurn::parse_size_range, default_value = "64-4K")]
+    alloc_size: (usize, usize),
+
+    /// alloc-churn 负载每秒的总分配次数，平均分配到各工作线程，默认不限速
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    alloc_rate: Option<u64>,
+
+    /// alloc-churn 负载每个线程保留的存活块数
+    #[arg(long, default_value_t = 1024, value_parser = clap::value_parser!(u64).range(1..))]
+    alloc_pool_size: u64,
+
+    /// fd-stress 负载每组同时打开的文件描述符数，超出 RLIMIT_NOFILE 时改用实际能打开的最大数量
+    #[arg(long, default_value_t = 10000, value_parser = clap::value_parser!(u64).range(1..))]
+    fd_count: u64,
+
+    /// fd-stress 负载打开的文件描述符类型
+    #[arg(long, value_enum, default_value_t = FdType::Null)]
+    fd_type: FdType,
+
+    /// 对硬盘占用文件并发随机读写的线程数（队列深度，1-256）
+    #[arg(long, requires = "disk", value_parser = clap::value_parser!(u16).range(1..=256))]
+    io_depth: Option<u16>,
+
+    /// 根据系统整体CPU使用率自动调整工作线程数（最多为系统核心数）和占空比
+    #[arg(long, requires = "target_system_cpu", conflicts_with_all = ["cpu_spike_to", "burst_count"])]
+    auto_cores: bool,
+
+    /// --auto-cores 的目标系统整体CPU使用率（百分比，1-100），包括其他进程的占用
+    #[arg(long, requires = "auto_cores", value_parser = clap::value_parser!(u8).range(1..=100))]
+    target_system_cpu: Option<u8>,
+
+    /// --auto-cores 的死区宽度（百分点）：系统CPU使用率偏离目标不超过该值时不调整
+    #[arg(long, requires = "auto_cores", default_value_t = 5, value_parser = clap::value_parser!(u8).range(0..=50))]
+    hysteresis: u8,
+
+    /// --auto-cores 两次调整之间的最短间隔（秒）
+    #[arg(long, requires = "auto_cores", default_value_t = 10)]
+    damping_time: u64,
+
+    /// 基础CPU使用率（百分比，1-100），默认为100；超过100时按100处理，只需内存负载时使用 `-c 0`
+    #[arg(long, aliases = ["cpu-percent", "target-percent"], value_parser = parse_cpu_base)]
+    cpu_base: Option<u8>,
+
+    /// 按线程覆盖CPU使用率（例如："0:95,2:30"），未列出的线程使用 --cpu-base；也可写作 --core-loads。
+    /// 序号是工作线程的编号（逻辑序号），不是物理核心编号；列出的序号超出 --cores 时改用较大的线程数
+    #[arg(long, alias = "core-loads", value_parser = parse_thread_overrides)]
+    thread_overrides: Option<HashMap<usize, u8>>,
+
+    /// 尖峰时的CPU使用率（百分比，0-100）
+    #[arg(long, alias = "spike-to", value_parser = clap::value_parser!(u8).range(0..=100),
+          requires = "cpu_spike_duration")]
+    cpu_spike_to: Option<u8>,
+
+    /// 每次尖峰的保持时间（例如："5s"）
+    #[arg(long, alias = "spike-duration", value_parser = parse_duration, requires = "cpu_spike_to")]
+    cpu_spike_duration: Option<Duration>,
+
+    /// 尖峰的触发周期（例如："60s"）
+    #[arg(long, value_parser = parse_duration, requires = "cpu_spike_to")]
+    cpu_spike_every: Option<Duration>,
+
+    /// 收到 SIGUSR1 时触发一次尖峰，代替 --cpu-spike-every 的周期触发（仅Unix）
+    #[arg(long, requires = "cpu_spike_to", conflicts_with = "cpu_spike_every")]
+    spike_on_signal: bool,
+
+    /// 运行中收到 SIGUSR1 时加大、收到 SIGUSR2 时减小目标使用率（percent）或启用的工作线程数（cores），
+    /// 新的取值输出到标准错误（仅Unix）
+    #[arg(long, value_enum,
+          conflicts_with_all = ["spike_on_signal", "auto_cores", "phase_cores", "step_cores", "burst_count",
+                                "cpu_spike_to", "ramp_up", "ramp_down", "profile"])]
+    signal_adjust: Option<sigadjust::AdjustTarget>,
+
+    /// --signal-adjust 每个信号的调整量，percent 默认10个百分点，cores 默认1个线程
+    #[arg(long, requires = "signal_adjust", value_parser = clap::value_parser!(u32).range(1..))]
+    signal_step: Option<u32>,
+
+    /// 温度保护：每5秒读取CPU温度，超过 --temp-max 时把目标使用率降低10个百分点（不低于5%），降到上限以下5°C后恢复
+    #[arg(long)]
+    cpu_thermal_protect: bool,
+
+    /// 温度保护的上限（摄氏度），默认为85
+    #[arg(long, requires = "cpu_thermal_protect", default_value_t = 85,
+          value_parser = clap::value_parser!(u8).range(30..=120))]
+    temp_max: u8,
+
+    /// 负载脉冲的周期数，完成全部周期后自动退出
+    #[arg(long, requires_all = ["burst_on_secs", "burst_off_secs"], conflicts_with = "cpu_spike_to",
+          value_parser = clap::value_parser!(u32).range(1..))]
+    burst_count: Option<u32>,
+
+    /// 每个脉冲周期的负载秒数
+    #[arg(long, requires = "burst_count")]
+    burst_on_secs: Option<u64>,
+
+    /// 每个脉冲周期的空闲秒数（CPU负载为0%）
+    #[arg(long, requires = "burst_count")]
+    burst_off_secs: Option<u64>,
+
+    /// 循环负载（例如："work=10s,pause=5s,repeat=4"）：按 --cpu-base 负载 work，再停顿 pause，重复 repeat 次后退出；
+    /// repeat 为0时一直循环，指定 --duration 时到期即停止
+    #[arg(long, value_parser = parse_load_schedule,
+          conflicts_with_all = ["burst_count", "cpu_spike_to", "auto_cores", "phase_cores", "step_cores",
+                                "ramp_up", "ramp_down", "profile", "signal_adjust"])]
+    load_schedule: Option<schedule::Schedule>,
+
+    /// 各阶段启用的工作线程数（例如："2,8,4"），按最大值预先启动工作线程，与 --phase-durations 一一对应
+    #[arg(long, value_delimiter = ',', requires = "phase_durations", conflicts_with = "auto_cores")]
+    phase_cores: Vec<usize>,
+
+    /// 按 TOML 负载配置文件依次执行各阶段（核心数、目标使用率、内存），全部阶段完成后自动退出；格式见 `validate-profile`
+    #[arg(long, conflicts_with_all = ["memory", "cpu_base", "duration", "phase_cores", "step_cores", "step_memory",
+          "auto_cores", "burst_count", "cpu_spike_to", "ramp_up", "ramp_down"])]
+    profile: Option<PathBuf>,
+
+    /// 各阶段的持续时间（例如："30s,2m,30s"），全部阶段完成后自动退出
+    #[arg(long, value_delimiter = ',', value_parser = parse_duration, requires = "phase_cores")]
+    phase_durations: Vec<Duration>,
+
+    /// 启动后在该时长内把启用的工作线程数从0线性增加到全部（例如："30s"）；指定 --cpu-base 时改为把占空比从0增加到该值
+    #[arg(long, value_parser = parse_duration,
+          conflicts_with_all = ["auto_cores", "phase_cores", "step_cores", "burst_count", "cpu_spike_to"])]
+    ramp_up: Option<Duration>,
+
+    /// 在 --duration 的最后这段时间内把负载线性减小到0后停止（例如："30s"）
+    #[arg(long, value_parser = parse_duration, requires = "duration",
+          conflicts_with_all = ["auto_cores", "phase_cores", "step_cores", "burst_count", "cpu_spike_to"])]
+    ramp_down: Option<Duration>,
+
+    /// 阶梯核心数：从 --step-start 个核心开始，每隔 --step-interval 增加 --step-size 个，到 --step-end 后保持
+    #[arg(long, requires_all = ["step_start", "step_end"], conflicts_with_all = ["auto_cores", "phase_cores", "step_memory"])]
+    step_cores: bool,
+
+    /// 阶梯内存：从 --step-start 开始，每隔 --step-interval 乘以 --step-multiplier，到 --step-end 后保持
+    #[arg(long, requires_all = ["step_start", "step_end"], conflicts_with = "memory")]
+    step_memory: bool,
+
+    /// 阶梯的起始值：--step-cores 为核心数，--step-memory 为内存大小（例如："256M"）
+    #[arg(long)]
+    step_start: Option<String>,
+
+    /// 阶梯的终值，格式同 --step-start
+    #[arg(long)]
+    step_end: Option<String>,
+
+    /// 每级阶梯的持续时间（例如："30s"、"1m"）
+    #[arg(long, value_parser = parse_duration, default_value = "30s")]
+    step_interval: Duration,
+
+    /// --step-cores 每级增加的核心数
+    #[arg(long, requires = "step_cores", default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    step_size: u64,
+
+    /// --step-memory 每级的倍数，必须大于1
+    #[arg(long, requires = "step_memory", default_value_t = 2.0)]
+    step_multiplier: f64,
+
+    /// 所有工作线程启动后执行的命令（Unix 用 sh -c，Windows 用 cmd.exe /c），输出写入日志
+    #[arg(long)]
+    post_start_cmd: Option<String>,
+
+    /// 停止负载前执行的命令，例如采集 perf 或堆转储
+    #[arg(long)]
+    pre_stop_cmd: Option<String>,
+
+    /// 钩子命令的超时时间（秒），超时后终止钩子并继续
+    #[arg(long, default_value_t = 30)]
+    hook_timeout: u64,
+
+    /// 负载持续时间（例如："30s"、"5m"、"1h"），到时自动停止
+    #[arg(long, value_parser = parse_duration)]
+    duration: Option<Duration>,
+
+    /// 从该文件读取CPU目标使用率（0-100），文件内容变化时更新目标，例如 `echo 80 > /tmp/cpu_target`；
+    /// Linux 上用 inotify 监视，其他平台每500ms读取一次
+    #[arg(long, alias = "cpu-usage-target-override-via-file")]
+    target_file: Option<PathBuf>,
+
+    /// 按 `/proc/stat` 中的窃取时间提高工作线程的占空比（最高99%），使虚拟机内实际得到的CPU时间达到目标（仅Linux）
+    #[arg(long)]
+    cpu_steal_compensation: bool,
+
+    /// 每个工作周期在目标使用率上叠加 ±N 个百分点的均匀随机扰动（0-20），使负载曲线更接近真实服务
+    #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u8).range(0..=20))]
+    cpu_noise: u8,
+
+    /// 负载期间临时切换工作线程所用CPU的调频策略（仅Linux，需要root权限），结束后恢复
+    #[arg(long, value_enum)]
+    cpufreq_governor: Option<Governor>,
+
+    /// 目标使用率不低于90%时检查CPU0的调频策略和频率范围，可能导致负载偏低时告警（仅Linux）
+    #[arg(long, conflicts_with_all = ["cpufreq_governor", "cpu_governor_profile"])]
+    cpu_governor_aware: bool,
+
+    /// 从JSON文件读取每个核心的调频策略和频率范围（kHz）并写入（仅Linux，需要root权限），结束后恢复
+    #[arg(long, conflicts_with = "cpufreq_governor")]
+    cpu_governor_profile: Option<PathBuf>,
+
+    /// 负载期间开启或关闭睿频（仅Linux，需要root权限），结束后恢复；auto 不修改
+    #[arg(long, value_enum, default_value_t = TurboBoost::Auto)]
+    turbo_boost: TurboBoost,
+
+    /// 负载期间停止 irqbalance，把所有中断绑定到第一个不参与负载的CPU（仅Linux，需要root权限），结束后恢复；
+    /// 可能影响多队列网卡的网络性能，需要同时指定 --force
+    #[arg(long)]
+    irq_balance_disable: bool,
+
+    /// 确认执行可能影响整机的操作（--irq-balance-disable）
+    #[arg(long)]
+    force: bool,
+
+    /// 工作线程的实时调度策略（仅Linux，需要root权限）
+    #[arg(long, value_enum, requires = "rt_priority")]
+    rt_policy: Option<RtPolicy>,
+
+    /// 实时调度优先级（1-99）
+    #[arg(long, requires = "rt_policy", value_parser = clap::value_parser!(u8).range(1..=99))]
+    rt_priority: Option<u8>,
+
+    /// 发现进程名匹配该正则表达式且优先级高于下限的进程时，临时把本进程的 nice 值调到19，进程退出后恢复（仅Linux）
+    #[arg(long, value_parser = diskstats::parse_disk_filter)]
+    yield_to: Option<regex::Regex>,
+
+    /// --yield-to 的优先级下限：匹配进程的 nice 值低于该值时才让路（-20到19）
+    #[arg(long, default_value_t = 0, allow_negative_numbers = true, value_parser = clap::value_parser!(i32).range(-20..=19))]
+    yield_priority_floor: i32,
+
+    /// 按物理插槽交错地把工作线程绑定到CPU：线程0到插槽0、线程1到插槽1……（仅Linux）
+    #[arg(long)]
+    cpu_affinity_interleave: bool,
+
+    /// 把工作线程依次绑定到这些逻辑CPU（例如："0,2,4"、"4-7"，仅Linux）：线程 i 绑定到列表中的第 i 个CPU
+    #[arg(long, conflicts_with_all = ["cpu_affinity_interleave", "hyper_threads_only", "numa_bind_all"])]
+    cpu_affinity: Option<String>,
+
+    /// 只在每个物理核心的第二个逻辑CPU（超线程兄弟）上运行工作线程，主线程留给其他负载（仅Linux）
+    #[arg(long, conflicts_with = "cpu_affinity_interleave")]
+    hyper_threads_only: bool,
+
+    /// 把状态刷新线程绑定到该逻辑CPU（仅Linux），工作线程默认不使用该CPU，满载时状态输出也不会被延迟
+    #[arg(long, alias = "pin-status-thread")]
+    status_core: Option<usize>,
+
+    /// 允许工作线程与状态线程共用 --status-core 指定的CPU
+    #[arg(long, requires = "status_core")]
+    allow_status_core_sharing: bool,
+
+    /// 只从该NUMA节点分配内存（仅Linux）；默认不做NUMA绑定
+    #[arg(long)]
+    numa_node: Option<usize>,
+
+    /// 同时把所有线程限制在 --numa-node 节点的CPU上，工作线程依次绑定到该节点的各个CPU，排除跨插槽访问
+    #[arg(long, requires = "numa_node", conflicts_with_all = ["cpu_affinity_interleave", "hyper_threads_only"])]
+    numa_bind_all: bool,
+
+    /// 内存块按节点分配的策略（仅Linux）：weighted 按 --numa-weights 的比例把内存块分段绑定到各节点
+    #[arg(long, alias = "memory-numa-interleave-policy", value_enum, requires_all = ["memory", "numa_weights"],
+          conflicts_with_all = ["numa_node", "memory_object_size"])]
+    numa_interleave_policy: Option<numa::NumaInterleavePolicy>,
+
+    /// 各节点提供的内存比例（例如："0:70,1:30"），比例之和必须为100
+    #[arg(long, value_delimiter = ',', value_parser = numa::parse_weight_entry, requires = "numa_interleave_policy")]
+    numa_weights: Vec<(usize, u8)>,
+
+    /// 分配并写入内存后检查各NUMA节点上的页面分布，没有页面的节点上补充分配4MiB（仅Linux）
+    #[arg(long, alias = "memory-allocate-on-all-numa-nodes", requires = "memory",
+          conflicts_with_all = ["numa_node", "numa_interleave_policy", "memory_object_size",
+                                "memory_dealloc_pattern", "no_prefault"])]
+    ensure_numa_coverage: bool,
+
+    /// 为工作线程统计的硬件性能计数器，逗号分隔（仅Linux，需要以 perf 特性编译）
+    /// 可选 instructions、cycles、cache-references、cache-misses、branches、branch-misses
+    #[arg(long, value_delimiter = ',', value_parser = perf::parse_perf_event)]
+    perf_counters: Vec<PerfEvent>,
+
+    /// 在状态输出中显示本进程每秒的系统调用和上下文切换次数（仅Linux）
+    #[arg(long)]
+    trace_syscalls: bool,
+
+    /// 使用 tokio 异步负载引擎：工作线程在阻塞线程池中运行，状态刷新为定时任务（需以 tokio 特性编译）
+    #[arg(long = "async")]
+    async_engine: bool,
+
+    /// 基准测试模式：每个工作线程只执行指定次数的计算迭代，完成后输出用时和得分
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    limit_cycles: Option<u64>,
+
+    /// 所有工作线程合计每秒最多执行的计算迭代次数，平均分配给各线程（令牌桶限速）
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    rate_limit: Option<u64>,
+
+    /// 停止后逐步清空并释放内存的最长秒数，为0时立即释放
+    #[arg(long, default_value_t = 5)]
+    graceful_stop_timeout: u64,
+
+    /// 负载结束后继续监控的秒数，期间CPU负载为0%
+    #[arg(long)]
+    measure_phase_secs: Option<u64>,
+
+    /// `-m` 的内存分配失败时输出说明并以退出码2退出；默认输出说明后直接 abort
+    #[arg(long, requires = "memory")]
+    abort_on_oom: bool,
+
+    /// 大块内存分配随机失败的概率（0.0-1.0），用于测试OOM处理
+    #[arg(long, value_parser = parse_fraction)]
+    alloc_fail_rate: Option<f64>,
+
+    /// 只有大于该大小的分配才会被注入失败（例如："4K"）
+    #[arg(long, value_parser = parse_byte_size, default_value = "4096")]
+    min_fail_size: ByteSize,
+
+    /// 控制套接字路径（仅Unix），可通过 `ctl` 子命令实时调整负载
+    #[arg(long)]
+    control_socket: Option<PathBuf>,
+
+    /// 分配内存后触发一次内核内存规整（写入 /proc/sys/vm/compact_memory），测量规整前后和期间的分配与访问延迟，
+    /// 并对比碎片化指数和透明大页覆盖率（仅Linux，需要root权限）
+    #[arg(long, requires = "memory")]
+    memory_compaction: bool,
+
+    /// 在状态输出中显示本进程的透明大页覆盖率（AnonHugePages / Rss，仅Linux）
+    #[arg(long)]
+    memory_huge_pages_status: bool,
+
+    /// 分配后立即让所有页面分配物理内存（优先 MADV_POPULATE_WRITE），消除首次访问的缺页延迟
+    #[arg(long, requires = "memory")]
+    memory_prefault: bool,
+
+    /// 把 `-m` 的总量拆成大量该大小的独立对象分配（例如："4KiB"），默认分配一整块；
+    /// 启动时输出1KiB、4KiB、64KiB、1MiB对象各分配1000次的平均耗时
+    #[arg(long, requires = "memory", value_parser = parse_byte_size,
+          conflicts_with_all = ["memory_prefault", "no_prefault", "lock_memory"])]
+    memory_object_size: Option<ByteSize>,
+
+    /// 把 `-m` 的总量拆成 `--chunk-count` 块分别分配，退出时按该顺序逐块释放，
+    /// 输出释放总耗时和单块释放耗时的分布
+    #[arg(long, value_enum, requires = "memory",
+          conflicts_with_all = ["memory_object_size", "memory_prefault", "no_prefault", "lock_memory"])]
+    memory_dealloc_pattern: Option<DeallocPattern>,
+
+    /// 把 `-m` 的总量拆成大量64B-4KiB随机大小的小块分别分配，并每秒释放、重新分配其中随机10%的小块，
+    /// 考验分配器在碎片化的堆上的表现
+    #[arg(long, requires = "memory",
+          conflicts_with_all = ["memory_pattern", "memory_object_size", "memory_dealloc_pattern", "memory_prefault",
+                                "no_prefault", "lock_memory", "memory_bandwidth_mode", "numa_interleave_policy",
+                                "ensure_numa_coverage"])]
+    memory_fragmentation: bool,
+
+    /// --memory-dealloc-pattern 拆分的块数
+    #[arg(long, requires = "memory_dealloc_pattern", default_value_t = 100,
+          value_parser = clap::value_parser!(u64).range(1..=1_000_000))]
+    chunk_count: u64,
+
+    /// 明确不预填充，并测量首次访问与再次访问每个页面的耗时，给出缺页的开销
+    #[arg(long, requires = "memory", conflicts_with = "memory_prefault")]
+    no_prefault: bool,
+
+    /// 结束后把工作线程启动与停止、内存分配与释放、阶段切换和尖峰写成 Chrome Trace 格式的JSON，
+    /// 可以在 chrome://tracing 或 ui.perfetto.dev 中查看时间线
+    #[arg(long)]
+    timeline_export: Option<PathBuf>,
+
+    /// 每秒把负载核心的平均CPU使用率追加到该文件，每行为 `<Unix毫秒时间戳>,<使用率>`
+    #[arg(long)]
+    cpu_realtime_log: Option<PathBuf>,
+
+    /// --cpu-realtime-log 的环形缓冲区容量（条），写入跟不上时只保留最近的N条
+    #[arg(long, requires = "cpu_realtime_log", default_value_t = 3600, value_parser = clap::value_parser!(u64).range(1..))]
+    cpu_realtime_buffer: u64,
+
+    /// 每次刷新状态时把各核心CPU使用率和内存统计写入该文件（已存在时截断），供事后分析
+    #[arg(long)]
+    output: Option<PathBuf>,
+
+    /// --output 文件的格式（默认按扩展名：`.csv` 为 CSV，其他为 JSON Lines）；
+    /// `--output-format` 已用于运行状态的输出格式
+    #[arg(long, value_enum, requires = "output")]
+    output_file_format: Option<metrics::MetricsFormat>,
+
+    /// 每隔指定秒数检查已分配内存的常驻页面比例（默认不检查）
+    #[arg(long)]
+    memory_check_interval: Option<u64>,
+
+    /// 常驻比例低于该百分比时告警
+    #[arg(long, default_value_t = 90.0)]
+    memory_min_resident: f64,
+
+    /// 常驻比例过低时调用 madvise(MADV_WILLNEED) 预取页面
+    #[arg(long, requires = "memory_check_interval")]
+    memory_willneed: bool,
+
+    /// 用 mlock 锁定分配的内存，防止被换出
+    #[arg(long, requires = "memory")]
+    lock_memory: bool,
+
+    /// 只锁定分配内存开头的百分比（0-100）
+    #[arg(long, requires = "lock_memory", default_value_t = 100,
+          value_parser = clap::value_parser!(u8).range(0..=100))]
+    memory_pin_percent: u8,
+
+    /// 持续访问已分配内存的模式，只写 `--memory-pattern` 时为 `sequential-write`
+    #[arg(long, value_enum, requires = "memory", num_args = 0..=1, default_missing_value = "sequential-write")]
+    memory_pattern: Option<MemoryPattern>,
+
+    /// 内存带宽模式：只读、只写或在两个等大的缓冲区之间拷贝，分别测量带宽；
+    /// `copy` 模式输出相对理论峰值（只读与只写带宽之和的一半）的内存控制器效率
+    #[arg(long, value_enum, requires = "memory", conflicts_with_all = ["memory_pattern", "memory_object_size"])]
+    memory_bandwidth_mode: Option<BandwidthMode>,
+
+    /// `stride` 模式的访问步长（例如："64"、"4KiB"）
+    #[arg(long, value_parser = parse_byte_size, default_value = "64")]
+    memory_stride: ByteSize,
+
+    /// `prefetch-train` 模式切换步长的间隔（毫秒）
+    #[arg(long, default_value_t = 500, value_parser = clap::value_parser!(u64).range(1..))]
+    prefetch_switch_interval: u64,
+
+    /// 同时访问内存的线程数，默认各自访问等分的一段
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    memory_access_threads: u16,
+
+    /// 让所有访问线程共享整块内存，模拟多个线程争用相同的缓存行
+    #[arg(long)]
+    memory_access_overlap: bool,
+
+    /// 内存访问模式下每次缓存行访问之后忙等的纳秒数，模拟 PMEM、远端NUMA节点或CXL内存等高延迟内存
+    #[arg(long, requires = "memory_pattern")]
+    memory_latency_ns: Option<u64>,
+
+    /// 内存增长模式：启动后逐块分配内存直到该上限，模拟内存泄漏（例如："4G"）
+    #[arg(long, value_parser = parse_byte_size)]
+    memory_growth: Option<ByteSize>,
+
+    /// 内存增长的最大速率（例如："100M/s"），默认不限速
+    #[arg(long, requires = "memory_growth", value_parser = parse_byte_rate)]
+    max_memory_growth_rate: Option<ByteSize>,
+
+    /// 系统可用内存低于该值时暂停内存增长，恢复后继续
+    #[arg(long, value_parser = parse_byte_size, default_value = "256MiB")]
+    memory_safety_margin: ByteSize,
+
+    /// 内存气球模式：周期性地逐块分配到 --balloon-max 再从尾部逐块释放，模拟虚拟化平台的气球驱动
+    #[arg(long, requires = "balloon_max")]
+    memory_balloon: bool,
+
+    /// 内存气球的周期（秒），前半段膨胀、后半段收缩
+    #[arg(long, requires = "memory_balloon", default_value_t = 60, value_parser = clap::value_parser!(u64).range(1..))]
+    balloon_period: u64,
+
+    /// 内存气球膨胀的上限（例如："2G"）
+    #[arg(long, requires = "memory_balloon", value_parser = parse_byte_size)]
+    balloon_max: Option<ByteSize>,
+
+    /// 将已分配的内存强制换出后反复随机访问，测量交换换入性能（仅Linux）；
+    /// 内存按256MiB分块分配，可以超出物理内存
+    #[arg(long, requires = "memory",
+          conflicts_with_all = ["memory_object_size", "memory_dealloc_pattern", "memory_fragmentation", "memory_prefault",
+                                "no_prefault", "lock_memory", "memory_bandwidth_mode", "numa_interleave_policy",
+                                "ensure_numa_coverage"])]
+    swap_pressure: bool,
+
+    /// 内存分配完成后周期性写入 /proc/sys/vm/drop_caches 迫使内核回收页面，测量回收和恢复的延迟（仅Linux，需要root）
+    #[arg(long, requires = "memory")]
+    memory_pressure_test: bool,
+
+    /// 内存压力测试的回收级别：low 回收页缓存，medium 回收 dentry/inode，critical 两者都回收
+    #[arg(long, requires = "memory_pressure_test", value_enum, default_value_t = PressureLevel::Low)]
+    memory_pressure_level: PressureLevel,
+
+    /// 启动前连接该地址的同步服务端（host:port），收到开始信号后所有机器同时开始负载
+    #[arg(long, conflicts_with = "sync_server")]
+    sync_with: Option<String>,
+
+    /// 作为同步服务端运行：等待 --sync-count 个客户端就绪后广播开始信号，本身不产生负载
+    #[arg(long, requires = "sync_count")]
+    sync_server: bool,
+
+    /// 同步服务端等待的客户端数量
+    #[arg(long, requires = "sync_server", value_parser = clap::value_parser!(u32).range(1..))]
+    sync_count: Option<u32>,
+
+    /// 同步服务端的监听地址
+    #[arg(long, default_value = "0.0.0.0:7070")]
+    sync_listen: String,
+
+    /// 运行状态的输出格式
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
+
+    /// `io-stress` 子命令的I/O参数，`start` 时为 None
+    #[arg(skip)]
+    io_stress: Option<IoStressArgs>,
+}
+
+/// `io-stress` 子命令在负载参数之外的参数；临时文件目录和大小沿用 `-p` 和 `--file-size`
+#[derive(Args, Clone)]
+struct IoStressArgs {
+    /// I/O线程数（1-256），每个线程使用自己的临时文件
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u16).range(1..=256))]
+    threads: u16,
+
+    /// 每次读写的字节数（例如："4K"、"1M"）
+    #[arg(long, value_parser = parse_byte_size, default_value = "4K")]
+    block_size: ByteSize,
+
+    /// 读写模式
+    #[arg(long, value_enum, default_value_t = iostress::IoPattern::Mixed)]
+    pattern: iostress::IoPattern,
+}
+
+#[derive(Subcommand)]
+#[allow(clippy::large_enum_variant)] // 只在启动时构造一次，无需装箱
+enum Commands {
+    /// 查看当前CPU、内存和硬盘使用率
+    #[command(group(clap::ArgGroup::new("sampling").args(["diff", "watch"])))]
+    Status {
+        /// 间隔 --interval 秒采样两次，显示变化量而不是绝对值
+        #[arg(long)]
+        diff: bool,
+
+        /// 像 top 一样每隔 --interval 秒清屏并刷新各核心使用率、内存和交换分区，按 Ctrl+C 退出；
+        /// 加 --json 时每次刷新输出一行JSON
+        #[arg(long)]
+        watch: bool,
+
+        /// --diff 两次采样的间隔秒数（默认1），或 --watch 的刷新间隔秒数（默认2）
+        #[arg(long, requires = "sampling", value_parser = clap::value_parser!(u64).range(1..))]
+        interval: Option<u64>,
+
+        /// --watch 刷新该次数后自动退出
+        #[arg(long, requires = "watch", value_parser = clap::value_parser!(u64).range(1..))]
+        count: Option<u64>,
+
+        /// 以JSON格式输出
+        #[arg(long)]
+        json: bool,
+
+        /// 只显示名称匹配该正则表达式的磁盘（含分区），默认为除 loop、ram 外的整块磁盘
+        #[arg(long, value_parser = diskstats::parse_disk_filter)]
+        disk_filter: Option<regex::Regex>,
+
+        /// 只显示名称匹配该正则表达式的网络接口，默认为除回环接口 lo 外的所有接口
+        #[arg(long, value_parser = netstats::parse_net_filter)]
+        net_filter: Option<regex::Regex>,
+
+        /// 显示该实例的状态文件信息
+        #[arg(long, default_value = instances::DEFAULT_NAME, value_parser = instances::parse_name)]
+        instance_name: String,
+    },
+
+    /// 列出所有实例的名称、PID以及进程是否仍在运行
+    List,
+    
+    /// 启动系统负载
+    Start {
+        #[command(flatten)]
+        load: LoadArgs,
+    },
+    
+    /// 在 -p 目录下创建临时文件并反复读写，同时可叠加CPU和内存负载；状态输出显示 IOPS 和 MB/s
+    IoStress {
+        #[command(flatten)]
+        load: LoadArgs,
+
+        #[command(flatten)]
+        io: IoStressArgs,
+    },
+
+    /// 停止正在运行的负载
+    Stop {
+        /// 改为发送指定的信号（仅Unix）：SIGINT、SIGKILL、SIGTERM、SIGUSR1、SIGUSR2 或编号1-64
+        #[arg(long, value_parser = signals::parse_signal)]
+        signal: Option<i32>,
+
+        /// 只停止该名称的实例
+        #[arg(long, default_value = instances::DEFAULT_NAME, value_parser = instances::parse_name)]
+        instance_name: String,
+
+        /// 等待进程退出的秒数，超时后进程仍在运行时报错并保留PID文件
+        #[arg(long, default_value_t = 5)]
+        timeout: u64,
+
+        /// 等待超时后强制终止进程（Unix 上为 SIGKILL，Windows 上为 taskkill /F）
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// 终止所有实例（先 SIGTERM，2秒后 SIGKILL）并删除所有PID文件
+    Reset,
+
+    /// 检查 TOML 负载配置文件：各阶段的CPU使用率、内存大小、时长和负载类型，有效时输出摘要
+    ValidateProfile {
+        /// 配置文件路径
+        file: PathBuf,
+    },
+
+    /// 基准测试
+    Benchmark {
+        #[command(subcommand)]
+        kind: BenchmarkKind,
+    },
+
+    /// 通过控制套接字向正在运行的负载发送命令（仅Unix）
+    Ctl {
+        /// 控制套接字路径，与启动时的 --control-socket 一致
+        #[arg(short, long)]
+        socket: PathBuf,
+
+        /// 命令：set_cpu、set_memory、pause、resume、status、stop
+        command: String,
+
+        /// 命令参数，例如 set_cpu 的百分比或 set_memory 的大小
+        value: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum BenchmarkKind {
+    /// 测量创建进程或线程的耗时：平均值和 p50/p95/p99（微秒）
+    ForkSpawn {
+        /// 创建次数
+        #[arg(long, default_value_t = 1000, value_parser = clap::value_parser!(u32).range(1..))]
+        count: u32,
+
+        /// 创建方式：fork（仅Unix）、exec（启动 /bin/true）、thread
+        #[arg(long, value_enum, default_value_t = SpawnMethod::Fork)]
+        method: SpawnMethod,
+    },
+
+    /// 测量本地与远端NUMA节点的指针追逐延迟、NUMA 系数和拷贝带宽（仅Linux）
+    NumaLatency {
+        /// 本地节点，测量线程绑定在该节点的CPU上
+        #[arg(long, default_value_t = 0)]
+        local_node: usize,
+
+        /// 远端节点，默认为第一个其他有内存的节点
+        #[arg(long)]
+        remote_node: Option<usize>,
+
+        /// 每个节点上分配的内存大小，应远大于末级缓存
+        #[arg(long, value_parser = parse_byte_size, default_value = "256MiB")]
+        size: ByteSize,
+    },
+
+    /// 在单个线程上依次满载运行各种计算负载，输出以 math（trig）为基准的吞吐量对比表并保存校准结果
+    AllWorkloads {
+        /// 每种负载的运行时长
+        #[arg(long, default_value = "10s", value_parser = parse_duration)]
+        duration: Duration,
+    },
+}
+
+/// 解析时长字符串（例如："500ms"、"5s"、"2m"、"1h"，纯数字按秒处理）
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let value: f64 = number.parse().map_err(|_| tr!("parse-invalid-duration", value = s))?;
+    let secs = match unit {
+        "ms" => value / 1000.0,
+        "" | "s" => value,
+        "m" => value * 60.0,
+        "h" => value * 3600.0,
+        _ => return Err(tr!("parse-invalid-duration-unit", unit = unit)),
+    };
+    if secs <= 0.0 {
+        return Err(tr!("parse-duration-not-positive"));
+    }
+    Ok(Duration::from_secs_f64(secs))
+}
+
+/// 解析0.0到1.0之间的小数
+fn parse_fraction(s: &str) -> Result<f64, String> {
+    let value: f64 = s.trim().parse().map_err(|_| tr!("parse-invalid-number", value = s))?;
+    if !(0.0..=1.0).contains(&value) {
+        return Err(tr!("parse-fraction-out-of-range"));
+    }
+    Ok(value)
+}
+
+/// 解析0到100之间的百分比，允许小数（例如："5"、"2.5"）
+fn parse_percent_f64(s: &str) -> Result<f64, String> {
+    let value: f64 = s.trim().trim_end_matches('%').parse().map_err(|_| tr!("parse-invalid-number", value = s))?;
+    if !(0.0..=100.0).contains(&value) {
+        return Err(tr!("parse-percent-out-of-range", value = s));
+    }
+    Ok(value)
+}
+
+/// 解析按线程覆盖的使用率，格式为 "线程序号:百分比,..."
+fn parse_thread_overrides(s: &str) -> Result<HashMap<usize, u8>, String> {
+    let mut overrides = HashMap::new();
+    for entry in s.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (index, percent) = entry
+            .split_once(':')
+            .ok_or_else(|| tr!("parse-invalid-thread-override", entry = entry))?;
+        let index: usize = index.trim().parse().map_err(|_| tr!("parse-invalid-thread-index", value = index))?;
+        let percent: u8 = percent.trim().parse().map_err(|_| tr!("parse-invalid-percent", value = percent))?;
+        if percent > 100 {
+            return Err(tr!("parse-percent-out-of-range", value = percent));
+        }
+        if overrides.insert(index, percent).is_some() {
+            return Err(tr!("parse-duplicate-thread", index = index));
+        }
+    }
+    Ok(overrides)
+}
+
+/// 解析 `--cpu-base`：0 视为错误，超过100的值告警后按100处理
+fn parse_cpu_base(s: &str) -> Result<u8, String> {
+    let percent: u64 = s.trim().parse().map_err(|_| tr!("parse-invalid-percent", value = s))?;
+    match percent {
+        0 => Err(tr!("parse-cpu-base-zero")),
+        1..=100 => Ok(percent as u8),
+        _ => {
+            warn!("{}", tr!("cpu-base-clamped", value = percent));
+            Ok(100)
+        }
+    }
+}
+
+/// 解析循环负载，格式为 "work=10s,pause=5s,repeat=4"，repeat 省略或为0时一直循环
+fn parse_load_schedule(s: &str) -> Result<schedule::Schedule, String> {
+    let (mut work, mut pause, mut repeat) = (None, None, 0);
+    for entry in s.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (key, value) = entry.split_once('=').ok_or_else(|| tr!("parse-invalid-schedule", entry = entry))?;
+        match key.trim() {
+            "work" => work = Some(parse_duration(value)?),
+            "pause" => pause = Some(parse_duration(value)?),
+            "repeat" => repeat = value.trim().parse().map_err(|_| tr!("parse-invalid-number", value = value))?,
+            _ => return Err(tr!("parse-invalid-schedule", entry = entry)),
+        }
+    }
+    match (work, pause) {
+        (Some(work), Some(pause)) => Ok(schedule::Schedule { work, pause, repeat }),
+        _ => Err(tr!("parse-schedule-incomplete", value = s)),
+    }
+}
+
+/// 解析字节大小（例如："4096"、"4K"、"1G"）
+fn parse_byte_size(s: &str) -> Result<ByteSize, String> {
+    ByteSize::from_str(s.trim()).map_err(|_| tr!("parse-invalid-size", value = s))
+}
+
+/// 解析每秒字节数，例如 "100M/s"，"/s" 后缀可省略
+fn parse_byte_rate(s: &str) -> Result<ByteSize, String> {
+    let size = s.trim();
+    let size = size.strip_suffix("/s").unwrap_or(size);
+    match ByteSize::from_str(size.trim()) {
+        Ok(rate) if rate.as_u64() > 0 => Ok(rate),
+        _ => Err(tr!("parse-invalid-rate", value = s)),
+    }
+}
+
+/// 解析命令行参数并执行，失败时按错误类型输出消息并以对应的退出码结束进程
+pub fn main() {
+    let cli = Cli::parse();
+    logger::set_quiet(cli.quiet);
+    if cli.syslog {
+        logger::enable_syslog();
+    }
+    if let Err(lang) = i18n::init(cli.lang.as_deref()) {
+        warn!("{}", tr!("lang-unsupported", lang = lang, supported = i18n::supported_locales()));
+    }
+    if let Some(path) = &cli.log_file
+        && let Err(e) = logger::enable_log_file(path, cli.log_rotation_interval, cli.log_keep_files) {
+        warn!("{}", tr!("log-file-open-failed", path = path.display(), error = e));
+    }
+
+    if let Err(e) = run(&cli) {
+        if e.is_notice() {
+            info!("{}", e);
+        } else {
+            error!("{}", e);
+        }
+        process::exit(e.exit_code());
+    }
+}
+
+/// 执行子命令
+fn run(cli: &Cli) -> Result<(), LoadError> {
+    match &cli.command {
+        Some(Commands::Status { diff, watch, interval, count, json, disk_filter, net_filter, instance_name }) => {
+            if *diff {
+                status::show_diff(Duration::from_secs(interval.unwrap_or(1)), *json);
+            } else if *watch {
+                status::watch(Duration::from_secs(interval.unwrap_or(2)), *count, *json);
+            } else {
+                show_cpu_status(*json, disk_filter.clone(), net_filter.clone(), instance_name);
+            }
+        },
+        Some(Commands::List) => {
+            instances::list();
+        },
+        Some(Commands::Start { load }) => {
+            launch(load)?;
+        },
+        Some(Commands::IoStress { load, io }) => {
+            let mut load = load.clone();
+            load.io_stress = Some(io.clone());
+            launch(&load)?;
+        },
+        Some(Commands::Stop { signal, instance_name, force, timeout }) => {
+            instances::stop(instance_name, *signal, *force, Duration::from_secs(*timeout))?;
+        },
+
+        Some(Commands::Reset) => {
+            if !reset::reset(&instances::pid_dir()) {
+                process::exit(1);
+            }
+        },
+
+        Some(Commands::ValidateProfile { file }) => {
+            profile::validate_file(file)?;
+        },
+
+        Some(Commands::Benchmark { kind: BenchmarkKind::ForkSpawn { count, method } }) => {
+            spawnbench::run(*method, *count)?;
+        },
+
+        Some(Commands::Benchmark { kind: BenchmarkKind::NumaLatency { local_node, remote_node, size } }) => {
+            numalatency::run(*local_node, *remote_node, *size)?;
+        },
+
+        Some(Commands::Benchmark { kind: BenchmarkKind::AllWorkloads { duration } }) => {
+            calibration::run(*duration)?;
+        },
+
+        Some(Commands::Ctl { socket, command, value }) => {
+            send_control_command(socket, command, value.as_deref())?;
+        },
+
+        None => {
+            launch(&cli.load)?;
+        }
+    }
+    Ok(())
+}
+
+/// 发送控制命令并打印响应；数值参数按数字发送，其余按字符串发送
+fn send_control_command(socket: &Path, command: &str, value: Option<&str>) -> Result<(), LoadError> {
+    let mut request = vec![("cmd", json::Json::from(command))];
+    if let Some(value) = value {
+        let value = match value.parse::<f64>() {
+            Ok(number) => json::Json::Number(number),
+            Err(_) => json::Json::from(value),
+        };
+        request.push(("value", value));
+    }
+    let request = json::Json::object(request);
+
+    #[cfg(unix)]
+    {
+        let response = control::send_command(socket, &request).map_err(|e| {
+            std::io::Error::new(e.kind(), tr!("ctl-connect-failed", path = socket.display(), error = e))
+        })?;
+        info!("{}", response);
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = (socket, request);
+        Err(LoadError::PlatformUnsupported(tr!("ctl-unsupported")))
+    }
+}
+
+/// 检查单实例、保存PID并启动负载
+fn launch(load: &LoadArgs) -> Result<(), LoadError> {
+    // 同步服务端只负责协调，不占用单实例PID文件
+    if load.sync_server {
+        let count = load.sync_count.unwrap_or(1) as usize;
+        sync::run_server(&load.sync_listen, count)
+            .map_err(|e| std::io::Error::new(e.kind(), tr!("sync-server-failed", error = e)))?;
+        return Ok(());
+    }
+
+    // 检查是否已经有实例在运行；PID文件损坏时视为没有实例，随后覆盖
+    match read_pid(&load.instance_name) {
+        Ok(Some(pid)) if load.background_child && pid == process::id() => {}
+        Ok(Some(pid)) => return Err(LoadError::AlreadyRunning(pid)),
+        Ok(None) => {}
+        Err(LoadError::PidFileCorrupted(path)) => {
+            warn!("{}", tr!("pid-file-corrupted-overwrite", path = path.display()));
+        }
+        Err(e) => return Err(e),
+    }
+    
+    // Windows 上没有 fork，在分配任何资源之前重新启动一个脱离控制台的子进程，父进程随即退出
+    #[cfg(windows)]
+    if load.background && !load.background_child {
+        return spawn_background_windows(&load.instance_name);
+    }
+    // 子进程的标准输出已指向日志文件，--log-file 又逐条记录日志，不再重复写入
+    #[cfg(windows)]
+    if load.background_child && logger::log_file_path().is_some() {
+        logger::set_quiet(true);
+    }
+
+    // 保存当前进程的PID
+    if let Err(e) = save_pid(&load.instance_name) {
+        warn!("{}", tr!("pid-save-failed", error = e));
+    }
+    
+    // 启动负载，启动失败时清理PID文件
+    let result = start_load(load, None);
+    if result.is_err() {
+        let _ = remove_pid_file(&load.instance_name);
+    }
+    result
+}
+
+/// Windows 后台模式：去掉 `-b` 后以 `DETACHED_PROCESS | CREATE_NO_WINDOW` 重新启动自身，
+/// 子进程带隐藏参数 `--_child`，标准输出和错误写入后台日志文件；父进程把子进程的PID写入PID文件后退出，
+/// 对应 Unix 上的 `fork::daemon`
+#[cfg(windows)]
+fn spawn_background_windows(instance_name: &str) -> Result<(), LoadError> {
+    use std::os::windows::process::CommandExt;
+    use std::process::{Command, Stdio};
+
+    const DETACHED_PROCESS: u32 = 0x0000_0008;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    info!("{}", tr!("background-starting"));
+    let args: Vec<_> = std::env::args_os()
+        .skip(1)
+        .filter(|arg| arg != "-b" && arg != "--background")
+        .chain([std::ffi::OsString::from("--_child")])
+        .collect();
+    let log_path = background_log_path(instance_name);
+    let (stdout, stderr) = match open_background_log(&log_path).map(|file| file.try_clone().map(|clone| (file, clone))) {
+        Some(Ok((out, err))) => (Stdio::from(out), Stdio::from(err)),
+        _ => (Stdio::null(), Stdio::null()),
+    };
+    let child = Command::new(std::env::current_exe()?)
+        .args(&args)
+        .creation_flags(DETACHED_PROCESS | CREATE_NO_WINDOW)
+        .stdin(Stdio::null())
+        .stdout(stdout)
+        .stderr(stderr)
+        .spawn()
+        .map_err(|e| std::io::Error::new(e.kind(), tr!("background-spawn-failed", error = e)))?;
+    if let Err(e) = instances::write_pid(instance_name, child.id()) {
+        warn!("{}", tr!("background-pid-save-failed", error = e));
+    }
+    info!("{}", tr!("background-log-file", path = log_path.display()));
+    info!("{}", tr!("background-parent-exit", pid = child.id()));
+    process::exit(0);
+}
+
+/// 后台进程的标准输出和错误写入的文件：`--log-file`，未指定时为临时目录中的 `enhancecpu-<名称>.log`
+#[cfg(any(unix, windows))]
+fn background_log_path(instance_name: &str) -> PathBuf {
+    logger::log_file_path().unwrap_or_else(|| instances::get_log_file(instance_name))
+}
+
+/// 以追加方式打开后台日志文件，失败时告警并返回 None，之后的输出被丢弃
+#[cfg(any(unix, windows))]
+fn open_background_log(path: &Path) -> Option<std::fs::File> {
+    match OpenOptions::new().create(true).append(true).open(path) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            warn!("{}", tr!("background-log-open-failed", path = path.display(), error = e));
+            None
+        }
+    }
+}
+
+/// `fork::daemon` 关闭了标准输入、输出和错误：标准输入重新指向 /dev/null，标准输出和错误指向 `log`
+/// （打开失败时为 /dev/null）；否则之后创建的管道（例如 ctrlc 的信号管道）会拿到 0-2 号描述符，日志输出会写进管道
+#[cfg(unix)]
+fn redirect_stdio(log: Option<std::fs::File>) {
+    use std::os::fd::IntoRawFd;
+    let Ok(null) = OpenOptions::new().read(true).write(true).open("/dev/null") else {
+        return;
+    };
+    let null = null.into_raw_fd();
+    let log = log.map_or(null, IntoRawFd::into_raw_fd);
+    for (source, target) in [(null, 0), (log, 1), (log, 2)] {
+        if source != target {
+            // SAFETY: source 是刚打开的有效描述符，dup2 覆盖的是已关闭的标准描述符
+            unsafe { libc::dup2(source, target) };
+        }
+    }
+    for fd in [null, log] {
+        if fd > 2 {
+            // SAFETY: fd 已复制到 0-2，不再使用；null 与 log 相同时第二次 close 返回 EBADF，没有影响
+            unsafe { libc::close(fd) };
+        }
+    }
+}
+
+/// `-m` 的内存分配失败：指定 `--abort-on-oom` 时返回错误，以退出码2正常退出；
+/// 否则输出同样的说明、删除PID文件后立即 `abort`，不展开栈也不输出 panic 信息
+fn out_of_memory(args: &LoadArgs, bytes: u64) -> LoadError {
+    let error = LoadError::MemoryAllocationFailed(bytes as usize);
+    if args.abort_on_oom {
+        return error;
+    }
+    error!("{}", error);
+    let _ = remove_pid_file(&args.instance_name);
+    process::abort();
+}
+
+/// 创建指定大小的文件
+fn create_disk_file(size: ByteSize, path: &str) -> std::io::Result<PathBuf> {
+    let path = Path::new(path);
+    let file_path = if path.is_dir() {
+        path.join("disk_load.tmp")
+    } else {
+        path.to_path_buf()
+    };
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(&file_path)?;
+
+    let size_bytes = size.as_u64() as usize;
+    
+    // 根据文件大小选择不同的写入策略
+    if size_bytes <= 1024 * 1024 * 10 { // 10MB以下使用随机数据
+        let mut rng = rand::thread_rng();
+        let mut buffer = vec![0u8; 8192]; // 8KB缓冲区
+        let mut remaining = size_bytes;
+        
+        while remaining > 0 {
+            let chunk_size = remaining.min(buffer.len());
+            rng.fill_bytes(&mut buffer[..chunk_size]);
+            file.write_all(&buffer[..chunk_size])?;
+            remaining -= chunk_size;
+        }
+    } else { // 大文件使用稀疏文件策略
+        file.seek(SeekFrom::Start((size_bytes - 1) as u64))?;
+        file.write_all(&[1])?;
+    }
+
+    file.sync_all()?;
+    Ok(file_path)
+}
+
+/// 只用于构造库方式启动时的 [`LoadArgs`]，未设置的参数取命令行的默认值
+#[derive(Parser)]
+struct EmbeddedArgs {
+    #[command(flatten)]
+    load: LoadArgs,
+}
+
+/// [`LoadConfig::start`](crate::LoadConfig::start) 的负载线程：把配置转换为命令行参数后走 `start` 的同一套流程，
+/// 所有工作线程启动后把运行状态发给 `ready`；内存分配失败时返回错误而不是 abort
+pub(crate) fn run_embedded(config: &LoadConfig, ready: mpsc::Sender<Arc<LoadState>>) -> Result<(), LoadError> {
+    let mut args = EmbeddedArgs::try_parse_from(["enhance-cpu-memory"])
+        .map_err(|e| LoadError::InvalidArgument(e.to_string()))?
+        .load;
+    args.cores = Some(config.cores);
+    // cpu_percent 已校验在 (0, 100] 之间，取整后至少为1%
+    args.cpu_base = Some((config.cpu_percent.round() as u8).max(1));
+    args.memory = config.memory.map(|size| size.as_u64().to_string());
+    args.abort_on_oom = true;
+    args.duration = config.duration;
+    args.workload = config.workload;
+    args.self_monitor = true;
+    start_load(&args, Some(ready))
+}
+
+/// 启动系统负载；`ready` 不为 None 时是以库的方式启动，不后台运行、不安装 Ctrl+C 处理函数，也不删除PID文件
+fn start_load(args: &LoadArgs, ready: Option<mpsc::Sender<Arc<LoadState>>>) -> Result<(), LoadError> {
+    // 负载配置文件的第一阶段决定启动时的目标使用率、内存和负载类型，之后由阶段驱动线程调整
+    let profiled;
+    let (args, profile_plan) = match &args.profile {
+        Some(path) => {
+            let profile = profile::load(path)?;
+            let mut sys = System::new();
+            sys.refresh_memory();
+            let plan = profile.plan(args.cores.unwrap_or(std::cmp::max(1, num_cpus::get() / 2)), sys.total_memory());
+            let first = &profile.phases[0];
+            let mut overridden = args.clone();
+            overridden.cpu_base = plan.targets[0];
+            overridden.memory = first.memory.as_ref().map(|size| size.get_ref().clone());
+            if let Some(workload) = profile.workload()? {
+                overridden.workload = workload;
+            }
+            match &profile.name {
+                Some(name) => info!("{}", tr!("profile-running-named", file = path.display(), name = name)),
+                None => info!("{}", tr!("profile-running", file = path.display())),
+            }
+            profiled = overridden;
+            (&profiled, Some(plan))
+        }
+        None => (args, None),
+    };
+    if args.timeline_export.is_some() {
+        timeline::enable();
+    }
+    // 指标文件在分配内存和启动工作线程之前创建，无法写入时直接退出
+    let metrics_writer = match &args.output {
+        Some(path) => {
+            let format = args.output_file_format.unwrap_or_else(|| metrics::MetricsFormat::from_path(path));
+            let writer = metrics::MetricsWriter::create(path, format)
+                .map_err(|e| LoadError::InvalidArgument(tr!("metrics-open-failed", path = path.display(), error = e)))?;
+            info!("{}", tr!("metrics-started", path = path.display()));
+            Some(writer)
+        }
+        None => None,
+    };
+    if let Some(rate) = args.alloc_fail_rate {
+        failalloc::configure(rate, args.min_fail_size.as_u64() as usize);
+        info!("{}", tr!("start-alloc-fail-injection", rate = rate, size = args.min_fail_size.as_u64()));
+    }
+
+    if args.irq_balance_disable && !args.force {
+        return Err(LoadError::InvalidArgument(tr!("irq-needs-force")));
+    }
+    // interruption-test 会替换进程的 SIGUSR2 处理函数，外部的 SIGUSR2 不再降低负载，反而计入打断次数
+    if args.signal_adjust.is_some() && args.workload == Workload::InterruptionTest {
+        return Err(LoadError::InvalidArgument(tr!("signal-adjust-interruption-conflict")));
+    }
+
+    // 状态线程独占的CPU，工作线程不使用
+    let status_core_reserved = args.status_core.filter(|_| !args.allow_status_core_sharing);
+    if let Some(core) = args.status_core
+        && core >= num_cpus::get() {
+        return Err(LoadError::InvalidArgument(tr!("status-core-out-of-range", core = core, count = num_cpus::get())));
+    }
+    if status_core_reserved.is_some() && num_cpus::get() == 1 {
+        return Err(LoadError::InvalidArgument(tr!("status-core-no-free-cpu")));
+    }
+
+    // 指定绑核列表时核心数取列表长度，同时指定的 --cores 必须与之一致
+    let cpu_affinity = match &args.cpu_affinity {
+        Some(list) => Some(parse_cpu_affinity(list)?),
+        None => None,
+    };
+    let cores = match (args.cores, &cpu_affinity) {
+        (Some(cores), Some(cpus)) if cores != cpus.len() => {
+            return Err(LoadError::InvalidArgument(tr!("cpu-affinity-cores-mismatch", cores = cores, count = cpus.len())));
+        }
+        (Some(cores), _) => cores,
+        (None, Some(cpus)) => cpus.len(),
+        (None, None) => std::cmp::max(1, num_cpus::get() / 2),
+    };
+    // 按线程覆盖的使用率列出了更多线程时取较大的线程数；绑核列表已决定线程数时不调整
+    let cores = match args.thread_overrides.as_ref().and_then(|overrides| overrides.keys().max()) {
+        Some(&max) if max >= cores && cpu_affinity.is_none() => {
+            if args.cores.is_some() {
+                warn!("{}", tr!("thread-overrides-cores-raised", cores = cores, count = max + 1));
+            }
+            max + 1
+        }
+        _ => cores,
+    };
+
+    // 校验CPU尖峰参数
+    let spike = match (args.cpu_spike_to, args.cpu_spike_duration, args.cpu_spike_every) {
+        (Some(spike_to), Some(hold), Some(every)) => {
+            let spike = SpikePattern {
+                base: args.cpu_base.unwrap_or(100),
+                spike_to,
+                hold,
+                every,
+            };
+            spike.validate().map_err(LoadError::InvalidArgument)?;
+            Some(spike)
+        }
+        (Some(_), _, None) if !args.spike_on_signal => {
+            return Err(LoadError::InvalidArgument(tr!("spike-trigger-missing")));
+        }
+        _ => None,
+    };
+    let signal_spike = match (args.spike_on_signal, args.cpu_spike_to, args.cpu_spike_duration) {
+        (true, Some(spike_to), Some(hold)) => Some(SignalSpike { spike_to, hold }),
+        _ => None,
+    };
+
+    // 校验分阶段核心数参数
+    let phases = profile_plan.or_else(|| {
+        (!args.phase_cores.is_empty()).then(|| PhasePlan {
+            cores: args.phase_cores.clone(),
+            durations: args.phase_durations.clone(),
+            targets: Vec::new(),
+            memory: Vec::new(),
+        })
+    });
+    if let Some(phases) = &phases {
+        phases.validate().map_err(LoadError::InvalidArgument)?;
+    }
+
+    // io-stress 的文件至少能容纳一个块
+    if let Some(io) = &args.io_stress
+        && (io.block_size.as_u64() == 0 || io.block_size > args.file_size) {
+        return Err(LoadError::InvalidArgument(
+            tr!("io-stress-block-too-large", block = io.block_size, size = args.file_size),
+        ));
+    }
+
+    // 逐步增减负载的时间都在 --duration 之内
+    if let Some(duration) = args.duration
+        && args.ramp_up.unwrap_or_default() + args.ramp_down.unwrap_or_default() > duration {
+        return Err(LoadError::InvalidArgument(tr!("ramp-exceeds-duration")));
+    }
+
+    // 校验阶梯参数
+    let step_range = args.step_start.as_deref().zip(args.step_end.as_deref());
+    let core_stepper = match step_range.filter(|_| args.step_cores) {
+        Some((start, end)) => {
+            let parse = |s: &str| {
+                s.trim().parse::<usize>().map_err(|_| LoadError::InvalidArgument(tr!("step-invalid-cores", value = s)))
+            };
+            let stepper = CoreStepper {
+                start: parse(start)?,
+                end: parse(end)?,
+                size: args.step_size as usize,
+                interval: args.step_interval,
+            };
+            stepper.validate().map_err(LoadError::InvalidArgument)?;
+            Some(stepper)
+        }
+        None => None,
+    };
+    let memory_stepper = match step_range.filter(|_| args.step_memory) {
+        Some((start, end)) => {
+            let parse = |s: &str| parse_byte_size(s).map(|size| size.as_u64()).map_err(LoadError::InvalidArgument);
+            let stepper = MemoryStepper {
+                start: parse(start)?,
+                end: parse(end)?,
+                multiplier: args.step_multiplier,
+                interval: args.step_interval,
+            };
+            stepper.validate().map_err(LoadError::InvalidArgument)?;
+            Some(stepper)
+        }
+        None => None,
+    };
+
+    if !args.cpu_work_mix.is_empty() {
+        let total: u32 = args.cpu_work_mix.iter().map(|&(_, percent)| u32::from(percent)).sum();
+        if total != 100 {
+            return Err(LoadError::InvalidArgument(tr!("work-mix-sum", total = total)));
+        }
+    }
+
+    // 读取并校验调频配置文件
+    let governor_profile = match &args.cpu_governor_profile {
+        Some(path) => {
+            let text = std::fs::read_to_string(path).map_err(|e| {
+                std::io::Error::new(e.kind(), tr!("governor-profile-read-failed", file = path.display(), error = e))
+            })?;
+            let cores = cpufreq::parse_governor_profile(&text).map_err(|e| {
+                LoadError::InvalidArgument(tr!("governor-profile-invalid", file = path.display(), error = e))
+            })?;
+            Some(cores)
+        }
+        None => None,
+    };
+
+    // 所有线程共享的运行状态
+    let state = Arc::new(LoadState::new(args.cpu_base.unwrap_or(100), None));
+    state.cpu_noise.store(args.cpu_noise, Ordering::Relaxed);
+
+    // 启动CPU负载
+    let available_cpus = num_cpus::get() - usize::from(status_core_reserved.is_some());
+    let actual_cores = if args.auto_cores {
+        available_cpus
+    } else if let Some(phases) = &phases {
+        phases.max_cores().min(available_cpus)
+    } else if let Some(stepper) = &core_stepper {
+        stepper.end.min(available_cpus)
+    } else {
+        cores.min(available_cpus)
+    };
+    // Rayon 负载的线程池同样占用 actual_cores 个核心，使用普通的启动提示
+    if !args.workload.uses_cpu_workers() && args.workload != Workload::Rayon {
+        info!("{}", tr!("start-workload-only", workload = format!("{:?}", args.workload)));
+    } else if let Some(target) = args.target_system_cpu {
+        info!("{}", tr!("start-auto-cores", cores = actual_cores, target = target));
+    } else {
+        info!("{}", tr!("start-cores", cores = actual_cores));
+    }
+    if args.workload.uses_cpu_workers()
+        && let Some(hybrid) = topology::check_hybrid_cores()
+        && actual_cores > hybrid.performance.len() {
+        warn!(
+            "{}",
+            tr!(
+                "start-hybrid-warning",
+                efficiency = topology::format_cpu_ranges(&hybrid.efficiency),
+                cores = actual_cores,
+                performance = hybrid.performance.len()
+            )
+        );
+    }
+
+    let turbo = match cpufreq::turbo_enabled() {
+        Some(true) => "enabled",
+        Some(false) => "disabled",
+        None => "unknown",
+    };
+    info!("{}", tr!("turbo-status", status = turbo));
+    if args.cpu_governor_aware {
+        cpufreq::check_governor(args.cpu_base.unwrap_or(100));
+    }
+    info!("{}", tr!("random-seed", seed = seed::init(args.random_seed)));
+
+    let show_target = args.cpu_base.is_some()
+        || args.auto_cores
+        || spike.is_some()
+        || signal_spike.is_some()
+        || args.burst_count.is_some()
+        || args.load_schedule.is_some()
+        || args.control_socket.is_some()
+        || args.target_file.is_some()
+        || args.cpu_thermal_protect
+        || args.signal_adjust == Some(sigadjust::AdjustTarget::Percent)
+        || args.cpu_noise > 0;
+    // 自动核心调整时占空比由控制器决定，启动时没有固定的目标
+    if show_target && !args.auto_cores {
+        match args.cpu_noise {
+            0 => info!("{}", tr!("start-target", target = state.target_percent.load(Ordering::Relaxed))),
+            noise => info!("{}", tr!("start-target-noise", target = state.target_percent.load(Ordering::Relaxed), noise = noise)),
+        }
+    }
+    
+    // 创建硬盘占用文件
+    let disk_file = if let Some(size_str) = &args.disk {
+        match ByteSize::from_str(size_str) {
+            Ok(size) => {
+                match create_disk_file(size, &args.path) {
+                    Ok(path) => {
+                        info!("{}", tr!("disk-file-created", path = path.display()));
+                        Some(path)
+                    }
+                    Err(e) => {
+                        warn!("{}", tr!("disk-file-create-failed", error = e));
+                        None
+                    }
+                }
+            }
+            Err(_) => {
+                warn!("{}", tr!("disk-invalid-size"));
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // NUMA 绑定在分配内存和启动线程之前进行，之后创建的线程都继承
+    let numa_cpus = match args.numa_node {
+        Some(node) => {
+            let cpus = numa::node_cpus(node)
+                .ok()
+                // 只有内存没有CPU的节点（如CXL内存扩展）可以用于内存绑定
+                .filter(|cpus| !args.numa_bind_all || !cpus.is_empty())
+                .ok_or_else(|| LoadError::InvalidArgument(tr!("numa-node-invalid", node = node)))?;
+            match numa::bind_memory(node) {
+                Ok(()) => info!("{}", tr!("numa-memory-bound", node = node)),
+                Err(e) => warn!("{}", tr!("numa-bind-failed", node = node, error = e)),
+            }
+            if args.numa_bind_all {
+                match numa::run_on_cpus(&cpus) {
+                    Ok(()) => info!("{}", tr!("numa-cpus-bound", node = node, cpus = topology::format_cpu_ranges(&cpus))),
+                    Err(e) => warn!("{}", tr!("numa-bind-failed", node = node, error = e)),
+                }
+            }
+            args.numa_bind_all.then_some(cpus)
+        }
+        None => None,
+    };
+    let weighted_numa = match args.numa_interleave_policy {
+        Some(numa::NumaInterleavePolicy::Weighted) => {
+            let total: u32 = args.numa_weights.iter().map(|&(_, percent)| u32::from(percent)).sum();
+            if total != 100 {
+                return Err(LoadError::InvalidArgument(tr!("numa-weights-sum", total = total)));
+            }
+            for (i, &(node, _)) in args.numa_weights.iter().enumerate() {
+                if args.numa_weights[..i].iter().any(|&(n, _)| n == node) {
+                    return Err(LoadError::InvalidArgument(tr!("numa-weights-duplicate", node = node)));
+                }
+                if !numa::node_exists(node) {
+                    return Err(LoadError::InvalidArgument(tr!("numa-weights-node-missing", node = node)));
+                }
+            }
+            Some(numa::WeightedNumaAllocator::new(args.numa_weights.clone()))
+        }
+        None => None,
+    };
+
+    // 解析并分配内存
+    if args.memory.is_some() {
+        timeline::begin(timeline::MEMORY_ROW, "memory_alloc");
+    }
+    let mut memory_objects = Vec::new();
+    // --ensure-numa-coverage 在缺少页面的节点上补充的内存，持有到负载结束
+    let mut numa_coverage = Vec::new();
+    let memory_vec = if let Some(size_str) = &args.memory {
+        let spec = memory::parse_memory_spec(size_str)
+            .map_err(|e| LoadError::ParseError(tr!("memory-invalid-size", error = e)))?;
+        let bytes = match spec {
+            MemorySpec::Absolute(bytes) => bytes,
+            MemorySpec::Percent(percent) => {
+                let mut sys = System::new();
+                sys.refresh_memory();
+                let bytes = spec.to_bytes(sys.total_memory());
+                info!("{}", tr!("memory-percent-of-total", percent = percent));
+                bytes
+            }
+        };
+        if let Some(pattern) = args.memory_dealloc_pattern {
+            let size = bytes / args.chunk_count;
+            if size == 0 {
+                return Err(LoadError::InvalidArgument(tr!(
+                    "dealloc-chunks-too-many",
+                    count = args.chunk_count,
+                    total = ByteSize::b(bytes)
+                )));
+            }
+            info!("{}", tr!("dealloc-allocating", count = args.chunk_count, size = ByteSize::b(size), pattern = pattern.name()));
+            match objects::alloc_chunks(bytes, args.chunk_count) {
+                Some(chunks) => memory_objects = chunks,
+                None => return Err(out_of_memory(args, bytes)),
+            }
+            None
+        } else if args.swap_pressure {
+            swap::check_swap(bytes);
+            let chunks = swap::alloc_chunks(bytes);
+            if objects::total_bytes(&chunks) < bytes {
+                return Err(out_of_memory(args, bytes));
+            }
+            memory_objects = chunks;
+            None
+        } else if args.memory_fragmentation {
+            info!("{}", tr!("fragmentation-allocating", size = ByteSize::b(bytes)));
+            let start = Instant::now();
+            match objects::alloc_fragments(bytes) {
+                Some(fragments) => {
+                    info!(
+                        "{}",
+                        tr!(
+                            "fragmentation-allocated",
+                            count = fragments.len(),
+                            ms = format!("{:.1}", start.elapsed().as_secs_f64() * 1e3)
+                        )
+                    );
+                    memory_objects = fragments;
+                }
+                None => return Err(out_of_memory(args, bytes)),
+            }
+            None
+        } else if let Some(object_size) = args.memory_object_size {
+            let object_size = object_size.as_u64();
+            if object_size == 0 || object_size > bytes {
+                return Err(LoadError::InvalidArgument(tr!(
+                    "objects-size-invalid",
+                    size = ByteSize::b(object_size),
+                    total = ByteSize::b(bytes)
+                )));
+            }
+            objects::report_allocator_overhead();
+            let count = bytes / object_size;
+            info!("{}", tr!("objects-allocating", count = count, size = ByteSize::b(object_size)));
+            let start = Instant::now();
+            match objects::alloc_objects(bytes, object_size) {
+                Some(objects) => {
+                    info!("{}", tr!("objects-allocated", ms = format!("{:.1}", start.elapsed().as_secs_f64() * 1e3)));
+                    memory_objects = objects;
+                }
+                None => return Err(out_of_memory(args, bytes)),
+            }
+            None
+        } else {
+            info!("{}", tr!("memory-allocating", size = ByteSize::b(bytes)));
+            let mut buffer = memory::try_alloc_zeroed(bytes as usize);
+            // 分段绑定须在首次访问之前进行；随后预分配页面，才能统计各节点实际提供的内存
+            if let (Some(buffer), Some(allocator)) = (&mut buffer, &weighted_numa) {
+                match allocator.bind(buffer) {
+                    Ok(()) => {
+                        memory::prefault(buffer, true);
+                        allocator.report(buffer);
+                    }
+                    Err(e) => warn!("{}", tr!("numa-weights-bind-failed", error = e)),
+                }
+            }
+            match &mut buffer {
+                None => return Err(out_of_memory(args, bytes)),
+                Some(buffer) if args.memory_prefault => {
+                    let (method, elapsed) = memory::prefault(buffer, true);
+                    let method = match method {
+                        memory::PrefaultMethod::Populate => "MADV_POPULATE_WRITE".to_string(),
+                        memory::PrefaultMethod::Sequential => tr!("memory-prefault-sequential"),
+                    };
+                    info!("{}", tr!("memory-prefaulted", method = method, ms = format!("{:.1}", elapsed.as_secs_f64() * 1e3)));
+                }
+                Some(buffer) if args.no_prefault => {
+                    let (first, again) = memory::measure_first_access(buffer);
+                    info!(
+                        "{}",
+                        tr!(
+                            "memory-first-access",
+                            first = format!("{:.2}", first),
+                            again = format!("{:.2}", again),
+                            fault = format!("{:.2}", first - again)
+                        )
+                    );
+                }
+                Some(_) => {}
+            }
+            // 页面写入后才能从 numa_maps 看到分布，这里同时完成预分配
+            if let Some(buffer) = buffer.as_mut().filter(|_| args.ensure_numa_coverage) {
+                if !args.memory_prefault {
+                    memory::prefault(buffer, true);
+                }
+                numa_coverage = numa::ensure_coverage(buffer);
+            }
+            buffer
+        }
+    } else {
+        None
+    };
+    state.replace_memory(memory_vec);
+    if !memory_objects.is_empty() {
+        state.replace_objects(memory_objects);
+    }
+    if args.memory.is_some() {
+        timeline::end(timeline::MEMORY_ROW, "memory_alloc");
+    }
+    
+    // 就绪后需要通知的文件描述符：--notify-fd 以及后台模式下父进程等待的管道
+    #[cfg_attr(not(unix), allow(unused_mut))]
+    let mut notify_fds: Vec<i32> = args.notify_fd.into_iter().collect();
+    #[cfg(not(unix))]
+    if !notify_fds.is_empty() {
+        warn!("{}", tr!("notify-fd-unsupported"));
+        notify_fds.clear();
+    }
+
+    if args.background {
+        #[cfg(unix)]
+        {
+            info!("{}", tr!("background-starting"));
+            let log_path = background_log_path(&args.instance_name);
+            // 父进程留在前台，等子进程报告就绪后才退出；fork::daemon 会让调用它的进程直接退出
+            let (read_fd, write_fd) = notify::ready_pipe()
+                .map_err(|e| std::io::Error::new(e.kind(), tr!("background-pipe-failed", error = e)))?;
+            match fork::fork() {
+                Ok(fork::Fork::Parent(_)) => {
+                    notify::close(write_fd);
+                    info!("{}", tr!("background-log-file", path = log_path.display()));
+                    let timeout = Duration::from_secs(args.notify_timeout);
+                    match notify::wait_ready(read_fd, timeout) {
+                        notify::Readiness::Ready => {
+                            info!("{}", tr!("background-ready"));
+                            process::exit(0);
+                        }
+                        notify::Readiness::Exited => {
+                            error!("{}", tr!("background-exited"));
+                            process::exit(1);
+                        }
+                        notify::Readiness::TimedOut => {
+                            error!("{}", tr!("background-timed-out", secs = args.notify_timeout));
+                            process::exit(1);
+                        }
+                    }
+                }
+                Ok(fork::Fork::Child) => {
+                    notify::close(read_fd);
+                    notify_fds.push(write_fd);
+                }
+                Err(_) => return Err(std::io::Error::other(tr!("background-fork-failed")).into()),
+            }
+            // 在 daemon 关闭标准输出之前打开，打开失败的告警仍能显示在终端上
+            let log = open_background_log(&log_path);
+            match fork::daemon(false, false) {
+                Ok(fork::Fork::Child) => {
+                    redirect_stdio(log);
+                    // --log-file 已逐条记录日志，不再同时写入指向同一文件的标准输出
+                    if logger::log_file_path().is_some() {
+                        logger::set_quiet(true);
+                    }
+                    // 子进程继续执行负载
+                    // 重新保存PID，因为子进程PID不同
+                    if let Err(e) = save_pid(&args.instance_name) {
+                        // 在后台模式下，打印到控制台可能不可见，可以考虑日志记录
+                        warn!("{}", tr!("background-pid-save-failed", error = e));
+                    }
+                    // 子进程继续执行下面的负载代码
+                }
+                Ok(fork::Fork::Parent(pid)) => {
+                    // 父进程退出
+                    info!("{}", tr!("background-parent-exit", pid = pid));
+                    std::process::exit(0); // 确保父进程干净退出
+                }
+                // 无法后台运行，由 launch 清理PID文件
+                Err(_) => return Err(std::io::Error::other(tr!("background-fork-failed")).into()),
+            }
+        }
+        // Windows 上已在 launch 中重新启动为子进程，子进程不带 -b，不会走到这里
+        #[cfg(not(any(unix, windows)))]
+        {
+            warn!("{}", tr!("background-windows-unsupported"));
+            // 不执行 fork，继续在前台运行
+        }
+    }
+
+    // 设置中断处理；ctrlc 的处理线程不会被 fork 出的子进程继承，所以放在 fork 之后
+    if ready.is_none() {
+        let state = state.clone();
+        let instance_name = args.instance_name.clone();
+        ctrlc::set_handler(move || {
+            state.request_stop();
+            info!("{}", tr!("start-stopping"));
+            state.remove_temp_files();
+            let _ = remove_pid_file(&instance_name);
+        }).map_err(|e| std::io::Error::other(tr!("ctrlc-handler-failed", error = e)))?;
+    }
+
+    // 锁定内存；子进程不会继承 mlock，所以放在 fork 之后
+    if args.lock_memory {
+        let guard = state.memory.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(buffer) = guard.as_deref() {
+            let requested = buffer.len() as u64 * u64::from(args.memory_pin_percent) / 100;
+            match mlock::lock_prefix(buffer, args.memory_pin_percent) {
+                Ok(locked) => {
+                    info!("{}", tr!("mlock-locked", locked = ByteSize::b(locked as u64), requested = ByteSize::b(requested)));
+                    state.locked_bytes.store(locked as u64, Ordering::Relaxed);
+                }
+                Err(e) => warn!("{}", tr!("mlock-failed", error = e)),
+            }
+        }
+    }
+
+    let overrides = args.thread_overrides.clone().unwrap_or_default();
+    let mut unused: Vec<_> = overrides.keys().filter(|&&i| i >= actual_cores).collect();
+    if !unused.is_empty() {
+        unused.sort();
+        warn!("{}", tr!("thread-overrides-unused", threads = format!("{:?}", unused), cores = actual_cores));
+    }
+
+    // 权限不足时改用不依赖 perf 的统计方式，或不统计硬件计数器
+    let uses_hardware_counters = !args.perf_counters.is_empty() && cfg!(feature = "perf");
+    let perf_allowed = !(args.trace_syscalls || uses_hardware_counters) || perf::check_paranoia();
+
+    // 系统调用计数器需在创建线程之前打开，之后创建的线程才会被统计
+    let syscall_tracer = args.trace_syscalls.then(|| syscalls::SyscallTracer::new(perf_allowed));
+
+    // 自带收发线程的负载不启动计算线程
+    let worker_count = if args.workload.uses_cpu_workers() { actual_cores } else { 0 };
+
+    // 跨机器同步时，工作线程在屏障处等待开始信号
+    let start_barrier = args.sync_with.as_ref().map(|_| Arc::new(Barrier::new(worker_count + 1)));
+
+    let limit_cycles = args.limit_cycles;
+    let thread_rate = args.rate_limit.map(|rate| rate as f64 / worker_count.max(1) as f64);
+    if let Some(rate) = args.rate_limit
+        && args.workload == Workload::Spin {
+        info!("{}", tr!("rate-limit", rate = rate, per_thread = format!("{:.1}", rate as f64 / worker_count.max(1) as f64)));
+    }
+    let perf_counters = if args.perf_counters.is_empty() {
+        None
+    } else if !perf_allowed {
+        warn!("{}", tr!("perf-disabled"));
+        None
+    } else {
+        PerfCounters::new(perf::with_companions(&args.perf_counters)).map(Arc::new)
+    };
+    let rt = args.rt_policy.zip(args.rt_priority);
+    if let Some((policy, priority)) = rt {
+        sched::check_support(policy, priority);
+    }
+    let affinity = if let Some(cpus) = cpu_affinity.filter(|_| worker_count > 0) {
+        explicit_affinity(&cpus)
+    } else if args.cpu_affinity_interleave && worker_count > 0 {
+        interleaved_affinity(worker_count)
+    } else if args.hyper_threads_only && worker_count > 0 {
+        hyper_thread_affinity(worker_count)
+    } else if let Some(cpus) = numa_cpus.filter(|_| worker_count > 0) {
+        let locations = topology::cpu_locations();
+        Some(cpus.iter().filter_map(|&cpu| locations.get(cpu).copied()).collect())
+    } else {
+        None
+    };
+    // 绑核时从中去掉状态线程的CPU，未绑核时把工作线程绑定到其余所有CPU
+    let affinity = match status_core_reserved.filter(|_| worker_count > 0) {
+        Some(core) => Some(affinity.unwrap_or_else(topology::cpu_locations))
+            .map(|order| order.into_iter().filter(|l| l.cpu != core).collect::<Vec<_>>())
+            .filter(|order| !order.is_empty()),
+        None => affinity,
+    };
+    let turbo_guard = cpufreq::apply_turbo(args.turbo_boost);
+    let profile_guard = governor_profile.as_deref().map(cpufreq::apply_profile);
+    // 未绑核时工作线程可能在任意CPU上运行，调频策略和频率跟踪都覆盖所有CPU
+    let worker_cpus = {
+        let mut cpus: Vec<usize> = match &affinity {
+            Some(order) => order.iter().cycle().take(worker_count).map(|l| l.cpu).collect(),
+            None => (0..num_cpus::get()).collect(),
+        };
+        cpus.sort_unstable();
+        cpus.dedup();
+        cpus
+    };
+    let governor_guard = args
+        .cpufreq_governor
+        .filter(|_| worker_count > 0)
+        .map(|governor| cpufreq::apply(governor, &worker_cpus));
+    let irq_guard = args.irq_balance_disable.then(|| irqaffinity::apply(&worker_cpus));
+    // 在工作线程启动前开始跟踪，捕捉负载开始时的升频
+    let freq_report = if args.cpufreq_report {
+        #[cfg(target_os = "linux")]
+        match freqtrace::spawn_freq_report(worker_cpus.clone(), state.clone()) {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                warn!("{}", tr!("freqtrace-start-failed", error = e));
+                None
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            warn!("{}", tr!("freqtrace-unsupported"));
+            None
+        }
+    } else {
+        None
+    };
+    let engine = Engine::new(args.async_engine);
+    let workload = args.workload;
+    let busy_loop = args.busy_loop_type;
+    let verify_baseline = (args.cpu_work_verify && workload == Workload::Spin && worker_count > 0).then(|| {
+        let baseline = workload::calibrate(busy_loop, CALIBRATION_WINDOW, u64::from(WORK_BURST_ITERATIONS));
+        info!("{}", tr!("work-verify-baseline", rate = format!("{:.0}", baseline)));
+        baseline
+    });
+    let spin_settings = SpinSettings {
+        busy_loop,
+        rate: thread_rate,
+        verify_baseline,
+        work_batch: args.work_batch,
+        cache_size: args.cache_size.as_u64() as usize,
+    };
+    let work_mix = if args.cpu_work_mix.is_empty() || workload != Workload::Spin {
+        Vec::new()
+    } else {
+        let assigned = workload::distribute_mix(&args.cpu_work_mix, worker_count);
+        for &(kind, percent) in &args.cpu_work_mix {
+            let threads = assigned.iter().filter(|&&k| k == kind).count();
+            info!("{}", tr!("work-mix-assigned", kind = kind.name(), percent = percent, threads = threads));
+        }
+        assigned
+    };
+    let compress_settings = CompressSettings {
+        level: args.compress_level,
+        block_size: (args.compress_block_size.as_u64() as usize).max(1),
+    };
+    let compress_stats = Arc::new(CompressStats::default());
+    let churn_settings = ChurnSettings {
+        min_size: args.alloc_size.0,
+        max_size: args.alloc_size.1,
+        pool_size: args.alloc_pool_size as usize,
+        rate: args.alloc_rate.map(|rate| rate as f64 / worker_count.max(1) as f64),
+    };
+    let churn_stats = Arc::new(ChurnStats::default());
+    let atomic_stress = Arc::new(AtomicStress::new(
+        if workload == Workload::AtomicStress { args.atomic_array_size as usize } else { 1 },
+        args.atomic_op,
+    ));
+    let cache_thrash = Arc::new(CacheThrash::new(
+        if workload == Workload::CacheThrash { args.shared_array_size.as_u64() as usize } else { 0 },
+    ));
+    let cache_alternating = Arc::new(CacheAlternating::new(
+        workload == Workload::CacheAlternating,
+        args.cold_period,
+        args.warm_period,
+    ));
+    #[cfg(unix)]
+    let interruption = Arc::new(InterruptionTest::new(args.interrupt_rate, worker_count));
+    let io_stress = args.io_stress.as_ref().map(|io| {
+        let settings = IoStressSettings {
+            dir: PathBuf::from(&args.path),
+            threads: usize::from(io.threads),
+            block_size: io.block_size.as_u64() as usize,
+            pattern: io.pattern,
+            file_size: args.file_size.as_u64(),
+        };
+        let counters = Arc::new(IoCounters::new(&settings));
+        (settings, counters)
+    });
+    if args.self_monitor {
+        let _ = state.self_monitor.set((0..worker_count).map(|_| Arc::default()).collect());
+    }
+    // 状态线程在工作线程之前启动并绑核，满载时仍能按时刷新
+    let status_pin = match args.status_core {
+        Some(_) if args.async_engine => {
+            warn!("{}", tr!("status-core-async"));
+            None
+        }
+        Some(_) if !cfg!(target_os = "linux") => {
+            warn!("{}", tr!("status-core-unsupported"));
+            None
+        }
+        core => core,
+    };
+    // 定期显示系统状态；测量阶段中工作线程已退出，但状态线程继续运行
+    let measuring = Arc::new(AtomicBool::new(false));
+    let status_thread = engine.spawn_status(
+        Duration::from_secs(2),
+        StatusMonitor {
+            sys: System::new_all(),
+            summary: RunSummary {
+                histogram_bins: args.cpu_utilization_histogram.then_some(usize::from(args.histogram_bins)),
+                max_steal: args.max_acceptable_steal,
+                ..Default::default()
+            },
+            renderer: output::renderer(args.output_format),
+            state: state.clone(),
+            measuring: measuring.clone(),
+            actual_cores,
+            show_target,
+            perf_counters: perf_counters.clone(),
+            syscall_tracer,
+            disk_monitor: diskstats::DiskMonitor::new(args.disk_filter.clone()),
+            net_monitor: netstats::NetMonitor::new(args.net_filter.clone()),
+            self_monitor: state.self_monitor.get().cloned().map(SelfMonitor::new),
+            thp_monitor: args.memory_huge_pages_status.then(ThpMonitor::default),
+            steal_monitor: StealMonitor::new(args.max_acceptable_steal),
+            io_monitor: io_stress.as_ref().map(|(_, counters)| IoMonitor::new(counters.clone())),
+            metrics_writer,
+            pin_core: status_pin,
+        },
+    );
+
+    // 阶梯核心数模式下工作线程一启动就只启用第一级的核心数
+    if let Some(stepper) = &core_stepper {
+        state.set_active_workers(stepper.start.min(worker_count));
+    }
+    // 逐步增加负载时工作线程从零负载开始
+    let ramp = (args.ramp_up.is_some() || args.ramp_down.is_some()).then_some(RampSettings {
+        target: match args.cpu_base {
+            Some(percent) => RampTarget::Percent(percent),
+            None => RampTarget::Workers(worker_count),
+        },
+        up: args.ramp_up,
+        down: args.ramp_down,
+    });
+    if let Some(ramp) = &ramp {
+        ramp.prepare(&state);
+    }
+    let handles: Vec<_> = (0..worker_count)
+        .map(|i| {
+            let state = state.clone();
+            let compress_stats = compress_stats.clone();
+            let atomic_stress = atomic_stress.clone();
+            let churn_stats = churn_stats.clone();
+            let cache_thrash = cache_thrash.clone();
+            let cache_alternating = cache_alternating.clone();
+            #[cfg(unix)]
+            let interruption = interruption.clone();
+            #[cfg(target_os = "linux")]
+            let cpu = affinity.as_ref().map(|order| order[i % order.len()].cpu);
+            let fixed_percent = overrides.get(&i).copied();
+            let spin_settings = SpinSettings { busy_loop: work_mix.get(i).copied().unwrap_or(busy_loop), ..spin_settings };
+            let start_barrier = start_barrier.clone();
+            let perf_counters = perf_counters.clone();
+            engine.spawn_worker(move || {
+                if let Some(counters) = &perf_counters
+                    && let Err(e) = counters.attach_current_thread() {
+                    warn!("{}", tr!("worker-perf-failed", thread = i, error = e));
+                }
+                match fixed_percent {
+                    Some(percent) => info!("{}", tr!("worker-started-target", thread = i, percent = percent)),
+                    None => info!("{}", tr!("worker-started", thread = i)),
+                }
+                // 非Linux平台已在启动时提示过，这里不再逐线程告警
+                #[cfg(target_os = "linux")]
+                if let Some((policy, priority)) = rt
+                    && let Err(e) = sched::apply_to_current_thread(policy, priority) {
+                    warn!("{}", tr!("worker-rt-failed", thread = i, error = e));
+                }
+                #[cfg(target_os = "linux")]
+                if let Some(cpu) = cpu
+                    && let Err(e) = topology::pin_current_thread(cpu) {
+                    warn!("{}", tr!("worker-affinity-failed", thread = i, cpu = cpu, error = e));
+                }
+                if let Some(barrier) = start_barrier {
+                    barrier.wait();
+                }
+                let row = timeline::worker_row(i);
+                timeline::begin(row, "cpu_worker");
+                let start = Instant::now();
+                let done = match workload {
+                    Workload::ZstdCompress => compress::compress_task(
+                        &state,
+                        i,
+                        fixed_percent,
+                        limit_cycles,
+                        compress_settings,
+                        &compress_stats,
+                    ),
+                    Workload::AllocChurn => allocchurn::churn_task(
+                        &state,
+                        i,
+                        fixed_percent,
+                        limit_cycles,
+                        churn_settings,
+                        &churn_stats,
+                    ),
+                    Workload::AtomicStress => {
+                        atomicstress::atomic_task(&state, i, fixed_percent, limit_cycles, &atomic_stress)
+                    }
+                    Workload::CacheThrash => {
+                        cachethrash::thrash_task(&state, i, fixed_percent, limit_cycles, &cache_thrash)
+                    }
+                    Workload::CacheAlternating => {
+                        cachealternate::alternating_task(&state, i, fixed_percent, limit_cycles, &cache_alternating)
+                    }
+                    #[cfg(unix)]
+                    Workload::InterruptionTest => {
+                        interruption::interruption_task(&state, i, fixed_percent, limit_cycles, &interruption)
+                    }
+                    _ => cpu_intensive_task(&state, i, fixed_percent, limit_cycles, spin_settings),
+                };
+                timeline::end(row, "cpu_worker");
+                (done, start.elapsed())
+            })
+        })
+        .collect();
+
+    if let (Some(addr), Some(barrier)) = (&args.sync_with, &start_barrier) {
+        if let Err(e) = sync::wait_for_go(addr, &state) {
+            error!("{}", tr!("sync-wait-failed", error = e));
+            state.request_stop();
+        }
+        // 无论是否收到信号都要释放屏障，停止时工作线程随即退出
+        barrier.wait();
+    }
+
+    // 随负载一同退出的辅助线程
+    let mut helper_threads: Vec<_> = freq_report.into_iter().collect();
+
+    // 启动内存碎片抖动线程
+    if args.memory_fragmentation && !state.objects.lock().unwrap_or_else(|e| e.into_inner()).is_empty() {
+        helper_threads.push(objects::spawn_fragment_churn(state.clone()));
+    }
+
+    // 启动信号调整线程
+    if let Some(target) = args.signal_adjust {
+        let adjust = sigadjust::SignalAdjust {
+            target,
+            step: args.signal_step.unwrap_or_else(|| target.default_step()),
+            workers: worker_count,
+        };
+        #[cfg(unix)]
+        match signals::install_usr1_counter().and_then(|()| signals::install_usr2_counter()) {
+            Ok(()) => helper_threads.push(sigadjust::spawn_signal_adjuster(adjust, state.clone())),
+            Err(e) => warn!("{}", tr!("signal-adjust-install-failed", error = e)),
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = adjust;
+            warn!("{}", tr!("signal-adjust-unsupported"));
+        }
+    }
+
+    // 启动尖峰模式驱动线程
+    if let Some(spike) = spike {
+        helper_threads.push(pattern::spawn_spike_driver(spike, state.clone()));
+    }
+    if let Some(spike) = signal_spike {
+        #[cfg(unix)]
+        match signals::install_usr1_counter() {
+            Ok(()) => helper_threads.push(pattern::spawn_signal_spike_driver(spike, state.clone())),
+            Err(e) => warn!("{}", tr!("spike-signal-install-failed", error = e)),
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = spike;
+            warn!("{}", tr!("spike-signal-unsupported"));
+        }
+    }
+
+    if args.cpu_thermal_protect {
+        helper_threads.push(thermal::spawn_thermal_guard(args.temp_max, state.clone()));
+    }
+
+    // 启动自动核心调整线程
+    if let Some(target) = args.target_system_cpu {
+        let settings = ControlSettings {
+            target,
+            hysteresis: args.hysteresis,
+            damping_time: Duration::from_secs(args.damping_time),
+        };
+        helper_threads.push(autocores::spawn_controller(settings, actual_cores, state.clone()));
+    }
+
+    // 启动负载脉冲驱动线程
+    if let (Some(count), Some(on), Some(off)) = (args.burst_count, args.burst_on_secs, args.burst_off_secs) {
+        let burst = BurstPattern {
+            count,
+            on: Duration::from_secs(on),
+            off: Duration::from_secs(off),
+            percent: args.cpu_base.unwrap_or(100),
+            cores: actual_cores,
+        };
+        info!("{}", tr!("burst-mode", count = count, on = on, off = off));
+        helper_threads.push(burst::spawn_burst_driver(burst, args.output_format, state.clone()));
+    }
+
+    // 启动循环负载驱动线程
+    if let Some(schedule) = args.load_schedule {
+        let repeat = match schedule.repeat {
+            0 => tr!("schedule-repeat-forever"),
+            count => count.to_string(),
+        };
+        info!(
+            "{}",
+            tr!(
+                "schedule-mode",
+                work = schedule.work.as_secs_f64(),
+                pause = schedule.pause.as_secs_f64(),
+                repeat = repeat
+            )
+        );
+        helper_threads.push(schedule::spawn_schedule_driver(schedule, args.cpu_base.unwrap_or(100), state.clone()));
+    }
+
+    // 启动分阶段核心数驱动线程
+    if let Some(phases) = phases {
+        info!("{}", tr!("phases-mode", count = phases.cores.len(), workers = worker_count));
+        helper_threads.push(phases::spawn_phase_driver(phases, worker_count, state.clone()));
+    }
+
+    // 启动阶梯驱动线程
+    if let Some(stepper) = core_stepper {
+        helper_threads.push(stepper.spawn(worker_count, state.clone()));
+    }
+    if let Some(stepper) = memory_stepper {
+        helper_threads.push(stepper.spawn(state.clone()));
+    }
+
+    // 启动逐步增减负载的驱动线程
+    if let Some(ramp) = ramp {
+        helper_threads.push(ramp::spawn_ramp_driver(ramp, state.clone()));
+    }
+
+    // 写入状态文件；后台模式下此时已是子进程的PID
+    if args.compress_pid_file {
+        let instance = statefile::InstanceState {
+            pid: process::id(),
+            started_at: timeutil::unix_secs(),
+            target_percent: state.target_percent.load(Ordering::Relaxed),
+            memory_bytes: state.allocated_bytes.load(Ordering::Relaxed),
+            cores: worker_count as u32,
+            profile: args.workload.to_possible_value().map_or_else(String::new, |v| v.get_name().to_string()),
+        };
+        let state_file = statefile::state_path(&instances::get_pid_file(&args.instance_name));
+        helper_threads.push(statefile::spawn_updater(state_file, instance, state.clone()));
+    }
+
+    // 启动逐秒CPU使用率日志
+    if let Some(path) = &args.cpu_realtime_log {
+        match realtimelog::spawn(path, args.cpu_realtime_buffer as usize, actual_cores, state.clone()) {
+            Ok(handle) => {
+                info!("{}", tr!("realtime-log-started", path = path.display(), capacity = args.cpu_realtime_buffer));
+                helper_threads.push(handle);
+            }
+            Err(e) => warn!("{}", tr!("realtime-log-open-failed", path = path.display(), error = e)),
+        }
+    }
+
+    // 启动控制套接字
+    if let Some(path) = &args.control_socket {
+        #[cfg(unix)]
+        match control::spawn_server(path, state.clone()) {
+            Ok(handle) => {
+                info!("{}", tr!("control-started", path = path.display()));
+                helper_threads.push(handle);
+            }
+            Err(e) => warn!("{}", tr!("control-start-failed", path = path.display(), error = e)),
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = path;
+            warn!("{}", tr!("control-unsupported"));
+        }
+    }
+
+    // 启动目标文件监视线程
+    if let Some(path) = &args.target_file {
+        helper_threads.push(targetfile::spawn_target_file_watcher(path.clone(), state.clone()));
+    }
+
+    // 启动压缩吞吐量输出线程
+    if workload == Workload::ZstdCompress {
+        info!(
+            "{}",
+            tr!(
+                "compress-settings",
+                level = compress_settings.level,
+                block = ByteSize::b(compress_settings.block_size as u64)
+            )
+        );
+        helper_threads.push(compress::spawn_reporter(compress_stats, state.clone()));
+    }
+
+    // 启动让路看门狗
+    if let Some(pattern) = &args.yield_to {
+        #[cfg(target_os = "linux")]
+        helper_threads.push(yieldto::spawn_yield_watchdog(
+            yieldto::YieldSettings { pattern: pattern.clone(), floor: args.yield_priority_floor },
+            state.clone(),
+        ));
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = pattern;
+            warn!("{}", tr!("yield-unsupported"));
+        }
+    }
+
+    // 启动原子操作速率输出线程
+    if workload == Workload::AtomicStress {
+        helper_threads.push(atomicstress::spawn_reporter(atomic_stress, state.clone()));
+    }
+
+    // 启动缓存颠簸写入速率输出线程
+    if workload == Workload::CacheThrash {
+        helper_threads.push(cachethrash::spawn_reporter(cache_thrash, state.clone()));
+    }
+
+    // 启动窃取时间补偿线程
+    if args.cpu_steal_compensation {
+        helper_threads.push(steal::spawn_compensation(state.clone()));
+    }
+
+    // 启动冷热缓存阶段切换输出线程
+    if workload == Workload::CacheAlternating {
+        helper_threads.push(cachealternate::spawn_reporter(cache_alternating, state.clone()));
+    }
+
+    // 启动信号打断线程和开销统计线程
+    if workload == Workload::InterruptionTest {
+        #[cfg(unix)]
+        match interruption::spawn_interrupter(interruption, state.clone()) {
+            Ok(handles) => helper_threads.extend(handles),
+            Err(e) => warn!("{}", tr!("interrupt-start-failed", error = e)),
+        }
+        #[cfg(not(unix))]
+        warn!("{}", tr!("interrupt-unsupported"));
+    }
+
+    // 启动分配速率输出线程
+    if workload == Workload::AllocChurn {
+        helper_threads.push(allocchurn::spawn_reporter(churn_settings, churn_stats, state.clone()));
+    }
+
+    // 启动UDP回环压力
+    if args.workload == Workload::UdpFlood {
+        match udpflood::spawn_udp_flood(args.udp_pps, state.clone()) {
+            Ok(handles) => helper_threads.extend(handles),
+            Err(e) => warn!("{}", tr!("udp-start-failed", error = e)),
+        }
+    }
+
+    // 启动进程间通信压力
+    if args.workload == Workload::IpcStress {
+        #[cfg(unix)]
+        match ipcstress::spawn_ipc_stress(args.ipc_type, usize::from(args.ipc_threads), state.clone()) {
+            Ok(handles) => helper_threads.extend(handles),
+            Err(e) => warn!("{}", tr!("ipc-start-failed", error = e)),
+        }
+        #[cfg(not(unix))]
+        warn!("{}", tr!("ipc-unsupported"));
+    }
+
+    // 启动TCP连接压力
+    if args.workload == Workload::SocketStress {
+        #[cfg(unix)]
+        match socketstress::spawn_socket_stress(args.connection_rate, usize::from(args.acceptor_threads), state.clone()) {
+            Ok(handles) => helper_threads.extend(handles),
+            Err(e) => warn!("{}", tr!("socket-start-failed", error = e)),
+        }
+        #[cfg(not(unix))]
+        warn!("{}", tr!("socket-unsupported"));
+    }
+
+    // 启动页缓存压力
+    if args.workload == Workload::PageCache {
+        match pagecache::spawn_page_cache_load(
+            args.file.as_deref(),
+            args.file_size,
+            Path::new(&args.path),
+            usize::from(args.read_threads),
+            state.clone(),
+        ) {
+            Ok(handles) => helper_threads.extend(handles),
+            Err(e) => warn!("{}", tr!("pagecache-start-failed", error = e)),
+        }
+    }
+
+    // 启动 io-stress 的读写线程
+    if let Some((settings, counters)) = io_stress {
+        helper_threads.extend(iostress::spawn_io_stress(settings, counters, state.clone()));
+    }
+
+    // 启动 Rayon 线程池负载
+    if args.workload == Workload::Rayon {
+        let unit = u64::from(WORK_BURST_ITERATIONS);
+        match rayonpool::spawn_rayon_load(actual_cores, busy_loop, unit, state.clone()) {
+            Ok(handles) => helper_threads.extend(handles),
+            Err(e) => warn!("{}", tr!("rayon-start-failed", error = e)),
+        }
+    }
+
+    // 启动文件描述符压力
+    if args.workload == Workload::FdStress {
+        match fdstress::spawn_fd_stress(args.fd_type, args.fd_count as usize, state.clone()) {
+            Ok(handle) => helper_threads.push(handle),
+            Err(e) => warn!("{}", tr!("fd-start-failed", error = e)),
+        }
+    }
+
+    // 启动硬盘I/O负载
+    if let (Some(depth), Some(path)) = (args.io_depth, &disk_file) {
+        match diskio::spawn_io_load(path, usize::from(depth), state.clone()) {
+            Ok(handles) => helper_threads.extend(handles),
+            Err(e) => warn!("{}", tr!("diskio-start-failed", error = e)),
+        }
+    }
+
+    // 启动内存访问线程
+    if let Some(pattern) = args.memory_pattern
+        && state.allocated_bytes.load(Ordering::Relaxed) > 0 {
+        let stride = match pattern {
+            MemoryPattern::Stride => (args.memory_stride.as_u64() as usize).max(1),
+            MemoryPattern::StridePrefetchDefeat => {
+                let vendor = memstress::cpu_vendor();
+                let stride = memstress::prefetch_defeat_stride(&vendor);
+                info!("{}", tr!("memstress-prefetch-stride", vendor = format!("{:?}", vendor), stride = stride));
+                stride
+            }
+            MemoryPattern::PrefetchTrain => memstress::TRAIN_STRIDE,
+            _ => memstress::CACHE_LINE,
+        };
+        let threads = AccessThreads {
+            count: usize::from(args.memory_access_threads),
+            overlap: args.memory_access_overlap,
+        };
+        let switch_interval = Duration::from_millis(args.prefetch_switch_interval);
+        let latency = Duration::from_nanos(args.memory_latency_ns.unwrap_or(0));
+        helper_threads.push(memstress::spawn_memory_stress(
+            pattern,
+            stride,
+            switch_interval,
+            threads,
+            latency,
+            state.clone(),
+        ));
+    }
+
+    // 启动内存带宽线程
+    if let Some(mode) = args.memory_bandwidth_mode
+        && state.allocated_bytes.load(Ordering::Relaxed) > 0 {
+        let threads = usize::from(args.memory_access_threads);
+        helper_threads.push(bandwidth::spawn_bandwidth_test(mode, threads, state.clone()));
+    }
+
+    // 启动内存增长线程
+    if let Some(limit) = args.memory_growth {
+        let settings = GrowthSettings {
+            limit: limit.as_u64(),
+            max_rate: args.max_memory_growth_rate.map(|rate| rate.as_u64()),
+            safety_margin: args.memory_safety_margin.as_u64(),
+        };
+        helper_threads.push(growth::spawn_growth(settings, state.clone()));
+    }
+
+    // 启动内存气球线程
+    if let Some(max) = args.balloon_max.filter(|_| args.memory_balloon) {
+        let settings = BalloonSettings { period: Duration::from_secs(args.balloon_period), max: max.as_u64() };
+        helper_threads.push(balloon::spawn_balloon(settings, state.clone()));
+    }
+
+    // 启动交换压力线程
+    if args.swap_pressure && state.allocated_bytes.load(Ordering::Relaxed) > 0 {
+        helper_threads.push(swap::spawn_swap_pressure(state.clone()));
+    }
+
+    // 启动内存压力测试线程
+    if args.memory_pressure_test
+        && state.allocated_bytes.load(Ordering::Relaxed) > 0
+        && memorypressure::check_support(args.memory_pressure_level) {
+        helper_threads.push(memorypressure::spawn_pressure_test(args.memory_pressure_level, state.clone()));
+    }
+
+    // 启动内存规整测试线程
+    if args.memory_compaction && state.allocated_bytes.load(Ordering::Relaxed) > 0 && compaction::check_support() {
+        helper_threads.push(compaction::spawn_compaction_test(state.clone()));
+    }
+
+    // 启动内存常驻检查线程
+    if let Some(secs) = args.memory_check_interval {
+        let check = ResidencyCheck {
+            interval: Duration::from_secs(secs.max(1)),
+            min_resident_percent: args.memory_min_resident,
+            willneed: args.memory_willneed,
+        };
+        helper_threads.push(residency::spawn_checker(check, state.clone()));
+    }
+    
+    #[cfg(unix)]
+    for fd in notify_fds {
+        notify::notify_ready(fd);
+    }
+    if let Some(ready) = &ready {
+        let _ = ready.send(state.clone());
+    }
+
+    let hook_timeout = Duration::from_secs(args.hook_timeout);
+    if let Some(cmd) = &args.post_start_cmd {
+        hooks::run_hook("post-start", cmd, hook_timeout);
+    }
+
+    // 等待负载结束：到达持续时间、完成全部迭代或收到中断
+    // 正常到期时先执行 pre-stop 钩子再停止；外部中断时负载已在停止，随后立即执行
+    let run_pre_stop = || {
+        if let Some(cmd) = &args.pre_stop_cmd {
+            hooks::run_hook("pre-stop", cmd, hook_timeout);
+        }
+    };
+    let load_start = Instant::now();
+    if let Some(duration) = args.duration {
+        let _ = state.deadline.set(load_start + duration);
+    }
+    while state.is_running() {
+        if args.duration.is_some_and(|d| load_start.elapsed() >= d) {
+            info!("{}", tr!("duration-reached"));
+            run_pre_stop();
+            state.finish();
+            break;
+        }
+        if args.limit_cycles.is_some() && handles.iter().all(|h| h.is_finished()) {
+            info!("{}", tr!("cycles-reached"));
+            run_pre_stop();
+            state.finish();
+            break;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    if state.interrupted.load(Ordering::SeqCst) {
+        run_pre_stop();
+    }
+    
+    // 等待所有线程完成
+    let results: Vec<(u64, Duration)> = handles.into_iter().filter_map(|h| engine.join(h)).collect();
+    if let Some(limit) = args.limit_cycles {
+        print_benchmark_score(&results, limit);
+    }
+    if let Some(counters) = &perf_counters {
+        match counters.total() {
+            Some(metrics) => info!("{}", tr!("perf-summary", metrics = metrics.describe())),
+            None => warn!("{}", tr!("perf-none-opened")),
+        }
+    }
+    for handle in helper_threads {
+        let _ = handle.join();
+    }
+    // 负载已结束，测量阶段之前恢复中断亲和性、原来的调频策略和睿频设置
+    drop(irq_guard);
+    drop(governor_guard);
+    drop(profile_guard);
+    drop(turbo_guard);
+
+    // 工作线程已停止，内存在超时时间内逐步清空后释放
+    if args.memory.is_some() {
+        timeline::begin(timeline::MEMORY_ROW, "memory_free");
+    }
+    drop(numa_coverage);
+    if let Some(buffer) = state.take_memory() {
+        memory::drain(buffer, Duration::from_secs(args.graceful_stop_timeout));
+    }
+    match args.memory_dealloc_pattern {
+        Some(pattern) => {
+            let chunks = std::mem::take(&mut *state.objects.lock().unwrap_or_else(|e| e.into_inner()));
+            objects::dealloc_chunks(chunks, pattern);
+            state.replace_objects(Vec::new());
+        }
+        None => state.replace_objects(Vec::new()),
+    }
+    if args.memory.is_some() {
+        timeline::end(timeline::MEMORY_ROW, "memory_free");
+    }
+
+    // 负载结束后的空闲测量阶段，中断时跳过
+    if let Some(secs) = args.measure_phase_secs
+        && !state.interrupted.load(Ordering::SeqCst) {
+        info!("{}", tr!("measurement-started", secs = secs));
+        measuring.store(true, Ordering::SeqCst);
+        let measure_start = Instant::now();
+        while measure_start.elapsed() < Duration::from_secs(secs) && !state.interrupted.load(Ordering::SeqCst) {
+            thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    engine.stop_status();
+    if let Some(summary) = engine.join(status_thread) {
+        info!("{}", summary.render());
+    }
+    
+    // 清理硬盘占用文件
+    if let Some(path) = disk_file
+        && let Err(e) = std::fs::remove_file(&path) {
+        warn!("{}", tr!("disk-file-cleanup-failed", error = e));
+    }
+    
+    if let Some(path) = &args.timeline_export {
+        match timeline::export(path) {
+            Some(Ok(count)) => {
+                info!("{}", tr!("timeline-written", path = path.display(), count = count));
+                info!("  {}", tr!("timeline-hint"));
+            }
+            Some(Err(e)) => warn!("{}", tr!("timeline-write-failed", path = path.display(), error = e)),
+            None => {}
+        }
+    }
+
+    // 清理PID文件；以库的方式启动时没有写PID文件
+    if ready.is_none() {
+        let _ = remove_pid_file(&args.instance_name);
+    }
+    Ok(())
+}
+
+/// 解析 `--cpu-affinity` 的CPU列表，CPU编号必须存在且不重复
+fn parse_cpu_affinity(list: &str) -> Result<Vec<usize>, LoadError> {
+    let cpus = topology::parse_cpu_list(list)
+        .filter(|cpus| !cpus.is_empty())
+        .ok_or_else(|| LoadError::InvalidArgument(tr!("cpu-affinity-invalid", value = list)))?;
+    let count = num_cpus::get();
+    for (i, &cpu) in cpus.iter().enumerate() {
+        if cpu >= count {
+            return Err(LoadError::InvalidArgument(tr!("cpu-affinity-out-of-range", cpu = cpu, count = count)));
+        }
+        if cpus[..i].contains(&cpu) {
+            return Err(LoadError::InvalidArgument(tr!("cpu-affinity-duplicate", cpu = cpu)));
+        }
+    }
+    Ok(cpus)
+}
+
+/// `--cpu-affinity` 指定的绑核顺序；非Linux平台告警并不绑核
+fn explicit_affinity(cpus: &[usize]) -> Option<Vec<topology::CpuLocation>> {
+    if !cfg!(target_os = "linux") {
+        warn!("{}", tr!("cpu-affinity-unsupported"));
+        return None;
+    }
+    info!("{}", tr!("cpu-affinity-pinned", cpus = topology::format_cpu_ranges(cpus)));
+    let locations = topology::cpu_locations();
+    Some(cpus.iter().filter_map(|&cpu| locations.get(cpu).copied()).collect())
+}
+
+/// 按插槽交错的绑核顺序，并打印每个工作线程的分配；非Linux平台不支持绑核
+fn interleaved_affinity(workers: usize) -> Option<Vec<topology::CpuLocation>> {
+    if !cfg!(target_os = "linux") {
+        warn!("{}", tr!("interleave-unsupported"));
+        return None;
+    }
+    let order = topology::interleaved(&topology::cpu_locations());
+    info!("{}", tr!("interleave-header"));
+    for (i, location) in order.iter().cycle().take(workers).enumerate() {
+        info!("  {}", tr!("interleave-entry", thread = i, cpu = location.cpu, package = location.package, core = location.core));
+    }
+    Some(order)
+}
+
+/// 超线程兄弟上的绑核顺序；没有超线程兄弟时告警并不绑核
+fn hyper_thread_affinity(workers: usize) -> Option<Vec<topology::CpuLocation>> {
+    let siblings = topology::get_ht_siblings();
+    if siblings.is_empty() {
+        warn!("{}", tr!("ht-siblings-not-found"));
+        return None;
+    }
+    info!("{}", tr!("ht-siblings", cpus = topology::format_cpu_ranges(&siblings), count = siblings.len()));
+    if workers > siblings.len() {
+        warn!("{}", tr!("ht-siblings-shared", workers = workers, count = siblings.len()));
+    }
+    let locations = topology::cpu_locations();
+    Some(siblings.iter().filter_map(|&cpu| locations.get(cpu).copied()).collect())
+}
+
+/// 状态线程：定期采集并输出系统状态，结束时给出运行汇总
+struct StatusMonitor {
+    sys: System,
+    summary: RunSummary,
+    renderer: Box<dyn output::Renderer>,
+    state: Arc<LoadState>,
+    measuring: Arc<AtomicBool>,
+    actual_cores: usize,
+    show_target: bool,
+    perf_counters: Option<Arc<PerfCounters>>,
+    syscall_tracer: Option<syscalls::SyscallTracer>,
+    disk_monitor: Option<diskstats::DiskMonitor>,
+    net_monitor: netstats::NetMonitor,
+    self_monitor: Option<SelfMonitor>,
+    thp_monitor: Option<ThpMonitor>,
+    steal_monitor: StealMonitor,
+    /// `io-stress` 的操作计数，未运行时为 None
+    io_monitor: Option<IoMonitor>,
+    /// `--output` 指定的指标文件
+    metrics_writer: Option<metrics::MetricsWriter>,
+    /// 首次刷新时把状态线程绑定到该CPU
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    pin_core: Option<usize>,
+}
+
+impl Periodic for StatusMonitor {
+    type Output = RunSummary;
+
+    fn tick(&mut self) -> Stats {
+        #[cfg(target_os = "linux")]
+        if let Some(cpu) = self.pin_core.take() {
+            match topology::pin_current_thread(cpu) {
+                Ok(()) => info!("{}", tr!("status-core-pinned", cpu = cpu)),
+                Err(e) => warn!("{}", tr!("status-core-failed", cpu = cpu, error = e)),
+            }
+        }
+        self.sys.refresh_all();
+        // -c 0（只有内存负载）时没有负载核心，记为0而不是 NaN
+        let avg_usage = if self.actual_cores == 0 {
+            0.0
+        } else {
+            self.sys.cpus().iter()
+                .take(self.actual_cores)
+                .map(|cpu| cpu.cpu_usage())
+                .sum::<f32>() / self.actual_cores as f32
+        };
+
+        let phase = if self.measuring.load(Ordering::SeqCst) {
+            RunPhase::Measurement
+        } else {
+            RunPhase::Load
+        };
+        let state = &self.state;
+        let stats = Stats {
+            timestamp_ms: timeutil::unix_millis(),
+            phase,
+            cpu_usage: avg_usage,
+            cpu_steal: self.steal_monitor.sample(),
+            target_percent: (self.show_target && phase == RunPhase::Load)
+                .then(|| state.effective_percent()),
+            target_noise: match state.cpu_noise.load(Ordering::Relaxed) {
+                0 => None,
+                noise => Some(noise),
+            },
+            burst_cycle: match state.burst_cycle.load(Ordering::Relaxed) {
+                0 => None,
+                cycle => Some(cycle),
+            },
+            ramp_phase: RampPhase::load(state).filter(|_| phase == RunPhase::Load),
+            schedule: (*state.schedule.lock().unwrap_or_else(|e| e.into_inner())).filter(|_| phase == RunPhase::Load),
+            remaining_secs: state
+                .deadline
+                .get()
+                .filter(|_| phase == RunPhase::Load)
+                .map(|deadline| deadline.saturating_duration_since(Instant::now()).as_secs_f64().ceil() as u64),
+            memory_used: self.sys.used_memory(),
+            memory_total: self.sys.total_memory(),
+            memory_allocated: match state.allocated_bytes.load(Ordering::Relaxed)
+                + state.grown_bytes.load(Ordering::Relaxed)
+                + state.balloon_bytes.load(Ordering::Relaxed)
+            {
+                0 => None,
+                bytes => Some(bytes),
+            },
+            memory_locked: match state.locked_bytes.load(Ordering::Relaxed) {
+                0 => None,
+                bytes => Some(bytes),
+            },
+            memory_pressure: MemoryPsi::read(),
+            thp_coverage: self.thp_monitor.as_mut().and_then(ThpMonitor::sample),
+            perf: self.perf_counters.as_ref().and_then(|counters| counters.sample()),
+            syscalls: self.syscall_tracer.as_mut().map(|tracer| tracer.sample()),
+            disks: self.disk_monitor.as_mut().map(|monitor| monitor.sample()),
+            networks: self.net_monitor.sample(),
+            io_stress: self.io_monitor.as_mut().filter(|_| phase == RunPhase::Load).map(IoMonitor::sample),
+        };
+        info!("{}", self.renderer.render_status(&stats));
+        if let Some(writer) = &mut self.metrics_writer {
+            writer.write(&metrics::MetricRecord {
+                timestamp_ms: stats.timestamp_ms,
+                per_core_percent: self.sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect(),
+                memory_total_bytes: stats.memory_total,
+                memory_used_bytes: stats.memory_used,
+                memory_available_bytes: self.sys.available_memory(),
+                memory_allocated_bytes: stats.memory_allocated,
+            });
+        }
+        if phase == RunPhase::Load
+            && let Some(monitor) = &mut self.self_monitor {
+            monitor.report();
+        }
+        self.summary.record(&stats);
+        stats
+    }
+
+    fn finish(self) -> RunSummary {
+        self.summary
+    }
+}
+
+/// `status --json` 的输出：[`StatusReport`] 的字段之后是主机信息、拓扑、内存压力、磁盘、网络和运行中的实例
+#[derive(Serialize)]
+struct SystemStatusJson {
+    #[serde(flatten)]
+    report: StatusReport,
+    hostname: String,
+    os_name: String,
+    os_version: String,
+    kernel_version: String,
+    uptime_secs: u64,
+    boot_time: String,
+    cpu_brand: String,
+    memory_total_gib: f64,
+    cpu_steal_percent: Option<f64>,
+    topology: json::Json,
+    cpu_topology: json::Json,
+    memory_pressure: json::Json,
+    disks: json::Json,
+    networks: json::Json,
+    instance: json::Json,
+}
+
+/// 显示当前系统状态
+fn show_cpu_status(json: bool, disk_filter: Option<regex::Regex>, net_filter: Option<regex::Regex>, instance_name: &str) {
+    let mut sys = System::new_all();
+    sys.refresh_all();
+    let mut disk_monitor = diskstats::DiskMonitor::new(disk_filter);
+    let mut net_monitor = netstats::NetMonitor::new(net_filter);
+    let mut steal_monitor = StealMonitor::new(steal::DEFAULT_MAX_STEAL);
+    let instance = statefile::InstanceState::read(&statefile::state_path(&instances::get_pid_file(instance_name)));
+    // 主机信息，使集中收集的状态输出和日志能区分来源机器
+    let unknown = || "unknown".to_string();
+    let host_name = sys.host_name().unwrap_or_else(unknown);
+    let os_name = sys.name().unwrap_or_else(unknown);
+    let os_version = sys.os_version().unwrap_or_else(unknown);
+    let kernel_version = sys.kernel_version().unwrap_or_else(unknown);
+    let uptime = sys.uptime();
+    let boot_time = timeutil::format_rfc3339(UNIX_EPOCH + Duration::from_secs(sys.boot_time()));
+    let cpu_brand = sys.cpus().first().map(|cpu| cpu.brand().trim().to_string()).unwrap_or_else(unknown);
+    let total_gib = sys.total_memory() as f64 / (1u64 << 30) as f64;
+    
+    if !json {
+        if let Some(instance) = &instance {
+            info!("{}\n", instance.describe());
+        }
+        info!("{}", tr!("status-system-header"));
+        info!("{}", tr!("status-host-name", name = host_name.clone()));
+        info!("{}", tr!("status-os", name = os_name.clone(), version = os_version.clone()));
+        info!("{}", tr!("status-kernel", version = kernel_version.clone()));
+        info!("{}", tr!("status-uptime", uptime = timeutil::format_uptime(uptime), boot = boot_time.clone()));
+        info!("{}", tr!("status-physical-memory", size = format!("{:.1}", total_gib)));
+        info!("{}", tr!("status-cpu-header"));
+        info!("{}", tr!("status-cpu-brand", brand = cpu_brand.clone()));
+        info!("{}", tr!("status-core-count", count = sys.cpus().len()));
+    }
+    
+    // 等待一秒以获取准确的系统使用率
+    thread::sleep(Duration::from_secs(1));
+    sys.refresh_all();
+    let disks = disk_monitor.as_mut().map(|monitor| monitor.sample());
+    let networks = net_monitor.sample();
+    let cpu_steal = steal_monitor.sample();
+    
+    let per_core: Vec<f32> = sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
+    let avg_usage = per_core.iter().sum::<f32>() / per_core.len() as f32;
+    // 拓扑不可用（如非Linux平台）时逐个列出逻辑CPU
+    let layout = topology::physical_layout(per_core.len());
+    
+    // 显示内存信息
+    let total = sys.total_memory();
+    let used = sys.used_memory();
+    let available = sys.available_memory();
+    let psi = MemoryPsi::read();
+
+    if json {
+        let report = SystemStatusJson {
+            report: StatusReport::read(&sys),
+            hostname: host_name,
+            os_name,
+            os_version,
+            kernel_version,
+            uptime_secs: uptime,
+            boot_time,
+            cpu_brand,
+            memory_total_gib: total_gib,
+            cpu_steal_percent: cpu_steal,
+            topology: layout.map(|layout| layout.into_iter().map(topology::CpuLocation::to_json).collect::<Vec<_>>()).into(),
+            cpu_topology: hybrid_json(topology::check_hybrid_cores()),
+            memory_pressure: psi.map(MemoryPsi::to_json).into(),
+            disks: disks.map(|disks| disks.iter().map(diskstats::DiskRates::to_json).collect::<Vec<_>>()).into(),
+            networks: networks.iter().map(netstats::NetRates::to_json).collect::<Vec<_>>().into(),
+            instance: instance.as_ref().map(statefile::InstanceState::to_json).into(),
+        };
+        match serde_json::to_string_pretty(&report) {
+            Ok(text) => info!("{}", text),
+            Err(e) => warn!("{}", tr!("status-json-failed", error = e)),
+        }
+        return;
+    }
+    
+    match &layout {
+        Some(layout) => {
+            for ((package, core), cpus) in topology::group_by_core(layout) {
+                let usages: Vec<_> = cpus.iter().map(|&cpu| format!("cpu{}: {:.1}%", cpu, per_core[cpu])).collect();
+                info!("{}", tr!("status-core-usage", package = package, core = core, usages = usages.join(", ")));
+            }
+        }
+        None => {
+            for (i, usage) in per_core.iter().enumerate() {
+                info!("{}", tr!("status-cpu-usage", cpu = i, usage = format!("{:.1}", usage)));
+            }
+        }
+    }
+    
+    info!("{}", tr!("status-avg-cpu", usage = format!("{:.1}", avg_usage)));
+    if let Some(steal) = cpu_steal {
+        info!("{}", tr!("status-cpu-steal", percent = format!("{:.1}", steal)));
+    }
+    
+    info!("\n{}", tr!("status-memory-header"));
+    info!("{}", tr!("status-memory-total", size = format!("{:.1}", total as f64 / 1024.0 / 1024.0)));
+    info!("{}", tr!("status-memory-used", size = format!("{:.1}", used as f64 / 1024.0 / 1024.0)));
+    info!("{}", tr!("status-memory-available", size = format!("{:.1}", available as f64 / 1024.0 / 1024.0)));
+    info!("{}", tr!("status-memory-usage", percent = format!("{:.1}", (used as f64 / total as f64) * 100.0)));
+    if let Some(psi) = psi {
+        info!("{}", psi.describe());
+    }
+
+    if let Some(disks) = disks {
+        info!("\n{}", tr!("status-disk-header"));
+        if disks.is_empty() {
+            info!("{}", tr!("status-no-disks"));
+        }
+        for disk in &disks {
+            info!("{}", disk.describe());
+        }
+    }
+
+    info!("\n{}", tr!("status-network-header"));
+    if networks.is_empty() {
+        info!("{}", tr!("status-no-networks"));
+    }
+    for net in &networks {
+        info!("{}", net.describe_detailed());
+    }
+}
+
+/// `status --json` 的 `cpu_topology` 字段
+fn hybrid_json(hybrid: Option<topology::HybridInfo>) -> json::Json {
+    let hybrid = hybrid.unwrap_or_default();
+    json::Json::object([
+        ("hybrid", hybrid.is_hybrid().into()),
+        ("performance_cpus", hybrid.performance.into()),
+        ("efficiency_cpus", hybrid.efficiency.into()),
+    ])
+}
+
+/// 输出 --limit-cycles 基准测试的耗时和得分
+fn print_benchmark_score(results: &[(u64, Duration)], limit: u64) {
+    let total: u64 = results.iter().map(|(done, _)| done).sum();
+    // 以最慢的线程为准，所有线程完成时才算结束
+    let elapsed = results.iter().map(|(_, elapsed)| *elapsed).max().unwrap_or_default();
+    info!("{}", tr!("benchmark-header"));
+    info!("  {}", tr!("benchmark-threads", threads = results.len(), limit = limit));
+    if total < limit * results.len() as u64 {
+        warn!("  {}", tr!("benchmark-stopped-early", total = total));
+    }
+    info!("  {}", tr!("benchmark-elapsed", secs = format!("{:.3}", elapsed.as_secs_f64())));
+    info!(
+        "  {}",
+        tr!("benchmark-score", score = format!("{:.0}", total as f64 / elapsed.as_secs_f64().max(f64::EPSILON)))
+    );
+}
+
+/// `--cpu-work-verify` 启动时测量基准迭代速率的时长
+const CALIBRATION_WINDOW: Duration = Duration::from_millis(500);
@@ -0,0 +1,204 @@
+//! 内存规整测试（仅Linux，需要root权限）：内存分配完成后写入 `/proc/sys/vm/compact_memory`
+//! 触发一次全局内存规整，测量规整前、规整中和规整后的分配与访问延迟
+//!
+//! 规整会迁移已分配的页面以拼出连续的大块空闲内存，迁移期间访问这些页面需要等待，
+//! 长期运行的服务中周期性规整造成的延迟尖峰往往难以定位。同时对比规整前后的
+//! 碎片化指数（来自 `/proc/buddyinfo`）和透明大页覆盖率。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use crate::histogram::LatencyHistogram;
+use crate::hugepages;
+use crate::residency;
+use crate::seed;
+use crate::state::LoadState;
+
+/// 规整前后各测量的时长
+const WINDOW: Duration = Duration::from_secs(2);
+
+/// 每次探测分配并逐页写入的大小
+const PROBE_SIZE: usize = 256 * 1024;
+
+/// 两次探测之间的间隔
+const PROBE_INTERVAL: Duration = Duration::from_millis(1);
+
+/// 大页（2MiB）对应的伙伴系统阶数
+const HUGE_ORDER: usize = 9;
+
+/// 探测线程所处的阶段
+const PHASE_BEFORE: u8 = 0;
+const PHASE_DURING: u8 = 1;
+const PHASE_AFTER: u8 = 2;
+const PHASE_DONE: u8 = 3;
+
+/// `/proc/buddyinfo` 中所有内存区域的空闲页统计
+#[derive(Clone, Copy, Debug, Default)]
+struct Buddyinfo {
+    /// 空闲页总数
+    free_pages: u64,
+    /// 位于不小于大页阶数的空闲块中的页数
+    huge_free_pages: u64,
+}
+
+impl Buddyinfo {
+    /// 每行形如 `Node 0, zone   Normal   12 8 4 ...`，第 n 列为 2^n 页的空闲块数量
+    fn read() -> Option<Self> {
+        let text = std::fs::read_to_string("/proc/buddyinfo").ok()?;
+        let mut info = Buddyinfo::default();
+        for line in text.lines() {
+            let (_, counts) = line.split_once("zone")?;
+            for (order, count) in counts.split_whitespace().skip(1).filter_map(|v| v.parse::<u64>().ok()).enumerate() {
+                let pages = count << order;
+                info.free_pages += pages;
+                if order >= HUGE_ORDER {
+                    info.huge_free_pages += pages;
+                }
+            }
+        }
+        Some(info)
+    }
+
+    /// 大页的碎片化指数（0-1）：无法用于分配大页的空闲内存占比，越接近1碎片越严重
+    fn fragmentation_index(&self) -> f64 {
+        if self.free_pages == 0 {
+            return 0.0;
+        }
+        1.0 - self.huge_free_pages as f64 / self.free_pages as f64
+    }
+}
+
+/// 启动前检查；非Linux平台或非root用户时返回 false
+pub fn check_support() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: geteuid 没有副作用
+        if unsafe { libc::geteuid() } != 0 {
+            warn!("{}", tr!("compaction-needs-root"));
+            return false;
+        }
+        true
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        warn!("{}", tr!("compaction-unsupported"));
+        false
+    }
+}
+
+/// 一次探测：分配一块内存并逐页写入的耗时，以及读取已分配内存中一个随机页面的耗时（纳秒）
+fn probe_once(state: &LoadState, page: usize, rng: &mut impl Rng) -> (u64, Option<u64>) {
+    let start = Instant::now();
+    let mut probe = vec![0u8; PROBE_SIZE];
+    for i in (0..probe.len()).step_by(page) {
+        probe[i] = 1;
+    }
+    std::hint::black_box(&probe);
+    let alloc = start.elapsed().as_nanos() as u64;
+
+    let guard = state.memory.lock().unwrap_or_else(|e| e.into_inner());
+    let access = guard.as_deref().filter(|buffer| !buffer.is_empty()).map(|buffer| {
+        let offset = rng.gen_range(0..buffer.len()) / page * page;
+        let start = Instant::now();
+        // SAFETY: offset 小于缓冲区长度
+        std::hint::black_box(unsafe { std::ptr::read_volatile(buffer.as_ptr().add(offset)) });
+        start.elapsed().as_nanos() as u64
+    });
+    (alloc, access)
+}
+
+/// 逐页写入已分配的内存，确保规整前页面都已分配物理内存
+fn touch_memory(state: &LoadState, page: usize) {
+    let mut guard = state.memory.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(buffer) = guard.as_deref_mut() {
+        for i in (0..buffer.len()).step_by(page) {
+            // SAFETY: i 小于缓冲区长度
+            unsafe { std::ptr::write_volatile(buffer.as_mut_ptr().add(i), buffer[i].wrapping_add(1)) };
+        }
+    }
+}
+
+/// 输出一个阶段的延迟统计
+fn report_phase(name: &str, alloc: &LatencyHistogram, access: &LatencyHistogram) {
+    let us = |ns: f64| format!("{:.1}", ns / 1e3);
+    info!(
+        "  {}",
+        tr!(
+            "compaction-phase",
+            phase = name,
+            count = alloc.count(),
+            alloc_mean = us(alloc.mean()),
+            alloc_p99 = us(alloc.percentile(99.0) as f64),
+            alloc_max = us(alloc.max() as f64),
+            access_mean = us(access.mean()),
+            access_p99 = us(access.percentile(99.0) as f64),
+            access_max = us(access.max() as f64)
+        )
+    );
+}
+
+/// 启动内存规整测试线程
+pub fn spawn_compaction_test(state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let page = residency::page_size();
+        touch_memory(&state, page);
+        let before = (Buddyinfo::read(), hugepages::thp_coverage());
+
+        let phase = AtomicU8::new(PHASE_BEFORE);
+        let mut histograms: [(LatencyHistogram, LatencyHistogram); 3] = Default::default();
+        let result = thread::scope(|scope| {
+            let probe = scope.spawn(|| {
+                let mut rng = seed::thread_rng(seed::COMPACTION_STREAM);
+                let mut histograms: [(LatencyHistogram, LatencyHistogram); 3] = Default::default();
+                loop {
+                    let current = phase.load(Ordering::Acquire);
+                    if current == PHASE_DONE {
+                        return histograms;
+                    }
+                    let (alloc, access) = probe_once(&state, page, &mut rng);
+                    let (alloc_hist, access_hist) = &mut histograms[usize::from(current)];
+                    alloc_hist.record(alloc);
+                    if let Some(access) = access {
+                        access_hist.record(access);
+                    }
+                    thread::sleep(PROBE_INTERVAL);
+                }
+            });
+            thread::sleep(WINDOW);
+            phase.store(PHASE_DURING, Ordering::Release);
+            let start = Instant::now();
+            let result = std::fs::write("/proc/sys/vm/compact_memory", "1").map(|()| start.elapsed());
+            phase.store(PHASE_AFTER, Ordering::Release);
+            if result.is_ok() {
+                thread::sleep(WINDOW);
+            }
+            phase.store(PHASE_DONE, Ordering::Release);
+            histograms = probe.join().unwrap_or_default();
+            result
+        });
+        let elapsed = match result {
+            Ok(elapsed) => elapsed,
+            Err(e) => {
+                warn!("{}", tr!("compaction-failed", error = e));
+                return;
+            }
+        };
+        let after = (Buddyinfo::read(), hugepages::thp_coverage());
+
+        info!("{}", tr!("compaction-done", ms = format!("{:.1}", elapsed.as_secs_f64() * 1e3)));
+        for (name, (alloc, access)) in [tr!("compaction-before"), tr!("compaction-during"), tr!("compaction-after")]
+            .iter()
+            .zip(&histograms)
+        {
+            report_phase(name, alloc, access);
+        }
+        let index = |info: Option<Buddyinfo>| info.map_or_else(|| "-".to_string(), |i| format!("{:.3}", i.fragmentation_index()));
+        info!("  {}", tr!("compaction-fragmentation", before = index(before.0), after = index(after.0)));
+        let coverage = |c: Option<f64>| c.map_or_else(|| "-".to_string(), |c| format!("{:.1}", c));
+        info!("  {}", tr!("compaction-thp", before = coverage(before.1), after = coverage(after.1)));
+    })
+}
@@ -0,0 +1,135 @@
+//! zstd 压缩负载：工作线程反复压缩并解压一块类似日志的伪随机数据
+//!
+//! 相比纯数学计算，压缩同时考验整数运算、分支预测和缓存，更接近日志处理等真实服务的CPU画像。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use crate::seed;
+use crate::selfmonitor;
+use crate::state::LoadState;
+
+/// 输出吞吐量的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 压缩参数
+#[derive(Clone, Copy, Debug)]
+pub struct CompressSettings {
+    /// zstd 压缩级别（1-22）
+    pub level: i32,
+    /// 每次压缩的数据块大小
+    pub block_size: usize,
+}
+
+/// 所有工作线程共享的累计量
+#[derive(Default)]
+pub struct CompressStats {
+    /// 已压缩的原始字节数
+    input_bytes: AtomicU64,
+    /// 压缩后的字节数
+    output_bytes: AtomicU64,
+}
+
+/// 生成类似应用日志的伪随机文本，压缩率与真实日志相近
+fn log_like_data(size: usize, rng: &mut impl Rng) -> Vec<u8> {
+    const LEVELS: [&str; 4] = ["INFO", "DEBUG", "WARN", "ERROR"];
+    const PATHS: [&str; 5] = ["/api/users", "/api/orders", "/healthz", "/static/app.js", "/api/search"];
+    let mut data = Vec::with_capacity(size + 128);
+    while data.len() < size {
+        let line = format!(
+            "2024-01-01T00:{:02}:{:02}.{:03}Z {} request_id={:016x} path={} status={} latency={}ms\n",
+            rng.gen_range(0..60),
+            rng.gen_range(0..60),
+            rng.gen_range(0..1000),
+            LEVELS[rng.gen_range(0..LEVELS.len())],
+            rng.r#gen::<u64>(),
+            PATHS[rng.gen_range(0..PATHS.len())],
+            [200, 200, 200, 404, 500][rng.gen_range(0..5)],
+            rng.gen_range(1..2000),
+        );
+        data.extend_from_slice(line.as_bytes());
+    }
+    data.truncate(size);
+    data
+}
+
+/// 工作线程主循环，按目标使用率在压缩和休眠之间切换，返回完成的数据块数
+pub fn compress_task(
+    state: &LoadState,
+    index: usize,
+    fixed_percent: Option<u8>,
+    limit: Option<u64>,
+    settings: CompressSettings,
+    stats: &CompressStats,
+) -> u64 {
+    let mut rng = seed::thread_rng(index);
+    let input = log_like_data(settings.block_size, &mut rng);
+    let mut done = 0u64;
+    while state.is_running() && limit.is_none_or(|limit| done < limit) {
+        state.wait_until_active(index);
+        let percent = state.thread_percent(index, fixed_percent);
+        if percent == 0 {
+            thread::sleep(crate::spin::IDLE_POLL_INTERVAL);
+            continue;
+        }
+        let percent = state.with_noise(percent, &mut rng);
+
+        let burst_start = Instant::now();
+        let compressed = match zstd::encode_all(input.as_slice(), settings.level) {
+            Ok(compressed) => compressed,
+            Err(e) => {
+                error!("{}", tr!("compress-encode-failed", thread = index, error = e));
+                break;
+            }
+        };
+        match zstd::decode_all(compressed.as_slice()) {
+            Ok(decoded) => debug_assert_eq!(decoded.len(), input.len()),
+            Err(e) => {
+                error!("{}", tr!("compress-decode-failed", thread = index, error = e));
+                break;
+            }
+        }
+        stats.input_bytes.fetch_add(input.len() as u64, Ordering::Relaxed);
+        stats.output_bytes.fetch_add(compressed.len() as u64, Ordering::Relaxed);
+        done += 1;
+
+        selfmonitor::duty_cycle_sleep(state.thread_stats(index), percent, 1, burst_start.elapsed());
+    }
+    done
+}
+
+/// 定期输出压缩吞吐量和压缩率
+pub fn spawn_reporter(stats: Arc<CompressStats>, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut last_input = 0u64;
+        let mut last_output = 0u64;
+        let mut last_report = Instant::now();
+        while state.is_running() {
+            thread::sleep(Duration::from_millis(100));
+            let elapsed = last_report.elapsed();
+            if elapsed < REPORT_INTERVAL {
+                continue;
+            }
+            let input = stats.input_bytes.load(Ordering::Relaxed);
+            let output = stats.output_bytes.load(Ordering::Relaxed);
+            let (input_delta, output_delta) = (input - last_input, output - last_output);
+            if output_delta > 0 {
+                info!(
+                    "{}",
+                    tr!(
+                        "compress-throughput",
+                        rate = format!("{:.1}", input_delta as f64 / elapsed.as_secs_f64() / (1024.0 * 1024.0)),
+                        ratio = format!("{:.2}", input_delta as f64 / output_delta as f64)
+                    )
+                );
+            }
+            last_input = input;
+            last_output = output;
+            last_report = Instant::now();
+        }
+    })
+}
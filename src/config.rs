@@ -0,0 +1,150 @@
+//! 以代码方式构造负载参数：`LoadConfig` 及其构建器，供把本程序当作库使用时按需设置并校验参数
+//!
+//! ```ignore
+//! let config = LoadConfig::new()
+//!     .cores(4)?
+//!     .cpu_percent(80.0)?
+//!     .memory(ByteSize::gib(2))?
+//!     .duration(Duration::from_secs(60))?
+//!     .build()?;
+//! let handle = config.start()?;
+//! ```
+//!
+//! 每个设置方法只校验自身的取值，`build` 再结合本机的CPU数和内存总量交叉校验。
+//! `LoadConfig` 可以用 serde 序列化，便于保存和导出配置。
+//!
+//! `LoadConfig` 只覆盖库接口需要的五项参数：核心数、CPU使用率、内存、持续时间和负载类型。
+//! 它不是命令行参数的完整镜像，[`LoadConfig::start`] 启动时其余选项一律取命令行的默认值；
+//! 需要尖峰、阶段、NUMA绑定等其他选项时使用可执行文件。序列化得到的也只是这五项。
+
+use std::fmt;
+use std::time::Duration;
+
+use bytesize::ByteSize;
+use serde::{Deserialize, Serialize};
+use sysinfo::{System, SystemExt};
+
+use crate::workload::Workload;
+
+/// 校验通过的负载参数，只包含库接口支持的五项
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LoadConfig {
+    /// 计算线程数
+    pub cores: usize,
+    /// 每个计算线程的目标CPU使用率（百分比）
+    pub cpu_percent: f64,
+    /// 占用的内存大小，None 表示不占用内存
+    pub memory: Option<ByteSize>,
+    /// 运行时长，None 表示一直运行到被停止
+    pub duration: Option<Duration>,
+    /// 负载类型
+    pub workload: Workload,
+}
+
+impl LoadConfig {
+    /// 从默认参数开始构建：系统核心数的一半（至少为1）、100%、不占用内存、一直运行、spin 负载
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> LoadConfigBuilder {
+        LoadConfigBuilder {
+            config: LoadConfig {
+                cores: std::cmp::max(1, num_cpus::get() / 2),
+                cpu_percent: 100.0,
+                memory: None,
+                duration: None,
+                workload: Workload::default(),
+            },
+        }
+    }
+}
+
+/// [`LoadConfig`] 的构建器，每个设置方法校验取值后返回新的构建器
+#[derive(Clone, Debug)]
+pub struct LoadConfigBuilder {
+    config: LoadConfig,
+}
+
+impl LoadConfigBuilder {
+    pub fn cores(mut self, cores: usize) -> Result<Self, ConfigError> {
+        if cores == 0 {
+            return Err(ConfigError::NoCores);
+        }
+        self.config.cores = cores;
+        Ok(self)
+    }
+
+    pub fn cpu_percent(mut self, percent: f64) -> Result<Self, ConfigError> {
+        if !(percent > 0.0 && percent <= 100.0) {
+            return Err(ConfigError::PercentOutOfRange(percent));
+        }
+        self.config.cpu_percent = percent;
+        Ok(self)
+    }
+
+    pub fn memory(mut self, size: ByteSize) -> Result<Self, ConfigError> {
+        if size.as_u64() == 0 {
+            return Err(ConfigError::EmptyMemory);
+        }
+        self.config.memory = Some(size);
+        Ok(self)
+    }
+
+    pub fn duration(mut self, duration: Duration) -> Result<Self, ConfigError> {
+        if duration.is_zero() {
+            return Err(ConfigError::ZeroDuration);
+        }
+        self.config.duration = Some(duration);
+        Ok(self)
+    }
+
+    pub fn workload(mut self, workload: Workload) -> Result<Self, ConfigError> {
+        self.config.workload = workload;
+        Ok(self)
+    }
+
+    /// 检查本机能否满足配置：核心数不超过CPU数，内存不超过物理内存总量
+    pub fn build(self) -> Result<LoadConfig, ConfigError> {
+        let config = self.config;
+        let cpus = num_cpus::get();
+        if config.workload.uses_cpu_workers() && config.cores > cpus {
+            return Err(ConfigError::TooManyCores { cores: config.cores, cpus });
+        }
+        if let Some(memory) = config.memory {
+            let mut sys = System::new();
+            sys.refresh_memory();
+            let total = sys.total_memory();
+            if memory.as_u64() > total {
+                return Err(ConfigError::MemoryExceedsTotal { memory, total: ByteSize::b(total) });
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// 参数取值无效或本机无法满足
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigError {
+    NoCores,
+    PercentOutOfRange(f64),
+    EmptyMemory,
+    ZeroDuration,
+    TooManyCores { cores: usize, cpus: usize },
+    MemoryExceedsTotal { memory: ByteSize, total: ByteSize },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ConfigError::NoCores => tr!("config-no-cores"),
+            ConfigError::PercentOutOfRange(percent) => tr!("config-percent-out-of-range", percent = percent),
+            ConfigError::EmptyMemory => tr!("config-empty-memory"),
+            ConfigError::ZeroDuration => tr!("config-zero-duration"),
+            ConfigError::TooManyCores { cores, cpus } => tr!("config-too-many-cores", cores = cores, cpus = cpus),
+            ConfigError::MemoryExceedsTotal { memory, total } => {
+                tr!("config-memory-exceeds-total", memory = memory, total = total)
+            }
+        };
+        write!(f, "{}", message)
+    }
+}
+
+impl std::error::Error for ConfigError {}
@@ -0,0 +1,170 @@
+//! Unix 域套接字控制接口：按行接收JSON命令，实时调整正在运行的负载
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use sysinfo::{System, SystemExt};
+
+use crate::json::Json;
+use crate::memory;
+use crate::state::LoadState;
+
+/// 单条命令（一行）的最大字节数
+const MAX_REQUEST_LEN: u64 = 64 * 1024;
+
+/// 检查运行标志的间隔
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 在给定路径上启动控制套接字服务线程，负载停止后自动删除套接字文件
+pub fn spawn_server(path: &Path, state: Arc<LoadState>) -> std::io::Result<JoinHandle<()>> {
+    // 清理上次异常退出遗留的套接字文件；路径上是普通文件、目录等其他内容时不删除
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_socket() => std::fs::remove_file(path)?,
+        Ok(_) => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                tr!("control-path-not-socket", path = path.display()),
+            ));
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+    let listener = UnixListener::bind(path)?;
+    listener.set_nonblocking(true)?;
+    let path = path.to_path_buf();
+
+    Ok(thread::spawn(move || {
+        while state.is_running() {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let state = state.clone();
+                    thread::spawn(move || handle_connection(stream, &state));
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    thread::sleep(ACCEPT_POLL_INTERVAL);
+                }
+                Err(e) => {
+                    warn!("{}", tr!("control-accept-failed", error = e));
+                    thread::sleep(ACCEPT_POLL_INTERVAL);
+                }
+            }
+        }
+        let _ = std::fs::remove_file(&path);
+    }))
+}
+
+fn handle_connection(stream: UnixStream, state: &LoadState) {
+    // 监听套接字为非阻塞，连接本身需要恢复为阻塞读取
+    if stream.set_nonblocking(false).is_err() {
+        return;
+    }
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut line = String::new();
+        match (&mut reader).take(MAX_REQUEST_LEN).read_line(&mut line) {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        // 超过长度上限仍未遇到换行时断开连接，不继续缓存剩余内容
+        if !line.ends_with('\n') && line.len() as u64 >= MAX_REQUEST_LEN {
+            let _ = writeln!(writer, "{}", error_response("request too long"));
+            break;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match Json::parse(line.trim_end()) {
+            Ok(request) => handle_command(&request, state),
+            Err(_) => error_response("invalid json"),
+        };
+        if writeln!(writer, "{}", response).is_err() {
+            break;
+        }
+    }
+}
+
+fn error_response(message: &str) -> Json {
+    Json::object([("error", message.into())])
+}
+
+/// 执行一条命令并返回响应对象
+fn handle_command(request: &Json, state: &LoadState) -> Json {
+    let Some(cmd) = request.get("cmd").and_then(Json::as_str) else {
+        return error_response("missing cmd");
+    };
+    info!("{}", tr!("control-command", request = request));
+
+    match cmd {
+        "set_cpu" => {
+            let Some(value) = request.get("value").and_then(Json::as_f64) else {
+                return error_response("value must be a number");
+            };
+            if !(0.0..=100.0).contains(&value) {
+                return error_response("value must be between 0 and 100");
+            }
+            let percent = value.round() as u8;
+            state.target_percent.store(percent, Ordering::Relaxed);
+            Json::object([("ok", true.into()), ("target_percent", percent.into())])
+        }
+        "set_memory" => {
+            let Some(value) = request.get("value").and_then(Json::as_str) else {
+                return error_response("value must be a size string");
+            };
+            let spec = match memory::parse_memory_spec(value) {
+                Ok(spec) => spec,
+                Err(e) => return error_response(&e.to_string()),
+            };
+            let mut sys = System::new();
+            sys.refresh_memory();
+            let bytes = spec.to_bytes(sys.total_memory());
+            // 先释放旧内存再分配新内存，避免峰值翻倍
+            state.replace_memory(None);
+            match memory::try_alloc_zeroed(bytes as usize) {
+                Some(buffer) => {
+                    state.replace_memory(Some(buffer));
+                    Json::object([("ok", true.into()), ("memory_bytes", bytes.into())])
+                }
+                None => error_response("memory allocation failed"),
+            }
+        }
+        "pause" => {
+            state.paused.store(true, Ordering::Relaxed);
+            Json::object([("ok", true.into()), ("paused", true.into())])
+        }
+        "resume" => {
+            state.paused.store(false, Ordering::Relaxed);
+            Json::object([("ok", true.into()), ("paused", false.into())])
+        }
+        "status" => Json::object([
+            ("ok", true.into()),
+            ("running", state.is_running().into()),
+            ("paused", state.paused.load(Ordering::Relaxed).into()),
+            ("target_percent", state.target_percent.load(Ordering::Relaxed).into()),
+            ("memory_bytes", state.allocated_bytes.load(Ordering::Relaxed).into()),
+        ]),
+        "stop" => {
+            info!("{}", tr!("control-stop"));
+            state.request_stop();
+            Json::object([("ok", true.into())])
+        }
+        _ => error_response("unknown command"),
+    }
+}
+
+/// 向控制套接字发送一条命令并返回响应行
+pub fn send_command(path: &Path, request: &Json) -> std::io::Result<String> {
+    let mut stream = UnixStream::connect(path)?;
+    writeln!(stream, "{}", request)?;
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response)?;
+    Ok(response.trim_end().to_string())
+}
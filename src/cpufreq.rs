@@ -0,0 +1,340 @@
+//! 临时切换CPU调频策略（仅Linux，需要root权限），负载结束后恢复原来的策略
+
+use clap::ValueEnum;
+
+use crate::json::Json;
+
+/// 调频策略
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Governor {
+    /// 固定在最高频率，基准测试结果更稳定
+    Performance,
+    /// 固定在最低频率，用于功耗测试
+    Powersave,
+    /// 按调度器的负载信息调整频率
+    Schedutil,
+}
+
+impl Governor {
+    fn name(self) -> &'static str {
+        match self {
+            Governor::Performance => "performance",
+            Governor::Powersave => "powersave",
+            Governor::Schedutil => "schedutil",
+        }
+    }
+}
+
+/// 已切换的CPU及其原来的策略，drop 时恢复
+pub struct GovernorGuard {
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    original: Vec<(usize, String)>,
+}
+
+#[cfg(target_os = "linux")]
+fn governor_path(cpu: usize) -> String {
+    format!("/sys/devices/system/cpu/cpu{}/cpufreq/scaling_governor", cpu)
+}
+
+/// 把 `cpus` 的调频策略切换为 `governor`，写入失败的CPU只告警并跳过
+#[cfg(target_os = "linux")]
+pub fn apply(governor: Governor, cpus: &[usize]) -> GovernorGuard {
+    let mut current: Vec<(usize, String)> = Vec::new();
+    for &cpu in cpus {
+        match std::fs::read_to_string(governor_path(cpu)) {
+            Ok(name) => current.push((cpu, name.trim().to_string())),
+            Err(e) => {
+                warn!("{}", tr!("cpufreq-read-failed", cpu = cpu, error = e));
+                return GovernorGuard { original: Vec::new() };
+            }
+        }
+    }
+    let mut names: Vec<&str> = current.iter().map(|(_, name)| name.as_str()).collect();
+    names.sort_unstable();
+    names.dedup();
+    info!("{}", tr!("cpufreq-switching", current = names.join("/"), target = governor.name()));
+
+    let mut original = Vec::new();
+    for (cpu, name) in current {
+        match std::fs::write(governor_path(cpu), governor.name()) {
+            Ok(()) => original.push((cpu, name)),
+            Err(e) => {
+                warn!("{}", tr!("cpufreq-set-failed", cpu = cpu, error = e));
+                break;
+            }
+        }
+    }
+    GovernorGuard { original }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn apply(_governor: Governor, _cpus: &[usize]) -> GovernorGuard {
+    warn!("{}", tr!("cpufreq-unsupported"));
+    GovernorGuard { original: Vec::new() }
+}
+
+impl Drop for GovernorGuard {
+    fn drop(&mut self) {
+        #[cfg(target_os = "linux")]
+        if !self.original.is_empty() {
+            for (cpu, name) in &self.original {
+                if let Err(e) = std::fs::write(governor_path(*cpu), name) {
+                    warn!("{}", tr!("cpufreq-restore-failed", cpu = cpu, governor = name, error = e));
+                }
+            }
+            info!("{}", tr!("cpufreq-restored", count = self.original.len()));
+        }
+    }
+}
+
+/// 目标使用率达到该值时才检查调频策略，较低的使用率本来就不要求最高频率
+const AWARE_MIN_PERCENT: u8 = 90;
+
+/// `--cpu-governor-aware`：CPU0 的调频策略不是 performance、或频率下限不到上限的一半时告警，
+/// 提示负载期间CPU可能运行在较低频率，测得的使用率和吞吐量会偏低
+#[cfg(target_os = "linux")]
+pub fn check_governor(target_percent: u8) {
+    if target_percent < AWARE_MIN_PERCENT {
+        return;
+    }
+    let Ok(governor) = std::fs::read_to_string(governor_path(0)) else {
+        warn!("{}", tr!("governor-aware-unavailable"));
+        return;
+    };
+    let governor = governor.trim();
+    if governor == Governor::Powersave.name() || governor == Governor::Schedutil.name() {
+        warn!("{}", tr!("governor-aware-not-performance", governor = governor));
+    }
+    let read_khz = |name| std::fs::read_to_string(cpufreq_file(0, name)).ok()?.trim().parse::<u64>().ok();
+    if let (Some(min), Some(max)) = (read_khz("scaling_min_freq"), read_khz("scaling_max_freq"))
+        && min * 2 < max
+    {
+        warn!("{}", tr!("governor-aware-freq-range", min = min / 1000, max = max / 1000));
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn check_governor(_target_percent: u8) {
+    warn!("{}", tr!("governor-aware-unsupported"));
+}
+
+/// `--turbo-boost` 的取值
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum TurboBoost {
+    /// 关闭睿频，CPU运行在基础频率，基准测试结果不受温度和负载时长影响
+    Disable,
+    /// 开启睿频
+    Enable,
+    /// 不修改当前设置（默认）
+    #[default]
+    Auto,
+}
+
+/// 睿频开关所在的 sysfs 文件；Intel 的 `no_turbo` 写1表示关闭，AMD 等驱动的 `boost` 写1表示开启
+#[cfg(target_os = "linux")]
+struct TurboControl {
+    path: &'static str,
+    inverted: bool,
+}
+
+#[cfg(target_os = "linux")]
+const TURBO_CONTROLS: [TurboControl; 2] = [
+    TurboControl { path: "/sys/devices/system/cpu/intel_pstate/no_turbo", inverted: true },
+    TurboControl { path: "/sys/devices/system/cpu/cpufreq/boost", inverted: false },
+];
+
+#[cfg(target_os = "linux")]
+fn turbo_control() -> Option<&'static TurboControl> {
+    TURBO_CONTROLS.iter().find(|control| std::path::Path::new(control.path).exists())
+}
+
+/// 当前睿频是否开启，无法读取时为 None
+#[cfg(target_os = "linux")]
+pub fn turbo_enabled() -> Option<bool> {
+    let control = turbo_control()?;
+    let value = std::fs::read_to_string(control.path).ok()?;
+    Some((value.trim() == "1") != control.inverted)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn turbo_enabled() -> Option<bool> {
+    None
+}
+
+/// 已修改的睿频开关及其原来的内容，drop 时恢复
+pub struct TurboGuard {
+    #[cfg(target_os = "linux")]
+    original: Option<(&'static str, String)>,
+}
+
+/// 按 `mode` 开启或关闭睿频，`auto` 时不做任何修改
+#[cfg(target_os = "linux")]
+pub fn apply_turbo(mode: TurboBoost) -> TurboGuard {
+    let enable = match mode {
+        TurboBoost::Auto => return TurboGuard { original: None },
+        TurboBoost::Enable => true,
+        TurboBoost::Disable => false,
+    };
+    let Some(control) = turbo_control() else {
+        warn!("{}", tr!("turbo-not-found"));
+        return TurboGuard { original: None };
+    };
+    let original = match std::fs::read_to_string(control.path) {
+        Ok(value) => value.trim().to_string(),
+        Err(e) => {
+            warn!("{}", tr!("turbo-set-failed", path = control.path, error = e));
+            return TurboGuard { original: None };
+        }
+    };
+    let value = if enable != control.inverted { "1" } else { "0" };
+    if original == value {
+        return TurboGuard { original: None };
+    }
+    match std::fs::write(control.path, value) {
+        Ok(()) => {
+            info!("{}", tr!("turbo-switched", enabled = enable.to_string()));
+            TurboGuard { original: Some((control.path, original)) }
+        }
+        Err(e) => {
+            warn!("{}", tr!("turbo-set-failed", path = control.path, error = e));
+            TurboGuard { original: None }
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn apply_turbo(mode: TurboBoost) -> TurboGuard {
+    if mode != TurboBoost::Auto {
+        warn!("{}", tr!("turbo-unsupported"));
+    }
+    TurboGuard {}
+}
+
+impl Drop for TurboGuard {
+    fn drop(&mut self) {
+        #[cfg(target_os = "linux")]
+        if let Some((path, value)) = &self.original {
+            match std::fs::write(path, value) {
+                Ok(()) => info!("{}", tr!("turbo-restored")),
+                Err(e) => warn!("{}", tr!("turbo-restore-failed", path = path, error = e)),
+            }
+        }
+    }
+}
+
+/// `--cpu-governor-profile` 中一个核心的设置；频率单位为 kHz，与 sysfs 一致
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CoreFrequency {
+    pub governor: Option<String>,
+    pub min_freq: Option<u64>,
+    pub max_freq: Option<u64>,
+}
+
+/// 解析调频配置文件：键为核心序号，值为包含 `governor`、`min_freq`、`max_freq`（kHz）的对象
+///
+/// ```json
+/// {"0": {"governor": "performance", "min_freq": 4000000, "max_freq": 4000000},
+///  "4": {"governor": "powersave", "max_freq": 2000000}}
+/// ```
+pub fn parse_governor_profile(text: &str) -> Result<Vec<(usize, CoreFrequency)>, String> {
+    let Json::Object(fields) = Json::parse(text)? else {
+        return Err(tr!("governor-profile-not-object"));
+    };
+    let mut cores = Vec::with_capacity(fields.len());
+    for (key, value) in &fields {
+        let cpu: usize = key.parse().map_err(|_| tr!("governor-profile-bad-core", core = key.as_str()))?;
+        if !matches!(value, Json::Object(_)) {
+            return Err(tr!("governor-profile-bad-entry", core = cpu));
+        }
+        let freq = |field: &str| -> Result<Option<u64>, String> {
+            match value.get(field) {
+                None => Ok(None),
+                Some(v) => match v.as_f64() {
+                    Some(khz) if khz > 0.0 && khz.fract() == 0.0 => Ok(Some(khz as u64)),
+                    _ => Err(tr!("governor-profile-bad-freq", core = cpu, field = field)),
+                },
+            }
+        };
+        let governor = match value.get("governor") {
+            None => None,
+            Some(v) => Some(v.as_str().ok_or_else(|| tr!("governor-profile-bad-governor", core = cpu))?.to_string()),
+        };
+        let setting = CoreFrequency { governor, min_freq: freq("min_freq")?, max_freq: freq("max_freq")? };
+        if let (Some(min), Some(max)) = (setting.min_freq, setting.max_freq)
+            && min > max {
+            return Err(tr!("governor-profile-min-above-max", core = cpu, min = min, max = max));
+        }
+        cores.push((cpu, setting));
+    }
+    Ok(cores)
+}
+
+/// 已写入的 sysfs 文件及其原来的内容，drop 时按写入的相反顺序恢复
+pub struct ProfileGuard {
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    original: Vec<(String, String)>,
+}
+
+#[cfg(target_os = "linux")]
+fn cpufreq_file(cpu: usize, name: &str) -> String {
+    format!("/sys/devices/system/cpu/cpu{}/cpufreq/{}", cpu, name)
+}
+
+/// 按配置逐个核心写入调频策略和频率范围，写入失败的项只告警并跳过
+#[cfg(target_os = "linux")]
+pub fn apply_profile(cores: &[(usize, CoreFrequency)]) -> ProfileGuard {
+    let mut original = Vec::new();
+    for (cpu, setting) in cores {
+        let current_max = std::fs::read_to_string(cpufreq_file(*cpu, "scaling_max_freq"))
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok());
+        let mut writes = Vec::new();
+        if let Some(governor) = &setting.governor {
+            writes.push(("scaling_governor", governor.clone()));
+        }
+        let min = setting.min_freq.map(|khz| ("scaling_min_freq", khz.to_string()));
+        let max = setting.max_freq.map(|khz| ("scaling_max_freq", khz.to_string()));
+        // 内核要求 min <= max：新的下限高于当前上限时先写上限
+        if setting.min_freq.zip(current_max).is_some_and(|(min, current)| min > current) {
+            writes.extend(max.into_iter().chain(min));
+        } else {
+            writes.extend(min.into_iter().chain(max));
+        }
+        for (name, value) in writes {
+            let path = cpufreq_file(*cpu, name);
+            let previous = match std::fs::read_to_string(&path) {
+                Ok(previous) => previous.trim().to_string(),
+                Err(e) => {
+                    warn!("{}", tr!("governor-profile-write-failed", path = path, error = e));
+                    continue;
+                }
+            };
+            match std::fs::write(&path, &value) {
+                Ok(()) => original.push((path, previous)),
+                Err(e) => warn!("{}", tr!("governor-profile-write-failed", path = path, error = e)),
+            }
+        }
+    }
+    info!("{}", tr!("governor-profile-applied", cores = cores.len(), files = original.len()));
+    ProfileGuard { original }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn apply_profile(_cores: &[(usize, CoreFrequency)]) -> ProfileGuard {
+    warn!("{}", tr!("governor-profile-unsupported"));
+    ProfileGuard { original: Vec::new() }
+}
+
+impl Drop for ProfileGuard {
+    fn drop(&mut self) {
+        #[cfg(target_os = "linux")]
+        if !self.original.is_empty() {
+            for (path, value) in self.original.iter().rev() {
+                if let Err(e) = std::fs::write(path, value) {
+                    warn!("{}", tr!("governor-profile-restore-failed", path = path, value = value, error = e));
+                }
+            }
+            info!("{}", tr!("governor-profile-restored", files = self.original.len()));
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! 多线程随机读写硬盘占用文件，模拟不同队列深度下的存储压力
+
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use rand::{Rng, RngCore};
+
+use crate::histogram::LatencyHistogram;
+use crate::seed;
+use crate::state::LoadState;
+
+/// 每次读写的块大小
+const BLOCK_SIZE: usize = 4096;
+
+/// 输出延迟统计的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 计数信号量，限制同时进行中的I/O数量
+pub struct Semaphore {
+    permits: Mutex<usize>,
+    available: Condvar,
+}
+
+impl Semaphore {
+    pub fn new(permits: usize) -> Self {
+        Semaphore { permits: Mutex::new(permits), available: Condvar::new() }
+    }
+
+    /// 获取一个许可，返回的守卫释放时归还
+    pub fn acquire(&self) -> SemaphoreGuard<'_> {
+        let mut permits = self.permits.lock().unwrap_or_else(|e| e.into_inner());
+        while *permits == 0 {
+            permits = self.available.wait(permits).unwrap_or_else(|e| e.into_inner());
+        }
+        *permits -= 1;
+        SemaphoreGuard { semaphore: self }
+    }
+}
+
+pub struct SemaphoreGuard<'a> {
+    semaphore: &'a Semaphore,
+}
+
+impl Drop for SemaphoreGuard<'_> {
+    fn drop(&mut self) {
+        *self.semaphore.permits.lock().unwrap_or_else(|e| e.into_inner()) += 1;
+        self.semaphore.available.notify_one();
+    }
+}
+
+/// 所有I/O线程共享的延迟统计（微秒）
+#[derive(Default)]
+struct Latencies {
+    interval: LatencyHistogram,
+    total: LatencyHistogram,
+}
+
+fn describe(histogram: &LatencyHistogram, elapsed: Duration, depth: usize) -> String {
+    tr!(
+        "diskio-stats",
+        depth = depth,
+        iops = format!("{:.0}", histogram.count() as f64 / elapsed.as_secs_f64().max(f64::EPSILON)),
+        p50 = histogram.percentile(50.0),
+        p95 = histogram.percentile(95.0),
+        p99 = histogram.percentile(99.0),
+        mean = format!("{:.1}", histogram.mean()),
+        max = histogram.max()
+    )
+}
+
+/// 从 `offset` 处读取一块
+#[cfg(unix)]
+pub fn read_block(file: &File, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+    std::os::unix::fs::FileExt::read_at(file, buf, offset)
+}
+
+#[cfg(unix)]
+fn write_block(file: &File, buf: &[u8], offset: u64) -> std::io::Result<usize> {
+    std::os::unix::fs::FileExt::write_at(file, buf, offset)
+}
+
+/// 从 `offset` 处读取一块
+#[cfg(windows)]
+pub fn read_block(file: &File, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+    std::os::windows::fs::FileExt::seek_read(file, buf, offset)
+}
+
+#[cfg(windows)]
+fn write_block(file: &File, buf: &[u8], offset: u64) -> std::io::Result<usize> {
+    std::os::windows::fs::FileExt::seek_write(file, buf, offset)
+}
+
+/// 启动 `depth` 个I/O线程和一个统计线程，对给定文件做随机 pread/pwrite
+pub fn spawn_io_load(path: &Path, depth: usize, state: Arc<LoadState>) -> std::io::Result<Vec<JoinHandle<()>>> {
+    let file = Arc::new(OpenOptions::new().read(true).write(true).open(path)?);
+    let blocks = (file.metadata()?.len() / BLOCK_SIZE as u64).max(1);
+    let semaphore = Arc::new(Semaphore::new(depth));
+    let latencies = Arc::new(Mutex::new(Latencies::default()));
+
+    let mut handles: Vec<JoinHandle<()>> = (0..depth)
+        .map(|i| {
+            let file = file.clone();
+            let semaphore = semaphore.clone();
+            let latencies = latencies.clone();
+            let state = state.clone();
+            thread::spawn(move || {
+                let mut rng = seed::thread_rng(seed::DISK_IO_STREAMS + i);
+                let mut buf = vec![0u8; BLOCK_SIZE];
+                while state.is_running() {
+                    let offset = rng.gen_range(0..blocks) * BLOCK_SIZE as u64;
+                    let write = rng.gen_bool(0.5);
+                    if write {
+                        rng.fill_bytes(&mut buf);
+                    }
+
+                    let _permit = semaphore.acquire();
+                    let start = Instant::now();
+                    let result = if write {
+                        write_block(&file, &buf, offset)
+                    } else {
+                        read_block(&file, &mut buf, offset)
+                    };
+                    let micros = start.elapsed().as_micros() as u64;
+                    if let Err(e) = result {
+                        warn!("{}", tr!("diskio-failed", error = e));
+                        return;
+                    }
+                    let mut latencies = latencies.lock().unwrap_or_else(|e| e.into_inner());
+                    latencies.interval.record(micros);
+                    latencies.total.record(micros);
+                }
+            })
+        })
+        .collect();
+
+    let path = path.display().to_string();
+    handles.push(thread::spawn(move || {
+        info!("{}", tr!("diskio-started", path = path, depth = depth));
+        let run_start = Instant::now();
+        let mut last_report = Instant::now();
+        while state.is_running() {
+            thread::sleep(Duration::from_millis(100));
+            if last_report.elapsed() < REPORT_INTERVAL {
+                continue;
+            }
+            let interval = std::mem::take(&mut latencies.lock().unwrap_or_else(|e| e.into_inner()).interval);
+            info!("{}", describe(&interval, last_report.elapsed(), depth));
+            last_report = Instant::now();
+        }
+        let total = &latencies.lock().unwrap_or_else(|e| e.into_inner()).total;
+        info!("{}", tr!("diskio-summary", stats = describe(total, run_start.elapsed(), depth)));
+    }));
+    Ok(handles)
+}
@@ -0,0 +1,161 @@
+//! 磁盘I/O统计：两次读取 `/proc/diskstats` 的差值，得到各磁盘的读写吞吐量、利用率和平均等待时间（仅Linux）
+//!
+//! sysinfo 0.29 只提供磁盘容量，不提供I/O计数，因此直接读取内核的统计。默认只统计
+//! `/sys/block` 下的整块磁盘（不含分区）并排除 loop 和 ram 设备；指定 `--disk-filter`
+//! 时改为统计名称匹配该正则表达式的所有设备，包括分区。
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+use regex::Regex;
+
+use crate::json::Json;
+
+/// 内核统计中一个扇区的字节数，与设备的实际扇区大小无关
+const SECTOR_SIZE: u64 = 512;
+
+/// 一块磁盘在一次采样间隔内的速率
+#[derive(Clone, Debug)]
+pub struct DiskRates {
+    pub name: String,
+    pub read_bytes_per_sec: f64,
+    pub write_bytes_per_sec: f64,
+    /// 有I/O在进行的时间占比（百分比）
+    pub utilization_percent: f64,
+    /// 间隔内完成的I/O的平均耗时（毫秒，含排队），没有I/O时为 None
+    pub avg_wait_ms: Option<f64>,
+}
+
+impl DiskRates {
+    /// 读写合计吞吐量
+    pub fn throughput(&self) -> f64 {
+        self.read_bytes_per_sec + self.write_bytes_per_sec
+    }
+
+    /// 文本格式，例如 "sda 读 1.2MiB/s 写 0.3MiB/s 利用率 5.0% 等待 0.8ms"
+    pub fn describe(&self) -> String {
+        const MIB: f64 = 1024.0 * 1024.0;
+        let mut out = tr!(
+            "diskstats-rates",
+            name = self.name,
+            read = format!("{:.1}", self.read_bytes_per_sec / MIB),
+            write = format!("{:.1}", self.write_bytes_per_sec / MIB),
+            utilization = format!("{:.1}", self.utilization_percent)
+        );
+        if let Some(wait) = self.avg_wait_ms {
+            out.push(' ');
+            out.push_str(&tr!("diskstats-wait", wait = format!("{:.1}", wait)));
+        }
+        out
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::object([
+            ("name", self.name.as_str().into()),
+            ("read_bytes_per_sec", self.read_bytes_per_sec.into()),
+            ("write_bytes_per_sec", self.write_bytes_per_sec.into()),
+            ("utilization_percent", self.utilization_percent.into()),
+            ("avg_wait_ms", self.avg_wait_ms.into()),
+        ])
+    }
+}
+
+/// `/proc/diskstats` 中一行的累计计数
+#[derive(Clone, Copy, Default)]
+struct Counters {
+    ios: u64,
+    sectors_read: u64,
+    sectors_written: u64,
+    /// 读写耗时合计（毫秒）
+    io_ms: u64,
+    /// 有I/O在进行的时间（毫秒）
+    busy_ms: u64,
+}
+
+/// 解析 `/proc/diskstats`，返回 (设备名, 累计计数)
+fn read_diskstats() -> Option<Vec<(String, Counters)>> {
+    let content = std::fs::read_to_string("/proc/diskstats").ok()?;
+    let disks = content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let num = |i: usize| fields.get(i).and_then(|v| v.parse::<u64>().ok());
+            let counters = Counters {
+                ios: num(3)? + num(7)?,
+                sectors_read: num(5)?,
+                sectors_written: num(9)?,
+                io_ms: num(6)? + num(10)?,
+                busy_ms: num(12)?,
+            };
+            Some((fields[2].to_string(), counters))
+        })
+        .collect();
+    Some(disks)
+}
+
+/// 周期性采样磁盘I/O
+pub struct DiskMonitor {
+    filter: Option<Regex>,
+    last: HashMap<String, Counters>,
+    last_sample: Instant,
+}
+
+impl DiskMonitor {
+    /// 记录当前的累计计数作为基准；非Linux平台或无法读取时返回 None
+    pub fn new(filter: Option<Regex>) -> Option<Self> {
+        let mut monitor = DiskMonitor { filter, last: HashMap::new(), last_sample: Instant::now() };
+        monitor.last = monitor.read()?.into_iter().collect();
+        Some(monitor)
+    }
+
+    fn selected(&self, name: &str) -> bool {
+        match &self.filter {
+            Some(filter) => filter.is_match(name),
+            None => {
+                !name.starts_with("loop")
+                    && !name.starts_with("ram")
+                    && std::path::Path::new("/sys/block").join(name).exists()
+            }
+        }
+    }
+
+    fn read(&self) -> Option<Vec<(String, Counters)>> {
+        if !cfg!(target_os = "linux") {
+            return None;
+        }
+        Some(read_diskstats()?.into_iter().filter(|(name, _)| self.selected(name)).collect())
+    }
+
+    /// 上次采样以来各磁盘的速率，按吞吐量从高到低排序
+    pub fn sample(&mut self) -> Vec<DiskRates> {
+        let secs = self.last_sample.elapsed().as_secs_f64().max(f64::EPSILON);
+        self.last_sample = Instant::now();
+        let Some(current) = self.read() else {
+            return Vec::new();
+        };
+        let mut rates: Vec<DiskRates> = current
+            .iter()
+            .map(|(name, now)| {
+                let before = self.last.get(name).copied().unwrap_or(*now);
+                let ios = now.ios.saturating_sub(before.ios);
+                let io_ms = now.io_ms.saturating_sub(before.io_ms);
+                DiskRates {
+                    name: name.clone(),
+                    read_bytes_per_sec: (now.sectors_read.saturating_sub(before.sectors_read) * SECTOR_SIZE) as f64 / secs,
+                    write_bytes_per_sec: (now.sectors_written.saturating_sub(before.sectors_written) * SECTOR_SIZE) as f64
+                        / secs,
+                    utilization_percent: (now.busy_ms.saturating_sub(before.busy_ms) as f64 / (secs * 10.0)).min(100.0),
+                    avg_wait_ms: (ios > 0).then(|| io_ms as f64 / ios as f64),
+                }
+            })
+            .collect();
+        self.last = current.into_iter().collect();
+        rates.sort_by(|a, b| b.throughput().total_cmp(&a.throughput()).then_with(|| a.name.cmp(&b.name)));
+        rates
+    }
+}
+
+/// 解析 `--disk-filter` 的正则表达式
+pub fn parse_disk_filter(s: &str) -> Result<Regex, String> {
+    Regex::new(s).map_err(|e| tr!("parse-invalid-regex", error = e))
+}
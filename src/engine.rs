@@ -0,0 +1,186 @@
+//! 负载引擎：默认为每个工作线程创建一个系统线程；`--async`（需 `tokio` 特性）改为在
+//! tokio 运行时中执行，工作线程进入阻塞线程池，状态刷新作为定时任务运行
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::output::Stats;
+
+/// 状态刷新线程在两次刷新之间检查停止请求的间隔，使停止时不必等满一个刷新周期
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 定期执行的状态刷新
+pub trait Periodic: Send + 'static {
+    type Output: Send + 'static;
+
+    /// 刷新一次并返回本次采集的数据
+    fn tick(&mut self) -> Stats;
+
+    /// 停止刷新后的收尾
+    fn finish(self) -> Self::Output;
+}
+
+/// 工作线程或任务的句柄
+pub enum WorkerHandle<T> {
+    Thread(JoinHandle<T>),
+    #[cfg(feature = "tokio")]
+    Task(tokio::task::JoinHandle<T>),
+}
+
+impl<T> WorkerHandle<T> {
+    pub fn is_finished(&self) -> bool {
+        match self {
+            WorkerHandle::Thread(handle) => handle.is_finished(),
+            #[cfg(feature = "tokio")]
+            WorkerHandle::Task(handle) => handle.is_finished(),
+        }
+    }
+}
+
+pub enum Engine {
+    Threads { monitoring: Arc<AtomicBool> },
+    #[cfg(feature = "tokio")]
+    Async(async_engine::AsyncEngine),
+}
+
+impl Engine {
+    /// 创建引擎，`use_async` 为 true 但无法使用异步引擎时退回系统线程
+    pub fn new(use_async: bool) -> Self {
+        if use_async {
+            #[cfg(feature = "tokio")]
+            match async_engine::AsyncEngine::new() {
+                Ok(engine) => {
+                    info!("{}", tr!("engine-tokio"));
+                    return Engine::Async(engine);
+                }
+                Err(e) => warn!("{}", tr!("engine-tokio-failed", error = e)),
+            }
+            #[cfg(not(feature = "tokio"))]
+            warn!("{}", tr!("engine-tokio-missing"));
+        }
+        Engine::Threads { monitoring: Arc::new(AtomicBool::new(true)) }
+    }
+
+    /// 启动一个工作线程
+    pub fn spawn_worker<T, F>(&self, f: F) -> WorkerHandle<T>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        match self {
+            Engine::Threads { .. } => WorkerHandle::Thread(thread::spawn(f)),
+            #[cfg(feature = "tokio")]
+            Engine::Async(engine) => WorkerHandle::Task(engine.spawn_worker(f)),
+        }
+    }
+
+    /// 以 `interval` 为间隔定期刷新状态，直到调用 [`Engine::stop_status`]
+    pub fn spawn_status<P: Periodic>(&self, interval: Duration, mut periodic: P) -> WorkerHandle<P::Output> {
+        match self {
+            Engine::Threads { monitoring } => {
+                let monitoring = monitoring.clone();
+                WorkerHandle::Thread(thread::spawn(move || {
+                    while monitoring.load(Ordering::SeqCst) {
+                        periodic.tick();
+                        let deadline = Instant::now() + interval;
+                        while monitoring.load(Ordering::SeqCst) {
+                            let now = Instant::now();
+                            if now >= deadline {
+                                break;
+                            }
+                            thread::sleep((deadline - now).min(STOP_POLL_INTERVAL));
+                        }
+                    }
+                    periodic.finish()
+                }))
+            }
+            #[cfg(feature = "tokio")]
+            Engine::Async(engine) => WorkerHandle::Task(engine.spawn_status(interval, periodic)),
+        }
+    }
+
+    /// 停止状态刷新
+    pub fn stop_status(&self) {
+        match self {
+            Engine::Threads { monitoring } => monitoring.store(false, Ordering::SeqCst),
+            #[cfg(feature = "tokio")]
+            Engine::Async(engine) => engine.stop_status(),
+        }
+    }
+
+    /// 等待线程或任务结束，发生 panic 时返回 None
+    pub fn join<T>(&self, handle: WorkerHandle<T>) -> Option<T> {
+        match handle {
+            WorkerHandle::Thread(handle) => handle.join().ok(),
+            #[cfg(feature = "tokio")]
+            WorkerHandle::Task(handle) => match self {
+                Engine::Async(engine) => engine.join(handle),
+                Engine::Threads { .. } => unreachable!("线程引擎不会创建异步任务"),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+pub mod async_engine {
+    use std::time::Duration;
+
+    use tokio::runtime::Runtime;
+    use tokio::sync::watch;
+
+    use super::Periodic;
+
+    pub struct AsyncEngine {
+        runtime: Runtime,
+        /// 状态任务是否继续运行
+        monitoring: watch::Sender<bool>,
+    }
+
+    impl AsyncEngine {
+        pub fn new() -> std::io::Result<Self> {
+            // 计算全部在阻塞线程池中进行，异步工作线程只负责定时任务
+            let runtime = tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(1)
+                .thread_name("load-engine")
+                .enable_time()
+                .build()?;
+            Ok(AsyncEngine { runtime, monitoring: watch::Sender::new(true) })
+        }
+
+        pub fn spawn_worker<T, F>(&self, f: F) -> tokio::task::JoinHandle<T>
+        where
+            T: Send + 'static,
+            F: FnOnce() -> T + Send + 'static,
+        {
+            self.runtime.spawn_blocking(f)
+        }
+
+        pub fn spawn_status<P: Periodic>(&self, interval: Duration, mut periodic: P) -> tokio::task::JoinHandle<P::Output> {
+            let mut monitoring = self.monitoring.subscribe();
+            self.runtime.spawn(async move {
+                let mut ticker = tokio::time::interval(interval);
+                ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                loop {
+                    tokio::select! {
+                        _ = ticker.tick() => {
+                            // 刷新系统信息会短暂阻塞，让出当前异步工作线程
+                            tokio::task::block_in_place(|| periodic.tick());
+                        }
+                        _ = monitoring.wait_for(|running| !running) => break,
+                    }
+                }
+                periodic.finish()
+            })
+        }
+
+        pub fn stop_status(&self) {
+            self.monitoring.send_replace(false);
+        }
+
+        pub fn join<T>(&self, handle: tokio::task::JoinHandle<T>) -> Option<T> {
+            self.runtime.block_on(handle).ok()
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! 命令执行失败时的错误类型，`main` 据此输出消息并选择退出码
+
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use sysinfo::{System, SystemExt};
+
+use crate::output;
+
+/// 指定 `--abort-on-oom` 且内存分配失败时的退出码
+pub const EXIT_OUT_OF_MEMORY: i32 = 2;
+
+/// 启动时已有实例在运行的退出码
+pub const EXIT_ALREADY_RUNNING: i32 = 3;
+
+/// `stop` 时没有正在运行的实例的退出码
+pub const EXIT_NOT_RUNNING: i32 = 4;
+
+/// `stop` 等待超时、进程仍未退出的退出码
+pub const EXIT_STOP_TIMED_OUT: i32 = 5;
+
+#[derive(Debug)]
+pub enum LoadError {
+    IoError(std::io::Error),
+    /// 参数值无法解析，内容为完整的说明
+    ParseError(String),
+    /// 已有实例在运行，内容为其PID
+    AlreadyRunning(u32),
+    NoInstanceFound,
+    /// `stop` 后进程在等待时间内仍未退出，内容为PID和等待的时长
+    StopTimedOut(u32, Duration),
+    /// 内存分配失败，内容为请求的字节数
+    MemoryAllocationFailed(usize),
+    PidFileCorrupted(PathBuf),
+    /// 参数组合无效，内容为完整的说明
+    InvalidArgument(String),
+    /// 当前平台不支持的功能，内容为完整的说明；目前只在非 Unix 平台上出现
+    #[cfg_attr(unix, allow(dead_code))]
+    PlatformUnsupported(String),
+}
+
+impl LoadError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            LoadError::MemoryAllocationFailed(_) => EXIT_OUT_OF_MEMORY,
+            LoadError::AlreadyRunning(_) => EXIT_ALREADY_RUNNING,
+            LoadError::NoInstanceFound => EXIT_NOT_RUNNING,
+            LoadError::StopTimedOut(..) => EXIT_STOP_TIMED_OUT,
+            _ => 1,
+        }
+    }
+
+    /// 是否只是提示（已有实例、没有实例），作为普通信息而不是错误输出
+    pub fn is_notice(&self) -> bool {
+        matches!(self, LoadError::AlreadyRunning(_) | LoadError::NoInstanceFound)
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            LoadError::AlreadyRunning(pid) => return write!(f, "{}", tr!("start-already-running", pid = pid)),
+            LoadError::NoInstanceFound => return write!(f, "{}", tr!("stop-not-running")),
+            LoadError::IoError(e) => e.to_string(),
+            LoadError::ParseError(message)
+            | LoadError::InvalidArgument(message)
+            | LoadError::PlatformUnsupported(message) => message.clone(),
+            LoadError::MemoryAllocationFailed(bytes) => {
+                let mut sys = System::new();
+                sys.refresh_memory();
+                tr!(
+                    "memory-oom",
+                    requested = format!("{:.1}", *bytes as f64 / output::GIB),
+                    available = format!("{:.1}", sys.available_memory() as f64 / output::GIB)
+                )
+            }
+            LoadError::PidFileCorrupted(path) => tr!("pid-file-corrupted", path = path.display()),
+            LoadError::StopTimedOut(pid, waited) => tr!("stop-timed-out", pid = pid, secs = waited.as_secs_f64()),
+        };
+        write!(f, "{}", tr!("error-prefixed", error = message))
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for LoadError {
+    fn from(e: std::io::Error) -> Self {
+        LoadError::IoError(e)
+    }
+}
@@ -0,0 +1,73 @@
+//! 可注入随机分配失败的全局分配器，用于OOM处理测试
+//!
+//! 只有可执行文件（`main.rs`）把 [`FailingAllocator`] 注册为全局分配器；以库的方式使用时不注册，
+//! 调用方可以使用自己的全局分配器，`configure` 也就没有效果。
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::ptr;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// 失败概率，以 0..=u32::MAX 的定点数表示，0 表示从不失败
+static FAIL_THRESHOLD: AtomicU64 = AtomicU64::new(0);
+
+/// 只有大于该大小的分配才可能失败，避免破坏工具自身的小分配
+static MIN_FAIL_SIZE: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// 伪随机数状态（xorshift64）
+static RNG_STATE: AtomicU64 = AtomicU64::new(0x9e37_79b9_7f4a_7c15);
+
+/// 包装系统分配器，按配置的概率对大块分配返回空指针
+pub struct FailingAllocator;
+
+/// 设置失败概率（0.0-1.0）和最小失败大小（字节）
+pub fn configure(fail_rate: f64, min_fail_size: usize) {
+    let seed = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    RNG_STATE.store(seed | 1, Ordering::Relaxed);
+    MIN_FAIL_SIZE.store(min_fail_size, Ordering::Relaxed);
+    FAIL_THRESHOLD.store((fail_rate.clamp(0.0, 1.0) * f64::from(u32::MAX)) as u64, Ordering::Relaxed);
+}
+
+/// 判断本次分配是否应当失败；此处不能分配内存
+fn should_fail(size: usize) -> bool {
+    let threshold = FAIL_THRESHOLD.load(Ordering::Relaxed);
+    if threshold == 0 || size <= MIN_FAIL_SIZE.load(Ordering::Relaxed) {
+        return false;
+    }
+    // 状态更新存在竞争也无妨，这里只需要足够随机
+    let mut x = RNG_STATE.load(Ordering::Relaxed);
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    RNG_STATE.store(x, Ordering::Relaxed);
+    (x >> 32) < threshold
+}
+
+unsafe impl GlobalAlloc for FailingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if should_fail(layout.size()) {
+            return ptr::null_mut();
+        }
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if should_fail(layout.size()) {
+            return ptr::null_mut();
+        }
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if new_size > layout.size() && should_fail(new_size) {
+            return ptr::null_mut();
+        }
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
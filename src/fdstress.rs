@@ -0,0 +1,187 @@
+//! 文件描述符压力：反复打开一组文件描述符再全部关闭，测试内核文件描述符表的性能
+//!
+//! 高吞吐的异步运行时会频繁创建和关闭套接字，文件描述符表的分配和回收是其中的热点。
+//! 同时打开的数量受 `RLIMIT_NOFILE` 限制，达到上限时改用实际能打开的最大数量继续测试。
+
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+
+use crate::state::LoadState;
+
+/// 输出统计的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 达到上限后为进程中其他线程（状态输出、日志等）留出的文件描述符数
+const FD_HEADROOM: usize = 64;
+
+/// 打开的文件描述符类型
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum FdType {
+    /// /dev/null（Windows 为 NUL）
+    #[default]
+    Null,
+    /// 临时目录中的普通文件
+    File,
+    /// 匿名管道，每次打开两个描述符（仅Unix）
+    Pipe,
+    /// Unix 域套接字对，每次打开两个描述符（仅Unix）
+    UnixSocket,
+    /// eventfd（仅Linux）
+    Eventfd,
+}
+
+impl FdType {
+    fn name(self) -> String {
+        self.to_possible_value().map_or_else(String::new, |v| v.get_name().to_string())
+    }
+}
+
+/// 打开一个（管道和套接字对为两个）文件描述符
+fn open_one(kind: FdType, path: &Path) -> std::io::Result<Vec<File>> {
+    match kind {
+        FdType::Null | FdType::File => Ok(vec![File::open(path)?]),
+        #[cfg(unix)]
+        FdType::Pipe => {
+            use std::os::fd::FromRawFd;
+            let mut fds = [0; 2];
+            // SAFETY: fds 有两个元素，pipe 成功时写入读写两端
+            if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            // SAFETY: 两个描述符刚由 pipe 创建，所有权转交给 File
+            Ok(fds.iter().map(|&fd| unsafe { File::from_raw_fd(fd) }).collect())
+        }
+        #[cfg(unix)]
+        FdType::UnixSocket => {
+            let (a, b) = std::os::unix::net::UnixStream::pair()?;
+            Ok(vec![File::from(std::os::fd::OwnedFd::from(a)), File::from(std::os::fd::OwnedFd::from(b))])
+        }
+        #[cfg(target_os = "linux")]
+        FdType::Eventfd => {
+            use std::os::fd::FromRawFd;
+            // SAFETY: eventfd 不访问用户内存
+            let fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC) };
+            if fd < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            // SAFETY: fd 刚由 eventfd 创建，所有权转交给 File
+            Ok(vec![unsafe { File::from_raw_fd(fd) }])
+        }
+        #[allow(unreachable_patterns)]
+        _ => Err(std::io::Error::new(std::io::ErrorKind::Unsupported, tr!("fd-type-unsupported", kind = kind.name()))),
+    }
+}
+
+/// 是否因达到文件描述符上限而失败
+fn is_fd_limit(e: &std::io::Error) -> bool {
+    #[cfg(unix)]
+    {
+        matches!(e.raw_os_error(), Some(libc::EMFILE) | Some(libc::ENFILE))
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = e;
+        false
+    }
+}
+
+/// 输出同时打开的最大数量，受限时给出 RLIMIT_NOFILE 和提高上限的方法
+fn report_limit(max: usize, limited: bool) {
+    info!("{}", tr!("fd-max-open", max = max));
+    if !limited {
+        return;
+    }
+    #[cfg(unix)]
+    {
+        let mut limit = libc::rlimit { rlim_cur: 0, rlim_max: 0 };
+        // SAFETY: limit 是有效的输出缓冲区
+        if unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) } == 0 {
+            warn!("{}", tr!("fd-limit-reached", soft = limit.rlim_cur, hard = limit.rlim_max));
+            return;
+        }
+    }
+    warn!("{}", tr!("fd-limit-reached-unknown"));
+}
+
+/// 启动文件描述符压力线程
+pub fn spawn_fd_stress(kind: FdType, count: usize, state: Arc<LoadState>) -> std::io::Result<JoinHandle<()>> {
+    // 普通文件打开同一个临时文件；线程结束时删除
+    let temp = match kind {
+        FdType::File => Some(tempfile::NamedTempFile::new()?),
+        _ => None,
+    };
+    let path = match &temp {
+        Some(temp) => temp.path().to_path_buf(),
+        None if cfg!(windows) => "NUL".into(),
+        None => "/dev/null".into(),
+    };
+    // 提前试打开一次，不支持的类型在启动时报错
+    drop(open_one(kind, &path)?);
+    info!("{}", tr!("fd-started", kind = kind.name(), count = count));
+
+    Ok(thread::spawn(move || {
+        let _temp = temp;
+        let mut target = count;
+        let mut fds: Vec<File> = Vec::with_capacity(count);
+        let mut reported = false;
+        let (mut ops, mut sets) = (0u64, 0u64);
+        let (mut open_time, mut close_time) = (Duration::ZERO, Duration::ZERO);
+        let mut last_report = Instant::now();
+        while state.is_running() {
+            let open_start = Instant::now();
+            let mut limited = false;
+            while fds.len() < target {
+                match open_one(kind, &path) {
+                    Ok(opened) => fds.extend(opened),
+                    Err(e) if is_fd_limit(&e) && !fds.is_empty() && !reported => {
+                        limited = true;
+                        break;
+                    }
+                    // 其他线程占用的描述符有波动，后续各组偶尔仍可能碰到上限
+                    Err(e) if is_fd_limit(&e) && !fds.is_empty() => break,
+                    Err(e) => {
+                        warn!("{}", tr!("fd-open-failed", error = e));
+                        return;
+                    }
+                }
+            }
+            open_time += open_start.elapsed();
+            if !reported {
+                report_limit(fds.len(), limited);
+                reported = true;
+            }
+            if limited {
+                target = fds.len().saturating_sub(FD_HEADROOM).max(1);
+                fds.truncate(target);
+            }
+            ops += fds.len() as u64 * 2;
+
+            let close_start = Instant::now();
+            fds.clear();
+            close_time += close_start.elapsed();
+            sets += 1;
+
+            let elapsed = last_report.elapsed();
+            if elapsed >= REPORT_INTERVAL {
+                let ms = |time: Duration| format!("{:.2}", time.as_secs_f64() * 1e3 / sets as f64);
+                info!(
+                    "{}",
+                    tr!(
+                        "fd-stats",
+                        rate = format!("{:.0}", ops as f64 / elapsed.as_secs_f64()),
+                        open = ms(open_time),
+                        close = ms(close_time)
+                    )
+                );
+                (ops, sets) = (0, 0);
+                (open_time, close_time) = (Duration::ZERO, Duration::ZERO);
+                last_report = Instant::now();
+            }
+        }
+    }))
+}
@@ -0,0 +1,274 @@
+//! CPU 频率变化跟踪（仅Linux，需要root权限）：记录运行期间每个核心的每一次调频
+//!
+//! 在 tracefs 中创建独立的跟踪实例并启用 `power:cpu_frequency` 事件，定期读取该实例的
+//! `trace_pipe`，解析出每次频率变化的时间、核心、旧频率和新频率，捕捉睿频、P-state 切换和
+//! 过热降频。实例使用 `mono` 时钟，事件时间戳与 CLOCK_MONOTONIC 一致，便于按时间加权统计；
+//! 结束时删除实例，不影响全局的跟踪设置。
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::state::LoadState;
+
+/// 读取跟踪缓冲区的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 频率分布直方图的区间数
+const FREQ_BINS: usize = 10;
+
+/// 直方图中最长的柱的宽度（字符）
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// 单个核心的频率统计，频率单位为 kHz，时间单位为秒
+#[derive(Clone, Copy, Debug, Default)]
+struct CoreTrack {
+    /// 当前频率，尚未知道时为0
+    freq: u64,
+    /// 当前频率开始的时间
+    since: f64,
+    /// 频率乘以持续时间之和，用于计算时间加权的平均频率
+    weighted: f64,
+    time: f64,
+    min: u64,
+    max: u64,
+    changes: u64,
+}
+
+/// 所有被跟踪核心的频率统计
+#[derive(Debug, Default)]
+struct FreqReport {
+    cores: BTreeMap<usize, CoreTrack>,
+    /// 每个频率上累计的核心时间，用于绘制分布直方图
+    time_at: BTreeMap<u64, f64>,
+}
+
+impl FreqReport {
+    /// 以 sysfs 中的当前频率作为各核心的初始频率
+    fn new(cpus: &[usize], now: f64) -> Self {
+        let mut report = FreqReport::default();
+        for &cpu in cpus {
+            let freq = std::fs::read_to_string(format!("/sys/devices/system/cpu/cpu{}/cpufreq/scaling_cur_freq", cpu))
+                .ok()
+                .and_then(|s| s.trim().parse().ok())
+                .unwrap_or(0);
+            report.cores.insert(cpu, CoreTrack { freq, since: now, min: freq, max: freq, ..Default::default() });
+        }
+        report
+    }
+
+    /// 把当前频率持续到 `until` 的时间计入统计
+    fn settle(track: &mut CoreTrack, time_at: &mut BTreeMap<u64, f64>, until: f64) {
+        let held = (until - track.since).max(0.0);
+        if track.freq > 0 {
+            track.weighted += track.freq as f64 * held;
+            track.time += held;
+            *time_at.entry(track.freq).or_default() += held;
+        }
+        track.since = until;
+    }
+
+    /// 记录一次频率变化，返回旧频率；不在跟踪范围内的核心返回 None
+    fn apply(&mut self, cpu: usize, freq: u64, time: f64) -> Option<u64> {
+        let track = self.cores.get_mut(&cpu)?;
+        Self::settle(track, &mut self.time_at, time);
+        let old = track.freq;
+        if old == 0 {
+            (track.min, track.max) = (freq, freq);
+        }
+        track.freq = freq;
+        track.min = track.min.min(freq);
+        track.max = track.max.max(freq);
+        track.changes += 1;
+        Some(old)
+    }
+
+    fn finish(&mut self, now: f64) {
+        for track in self.cores.values_mut() {
+            Self::settle(track, &mut self.time_at, now);
+        }
+    }
+
+    /// 渲染每个核心的平均、最低、最高频率，以及按时间占比的频率分布直方图
+    fn render(&self) -> String {
+        let mhz = |khz: f64| format!("{:.0}", khz / 1000.0);
+        let mut out = tr!("freqtrace-summary-header");
+        for (cpu, track) in &self.cores {
+            let line = if track.time > 0.0 {
+                tr!(
+                    "freqtrace-summary-core",
+                    cpu = cpu,
+                    avg = mhz(track.weighted / track.time),
+                    min = mhz(track.min as f64),
+                    max = mhz(track.max as f64),
+                    changes = track.changes
+                )
+            } else {
+                tr!("freqtrace-summary-core-unknown", cpu = cpu)
+            };
+            let _ = write!(out, "\n  {}", line);
+        }
+        if self.cores.values().all(|track| track.changes == 0) {
+            let _ = write!(out, "\n  {}", tr!("freqtrace-no-events"));
+        }
+
+        let (Some((&low, _)), Some((&high, _))) = (self.time_at.first_key_value(), self.time_at.last_key_value()) else {
+            return out;
+        };
+        let total: f64 = self.time_at.values().sum();
+        if total <= 0.0 {
+            return out;
+        }
+        let bins = if low == high { 1 } else { FREQ_BINS };
+        let width = ((high - low) as f64 / bins as f64).max(1.0);
+        let mut times = vec![0.0f64; bins];
+        for (&freq, &time) in &self.time_at {
+            // 最高频率落在最后一个区间
+            let bin = ((freq - low) as f64 / width) as usize;
+            times[bin.min(bins - 1)] += time;
+        }
+        let max = times.iter().copied().fold(0.0, f64::max);
+        let _ = write!(out, "\n  {}", tr!("freqtrace-histogram-header"));
+        for (i, &time) in times.iter().enumerate() {
+            let bar = (time / max * HISTOGRAM_WIDTH as f64).ceil() as usize;
+            let from = low as f64 + i as f64 * width;
+            let _ = write!(
+                out,
+                "\n    [{}-{} MHz]: {} ({:.1}%)",
+                mhz(from),
+                mhz(if bins == 1 { high as f64 } else { from + width }),
+                "█".repeat(bar),
+                time * 100.0 / total
+            );
+        }
+        out
+    }
+}
+
+/// 解析一行 cpu_frequency 事件，返回（时间戳秒数，核心，频率 kHz），例如：
+/// `<idle>-0  [001] d..2.  1234.567890: cpu_frequency: state=2400000 cpu_id=1`
+fn parse_event(line: &str) -> Option<(f64, usize, u64)> {
+    let (head, fields) = line.split_once(" cpu_frequency: ")?;
+    let time = head.trim_end().strip_suffix(':')?.rsplit(' ').next()?.parse().ok()?;
+    let mut freq = None;
+    let mut cpu = None;
+    for field in fields.split_whitespace() {
+        match field.split_once('=') {
+            Some(("state", value)) => freq = value.parse().ok(),
+            Some(("cpu_id", value)) => cpu = value.parse().ok(),
+            _ => {}
+        }
+    }
+    Some((time, cpu?, freq?))
+}
+
+mod sys {
+    use std::fs::File;
+    use std::io::{self, Read};
+    use std::os::unix::fs::OpenOptionsExt;
+    use std::path::PathBuf;
+
+    /// 当前的 CLOCK_MONOTONIC 时间（秒），与 `mono` 跟踪时钟一致
+    pub fn monotonic_now() -> f64 {
+        let mut ts = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+        // SAFETY: ts 是有效的输出缓冲区
+        unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts) };
+        ts.tv_sec as f64 + ts.tv_nsec as f64 / 1e9
+    }
+
+    /// 本进程专用的 tracefs 实例，丢弃时删除
+    pub struct TraceInstance {
+        dir: PathBuf,
+    }
+
+    impl TraceInstance {
+        /// 创建实例，切换到 `mono` 时钟并启用 cpu_frequency 事件
+        pub fn create() -> io::Result<Self> {
+            let mut last_error = io::Error::from(io::ErrorKind::NotFound);
+            for root in ["/sys/kernel/tracing", "/sys/kernel/debug/tracing"] {
+                let dir = PathBuf::from(root).join("instances").join(format!("enhancecpu-{}", std::process::id()));
+                match std::fs::create_dir(&dir) {
+                    Ok(()) => {
+                        let instance = TraceInstance { dir };
+                        instance.write("trace_clock", "mono")?;
+                        instance.write("events/power/cpu_frequency/enable", "1")?;
+                        instance.write("tracing_on", "1")?;
+                        return Ok(instance);
+                    }
+                    Err(e) => last_error = e,
+                }
+            }
+            Err(last_error)
+        }
+
+        fn write(&self, name: &str, value: &str) -> io::Result<()> {
+            std::fs::write(self.dir.join(name), value)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", self.dir.join(name).display(), e)))
+        }
+
+        /// 以非阻塞方式打开 trace_pipe，读取时消费已读出的事件
+        pub fn open_pipe(&self) -> io::Result<File> {
+            std::fs::OpenOptions::new().read(true).custom_flags(libc::O_NONBLOCK).open(self.dir.join("trace_pipe"))
+        }
+    }
+
+    impl Drop for TraceInstance {
+        fn drop(&mut self) {
+            let _ = self.write("events/power/cpu_frequency/enable", "0");
+            let _ = std::fs::remove_dir(&self.dir);
+        }
+    }
+
+    /// 读出 trace_pipe 中当前所有的数据，追加到 `pending`
+    pub fn drain(pipe: &mut File, pending: &mut Vec<u8>) {
+        let mut buf = [0u8; 64 * 1024];
+        loop {
+            match pipe.read(&mut buf) {
+                Ok(0) => return,
+                Ok(n) => pending.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(_) => return,
+            }
+        }
+    }
+}
+
+/// 启动频率跟踪线程；`cpus` 为被跟踪的核心，负载结束时输出汇总
+pub fn spawn_freq_report(cpus: Vec<usize>, state: Arc<LoadState>) -> std::io::Result<JoinHandle<()>> {
+    let instance = sys::TraceInstance::create()?;
+    let mut pipe = instance.open_pipe()?;
+    info!("{}", tr!("freqtrace-started", count = cpus.len()));
+    Ok(thread::spawn(move || {
+        let mut report = FreqReport::new(&cpus, sys::monotonic_now());
+        let mut pending = Vec::new();
+        loop {
+            let running = state.is_running();
+            sys::drain(&mut pipe, &mut pending);
+            // 只处理完整的行，不完整的尾部留到下一次
+            let complete = pending.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+            for line in String::from_utf8_lossy(&pending[..complete]).lines() {
+                let Some((time, cpu, freq)) = parse_event(line) else {
+                    continue;
+                };
+                if let Some(old) = report.apply(cpu, freq, time) {
+                    let mhz = |khz: u64| if khz == 0 { "?".to_string() } else { (khz / 1000).to_string() };
+                    info!(
+                        "{}",
+                        tr!("freqtrace-change", time = format!("{:.6}", time), cpu = cpu, old = mhz(old), new = mhz(freq))
+                    );
+                }
+            }
+            pending.drain(..complete);
+            if !running {
+                break;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+        report.finish(sys::monotonic_now());
+        drop(pipe);
+        drop(instance);
+        info!("{}", report.render());
+    }))
+}
@@ -0,0 +1,140 @@
+//! 内存增长模式：逐块分配并写入内存，模拟内存泄漏
+//!
+//! 可以限制增长速率；系统可用内存低于安全余量时暂停增长，恢复后继续，避免把主机拖入OOM。
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use bytesize::ByteSize;
+use sysinfo::{System, SystemExt};
+
+use crate::memory;
+use crate::state::LoadState;
+
+/// 每次分配的最大块大小
+const MAX_CHUNK: u64 = 16 << 20;
+
+/// 限速时每秒大约分配的块数，块越小速率越平滑
+const CHUNKS_PER_SEC: u64 = 10;
+
+/// 暂停或到达上限后检查状态的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 暂停期间检查可用内存的间隔
+const SAFETY_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 输出增长进度的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Copy, Debug)]
+pub struct GrowthSettings {
+    /// 增长的上限（字节）
+    pub limit: u64,
+    /// 最大增长速率（字节/秒），None 表示不限速
+    pub max_rate: Option<u64>,
+    /// 系统可用内存低于该值时暂停增长
+    pub safety_margin: u64,
+}
+
+/// 启动内存增长线程，增长的内存在负载停止时释放
+pub fn spawn_growth(settings: GrowthSettings, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let chunk_size = chunk_size(settings.max_rate);
+        match settings.max_rate {
+            Some(rate) => info!("{}", tr!("growth-started-rate", limit = ByteSize::b(settings.limit), rate = ByteSize::b(rate))),
+            None => info!("{}", tr!("growth-started", limit = ByteSize::b(settings.limit))),
+        }
+
+        let mut sys = System::new();
+        let mut chunks: Vec<Vec<u8>> = Vec::new();
+        let mut grown = 0u64;
+        let mut paused = false;
+        let mut last_report = Instant::now();
+        let mut reported_bytes = 0u64;
+        while state.is_running() && grown < settings.limit {
+            sys.refresh_memory();
+            let available = sys.available_memory();
+            if available < settings.safety_margin {
+                if !paused {
+                    warn!(
+                        "{}",
+                        tr!(
+                            "growth-paused",
+                            available = ByteSize::b(available),
+                            margin = ByteSize::b(settings.safety_margin)
+                        )
+                    );
+                    paused = true;
+                }
+                sleep_while_running(&state, SAFETY_CHECK_INTERVAL);
+                continue;
+            }
+            if paused {
+                info!("{}", tr!("growth-resumed", available = ByteSize::b(available)));
+                paused = false;
+            }
+
+            let size = chunk_size.min(settings.limit - grown);
+            let alloc_start = Instant::now();
+            let Some(chunk) = alloc_resident(size) else {
+                warn!("{}", tr!("growth-alloc-failed", size = ByteSize::b(size)));
+                break;
+            };
+            chunks.push(chunk);
+            grown += size;
+            state.grown_bytes.store(grown, Ordering::Relaxed);
+
+            // 按本次分配的耗时计算当前速率，快于上限时休眠补足
+            if let Some(rate) = settings.max_rate {
+                let min_duration = Duration::from_secs_f64(size as f64 / rate as f64);
+                if let Some(rest) = min_duration.checked_sub(alloc_start.elapsed()) {
+                    sleep_while_running(&state, rest);
+                }
+            }
+
+            let elapsed = last_report.elapsed();
+            if elapsed >= REPORT_INTERVAL {
+                let rate = (grown - reported_bytes) as f64 / elapsed.as_secs_f64();
+                info!("{}", tr!("growth-progress", grown = ByteSize::b(grown), rate = ByteSize::b(rate as u64)));
+                reported_bytes = grown;
+                last_report = Instant::now();
+            }
+        }
+
+        if grown >= settings.limit {
+            info!("{}", tr!("growth-limit-reached", limit = ByteSize::b(settings.limit)));
+        }
+        // 保持已增长的内存直到负载停止
+        while state.is_running() {
+            thread::sleep(POLL_INTERVAL);
+        }
+        state.grown_bytes.store(0, Ordering::Relaxed);
+        drop(chunks);
+    })
+}
+
+/// 按速率（字节/秒）选择每次分配的块大小，不限速时使用最大块
+pub fn chunk_size(rate: Option<u64>) -> u64 {
+    rate.map_or(MAX_CHUNK, |rate| (rate / CHUNKS_PER_SEC).clamp(4096, MAX_CHUNK))
+}
+
+/// 分配一块内存并写入每一页使其真正驻留，分配失败时返回 None
+pub fn alloc_resident(size: u64) -> Option<Vec<u8>> {
+    let mut chunk = memory::try_alloc_zeroed(size as usize)?;
+    chunk.fill(0xA5);
+    Some(chunk)
+}
+
+/// 休眠 `duration`，期间负载停止时提前返回
+pub fn sleep_while_running(state: &LoadState, duration: Duration) {
+    let deadline = Instant::now() + duration;
+    while state.is_running() {
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+        thread::sleep((deadline - now).min(POLL_INTERVAL));
+    }
+}
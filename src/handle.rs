@@ -0,0 +1,90 @@
+//! 以代码方式启动的负载：[`LoadConfig::start`] 返回的 [`LoadHandle`] 持有运行状态和负载线程
+//!
+//! 负载线程执行与命令行 `start` 相同的启动流程（`cli::start_load`），参数由 [`LoadConfig`] 转换而来，
+//! 其余选项取命令行的默认值。与命令行不同，不写PID文件、不安装 Ctrl+C 处理函数，也不输出状态；
+//! 未调用 [`LoadHandle::stop`] 就离开作用域（包括调用方 panic 时）由 `Drop` 停止并等待线程退出。
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::sync::mpsc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::cli;
+use crate::config::LoadConfig;
+use crate::error::LoadError;
+use crate::logger;
+use crate::state::LoadState;
+
+/// 负载结束时的统计
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LoadStats {
+    /// 全部工作线程的计算时间之和（秒），不含按目标使用率休眠的时间
+    pub total_cpu_seconds: f64,
+    /// 运行期间占用的最大内存字节数
+    pub peak_memory_bytes: usize,
+}
+
+/// 正在运行的负载
+pub struct LoadHandle {
+    state: Arc<LoadState>,
+    /// 执行启动流程的负载线程，停止后为 None
+    thread: Option<JoinHandle<Result<(), LoadError>>>,
+    peak_memory_bytes: usize,
+}
+
+impl LoadConfig {
+    /// 分配内存并启动 `cores` 个工作线程，全部就绪后返回；设置了 `duration` 时到期后自动停止
+    pub fn start(&self) -> Result<LoadHandle, LoadError> {
+        logger::set_quiet(true);
+        let (ready, receiver) = mpsc::channel();
+        let config = self.clone();
+        let thread = thread::spawn(move || cli::run_embedded(&config, ready));
+        match receiver.recv() {
+            Ok(state) => {
+                let peak_memory_bytes = state.allocated_bytes.load(Ordering::Relaxed) as usize;
+                Ok(LoadHandle { state, thread: Some(thread), peak_memory_bytes })
+            }
+            // 就绪之前负载线程已经退出，返回它的错误
+            Err(_) => match thread.join() {
+                Ok(Err(e)) => Err(e),
+                // start_load 只在发出就绪通知之后才返回 Ok
+                Ok(Ok(())) => unreachable!(),
+                Err(panic) => std::panic::resume_unwind(panic),
+            },
+        }
+    }
+}
+
+impl LoadHandle {
+    /// 负载是否仍在运行；到达 `duration` 后为 false
+    pub fn is_running(&self) -> bool {
+        self.state.is_running()
+    }
+
+    /// 停止负载，等待负载线程退出（其中释放内存）后返回统计
+    pub fn stop(mut self) -> LoadStats {
+        self.shutdown()
+    }
+
+    fn shutdown(&mut self) -> LoadStats {
+        self.state.request_stop();
+        if let Some(thread) = self.thread.take() {
+            match thread.join() {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => error!("{}", e),
+                Err(panic) => std::panic::resume_unwind(panic),
+            }
+        }
+        let busy: Duration = self.state.self_monitor.get().into_iter().flatten().map(|stats| stats.busy_time()).sum();
+        LoadStats { total_cpu_seconds: busy.as_secs_f64(), peak_memory_bytes: self.peak_memory_bytes }
+    }
+}
+
+impl Drop for LoadHandle {
+    fn drop(&mut self) {
+        if self.thread.is_some() {
+            self.shutdown();
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! 内存占用固定的对数线性直方图，用于统计延迟分布
+
+/// 小于该值的样本逐一计数
+const LINEAR_LIMIT: u64 = 128;
+
+/// 每个2的幂区间细分的桶数，相对误差约为 1/64
+const SUB_BUCKETS: u64 = 64;
+
+const BUCKETS: usize = (LINEAR_LIMIT + (64 - 7) * SUB_BUCKETS) as usize;
+
+/// 延迟直方图，单位由调用方决定（微秒、纳秒等）
+pub struct LatencyHistogram {
+    counts: Vec<u64>,
+    count: u64,
+    sum: u128,
+    max: u64,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        LatencyHistogram { counts: vec![0; BUCKETS], count: 0, sum: 0, max: 0 }
+    }
+}
+
+fn bucket_of(value: u64) -> usize {
+    if value < LINEAR_LIMIT {
+        return value as usize;
+    }
+    let exp = 63 - u64::from(value.leading_zeros());
+    let sub = (value >> (exp - 6)) & (SUB_BUCKETS - 1);
+    (LINEAR_LIMIT + (exp - 7) * SUB_BUCKETS + sub) as usize
+}
+
+/// 桶的下界
+fn value_of(bucket: usize) -> u64 {
+    let bucket = bucket as u64;
+    if bucket < LINEAR_LIMIT {
+        return bucket;
+    }
+    let exp = (bucket - LINEAR_LIMIT) / SUB_BUCKETS + 7;
+    let sub = (bucket - LINEAR_LIMIT) % SUB_BUCKETS;
+    (1 << exp) | (sub << (exp - 6))
+}
+
+impl LatencyHistogram {
+    pub fn record(&mut self, value: u64) {
+        self.counts[bucket_of(value)] += 1;
+        self.count += 1;
+        self.sum += u128::from(value);
+        self.max = self.max.max(value);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 { 0.0 } else { self.sum as f64 / self.count as f64 }
+    }
+
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// 第 p 百分位（0-100）的近似值
+    pub fn percentile(&self, p: f64) -> u64 {
+        if self.count == 0 {
+            return 0;
+        }
+        let rank = ((self.count as f64 * p / 100.0).ceil() as u64).clamp(1, self.count);
+        let mut seen = 0;
+        for (bucket, &n) in self.counts.iter().enumerate() {
+            seen += n;
+            if seen >= rank {
+                return value_of(bucket).min(self.max);
+            }
+        }
+        self.max
+    }
+}
@@ -0,0 +1,83 @@
+//! 生命周期钩子：在负载启动后、停止前执行外部命令，便于通知监控系统或采集现场
+
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 等待钩子退出时的轮询间隔
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// 钩子退出后等待其输出读完的最长时间
+const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+fn shell(cmd: &str) -> Command {
+    #[cfg(windows)]
+    {
+        let mut command = Command::new("cmd.exe");
+        command.arg("/c").arg(cmd);
+        command
+    }
+    #[cfg(not(windows))]
+    {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(cmd);
+        command
+    }
+}
+
+/// 把钩子的输出逐行写入日志
+fn forward_output(name: &'static str, stream: impl Read + Send + 'static) -> thread::JoinHandle<()> {
+    thread::spawn(move || {
+        for line in BufReader::new(stream).lines().map_while(Result::ok) {
+            info!("[{}] {}", name, line);
+        }
+    })
+}
+
+/// 执行钩子命令并等待结束，超过 `timeout` 时终止；失败只告警，不影响负载
+pub fn run_hook(name: &'static str, cmd: &str, timeout: Duration) {
+    info!("{}", tr!("hook-running", name = name, cmd = cmd));
+    let mut child = match shell(cmd).stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            warn!("{}", tr!("hook-spawn-failed", name = name, error = e));
+            return;
+        }
+    };
+    let readers: Vec<_> = [
+        child.stdout.take().map(|out| forward_output(name, out)),
+        child.stderr.take().map(|err| forward_output(name, err)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let start = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if start.elapsed() >= timeout => {
+                warn!("{}", tr!("hook-timed-out", name = name, secs = timeout.as_secs()));
+                let _ = child.kill();
+                let _ = child.wait();
+                break None;
+            }
+            Ok(None) => thread::sleep(WAIT_POLL_INTERVAL),
+            Err(e) => {
+                warn!("{}", tr!("hook-wait-failed", name = name, error = e));
+                break None;
+            }
+        }
+    };
+    // 钩子启动的后台进程可能继续持有管道，最多再等一会儿让输出读完
+    let drain_deadline = Instant::now() + OUTPUT_DRAIN_TIMEOUT;
+    while readers.iter().any(|r| !r.is_finished()) && Instant::now() < drain_deadline {
+        thread::sleep(WAIT_POLL_INTERVAL);
+    }
+    match status {
+        Some(status) if status.success() => info!("{}", tr!("hook-finished", name = name)),
+        Some(status) => warn!("{}", tr!("hook-failed", name = name, status = status)),
+        None => {}
+    }
+}
@@ -0,0 +1,54 @@
+//! 透明大页（THP）覆盖率（仅Linux）：本进程匿名内存中由大页承载的比例
+//!
+//! 数据来自 `/proc/self/smaps_rollup` 的 `AnonHugePages` 和 `Rss`。
+
+/// 启用 THP 时覆盖率低于该百分比则告警
+const LOW_COVERAGE_PERCENT: f64 = 50.0;
+
+/// 开始判断覆盖率之前跳过的采样次数，给 khugepaged 留出合并大页的时间
+const WARMUP_SAMPLES: u32 = 3;
+
+/// 读取 smaps_rollup 中以 kB 为单位的字段
+fn rollup_field(rollup: &str, name: &str) -> Option<u64> {
+    rollup.lines().find_map(|line| {
+        let value = line.strip_prefix(name)?.strip_prefix(':')?;
+        value.trim().strip_suffix("kB")?.trim().parse().ok()
+    })
+}
+
+/// THP 覆盖率（百分比）：`AnonHugePages / Rss`；不可读或 Rss 为0时返回 None
+pub fn thp_coverage() -> Option<f64> {
+    let rollup = std::fs::read_to_string("/proc/self/smaps_rollup").ok()?;
+    let rss = rollup_field(&rollup, "Rss")?;
+    let huge = rollup_field(&rollup, "AnonHugePages")?;
+    (rss > 0).then(|| huge as f64 * 100.0 / rss as f64)
+}
+
+/// 当前的 THP 模式，即 `/sys/kernel/mm/transparent_hugepage/enabled` 中方括号内的值
+fn thp_mode() -> Option<String> {
+    let enabled = std::fs::read_to_string("/sys/kernel/mm/transparent_hugepage/enabled").ok()?;
+    let (_, rest) = enabled.split_once('[')?;
+    Some(rest.split_once(']')?.0.to_string())
+}
+
+/// 每次状态刷新时采样覆盖率，THP 模式为 always 而覆盖率偏低时告警一次
+#[derive(Default)]
+pub struct ThpMonitor {
+    samples: u32,
+    warned: bool,
+}
+
+impl ThpMonitor {
+    pub fn sample(&mut self) -> Option<f64> {
+        let coverage = thp_coverage()?;
+        self.samples += 1;
+        if !self.warned
+            && self.samples > WARMUP_SAMPLES
+            && coverage < LOW_COVERAGE_PERCENT
+            && thp_mode().as_deref() == Some("always") {
+            warn!("{}", tr!("thp-low-coverage", coverage = format!("{:.1}", coverage)));
+            self.warned = true;
+        }
+        Some(coverage)
+    }
+}
@@ -0,0 +1,112 @@
+//! 国际化：用户可见的消息来自编译进程序的 Fluent 资源（`locales/*.ftl`）
+//!
+//! 语言依次取自 `--lang`、`LC_ALL`、`LC_MESSAGES`、`LANG`，无法识别时使用 zh-CN。
+//! 当前语言缺少某条消息时回退到 zh-CN，仍然缺少时直接输出消息ID，便于发现遗漏。
+
+use std::sync::OnceLock;
+
+use fluent::concurrent::FluentBundle;
+use fluent::{FluentArgs, FluentResource};
+use unic_langid::LanguageIdentifier;
+
+/// 内置的语言及其资源，第一项为默认语言
+const LOCALES: [(&str, &str); 2] = [
+    ("zh-CN", include_str!("../locales/zh-CN.ftl")),
+    ("en-US", include_str!("../locales/en-US.ftl")),
+];
+
+struct Localizer {
+    primary: FluentBundle<FluentResource>,
+    /// 当前语言不是默认语言时使用的回退
+    fallback: Option<FluentBundle<FluentResource>>,
+}
+
+static LOCALIZER: OnceLock<Localizer> = OnceLock::new();
+
+/// 这里的 expect 只检查编译进程序的资源，资源有误时任何命令都会在启动时失败、由测试发现，
+/// 发布的程序不会触发；此时本地化尚未就绪，消息不经过 `tr!`
+fn bundle(locale: &str, source: &str) -> FluentBundle<FluentResource> {
+    let id: LanguageIdentifier = locale.parse().expect("内置语言标识无效");
+    let resource = FluentResource::try_new(source.to_string()).expect("内置的 Fluent 资源有语法错误");
+    let mut bundle = FluentBundle::new_concurrent(vec![id]);
+    // 终端输出不需要 Unicode 双向隔离字符
+    bundle.set_use_isolating(false);
+    bundle.add_resource(resource).expect("内置的 Fluent 资源有重复的消息ID");
+    bundle
+}
+
+/// 把 `zh_CN.UTF-8`、`en`、`en-GB` 这样的区域设置映射到内置语言，无法识别时返回 None
+fn match_locale(value: &str) -> Option<&'static str> {
+    let language = value.split(['_', '-', '.', '@']).next()?.to_ascii_lowercase();
+    LOCALES
+        .iter()
+        .map(|(locale, _)| *locale)
+        .find(|locale| locale.split('-').next().is_some_and(|l| l.eq_ignore_ascii_case(&language)))
+}
+
+/// 从环境变量检测语言
+fn detect_locale() -> Option<&'static str> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .and_then(|value| match_locale(&value))
+}
+
+impl Localizer {
+    fn new(locale: &'static str) -> Self {
+        let (default_locale, default_source) = LOCALES[0];
+        let source = LOCALES.iter().find(|(l, _)| *l == locale).map_or(default_source, |(_, s)| *s);
+        let fallback = (locale != default_locale).then(|| bundle(default_locale, default_source));
+        Localizer { primary: bundle(locale, source), fallback }
+    }
+}
+
+/// 选择语言；应在输出任何消息之前调用，`requested` 为 `--lang` 的值。
+/// 不支持的语言返回 Err，此时已回退到环境变量检测到的语言。
+pub fn init(requested: Option<&str>) -> Result<(), String> {
+    let matched = requested.map(match_locale);
+    let locale = matched.flatten().or_else(detect_locale).unwrap_or(LOCALES[0].0);
+    let _ = LOCALIZER.set(Localizer::new(locale));
+    match (requested, matched) {
+        (Some(requested), Some(None)) => Err(requested.to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// 内置语言列表，例如 "zh-CN, en-US"
+pub fn supported_locales() -> String {
+    LOCALES.iter().map(|(locale, _)| *locale).collect::<Vec<_>>().join(", ")
+}
+
+fn format(bundle: &FluentBundle<FluentResource>, id: &str, args: Option<&FluentArgs>) -> Option<String> {
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = Vec::new();
+    Some(bundle.format_pattern(pattern, args, &mut errors).into_owned())
+}
+
+/// 格式化一条消息，参数以字符串形式传入；通常通过 [`tr!`] 调用
+pub fn message(id: &str, args: &[(&str, String)]) -> String {
+    let localizer = LOCALIZER.get_or_init(|| Localizer::new(detect_locale().unwrap_or(LOCALES[0].0)));
+    let fluent_args = (!args.is_empty()).then(|| {
+        let mut fluent_args = FluentArgs::new();
+        for (key, value) in args {
+            fluent_args.set(*key, value.clone());
+        }
+        fluent_args
+    });
+    format(&localizer.primary, id, fluent_args.as_ref())
+        .or_else(|| format(localizer.fallback.as_ref()?, id, fluent_args.as_ref()))
+        .unwrap_or_else(|| id.to_string())
+}
+
+/// 按当前语言格式化消息：`tr!("消息ID")` 或 `tr!("消息ID", 参数 = 值, ...)`，
+/// 参数通过 `Display` 转为字符串，需要控制小数位数时先用 `format!` 格式化
+macro_rules! tr {
+    ($id:literal) => {
+        $crate::i18n::message($id, &[])
+    };
+    ($id:literal, $($key:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::message($id, &[$((stringify!($key), ($value).to_string())),+])
+    };
+}
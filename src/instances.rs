@@ -0,0 +1,351 @@
+//! 命名实例：每个实例在临时目录中有自己的PID文件 `enhancecpu-<名称>.pid`，
+//! 用 `--instance-name` 区分即可同时运行多个实例，`list` 子命令列出所有实例及其进程是否仍存在，
+//! `stop` 子命令终止实例并确认进程确实已退出
+
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::error::LoadError;
+#[cfg(unix)]
+use crate::signals;
+use crate::statefile;
+
+/// 未指定 `--instance-name` 时的实例名称
+pub const DEFAULT_NAME: &str = "default";
+
+const PID_FILE_PREFIX: &str = "enhancecpu-";
+const PID_FILE_SUFFIX: &str = ".pid";
+
+/// 解析实例名称：只允许字母、数字、`-`、`_` 和 `.`，名称会成为文件名的一部分
+pub fn parse_name(s: &str) -> Result<String, String> {
+    let valid = !s.is_empty()
+        && !s.starts_with('.')
+        && s.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid { Ok(s.to_string()) } else { Err(tr!("instance-name-invalid", name = s)) }
+}
+
+/// PID文件所在的目录
+pub fn pid_dir() -> PathBuf {
+    std::env::temp_dir()
+}
+
+/// 获取实例的PID文件路径
+pub fn get_pid_file(name: &str) -> PathBuf {
+    pid_dir().join(format!("{}{}{}", PID_FILE_PREFIX, name, PID_FILE_SUFFIX))
+}
+
+/// 后台运行时未指定 `--log-file` 的默认日志文件路径：临时目录中的 `enhancecpu-<名称>.log`
+pub fn get_log_file(name: &str) -> PathBuf {
+    pid_dir().join(format!("{}{}.log", PID_FILE_PREFIX, name))
+}
+
+/// 保存当前进程的PID
+pub fn save_pid(name: &str) -> std::io::Result<()> {
+    write_pid(name, std::process::id())
+}
+
+/// 把指定的PID写入实例的PID文件，Windows 后台模式下父进程用它记录子进程
+pub fn write_pid(name: &str, pid: u32) -> std::io::Result<()> {
+    File::create(get_pid_file(name))?.write_all(pid.to_string().as_bytes())
+}
+
+/// 读取PID文件，优先使用状态文件，状态文件不存在或版本不匹配时读取文本PID文件
+pub fn read_pid(name: &str) -> Result<Option<u32>, LoadError> {
+    read_pid_file(&get_pid_file(name))
+}
+
+fn read_pid_file(pid_file: &Path) -> Result<Option<u32>, LoadError> {
+    if let Some(instance) = statefile::InstanceState::read(&statefile::state_path(pid_file)) {
+        return Ok(Some(instance.pid));
+    }
+    if !pid_file.exists() {
+        return Ok(None);
+    }
+
+    let mut pid_str = String::new();
+    File::open(pid_file)?.read_to_string(&mut pid_str)?;
+
+    match pid_str.trim().parse::<u32>() {
+        Ok(pid) => Ok(Some(pid)),
+        Err(_) => Err(LoadError::PidFileCorrupted(pid_file.to_path_buf())),
+    }
+}
+
+/// 删除实例的PID文件和状态文件
+pub fn remove_pid_file(name: &str) -> std::io::Result<()> {
+    let pid_file = get_pid_file(name);
+    let state_file = statefile::state_path(&pid_file);
+    if state_file.exists() {
+        std::fs::remove_file(state_file)?;
+    }
+    if pid_file.exists() {
+        std::fs::remove_file(pid_file)?;
+    }
+    Ok(())
+}
+
+/// 进程是否仍存在；已退出但尚未被父进程回收的僵尸进程视为已停止
+#[cfg(unix)]
+pub fn is_alive(pid: u32) -> bool {
+    // kill(0, 0) 检查的是调用者的整个进程组，PID文件中的0不代表任何实例
+    let Ok(pid @ 1..) = i32::try_from(pid) else {
+        return false;
+    };
+    // SAFETY: 信号0只检查进程是否存在，不会投递信号
+    if unsafe { libc::kill(pid, 0) } != 0 {
+        return false;
+    }
+    #[cfg(target_os = "linux")]
+    if let Ok(stat) = std::fs::read_to_string(format!("/proc/{}/stat", pid))
+        && let Some((_, rest)) = stat.rsplit_once(')') {
+        return !rest.trim_start().starts_with('Z');
+    }
+    true
+}
+
+/// 进程是否仍存在：能打开进程且退出码为 `STILL_ACTIVE`
+#[cfg(windows)]
+pub fn is_alive(pid: u32) -> bool {
+    use std::ffi::c_void;
+
+    const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+    const STILL_ACTIVE: u32 = 259;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn OpenProcess(access: u32, inherit: i32, pid: u32) -> *mut c_void;
+        fn GetExitCodeProcess(process: *mut c_void, code: *mut u32) -> i32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+
+    // SAFETY: OpenProcess 失败时返回空句柄；成功时句柄在 CloseHandle 之前有效，code 指向局部变量
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process.is_null() {
+            return false;
+        }
+        let mut code = 0u32;
+        let ok = GetExitCodeProcess(process, &mut code) != 0;
+        CloseHandle(process);
+        ok && code == STILL_ACTIVE
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn is_alive(_pid: u32) -> bool {
+    true
+}
+
+/// `pid` 是否是可以终止的本程序实例：不是0、1或本进程，且进程名或可执行文件名与本进程相同；
+/// PID文件损坏或PID已被系统分配给其他程序时返回 false，避免误杀无关进程
+pub fn is_instance_process(pid: u32) -> bool {
+    use sysinfo::{Pid, PidExt, ProcessExt, System, SystemExt};
+
+    let own = std::process::id();
+    if pid <= 1 || pid == own {
+        return false;
+    }
+    let mut sys = System::new();
+    let (target, own) = (Pid::from_u32(pid), Pid::from_u32(own));
+    if !sys.refresh_process(target) || !sys.refresh_process(own) {
+        return false;
+    }
+    let (Some(target), Some(own)) = (sys.process(target), sys.process(own)) else {
+        return false;
+    };
+    target.name() == own.name() || (target.exe().file_name().is_some() && target.exe().file_name() == own.exe().file_name())
+}
+
+/// 向 `pid` 发送信号；拒绝0（会发给调用者的整个进程组）、1（init）和本进程，`stop` 与 `reset` 共用
+#[cfg(unix)]
+pub fn signal(pid: u32, signal: i32) -> std::io::Result<()> {
+    let target = i32::try_from(pid).ok().filter(|&target| target > 1 && pid != std::process::id());
+    let Some(target) = target else {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, tr!("signal-refused-pid", pid = pid)));
+    };
+    // SAFETY: 向指定进程发送信号没有内存安全方面的要求；target 已排除0、负数（进程组）和本进程
+    if unsafe { libc::kill(target, signal) } == 0 { Ok(()) } else { Err(std::io::Error::last_os_error()) }
+}
+
+/// 轮询进程是否存在的间隔
+#[cfg(not(windows))]
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// `stop --force` 发送 SIGKILL 后等待进程消失的时间
+const FORCE_KILL_WAIT: Duration = Duration::from_secs(2);
+
+/// 等待进程退出，最多 `timeout`；进程已退出时返回 true
+#[cfg(not(windows))]
+pub fn wait_for_exit(pid: u32, timeout: Duration) -> bool {
+    let start = std::time::Instant::now();
+    loop {
+        if !is_alive(pid) {
+            return true;
+        }
+        if start.elapsed() >= timeout {
+            return false;
+        }
+        std::thread::sleep(EXIT_POLL_INTERVAL);
+    }
+}
+
+/// 等待进程退出，最多 `timeout`；进程已退出或无法打开时返回 true
+#[cfg(windows)]
+pub fn wait_for_exit(pid: u32, timeout: Duration) -> bool {
+    use std::ffi::c_void;
+
+    const SYNCHRONIZE: u32 = 0x0010_0000;
+    const WAIT_OBJECT_0: u32 = 0;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn OpenProcess(access: u32, inherit: i32, pid: u32) -> *mut c_void;
+        fn WaitForSingleObject(handle: *mut c_void, millis: u32) -> u32;
+        fn CloseHandle(handle: *mut c_void) -> i32;
+    }
+
+    let millis = u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX - 1);
+    // SAFETY: OpenProcess 失败时返回空句柄；成功时句柄在 CloseHandle 之前有效
+    unsafe {
+        let process = OpenProcess(SYNCHRONIZE, 0, pid);
+        if process.is_null() {
+            return true;
+        }
+        let exited = WaitForSingleObject(process, millis) == WAIT_OBJECT_0;
+        CloseHandle(process);
+        exited
+    }
+}
+
+/// 向进程发送 `signal`（默认 SIGTERM），返回所用信号的名称；不会终止进程的信号返回 None
+#[cfg(unix)]
+fn request_exit(pid: u32, signal: Option<i32>) -> Result<Option<String>, LoadError> {
+    match signal {
+        None => info!("{}", tr!("stop-stopping", pid = pid)),
+        Some(signal) => info!("{}", tr!("stop-signal-sending", pid = pid, signal = signals::name(signal))),
+    }
+    let signal = signal.unwrap_or(libc::SIGTERM);
+    self::signal(pid, signal)?;
+    Ok(signals::terminates(signal).then(|| signals::name(signal)))
+}
+
+/// 不带 /F 调用 taskkill 请求进程退出，与 Unix 上的 SIGTERM 对应
+#[cfg(windows)]
+fn request_exit(pid: u32, signal: Option<i32>) -> Result<Option<String>, LoadError> {
+    if signal.is_some() {
+        return Err(LoadError::PlatformUnsupported(tr!("signal-unsupported")));
+    }
+    info!("{}", tr!("stop-stopping", pid = pid));
+    taskkill(pid, false);
+    Ok(Some("taskkill".to_string()))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn request_exit(_pid: u32, _signal: Option<i32>) -> Result<Option<String>, LoadError> {
+    Err(LoadError::PlatformUnsupported(tr!("signal-unsupported")))
+}
+
+/// 强制终止进程，返回所用方式的名称
+#[cfg(unix)]
+fn force_kill(pid: u32) -> String {
+    // 失败时由随后的等待超时报告
+    let _ = signal(pid, libc::SIGKILL);
+    signals::name(libc::SIGKILL)
+}
+
+#[cfg(windows)]
+fn force_kill(pid: u32) -> String {
+    taskkill(pid, true);
+    "taskkill /F".to_string()
+}
+
+#[cfg(not(any(unix, windows)))]
+fn force_kill(_pid: u32) -> String {
+    String::new()
+}
+
+#[cfg(windows)]
+fn taskkill(pid: u32, force: bool) {
+    use std::process::{Command, Stdio};
+    let pid = pid.to_string();
+    let mut args = vec!["/PID", pid.as_str()];
+    if force {
+        args.push("/F");
+    }
+    let _ = Command::new("taskkill").args(args).stdout(Stdio::null()).stderr(Stdio::null()).status();
+}
+
+/// `stop` 子命令：请求实例退出，等待最多 `timeout` 确认进程确实已退出；超时且指定了 `force`
+/// 时强制终止。进程退出后删除PID文件，超时仍未退出时保留PID文件并返回错误
+pub fn stop(name: &str, signal: Option<i32>, force: bool, timeout: Duration) -> Result<(), LoadError> {
+    let pid = read_pid(name)?.ok_or(LoadError::NoInstanceFound)?;
+    if !is_alive(pid) {
+        // 进程已退出但没有删除PID文件，或PID已被系统分配给其他进程
+        warn!("{}", tr!("stop-stale", pid = pid));
+        let _ = remove_pid_file(name);
+        return Ok(());
+    }
+    if !is_instance_process(pid) {
+        // PID已被系统分配给其他程序，或PID文件内容无效（如0、1），不能向它发送信号
+        warn!("{}", tr!("stop-foreign", pid = pid));
+        let _ = remove_pid_file(name);
+        return Ok(());
+    }
+    // 其他信号（如 SIGUSR1）用于控制仍在运行的进程，保留PID文件
+    let Some(mut how) = request_exit(pid, signal)? else {
+        return Ok(());
+    };
+    if !wait_for_exit(pid, timeout) {
+        if !force {
+            return Err(LoadError::StopTimedOut(pid, timeout));
+        }
+        warn!("{}", tr!("stop-force-kill", pid = pid, secs = timeout.as_secs_f64()));
+        how = force_kill(pid);
+        if !wait_for_exit(pid, FORCE_KILL_WAIT) {
+            return Err(LoadError::StopTimedOut(pid, timeout + FORCE_KILL_WAIT));
+        }
+    }
+    let _ = remove_pid_file(name);
+    info!("{}", tr!("stop-stopped", pid = pid, how = how));
+    Ok(())
+}
+
+/// 列出临时目录中所有 `enhancecpu-*.pid` 对应的实例：名称、PID以及进程是否仍存在
+pub fn list() {
+    let Ok(entries) = std::fs::read_dir(pid_dir()) else {
+        info!("{}", tr!("list-empty"));
+        return;
+    };
+    let mut instances: Vec<(String, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().into_string().ok()?;
+            let name = file_name.strip_prefix(PID_FILE_PREFIX)?.strip_suffix(PID_FILE_SUFFIX)?;
+            Some((name.to_string(), entry.path()))
+        })
+        .collect();
+    if instances.is_empty() {
+        info!("{}", tr!("list-empty"));
+        return;
+    }
+    instances.sort();
+
+    let width = instances.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0).max(4);
+    info!("{:<width$}  {:>8}  {}", tr!("list-column-name"), "PID", tr!("list-column-state"), width = width);
+    for (name, path) in instances {
+        let (pid, state, running) = match read_pid_file(&path) {
+            Ok(Some(pid)) if is_alive(pid) => (pid.to_string(), tr!("list-state-running"), Some(pid)),
+            Ok(Some(pid)) => (pid.to_string(), tr!("list-state-stale"), None),
+            _ => ("-".to_string(), tr!("list-state-corrupted"), None),
+        };
+        info!("{:<width$}  {:>8}  {}", name, pid, state, width = width);
+        // 运行中的实例有状态文件时，另起一行显示启动时间和负载参数
+        if let Some(instance) = running
+            .and_then(|pid| statefile::InstanceState::read(&statefile::state_path(&path)).filter(|s| s.pid == pid))
+        {
+            info!("{:<width$}  {}", "", instance.describe(), width = width);
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! 信号打断开销测试（仅Unix）：打断线程按给定速率向工作线程发送 SIGUSR2，测量每次打断损失的时间
+//!
+//! 工作线程把计算切成耗时约1µs的固定单元，逐个单元计时，并在单元前后读取本线程收到的信号数。
+//! 信号处理函数只把计数加一后立即返回，因此被打断的单元比同一轮中未被打断单元的最短耗时多出的
+//! 部分，就是一次信号投递（进入内核、切换到处理函数、sigreturn 返回）的代价，
+//! 也即基于信号的抢占在本机内核和CPU组合上的实际开销。
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::histogram::LatencyHistogram;
+use crate::selfmonitor;
+use crate::signals;
+use crate::state::LoadState;
+
+/// 输出统计的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 每个计时单元的迭代次数，约1µs，远大于两次读取时钟的开销
+const UNIT_ITERATIONS: u32 = 1000;
+
+/// 每轮执行的单元数，之后检查运行标志并按占空比休眠
+const BURST_UNITS: usize = 1000;
+
+/// 打断线程每次补发信号后休眠的时间
+const PACING_SLEEP: Duration = Duration::from_millis(1);
+
+thread_local! {
+    /// 本线程收到的 SIGUSR2 次数
+    static RECEIVED: Cell<u64> = const { Cell::new(0) };
+}
+
+/// 安装只计数的 SIGUSR2 处理函数；不安装时 SIGUSR2 的默认动作是结束进程
+fn install_handler() -> std::io::Result<()> {
+    // SAFETY: 常量初始化且无析构的线程局部变量不会触发惰性初始化或分配，可以在信号处理函数中访问
+    unsafe { signals::register(libc::SIGUSR2, || RECEIVED.with(|received| received.set(received.get() + 1))) }
+}
+
+/// 打断开销的分布（纳秒）
+#[derive(Default)]
+struct Overheads {
+    interval: LatencyHistogram,
+    total: LatencyHistogram,
+}
+
+/// 工作线程登记表、打断速率和统计
+pub struct InterruptionTest {
+    /// 每秒发送的信号总数，轮流发给各工作线程
+    rate: u64,
+    /// 正在运行的工作线程，按线程序号索引；线程退出前注销，打断线程持锁发送，不会打断已退出的线程
+    threads: Mutex<Vec<Option<libc::pthread_t>>>,
+    sent: AtomicU64,
+    overheads: Mutex<Overheads>,
+}
+
+impl InterruptionTest {
+    pub fn new(rate: u64, workers: usize) -> Self {
+        InterruptionTest {
+            rate,
+            threads: Mutex::new(vec![None; workers]),
+            sent: AtomicU64::new(0),
+            overheads: Mutex::default(),
+        }
+    }
+
+    fn set_thread(&self, index: usize, thread: Option<libc::pthread_t>) {
+        if let Some(slot) = self.threads.lock().unwrap_or_else(|e| e.into_inner()).get_mut(index) {
+            *slot = thread;
+        }
+    }
+}
+
+/// 工作线程返回或 panic 时注销自己
+struct Registration<'a> {
+    shared: &'a InterruptionTest,
+    index: usize,
+}
+
+impl Drop for Registration<'_> {
+    fn drop(&mut self) {
+        self.shared.set_thread(self.index, None);
+    }
+}
+
+/// 一个计时单元的计算：依赖链上的乘加，编译器无法合并或向量化
+fn work_unit(mut x: u64) -> u64 {
+    for _ in 0..UNIT_ITERATIONS {
+        x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+    }
+    x
+}
+
+/// 工作线程主循环，按目标使用率在计算和休眠之间切换，返回完成的轮数
+pub fn interruption_task(
+    state: &LoadState,
+    index: usize,
+    fixed_percent: Option<u8>,
+    limit: Option<u64>,
+    shared: &InterruptionTest,
+) -> u64 {
+    let mut rng = crate::seed::thread_rng(index);
+    // SAFETY: pthread_self 总是成功，返回当前线程的句柄
+    shared.set_thread(index, Some(unsafe { libc::pthread_self() }));
+    let _registration = Registration { shared, index };
+
+    let mut x = index as u64;
+    // 最近一轮中未被打断单元的最短耗时，即不受打扰时一个单元的耗时
+    let mut baseline: Option<u64> = None;
+    let mut interrupted = Vec::new();
+    let mut done = 0u64;
+    while state.is_running() {
+        if limit.is_some_and(|limit| done >= limit) {
+            break;
+        }
+        state.wait_until_active(index);
+        let percent = state.thread_percent(index, fixed_percent);
+        if percent == 0 {
+            thread::sleep(crate::spin::IDLE_POLL_INTERVAL);
+            continue;
+        }
+        let percent = state.with_noise(percent, &mut rng);
+
+        let burst_start = Instant::now();
+        let mut fastest = u64::MAX;
+        for _ in 0..BURST_UNITS {
+            let before = RECEIVED.with(Cell::get);
+            let start = Instant::now();
+            x = std::hint::black_box(work_unit(x));
+            let nanos = start.elapsed().as_nanos() as u64;
+            let signals = RECEIVED.with(Cell::get) - before;
+            if signals == 0 {
+                fastest = fastest.min(nanos);
+            } else {
+                interrupted.push((nanos, signals));
+            }
+        }
+        let busy = burst_start.elapsed();
+        if fastest != u64::MAX {
+            baseline = Some(fastest);
+        }
+        // 一个单元内收到多个信号时平均分摊
+        if let Some(baseline) = baseline
+            && !interrupted.is_empty()
+        {
+            let mut overheads = shared.overheads.lock().unwrap_or_else(|e| e.into_inner());
+            for (nanos, signals) in interrupted.drain(..) {
+                let overhead = nanos.saturating_sub(baseline) / signals;
+                for _ in 0..signals {
+                    overheads.interval.record(overhead);
+                    overheads.total.record(overhead);
+                }
+            }
+        }
+        interrupted.clear();
+        done += 1;
+
+        selfmonitor::duty_cycle_sleep(state.thread_stats(index), percent, 1, busy);
+    }
+    done
+}
+
+/// 轮流向下一个已登记的工作线程发送 SIGUSR2，没有已登记的线程时返回 false
+fn interrupt_next(shared: &InterruptionTest, next: &mut usize) -> bool {
+    let threads = shared.threads.lock().unwrap_or_else(|e| e.into_inner());
+    for _ in 0..threads.len() {
+        let slot = *next % threads.len();
+        *next = slot + 1;
+        if let Some(thread) = threads[slot] {
+            // SAFETY: 持锁期间登记的线程不会注销，句柄一定有效
+            unsafe { libc::pthread_kill(thread, libc::SIGUSR2) };
+            return true;
+        }
+    }
+    false
+}
+
+fn describe(histogram: &LatencyHistogram, sent: u64) -> String {
+    tr!(
+        "interrupt-stats",
+        sent = sent,
+        measured = histogram.count(),
+        mean = format!("{:.0}", histogram.mean()),
+        p50 = histogram.percentile(50.0),
+        p99 = histogram.percentile(99.0),
+        max = histogram.max()
+    )
+}
+
+/// 安装信号处理函数，启动打断线程和统计线程
+pub fn spawn_interrupter(shared: Arc<InterruptionTest>, state: Arc<LoadState>) -> std::io::Result<Vec<JoinHandle<()>>> {
+    install_handler()?;
+    info!("{}", tr!("interrupt-started", rate = shared.rate));
+
+    let sender = {
+        let shared = shared.clone();
+        let state = state.clone();
+        thread::spawn(move || {
+            let start = Instant::now();
+            let mut next = 0usize;
+            while state.is_running() {
+                // 按从开始到现在应发送的数量补发，速率不受单次休眠精度影响
+                let due = (start.elapsed().as_secs_f64() * shared.rate as f64) as u64;
+                while shared.sent.load(Ordering::Relaxed) < due && interrupt_next(&shared, &mut next) {
+                    shared.sent.fetch_add(1, Ordering::Relaxed);
+                }
+                thread::sleep(PACING_SLEEP);
+            }
+        })
+    };
+
+    let reporter = thread::spawn(move || {
+        let mut last_report = Instant::now();
+        let mut last_sent = 0u64;
+        while state.is_running() {
+            thread::sleep(Duration::from_millis(100));
+            if last_report.elapsed() < REPORT_INTERVAL {
+                continue;
+            }
+            last_report = Instant::now();
+            let sent = shared.sent.load(Ordering::Relaxed);
+            let interval = std::mem::take(&mut shared.overheads.lock().unwrap_or_else(|e| e.into_inner()).interval);
+            info!("{}", describe(&interval, sent - last_sent));
+            last_sent = sent;
+        }
+        let total = &shared.overheads.lock().unwrap_or_else(|e| e.into_inner()).total;
+        info!("{}", tr!("interrupt-summary", stats = describe(total, shared.sent.load(Ordering::Relaxed))));
+    });
+    Ok(vec![sender, reporter])
+}
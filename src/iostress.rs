@@ -0,0 +1,240 @@
+//! `io-stress` 子命令：每个线程在 `-p` 目录下创建自己的临时文件，按所选模式反复读写，
+//! 与CPU和内存负载同时制造存储压力
+//!
+//! 读写用 `std::fs::File` 加 `seek` 定位，每个线程用一个 `AtomicU64` 累计完成的操作数，
+//! 状态线程据此计算 IOPS 和 MB/s。临时文件在线程退出时删除，收到 Ctrl+C 时由中断处理函数删除。
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use bytesize::ByteSize;
+use clap::ValueEnum;
+use rand::{Rng, RngCore};
+
+use crate::json::Json;
+use crate::seed;
+use crate::state::LoadState;
+
+/// 预先写入文件内容时每次写入的大小
+const WRITE_CHUNK: usize = 1 << 20;
+
+/// 读写模式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum IoPattern {
+    /// 从头到尾顺序写，到达文件末尾后从头开始
+    SeqWrite,
+    /// 从头到尾顺序读
+    SeqRead,
+    /// 随机位置写
+    RandWrite,
+    /// 随机位置读
+    RandRead,
+    /// 随机位置读写各一半（默认）
+    #[default]
+    Mixed,
+}
+
+impl IoPattern {
+    /// 命令行中的名称，例如 "rand-read"
+    pub fn name(self) -> String {
+        self.to_possible_value().map_or_else(String::new, |v| v.get_name().to_string())
+    }
+
+    /// 是否需要先写满文件，使读取落在真实的数据上而不是文件空洞
+    fn reads(self) -> bool {
+        matches!(self, IoPattern::SeqRead | IoPattern::RandRead | IoPattern::Mixed)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct IoStressSettings {
+    /// 临时文件所在的目录
+    pub dir: PathBuf,
+    pub threads: usize,
+    pub block_size: usize,
+    pub pattern: IoPattern,
+    /// 每个线程的文件大小
+    pub file_size: u64,
+}
+
+/// 每个I/O线程完成的操作数
+pub struct IoCounters {
+    ops: Vec<AtomicU64>,
+    block_size: usize,
+}
+
+impl IoCounters {
+    pub fn new(settings: &IoStressSettings) -> Self {
+        IoCounters { ops: (0..settings.threads).map(|_| AtomicU64::new(0)).collect(), block_size: settings.block_size }
+    }
+
+    fn total(&self) -> u64 {
+        self.ops.iter().map(|ops| ops.load(Ordering::Relaxed)).sum()
+    }
+}
+
+/// 一个采样间隔内的I/O速率
+#[derive(Clone, Copy, Debug)]
+pub struct IoRates {
+    pub iops: f64,
+    pub mb_per_sec: f64,
+}
+
+impl IoRates {
+    fn between(ops: u64, block_size: usize, elapsed: Duration) -> Self {
+        let iops = ops as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+        IoRates { iops, mb_per_sec: iops * block_size as f64 / 1e6 }
+    }
+
+    pub fn describe(&self) -> String {
+        tr!("output-io-stress", iops = format!("{:.0}", self.iops), mb = format!("{:.1}", self.mb_per_sec))
+    }
+
+    pub fn to_json(self) -> Json {
+        Json::object([("iops", self.iops.into()), ("mb_per_sec", self.mb_per_sec.into())])
+    }
+}
+
+/// 状态线程使用：每次采样返回距上次采样的速率
+pub struct IoMonitor {
+    counters: Arc<IoCounters>,
+    last_ops: u64,
+    last_at: Instant,
+}
+
+impl IoMonitor {
+    pub fn new(counters: Arc<IoCounters>) -> Self {
+        IoMonitor { last_ops: counters.total(), counters, last_at: Instant::now() }
+    }
+
+    pub fn sample(&mut self) -> IoRates {
+        let (ops, now) = (self.counters.total(), Instant::now());
+        let rates = IoRates::between(ops - self.last_ops, self.counters.block_size, now - self.last_at);
+        (self.last_ops, self.last_at) = (ops, now);
+        rates
+    }
+}
+
+/// 在 `dir` 下创建临时文件；需要读取时写满随机内容，否则只设置长度
+fn create_file(settings: &IoStressSettings, index: usize) -> io::Result<tempfile::NamedTempFile> {
+    let mut file = tempfile::Builder::new().prefix("enhancecpu-io").tempfile_in(&settings.dir)?;
+    if settings.pattern.reads() {
+        let mut rng = seed::thread_rng(seed::IO_STRESS_STREAMS + index);
+        let mut chunk = vec![0u8; WRITE_CHUNK];
+        let mut remaining = settings.file_size;
+        while remaining > 0 {
+            let len = remaining.min(WRITE_CHUNK as u64) as usize;
+            rng.fill_bytes(&mut chunk[..len]);
+            file.write_all(&chunk[..len])?;
+            remaining -= len as u64;
+        }
+        file.as_file().sync_all()?;
+    } else {
+        file.as_file().set_len(settings.file_size)?;
+    }
+    Ok(file)
+}
+
+/// 按 `pattern` 执行一次读写；`position` 为顺序模式的下一个块号
+fn run_op(
+    file: &mut File,
+    pattern: IoPattern,
+    buf: &mut [u8],
+    position: &mut u64,
+    blocks: u64,
+    rng: &mut impl Rng,
+) -> io::Result<()> {
+    let block_size = buf.len() as u64;
+    let (block, write) = match pattern {
+        IoPattern::SeqWrite | IoPattern::SeqRead => {
+            let block = *position;
+            *position = (block + 1) % blocks;
+            (block, pattern == IoPattern::SeqWrite)
+        }
+        IoPattern::RandWrite => (rng.gen_range(0..blocks), true),
+        IoPattern::RandRead => (rng.gen_range(0..blocks), false),
+        IoPattern::Mixed => (rng.gen_range(0..blocks), rng.gen_bool(0.5)),
+    };
+    // 顺序模式只在回到文件开头时定位，其余时候文件位置已在下一块
+    if block == 0 || !matches!(pattern, IoPattern::SeqWrite | IoPattern::SeqRead) {
+        file.seek(SeekFrom::Start(block * block_size))?;
+    }
+    if write {
+        rng.fill_bytes(buf);
+        file.write_all(buf)
+    } else {
+        file.read_exact(buf)
+    }
+}
+
+/// 启动 `settings.threads` 个I/O线程和一个在结束时输出汇总的线程，新建的临时文件登记到 `LoadState::temp_files`
+pub fn spawn_io_stress(
+    settings: IoStressSettings,
+    counters: Arc<IoCounters>,
+    state: Arc<LoadState>,
+) -> Vec<JoinHandle<()>> {
+    // 调用前已校验块大小不超过文件大小
+    let blocks = settings.file_size / settings.block_size as u64;
+    info!(
+        "{}",
+        tr!(
+            "io-stress-started",
+            path = settings.dir.display(),
+            threads = settings.threads,
+            pattern = settings.pattern.name(),
+            block = ByteSize::b(settings.block_size as u64),
+            size = ByteSize::b(settings.file_size)
+        )
+    );
+
+    let mut handles: Vec<JoinHandle<()>> = (0..settings.threads)
+        .map(|i| {
+            let settings = settings.clone();
+            let counters = counters.clone();
+            let state = state.clone();
+            thread::spawn(move || {
+                let temp = match create_file(&settings, i) {
+                    Ok(temp) => temp,
+                    Err(e) => {
+                        warn!("{}", tr!("io-stress-create-failed", path = settings.dir.display(), error = e));
+                        return;
+                    }
+                };
+                state.register_temp_file(temp.path());
+                let mut file = match temp.reopen() {
+                    Ok(file) => file,
+                    Err(e) => {
+                        warn!("{}", tr!("io-stress-create-failed", path = temp.path().display(), error = e));
+                        return;
+                    }
+                };
+                let mut rng = seed::thread_rng(seed::IO_STRESS_STREAMS + i);
+                let mut buf = vec![0u8; settings.block_size];
+                let mut position = 0;
+                while state.is_running() {
+                    if let Err(e) = run_op(&mut file, settings.pattern, &mut buf, &mut position, blocks, &mut rng) {
+                        warn!("{}", tr!("io-stress-failed", error = e));
+                        return;
+                    }
+                    counters.ops[i].fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        })
+        .collect();
+
+    handles.push(thread::spawn(move || {
+        let start = Instant::now();
+        while state.is_running() {
+            thread::sleep(Duration::from_millis(100));
+        }
+        let total = counters.total();
+        let rates = IoRates::between(total, counters.block_size, start.elapsed());
+        info!("{}", tr!("io-stress-summary", ops = total, stats = rates.describe()));
+    }));
+    handles
+}
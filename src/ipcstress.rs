@@ -0,0 +1,230 @@
+//! 进程间通信压力：N 对生产者/消费者线程通过管道、Unix 域套接字或共享内存传递4096字节的消息
+//!
+//! 共享内存通道是一个内存映射文件，文件头为自旋锁和读写位置，其后是固定数量的消息槽。
+//! 每2秒输出所有通道合计的消息速率和吞吐量。
+
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::ptr::NonNull;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::state::LoadState;
+use crate::workload::IpcType;
+
+/// 消息大小
+const MESSAGE_SIZE: usize = 4096;
+
+/// 共享内存环形缓冲区的消息槽数
+const RING_SLOTS: usize = 64;
+
+/// 输出统计的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 一对通道两端的线程体：生产者和消费者
+type Endpoints = (Box<dyn FnOnce() + Send>, Box<dyn FnOnce() + Send>);
+
+/// 启动 `pairs` 对生产者/消费者线程和统计线程
+pub fn spawn_ipc_stress(kind: IpcType, pairs: usize, state: Arc<LoadState>) -> std::io::Result<Vec<JoinHandle<()>>> {
+    let received = Arc::new(AtomicU64::new(0));
+    let mut handles = Vec::with_capacity(pairs * 2 + 1);
+    for _ in 0..pairs {
+        let (producer, consumer): Endpoints = match kind {
+            IpcType::Pipe => {
+                let (reader, writer) = std::io::pipe()?;
+                stream_pair(writer, reader, received.clone(), state.clone())
+            }
+            IpcType::UnixSocket => {
+                let (writer, reader) = UnixStream::pair()?;
+                stream_pair(writer, reader, received.clone(), state.clone())
+            }
+            IpcType::SharedMem => {
+                let ring = Arc::new(SharedRing::new()?);
+                let (ring2, state2, received) = (ring.clone(), state.clone(), received.clone());
+                let state = state.clone();
+                (
+                    Box::new(move || ring.produce(&state)),
+                    Box::new(move || ring2.consume(&received, &state2)),
+                )
+            }
+        };
+        handles.push(thread::spawn(producer));
+        handles.push(thread::spawn(consumer));
+    }
+    info!("{}", tr!("ipc-started", kind = kind.name(), pairs = pairs, size = MESSAGE_SIZE));
+    handles.push(spawn_reporter(kind, received, state));
+    Ok(handles)
+}
+
+/// 基于字节流的通道：生产者停止后关闭写端，消费者读到EOF后退出
+fn stream_pair<W, R>(
+    mut writer: W,
+    mut reader: R,
+    received: Arc<AtomicU64>,
+    state: Arc<LoadState>,
+) -> Endpoints
+where
+    W: Write + Send + 'static,
+    R: Read + Send + 'static,
+{
+    let producer = move || {
+        let message = [0x5Au8; MESSAGE_SIZE];
+        while state.is_running() {
+            if writer.write_all(&message).is_err() {
+                break;
+            }
+        }
+    };
+    let consumer = move || {
+        let mut message = [0u8; MESSAGE_SIZE];
+        while reader.read_exact(&mut message).is_ok() {
+            received.fetch_add(1, Ordering::Relaxed);
+        }
+    };
+    (Box::new(producer), Box::new(consumer))
+}
+
+/// 共享内存文件头；读写位置只在持有自旋锁时访问
+#[repr(C)]
+struct RingHeader {
+    lock: AtomicBool,
+    head: AtomicU64,
+    tail: AtomicU64,
+}
+
+/// 映射到内存的环形缓冲区
+struct SharedRing {
+    base: NonNull<u8>,
+    len: usize,
+}
+
+// SAFETY: 映射在 SharedRing 存活期间有效，消息槽只在持有文件头中的自旋锁时读写
+unsafe impl Send for SharedRing {}
+unsafe impl Sync for SharedRing {}
+
+impl SharedRing {
+    fn new() -> std::io::Result<Self> {
+        use std::os::fd::AsRawFd;
+
+        let len = size_of::<RingHeader>().next_multiple_of(64) + RING_SLOTS * MESSAGE_SIZE;
+        // 映射建立后文件可以关闭，映射本身保持有效
+        let file = tempfile::tempfile()?;
+        file.set_len(len as u64)?;
+        // SAFETY: 以读写、共享方式映射整个文件；文件由 set_len 填零，文件头全零即为未加锁的空缓冲区
+        let ptr = unsafe {
+            libc::mmap(
+                std::ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(std::io::Error::last_os_error());
+        }
+        let base = NonNull::new(ptr.cast()).ok_or_else(std::io::Error::last_os_error)?;
+        Ok(SharedRing { base, len })
+    }
+
+    fn header(&self) -> &RingHeader {
+        // SAFETY: 映射按页对齐，开头足够容纳 RingHeader
+        unsafe { &*self.base.as_ptr().cast::<RingHeader>() }
+    }
+
+    fn slot(&self, index: u64) -> *mut u8 {
+        let offset = size_of::<RingHeader>().next_multiple_of(64) + (index as usize % RING_SLOTS) * MESSAGE_SIZE;
+        // SAFETY: offset + MESSAGE_SIZE 不超过映射长度
+        unsafe { self.base.as_ptr().add(offset) }
+    }
+
+    /// 持有自旋锁执行 `f`
+    fn locked<T>(&self, f: impl FnOnce(&RingHeader) -> T) -> T {
+        let header = self.header();
+        while header.lock.compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed).is_err() {
+            std::hint::spin_loop();
+        }
+        let result = f(header);
+        header.lock.store(false, Ordering::Release);
+        result
+    }
+
+    fn produce(&self, state: &LoadState) {
+        let message = [0x5Au8; MESSAGE_SIZE];
+        while state.is_running() {
+            let written = self.locked(|header| {
+                let head = header.head.load(Ordering::Relaxed);
+                if head - header.tail.load(Ordering::Relaxed) >= RING_SLOTS as u64 {
+                    return false;
+                }
+                // SAFETY: 持有锁，且该槽位已被消费者读完
+                unsafe { std::ptr::copy_nonoverlapping(message.as_ptr(), self.slot(head), MESSAGE_SIZE) };
+                header.head.store(head + 1, Ordering::Relaxed);
+                true
+            });
+            if !written {
+                thread::yield_now();
+            }
+        }
+    }
+
+    fn consume(&self, received: &AtomicU64, state: &LoadState) {
+        let mut message = [0u8; MESSAGE_SIZE];
+        while state.is_running() {
+            let read = self.locked(|header| {
+                let tail = header.tail.load(Ordering::Relaxed);
+                if tail == header.head.load(Ordering::Relaxed) {
+                    return false;
+                }
+                // SAFETY: 持有锁，且该槽位已被生产者写完
+                unsafe { std::ptr::copy_nonoverlapping(self.slot(tail), message.as_mut_ptr(), MESSAGE_SIZE) };
+                header.tail.store(tail + 1, Ordering::Relaxed);
+                true
+            });
+            if read {
+                received.fetch_add(1, Ordering::Relaxed);
+            } else {
+                thread::yield_now();
+            }
+        }
+    }
+}
+
+impl Drop for SharedRing {
+    fn drop(&mut self) {
+        // SAFETY: base 和 len 来自 new 中成功的 mmap
+        unsafe {
+            libc::munmap(self.base.as_ptr().cast(), self.len);
+        }
+    }
+}
+
+fn spawn_reporter(kind: IpcType, received: Arc<AtomicU64>, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut last = 0u64;
+        let mut last_report = Instant::now();
+        while state.is_running() {
+            thread::sleep(Duration::from_millis(100));
+            let elapsed = last_report.elapsed();
+            if elapsed < REPORT_INTERVAL {
+                continue;
+            }
+            let total = received.load(Ordering::Relaxed);
+            let messages = (total - last) as f64 / elapsed.as_secs_f64();
+            info!(
+                "{}",
+                tr!(
+                    "ipc-stats",
+                    kind = kind.name(),
+                    messages = format!("{:.0}", messages),
+                    rate = format!("{:.1}", messages * MESSAGE_SIZE as f64 / (1024.0 * 1024.0))
+                )
+            );
+            last = total;
+            last_report = Instant::now();
+        }
+    })
+}
@@ -0,0 +1,126 @@
+//! 把中断绑定到不参与负载的CPU（仅Linux，需要root权限），负载结束后恢复
+//!
+//! 停止 `irqbalance` 守护进程，再把 `/proc/irq/*/smp_affinity` 全部写成第一个不参与负载的CPU，
+//! 避免中断处理的开销落到被测核心上。结束时恢复每个中断原来的亲和性并重新启动 `irqbalance`。
+//! 多队列网卡的中断集中到一个CPU后网络吞吐可能明显下降，因此需要 `--force` 确认。
+
+/// 已修改的中断亲和性及 irqbalance 状态，drop 时恢复
+pub struct IrqGuard {
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    original: Vec<(u32, String)>,
+    #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+    irqbalance_stopped: bool,
+}
+
+/// `smp_affinity` 格式的CPU掩码：十六进制，每32个CPU一组，以逗号分隔，高位组在前
+#[cfg(target_os = "linux")]
+fn hex_cpumask(cpu: usize) -> String {
+    let groups: Vec<String> = (0..=cpu / 32)
+        .rev()
+        .map(|group| format!("{:08x}", if group == cpu / 32 { 1u32 << (cpu % 32) } else { 0 }))
+        .collect();
+    groups.join(",")
+}
+
+#[cfg(target_os = "linux")]
+fn affinity_path(irq: u32) -> String {
+    format!("/proc/irq/{}/smp_affinity", irq)
+}
+
+/// `/proc/irq` 下所有中断号
+#[cfg(target_os = "linux")]
+fn list_irqs() -> std::io::Result<Vec<u32>> {
+    let mut irqs: Vec<u32> = std::fs::read_dir("/proc/irq")?
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+        .collect();
+    irqs.sort_unstable();
+    Ok(irqs)
+}
+
+/// 运行 `systemctl <action> irqbalance`，返回是否成功
+#[cfg(target_os = "linux")]
+fn systemctl(action: &str) -> bool {
+    std::process::Command::new("systemctl")
+        .args([action, "--quiet", "irqbalance"])
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// 停止 irqbalance，并把所有中断绑定到不在 `load_cpus` 中的第一个CPU；
+/// 无法修改的中断（如定时器、部分 MSI 中断）只计数并跳过
+#[cfg(target_os = "linux")]
+pub fn apply(load_cpus: &[usize]) -> IrqGuard {
+    let mut guard = IrqGuard { original: Vec::new(), irqbalance_stopped: false };
+    // SAFETY: geteuid 没有副作用
+    if unsafe { libc::geteuid() } != 0 {
+        warn!("{}", tr!("irq-needs-root"));
+        return guard;
+    }
+    let Some(target) = (0..num_cpus::get()).find(|cpu| !load_cpus.contains(cpu)) else {
+        warn!("{}", tr!("irq-no-free-cpu"));
+        return guard;
+    };
+    let irqs = match list_irqs() {
+        Ok(irqs) => irqs,
+        Err(e) => {
+            warn!("{}", tr!("irq-read-failed", error = e));
+            return guard;
+        }
+    };
+    warn!("{}", tr!("irq-nic-warning"));
+    // 先停止 irqbalance，否则它会很快把中断重新分散开
+    if systemctl("is-active") {
+        if systemctl("stop") {
+            guard.irqbalance_stopped = true;
+            info!("{}", tr!("irq-irqbalance-stopped"));
+        } else {
+            warn!("{}", tr!("irq-irqbalance-stop-failed"));
+        }
+    }
+
+    let mask = hex_cpumask(target);
+    let mut skipped = 0;
+    for irq in irqs {
+        let Ok(current) = std::fs::read_to_string(affinity_path(irq)) else {
+            continue;
+        };
+        match std::fs::write(affinity_path(irq), &mask) {
+            Ok(()) => guard.original.push((irq, current.trim().to_string())),
+            Err(_) => skipped += 1,
+        }
+    }
+    info!("{}", tr!("irq-pinned", count = guard.original.len(), cpu = target, mask = mask, skipped = skipped));
+    guard
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn apply(_load_cpus: &[usize]) -> IrqGuard {
+    warn!("{}", tr!("irq-unsupported"));
+    IrqGuard { original: Vec::new(), irqbalance_stopped: false }
+}
+
+impl Drop for IrqGuard {
+    fn drop(&mut self) {
+        #[cfg(target_os = "linux")]
+        {
+            let mut failed = 0;
+            for (irq, mask) in &self.original {
+                if std::fs::write(affinity_path(*irq), mask).is_err() {
+                    failed += 1;
+                }
+            }
+            if !self.original.is_empty() {
+                info!("{}", tr!("irq-restored", count = self.original.len() - failed, failed = failed));
+            }
+            if self.irqbalance_stopped {
+                if systemctl("start") {
+                    info!("{}", tr!("irq-irqbalance-started"));
+                } else {
+                    warn!("{}", tr!("irq-irqbalance-start-failed"));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+//! 系统负载工具的库：以代码方式启动负载的 [`LoadConfig::start`]
+//!
+//! [`LoadConfig::start`] 与命令行的 `start` 走同一套启动流程，[`LoadConfig`] 设置核心数、CPU使用率、
+//! 内存、持续时间和负载类型，其余选项取命令行的默认值。`cli` 模块只供可执行文件调用，不属于库的接口。
+//!
+//! 在测试中制造负载：
+//!
+//! ```no_run
+//! use enhance_cpu_memory::LoadConfig;
+//!
+//! let config = LoadConfig::new().cores(2)?.cpu_percent(50.0)?.build()?;
+//! let handle = config.start()?;
+//! // ……运行被测代码……
+//! let stats = handle.stop();
+//! println!("{:.1} CPU秒, 峰值内存 {} 字节", stats.total_cpu_seconds, stats.peak_memory_bytes);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+#[macro_use]
+mod i18n;
+#[macro_use]
+mod logger;
+mod allocchurn;
+mod atomicstress;
+mod autocores;
+mod balloon;
+mod bandwidth;
+mod burst;
+mod cachealternate;
+mod calibration;
+/// 命令行程序的入口，只供可执行文件调用
+#[doc(hidden)]
+pub mod cli;
+mod cachethrash;
+mod compaction;
+mod compress;
+mod config;
+#[cfg(unix)]
+mod control;
+mod cpufreq;
+mod diskio;
+mod diskstats;
+mod engine;
+mod error;
+mod failalloc;
+mod fdstress;
+#[cfg(target_os = "linux")]
+mod freqtrace;
+mod growth;
+mod handle;
+mod histogram;
+mod hugepages;
+mod hooks;
+mod instances;
+#[cfg(unix)]
+mod interruption;
+mod irqaffinity;
+mod iostress;
+#[cfg(unix)]
+mod ipcstress;
+mod json;
+mod memory;
+mod memorypressure;
+mod memstress;
+mod metrics;
+mod mlock;
+mod netstats;
+#[cfg(unix)]
+mod notify;
+mod numa;
+mod numalatency;
+mod objects;
+mod output;
+mod pagecache;
+mod pattern;
+mod phases;
+mod profile;
+mod ramp;
+mod ratelimit;
+mod rayonpool;
+mod realtimelog;
+mod perf;
+mod reset;
+mod residency;
+mod sched;
+mod schedule;
+mod seed;
+mod selfmonitor;
+mod sigadjust;
+mod signals;
+#[cfg(unix)]
+mod socketstress;
+mod spawnbench;
+mod spin;
+mod state;
+mod steal;
+mod statefile;
+mod status;
+mod stepper;
+mod swap;
+mod summary;
+mod sync;
+mod syscalls;
+mod targetfile;
+mod thermal;
+mod timeline;
+mod timeutil;
+mod topology;
+mod udpflood;
+mod workload;
+#[cfg(target_os = "linux")]
+mod yieldto;
+
+pub use config::{ConfigError, LoadConfig, LoadConfigBuilder};
+pub use error::LoadError;
+/// 供可执行文件注册为全局分配器，见 `--alloc-fail-rate`
+#[doc(hidden)]
+pub use failalloc::FailingAllocator;
+pub use handle::{LoadHandle, LoadStats};
+pub use workload::Workload;
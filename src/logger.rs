@@ -0,0 +1,306 @@
+//! 统一的日志输出：默认写到标准输出，`--quiet` 时静默，`--syslog` 时转发到系统日志（Windows为事件日志），
+//! `--log-file` 时同时追加到文件，并可按小时或按天轮转
+
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use clap::ValueEnum;
+
+use crate::timeutil;
+
+/// 是否抑制标准输出/标准错误
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// `--syslog` 在 Unix 上连接的系统日志
+#[cfg(unix)]
+static SYSLOG: Mutex<Option<syslog::Logger<syslog::LoggerBackend, syslog::Formatter3164>>> = Mutex::new(None);
+
+/// `--syslog` 在 Windows 上是否已打开事件日志；打开后由 `log` 的全局 logger 写入
+#[cfg(windows)]
+static EVENT_LOG: AtomicBool = AtomicBool::new(false);
+
+/// `--log-file` 打开的日志文件
+static LOG_FILE: Mutex<Option<LogFile>> = Mutex::new(None);
+
+/// 日志级别，对应 syslog 的 `LOG_INFO`、`LOG_NOTICE`、`LOG_WARNING`、`LOG_ERR`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    /// 运行中对外部操作的回应，输出到标准错误，标准输出被重定向时仍能看到
+    Notice,
+    Warning,
+    Error,
+}
+
+/// 设置静默模式
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+/// 启用系统日志输出，同时进入静默模式；连接不上系统日志时告警并继续输出到控制台
+#[cfg(unix)]
+pub fn enable_syslog() {
+    let formatter = syslog::Formatter3164 {
+        facility: syslog::Facility::LOG_DAEMON,
+        hostname: None,
+        process: "enhance-cpu-memory".to_string(),
+        pid: std::process::id(),
+    };
+    match syslog::unix(formatter) {
+        Ok(logger) => {
+            *SYSLOG.lock().unwrap_or_else(|e| e.into_inner()) = Some(logger);
+            set_quiet(true);
+        }
+        Err(e) => log(Level::Warning, format_args!("{}", tr!("syslog-open-failed", error = e))),
+    }
+}
+
+/// Windows 上写入“应用程序”事件日志，源名称为 `enhance-cpu-memory`，同时进入静默模式
+///
+/// 登记事件源需要管理员权限，这里不修改注册表；源未登记时事件查看器仍显示消息内容，
+/// 只是附带找不到事件描述的提示。
+#[cfg(windows)]
+pub fn enable_syslog() {
+    if let Err(e) = eventlog::init("enhance-cpu-memory", log::Level::Info) {
+        log(Level::Warning, format_args!("{}", tr!("eventlog-open-failed", error = e)));
+        return;
+    }
+    EVENT_LOG.store(true, Ordering::Relaxed);
+    set_quiet(true);
+}
+
+/// 其他平台暂不支持系统日志，保留标准输出
+#[cfg(not(any(unix, windows)))]
+pub fn enable_syslog() {
+    log(Level::Warning, format_args!("{}", tr!("syslog-unsupported")));
+}
+
+/// 日志文件按时间轮转的周期
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum RotationInterval {
+    /// 不轮转，一直写入 `--log-file` 指定的文件
+    #[default]
+    Never,
+    /// 每小时换一个文件
+    Hourly,
+    /// 每天零点（本地时区）换一个文件
+    Daily,
+}
+
+impl RotationInterval {
+    /// 当前时间所在的周期，例如 "2024-01-02T03"；不轮转时为空
+    fn current_period(self) -> String {
+        let (year, month, day, hour) = timeutil::local_date_hour();
+        match self {
+            RotationInterval::Never => String::new(),
+            RotationInterval::Hourly => format!("{:04}-{:02}-{:02}T{:02}", year, month, day, hour),
+            RotationInterval::Daily => format!("{:04}-{:02}-{:02}", year, month, day),
+        }
+    }
+}
+
+/// 正在写入的日志文件；轮转时每个周期写入 `<去掉扩展名的路径>-<周期>.log`
+struct LogFile {
+    path: PathBuf,
+    interval: RotationInterval,
+    keep: usize,
+    period: String,
+    file: File,
+    /// 已报告过写入或换文件失败，恢复正常之前不再重复报告
+    failing: bool,
+}
+
+impl LogFile {
+    fn open(path: &Path, interval: RotationInterval, keep: usize) -> io::Result<Self> {
+        let period = interval.current_period();
+        let file = open_append(&period_path(path, &period))?;
+        let log_file = LogFile { path: path.to_path_buf(), interval, keep, period, file, failing: false };
+        log_file.prune();
+        Ok(log_file)
+    }
+
+    /// 写入一行，周期变化时先换到新文件，换文件失败时本行仍写入旧文件、下一行再重试。
+    /// 出错时只在第一次返回错误，避免报告错误的日志再次出错而反复告警
+    fn write_line(&mut self, message: &str) -> io::Result<()> {
+        let rotated = self.rotate();
+        let time = timeutil::now_rfc3339();
+        let written = message.lines().try_for_each(|line| writeln!(self.file, "{} {}", time, line));
+        match rotated.and(written) {
+            Err(_) if self.failing => Ok(()),
+            Err(e) => {
+                self.failing = true;
+                Err(e)
+            }
+            Ok(()) => {
+                self.failing = false;
+                Ok(())
+            }
+        }
+    }
+
+    /// 进入新周期时换到新文件并清理旧文件
+    fn rotate(&mut self) -> io::Result<()> {
+        let period = self.interval.current_period();
+        if period != self.period {
+            self.file = open_append(&period_path(&self.path, &period))?;
+            self.period = period;
+            self.prune();
+        }
+        Ok(())
+    }
+
+    /// 删除超出 `keep` 个的旧周期文件，按文件名中的周期从旧到新删除，不删除正在写入的文件
+    fn prune(&self) {
+        if self.interval == RotationInterval::Never {
+            return;
+        }
+        let current = period_path(&self.path, &self.period);
+        let (Some(dir), Some(prefix)) = (current.parent(), stem_prefix(&self.path)) else {
+            return;
+        };
+        let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut rotated: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| *path != current && path.file_name().and_then(|n| n.to_str()).is_some_and(|name| {
+                name.strip_prefix(&prefix).and_then(|rest| rest.strip_suffix(".log")).is_some_and(is_period)
+            }))
+            .collect();
+        rotated.sort();
+        let excess = rotated.len().saturating_sub(self.keep);
+        for path in &rotated[..excess] {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+/// 周期 `period` 对应的文件路径；不轮转时就是 `path` 本身
+fn period_path(path: &Path, period: &str) -> PathBuf {
+    if period.is_empty() {
+        return path.to_path_buf();
+    }
+    let mut name = path.with_extension("").into_os_string();
+    name.push(format!("-{}.log", period));
+    PathBuf::from(name)
+}
+
+/// 轮转文件名的公共前缀，例如 `app.log` 对应 "app-"
+fn stem_prefix(path: &Path) -> Option<String> {
+    Some(format!("{}-", path.file_stem()?.to_str()?))
+}
+
+/// 是否为 "YYYY-MM-DD" 或 "YYYY-MM-DDTHH" 形式的周期
+fn is_period(s: &str) -> bool {
+    let pattern = match s.len() {
+        10 => "dddd-dd-dd",
+        13 => "dddd-dd-ddTdd",
+        _ => return false,
+    };
+    s.chars().zip(pattern.chars()).all(|(c, p)| if p == 'd' { c.is_ascii_digit() } else { c == p })
+}
+
+/// 同时把日志追加到 `path`；`interval` 不为 never 时按周期换文件，最多保留 `keep` 个旧文件
+pub fn enable_log_file(path: &Path, interval: RotationInterval, keep: usize) -> io::Result<()> {
+    let log_file = LogFile::open(path, interval, keep)?;
+    *LOG_FILE.lock().unwrap_or_else(|e| e.into_inner()) = Some(log_file);
+    Ok(())
+}
+
+/// `--log-file` 指定的路径，未启用时为 None
+pub fn log_file_path() -> Option<PathBuf> {
+    LOG_FILE.lock().unwrap_or_else(|e| e.into_inner()).as_ref().map(|file| file.path.clone())
+}
+
+/// 输出一条日志
+pub fn log(level: Level, args: fmt::Arguments<'_>) {
+    #[cfg(unix)]
+    if let Some(logger) = SYSLOG.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        send_syslog(logger, level, &args.to_string());
+    }
+    #[cfg(windows)]
+    if EVENT_LOG.load(Ordering::Relaxed) {
+        send_event_log(level, args);
+    }
+
+    let file_error = LOG_FILE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_mut()
+        .and_then(|file| file.write_line(&args.to_string()).err());
+    // 释放锁之后再报告，告警本身也要写入日志文件
+    if let Some(e) = file_error {
+        log(Level::Warning, format_args!("{}", tr!("log-file-write-failed", error = e)));
+    }
+
+    if QUIET.load(Ordering::Relaxed) {
+        return;
+    }
+    match level {
+        Level::Info | Level::Warning => println!("{}", args),
+        Level::Notice | Level::Error => eprintln!("{}", args),
+    }
+}
+
+#[cfg(unix)]
+fn send_syslog(logger: &mut syslog::Logger<syslog::LoggerBackend, syslog::Formatter3164>, level: Level, message: &str) {
+    // 后台模式 fork 后PID会变化，每条消息使用当前进程的PID
+    logger.formatter.pid = std::process::id();
+    // syslog 每条消息只记录一行，多行内容拆分发送；发送失败时无处报告，直接丢弃
+    for line in message.lines().filter(|line| !line.trim().is_empty()) {
+        let _ = match level {
+            Level::Info => logger.info(line),
+            Level::Notice => logger.notice(line),
+            Level::Warning => logger.warning(line),
+            Level::Error => logger.err(line),
+        };
+    }
+}
+
+#[cfg(windows)]
+fn send_event_log(level: Level, args: fmt::Arguments<'_>) {
+    // 事件日志没有 NOTICE 级别，按信息记录
+    let level = match level {
+        Level::Info | Level::Notice => log::Level::Info,
+        Level::Warning => log::Level::Warn,
+        Level::Error => log::Level::Error,
+    };
+    log::log!(level, "{}", args);
+}
+
+/// 输出普通信息
+macro_rules! info {
+    ($($arg:tt)*) => {
+        $crate::logger::log($crate::logger::Level::Info, format_args!($($arg)*))
+    };
+}
+
+/// 输出对外部操作的回应（标准错误）
+macro_rules! notice {
+    ($($arg:tt)*) => {
+        $crate::logger::log($crate::logger::Level::Notice, format_args!($($arg)*))
+    };
+}
+
+/// 输出警告信息
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        $crate::logger::log($crate::logger::Level::Warning, format_args!($($arg)*))
+    };
+}
+
+/// 输出错误信息
+macro_rules! error {
+    ($($arg:tt)*) => {
+        $crate::logger::log($crate::logger::Level::Error, format_args!($($arg)*))
+    };
+}
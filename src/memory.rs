@@ -0,0 +1,239 @@
+//! 内存大小参数解析，支持绝对大小和占系统总内存的百分比
+
+use std::fmt;
+use std::io::{IsTerminal, Write};
+use std::time::{Duration, Instant};
+
+use crate::output::GIB;
+use crate::residency;
+
+/// 逐步释放内存时每次清零的块大小
+const DRAIN_CHUNK: usize = 64 << 20;
+
+/// 逐块预填充时每块的大小，每块之后刷新一次进度条
+const PREFAULT_CHUNK: usize = 64 << 20;
+
+/// 进度条的宽度（字符）
+const PROGRESS_WIDTH: usize = 30;
+
+/// 解析后的内存大小
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MemorySpec {
+    /// 绝对字节数
+    Absolute(u64),
+    /// 占系统总内存的百分比（0-100]
+    Percent(f64),
+}
+
+impl MemorySpec {
+    /// 换算为字节数，百分比按给定的系统总内存计算
+    pub fn to_bytes(self, total_memory: u64) -> u64 {
+        match self {
+            MemorySpec::Absolute(bytes) => bytes,
+            MemorySpec::Percent(percent) => (total_memory as f64 * percent / 100.0) as u64,
+        }
+    }
+}
+
+/// 内存大小解析错误
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    Empty,
+    InvalidNumber(String),
+    InvalidUnit(String),
+    PercentOutOfRange(f64),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "{}", tr!("memory-size-empty")),
+            ParseError::InvalidNumber(s) => write!(f, "{}", tr!("memory-size-invalid-number", value = format!("{:?}", s))),
+            ParseError::InvalidUnit(s) => write!(f, "{}", tr!("memory-size-invalid-unit", unit = format!("{:?}", s))),
+            ParseError::PercentOutOfRange(p) => write!(f, "{}", tr!("memory-size-percent-out-of-range", percent = p)),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// 解析内存大小，例如 "1G"、"512M"、"2.5G"、"50%"、"50.5%"
+///
+/// 单位与 `bytesize` 保持一致：K/M/G/T 为十进制，Ki/Mi/Gi/Ti 为二进制，不区分大小写。
+pub fn parse_memory_spec(s: &str) -> Result<MemorySpec, ParseError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(ParseError::Empty);
+    }
+
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let value: f64 = number
+        .parse()
+        .map_err(|_| ParseError::InvalidNumber(number.to_string()))?;
+    let unit = unit.trim();
+
+    if unit == "%" {
+        if value <= 0.0 || value > 100.0 {
+            return Err(ParseError::PercentOutOfRange(value));
+        }
+        return Ok(MemorySpec::Percent(value));
+    }
+
+    let factor: u64 = match unit.to_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1_000,
+        "m" | "mb" => 1_000_000,
+        "g" | "gb" => 1_000_000_000,
+        "t" | "tb" => 1_000_000_000_000,
+        "ki" | "kib" => 1 << 10,
+        "mi" | "mib" => 1 << 20,
+        "gi" | "gib" => 1 << 30,
+        "ti" | "tib" => 1 << 40,
+        _ => return Err(ParseError::InvalidUnit(unit.to_string())),
+    };
+    Ok(MemorySpec::Absolute((value * factor as f64) as u64))
+}
+
+/// 分配全零的缓冲区，失败时返回 None 而不是终止进程
+pub fn try_alloc_zeroed(len: usize) -> Option<Vec<u8>> {
+    if len == 0 {
+        return Some(Vec::new());
+    }
+    let layout = std::alloc::Layout::array::<u8>(len).ok()?;
+    // SAFETY: layout 大小非零；指针来自全局分配器且长度、容量与 layout 一致
+    unsafe {
+        let ptr = std::alloc::alloc_zeroed(layout);
+        if ptr.is_null() {
+            return None;
+        }
+        Some(Vec::from_raw_parts(ptr, len, len))
+    }
+}
+
+/// 在 `timeout` 内从尾部开始逐块清零并归还内存，避免一次性释放造成的延迟尖峰
+///
+/// 超时后剩余部分直接释放。
+pub fn drain(mut buffer: Vec<u8>, timeout: Duration) {
+    let chunks = buffer.len().div_ceil(DRAIN_CHUNK).max(1);
+    let budget = timeout / chunks as u32;
+    let start = Instant::now();
+    let mut last_report: Option<Instant> = None;
+    while !buffer.is_empty() && start.elapsed() < timeout {
+        if last_report.is_none_or(|t| t.elapsed() >= Duration::from_secs(1)) {
+            info!("{}", tr!("memory-draining", size = format!("{:.1}", buffer.len() as f64 / GIB)));
+            last_report = Some(Instant::now());
+        }
+        let chunk_start = Instant::now();
+        let keep = buffer.len().saturating_sub(DRAIN_CHUNK);
+        buffer[keep..].fill(0);
+        std::hint::black_box(&mut buffer[keep..]);
+        buffer.truncate(keep);
+        buffer.shrink_to_fit();
+        if let Some(rest) = budget.checked_sub(chunk_start.elapsed()) {
+            std::thread::sleep(rest);
+        }
+    }
+    if !buffer.is_empty() {
+        info!("{}", tr!("memory-drain-timed-out", size = format!("{:.1}", buffer.len() as f64 / GIB)));
+    }
+}
+
+/// 预填充的方式
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrefaultMethod {
+    /// madvise(MADV_POPULATE_WRITE)，Linux 5.14+
+    Populate,
+    /// 逐页顺序写入
+    Sequential,
+}
+
+/// 让缓冲区的所有页面都分配物理内存，返回使用的方式和耗时
+///
+/// 优先使用 `MADV_POPULATE_WRITE` 一次性填充，不需要逐页写入；内核不支持时改为按64MiB分块
+/// 逐页写入，`show_progress` 为 true 且标准错误是终端时显示进度条。
+pub fn prefault(buffer: &mut [u8], show_progress: bool) -> (PrefaultMethod, Duration) {
+    let start = Instant::now();
+    if populate_write(buffer).is_ok() {
+        return (PrefaultMethod::Populate, start.elapsed());
+    }
+    let show_progress = show_progress && std::io::stderr().is_terminal();
+    let total = buffer.len();
+    for (i, chunk) in buffer.chunks_mut(PREFAULT_CHUNK).enumerate() {
+        touch_pages(chunk);
+        if show_progress {
+            draw_progress((i * PREFAULT_CHUNK + chunk.len()) as f64 / total as f64);
+        }
+    }
+    if show_progress {
+        eprintln!();
+    }
+    (PrefaultMethod::Sequential, start.elapsed())
+}
+
+/// 对缓冲区中完全对齐的页面调用 MADV_POPULATE_WRITE，首尾不完整的页面逐页写入
+#[cfg(target_os = "linux")]
+fn populate_write(buffer: &mut [u8]) -> std::io::Result<()> {
+    let page = residency::page_size();
+    let base = buffer.as_ptr() as usize;
+    let start = base.next_multiple_of(page);
+    let end = (base + buffer.len()) & !(page - 1);
+    if end > start {
+        // SAFETY: 地址范围完全位于缓冲区内；MADV_POPULATE_WRITE 只分配页面，不改变内容
+        let ret = unsafe { libc::madvise(start as *mut libc::c_void, end - start, libc::MADV_POPULATE_WRITE) };
+        if ret != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    // 缓冲区不足一页时 head 覆盖全部，tail 为空
+    let head = (start - base).min(buffer.len());
+    let tail = end.saturating_sub(base).clamp(head, buffer.len());
+    touch_pages(&mut buffer[..head]);
+    touch_pages(&mut buffer[tail..]);
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn populate_write(_buffer: &mut [u8]) -> std::io::Result<()> {
+    Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+}
+
+/// 逐页读出再写回一个字节，触发写缺页而不改变内容
+fn touch_pages(buffer: &mut [u8]) {
+    let page = residency::page_size();
+    for i in (0..buffer.len()).step_by(page) {
+        // SAFETY: i 小于缓冲区长度
+        unsafe {
+            let ptr = buffer.as_mut_ptr().add(i);
+            std::ptr::write_volatile(ptr, std::ptr::read_volatile(ptr));
+        }
+    }
+    if let Some(last) = buffer.last_mut() {
+        // SAFETY: last 是缓冲区内的有效引用
+        unsafe { std::ptr::write_volatile(last, std::ptr::read_volatile(last)) };
+    }
+}
+
+fn draw_progress(ratio: f64) {
+    let filled = (ratio * PROGRESS_WIDTH as f64).round() as usize;
+    let _ = write!(
+        std::io::stderr(),
+        "\r{}[{}{}] {:>3.0}%",
+        tr!("memory-prefault-progress"),
+        "#".repeat(filled),
+        "-".repeat(PROGRESS_WIDTH - filled),
+        ratio * 100.0
+    );
+}
+
+/// 首次访问和再次访问每个页面的平均耗时（微秒），两者之差即为缺页的开销
+pub fn measure_first_access(buffer: &mut [u8]) -> (f64, f64) {
+    let pages = buffer.len().div_ceil(residency::page_size()).max(1) as f64;
+    let mut pass = || {
+        let start = Instant::now();
+        touch_pages(buffer);
+        start.elapsed().as_secs_f64() * 1e6 / pages
+    };
+    let first = pass();
+    (first, pass())
+}
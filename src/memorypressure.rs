@@ -0,0 +1,214 @@
+//! 内存压力测试（仅Linux）：内存分配完成后周期性写入 `/proc/sys/vm/drop_caches` 迫使内核回收页面，
+//! 随后立即重新访问已分配的内存并重新分配一块缓冲区触发缺页，测量回收和恢复期间的延迟尖峰
+//!
+//! 同时读取 `/proc/pressure/memory`（PSI，Linux 4.20+）的压力值供状态输出使用。
+
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use bytesize::ByteSize;
+use clap::ValueEnum;
+
+use crate::growth;
+use crate::json::Json;
+use crate::residency;
+use crate::state::LoadState;
+use crate::timeutil;
+
+/// 两次回收之间的间隔
+const PRESSURE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 重新分配的缓冲区大小上限
+const PROBE_MAX: usize = 256 * 1024 * 1024;
+
+/// 回收级别，对应写入 drop_caches 的值
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum PressureLevel {
+    /// 1：只回收页缓存（默认）
+    #[default]
+    Low,
+    /// 2：只回收 dentry 和 inode 缓存
+    Medium,
+    /// 3：回收页缓存、dentry 和 inode 缓存
+    Critical,
+}
+
+impl PressureLevel {
+    fn drop_caches_value(self) -> u8 {
+        match self {
+            PressureLevel::Low => 1,
+            PressureLevel::Medium => 2,
+            PressureLevel::Critical => 3,
+        }
+    }
+}
+
+/// `/proc/pressure/memory` 中的压力值（百分比）
+///
+/// `some` 为至少一个任务因等待内存而停顿的时间占比，`full` 为所有非空闲任务同时停顿的时间占比。
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MemoryPsi {
+    pub some_avg10: f32,
+    pub some_avg60: f32,
+    pub full_avg10: f32,
+    pub full_avg60: f32,
+}
+
+impl MemoryPsi {
+    /// 读取当前压力值，内核不支持PSI或非Linux平台返回 None
+    pub fn read() -> Option<Self> {
+        let text = std::fs::read_to_string("/proc/pressure/memory").ok()?;
+        let mut psi = MemoryPsi::default();
+        for line in text.lines() {
+            let mut fields = line.split_whitespace();
+            let kind = fields.next()?;
+            let mut avg10 = None;
+            let mut avg60 = None;
+            for field in fields {
+                match field.split_once('=') {
+                    Some(("avg10", value)) => avg10 = value.parse().ok(),
+                    Some(("avg60", value)) => avg60 = value.parse().ok(),
+                    _ => {}
+                }
+            }
+            let (Some(avg10), Some(avg60)) = (avg10, avg60) else {
+                continue;
+            };
+            match kind {
+                "some" => (psi.some_avg10, psi.some_avg60) = (avg10, avg60),
+                "full" => (psi.full_avg10, psi.full_avg60) = (avg10, avg60),
+                _ => {}
+            }
+        }
+        Some(psi)
+    }
+
+    /// 文本格式，例如 "内存压力 some: 1.20%/0.80%, full: 0.50%/0.30%"
+    pub fn describe(&self) -> String {
+        tr!(
+            "memory-psi",
+            some10 = format!("{:.2}", self.some_avg10),
+            some60 = format!("{:.2}", self.some_avg60),
+            full10 = format!("{:.2}", self.full_avg10),
+            full60 = format!("{:.2}", self.full_avg60)
+        )
+    }
+
+    pub fn to_json(self) -> Json {
+        Json::object([
+            ("some_avg10", self.some_avg10.into()),
+            ("some_avg60", self.some_avg60.into()),
+            ("full_avg10", self.full_avg10.into()),
+            ("full_avg60", self.full_avg60.into()),
+        ])
+    }
+}
+
+/// 启动前检查：输出系统可能不稳定的警告；非Linux平台或非root用户时返回 false
+pub fn check_support(level: PressureLevel) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: geteuid 没有副作用
+        if unsafe { libc::geteuid() } != 0 {
+            warn!("{}", tr!("memory-pressure-needs-root"));
+            return false;
+        }
+        warn!("{}", tr!("memory-pressure-danger", level = level.drop_caches_value()));
+        true
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = level;
+        warn!("{}", tr!("memory-pressure-unsupported"));
+        false
+    }
+}
+
+/// 写入 drop_caches；页缓存中的脏页不会被回收，先 sync 写回
+fn drop_caches(level: PressureLevel) -> std::io::Result<Duration> {
+    let start = Instant::now();
+    #[cfg(unix)]
+    // SAFETY: sync 没有参数，只触发脏页写回
+    unsafe {
+        libc::sync()
+    };
+    std::fs::write("/proc/sys/vm/drop_caches", level.drop_caches_value().to_string())?;
+    Ok(start.elapsed())
+}
+
+/// 分配 `size` 字节并逐页写入，返回每页的平均缺页耗时（微秒）
+fn fault_probe(size: usize) -> f64 {
+    let page = residency::page_size();
+    let start = Instant::now();
+    let mut probe = vec![0u8; size];
+    for i in (0..probe.len()).step_by(page) {
+        probe[i] = 1;
+    }
+    let elapsed = start.elapsed();
+    std::hint::black_box(&probe);
+    elapsed.as_secs_f64() * 1e6 / size.div_ceil(page).max(1) as f64
+}
+
+/// 启动内存压力测试线程
+pub fn spawn_pressure_test(level: PressureLevel, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let page = residency::page_size();
+        let probe_size = {
+            let guard = state.memory.lock().unwrap_or_else(|e| e.into_inner());
+            guard.as_ref().map_or(0, |buffer| buffer.len()).clamp(page, PROBE_MAX)
+        };
+        let baseline = fault_probe(probe_size);
+        info!(
+            "{}",
+            tr!("memory-pressure-baseline", size = ByteSize::b(probe_size as u64), fault = format!("{:.2}", baseline))
+        );
+
+        let mut cycle = 0u32;
+        while state.is_running() {
+            growth::sleep_while_running(&state, PRESSURE_INTERVAL);
+            if !state.is_running() {
+                break;
+            }
+            cycle += 1;
+            let drop_time = match drop_caches(level) {
+                Ok(elapsed) => elapsed,
+                Err(e) => {
+                    warn!("{}", tr!("memory-pressure-drop-failed", error = e));
+                    return;
+                }
+            };
+
+            // 顺序读取已分配内存的每个页面
+            let access_start = Instant::now();
+            let accessed = {
+                let guard = state.memory.lock().unwrap_or_else(|e| e.into_inner());
+                let buffer = guard.as_deref().unwrap_or_default();
+                let mut sum = 0u8;
+                for i in (0..buffer.len()).step_by(page) {
+                    // SAFETY: i 小于缓冲区长度
+                    sum = sum.wrapping_add(unsafe { std::ptr::read_volatile(buffer.as_ptr().add(i)) });
+                }
+                std::hint::black_box(sum);
+                buffer.len()
+            };
+            let access_time = access_start.elapsed();
+            let fault = fault_probe(probe_size);
+
+            info!(
+                "[{}] {}",
+                timeutil::now_rfc3339(),
+                tr!(
+                    "memory-pressure-cycle",
+                    cycle = cycle,
+                    level = level.drop_caches_value(),
+                    drop = format!("{:.1}", drop_time.as_secs_f64() * 1e3),
+                    size = ByteSize::b(accessed as u64),
+                    access = format!("{:.1}", access_time.as_secs_f64() * 1e3),
+                    fault = format!("{:.2}", fault),
+                    ratio = format!("{:.1}", fault / baseline.max(f64::EPSILON))
+                )
+            );
+        }
+    })
+}
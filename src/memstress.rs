@@ -0,0 +1,431 @@
+//! 内存访问模式：持续访问已分配的内存，对内存子系统施加压力
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use rand::Rng;
+use sysinfo::{CpuExt, System, SystemExt};
+
+use crate::seed;
+use crate::state::LoadState;
+
+/// 缓存行大小，也是顺序和随机模式的访问粒度
+pub const CACHE_LINE: usize = 64;
+
+/// 每次持有内存锁的时长，之后释放锁以便控制接口替换内存
+const LOCK_SLICE: Duration = Duration::from_millis(50);
+
+/// 访问线程每执行这么多次访问检查一次时间片是否用完
+const ACCESSES_PER_CHECK: usize = 4096;
+
+/// 输出吞吐量的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 内存访问模式
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum MemoryPattern {
+    /// 按 `--memory-stride` 指定的步长访问
+    Stride,
+    /// 按根据CPU厂商选择的步长访问，使硬件预取失效
+    StridePrefetchDefeat,
+    /// 交替使用隔一条缓存行的步长（训练预取器）和97字节的质数步长（预取器难以预测），
+    /// 比较两个阶段的吞吐量
+    PrefetchTrain,
+    /// 逐条缓存行顺序写入
+    SequentialWrite,
+    /// 逐条缓存行顺序读取，不写回
+    SequentialRead,
+    /// 用LCG随机选择缓存行写入
+    RandomWrite,
+    /// 用LCG随机选择缓存行读取，不写回
+    RandomRead,
+}
+
+impl MemoryPattern {
+    fn access(self) -> Access {
+        match self {
+            MemoryPattern::SequentialWrite | MemoryPattern::RandomWrite => Access::Write,
+            MemoryPattern::SequentialRead | MemoryPattern::RandomRead => Access::Read,
+            _ => Access::Modify,
+        }
+    }
+
+    fn is_random(self) -> bool {
+        matches!(self, MemoryPattern::RandomWrite | MemoryPattern::RandomRead)
+    }
+}
+
+/// 每次访问对一个字节做的操作
+#[derive(Clone, Copy, Debug)]
+enum Access {
+    /// 读改写
+    Modify,
+    Read,
+    Write,
+}
+
+impl Access {
+    #[inline]
+    fn apply(self, byte: &mut u8) {
+        match self {
+            Access::Modify => *byte = std::hint::black_box(*byte).wrapping_add(1),
+            Access::Read => {
+                std::hint::black_box(*byte);
+            }
+            // SAFETY: byte 是有效的可写引用；volatile 写入防止编译器合并或消除对同一缓冲区的重复写入
+            Access::Write => unsafe { std::ptr::write_volatile(byte, 1) },
+        }
+    }
+
+    #[inline]
+    fn apply_atomic(self, cell: &AtomicU8) {
+        match self {
+            Access::Modify => {
+                cell.fetch_add(1, Ordering::Relaxed);
+            }
+            Access::Read => {
+                std::hint::black_box(cell.load(Ordering::Relaxed));
+            }
+            Access::Write => cell.store(1, Ordering::Relaxed),
+        }
+    }
+}
+
+/// 一个时间片内的访问方式
+#[derive(Clone, Copy, Debug)]
+struct Sweep {
+    stride: usize,
+    /// 为 true 时忽略步长，随机选择缓存行
+    random: bool,
+    access: Access,
+}
+
+impl Sweep {
+    /// 长度为 `len` 的内存中下一个访问位置：顺序访问时按步长推进 `offset`，
+    /// 随机访问时把 `offset` 当作LCG的状态，用其高位选择一条缓存行
+    #[inline]
+    fn next(&self, offset: &mut usize, len: usize) -> usize {
+        if self.random {
+            let x = (*offset as u64).wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            *offset = x as usize;
+            (x >> 33) as usize % (len / CACHE_LINE).max(1) * CACHE_LINE
+        } else {
+            *offset %= len;
+            let index = *offset;
+            *offset += self.stride;
+            index
+        }
+    }
+}
+
+/// `prefetch-train` 训练阶段的步长：隔一条缓存行访问
+pub const TRAIN_STRIDE: usize = 2 * CACHE_LINE;
+
+/// `prefetch-train` 对照阶段的步长：质数，不是缓存行的整数倍，步长预测器难以识别
+pub const PRIME_STRIDE: usize = 97;
+
+/// 根据CPU厂商选择使硬件预取器失效的步长
+///
+/// 两家的预取器都不会跨越4KB页（Intel 优化手册 "Hardware Prefetching" 一节，
+/// AMD Family 19h 软件优化指南 "Prefetching" 一节），所以步长都取一页再加若干缓存行：
+/// 每次访问落在新的页面上，流预取器无法建立访问流；而纯4KB步长会让所有访问映射到同一个
+/// L1/L2 组，测到的是组冲突而不是DRAM，因此额外偏移缓存行使访问轮换不同的组和DRAM行。
+///
+/// - Intel Skylake：L1 的 IP 步长预取器只识别不超过2KB的步长，L2 的空间预取器会补齐
+///   128字节对齐的相邻缓存行，因此偏移两个缓存行（128字节），避免总是落在上一次访问的配对行上。
+/// - AMD Zen3：L1 步长预取器和 L2 流预取器同样止步于页边界，没有 Intel 那样的配对行预取，
+///   偏移一个缓存行即可。
+///
+/// 以上依据的是公开文档中的预取器行为，具体效果应在目标机器上用性能计数器（如 LLC miss）确认。
+pub fn prefetch_defeat_stride(vendor: &str) -> usize {
+    match vendor {
+        "GenuineIntel" => 4096 + 2 * CACHE_LINE,
+        "AuthenticAMD" => 4096 + CACHE_LINE,
+        // 未知厂商按 Intel 的规则取保守值
+        _ => 4096 + 2 * CACHE_LINE,
+    }
+}
+
+/// 读取CPU厂商标识，例如 "GenuineIntel"、"AuthenticAMD"
+pub fn cpu_vendor() -> String {
+    let mut sys = System::new();
+    sys.refresh_cpu();
+    sys.cpus().first().map(|cpu| cpu.vendor_id().to_string()).unwrap_or_default()
+}
+
+/// 多个访问线程如何分配内存
+#[derive(Clone, Copy, Debug)]
+pub struct AccessThreads {
+    /// 访问线程数
+    pub count: usize,
+    /// 为 true 时所有线程访问整块内存，争用相同的缓存行；否则各自访问等分的一段
+    pub overlap: bool,
+}
+
+/// 忙等 `latency`，模拟高延迟内存；睡眠的精度远达不到纳秒级
+#[inline]
+fn inject_latency(latency: Duration) {
+    if latency.is_zero() {
+        return;
+    }
+    let start = Instant::now();
+    while start.elapsed() < latency {
+        std::hint::spin_loop();
+    }
+}
+
+/// 两次检查时间片之间的访问次数；注入的延迟较大时相应减少，避免超出时间片太多
+fn accesses_per_check(latency: Duration) -> usize {
+    match latency.as_nanos() {
+        0 => ACCESSES_PER_CHECK,
+        ns => (LOCK_SLICE.as_nanos() / 10 / ns).clamp(1, ACCESSES_PER_CHECK as u128) as usize,
+    }
+}
+
+/// 启动内存访问驱动线程，每次访问对一个字节做读改写；`sequential-*`、`random-*` 模式只读或只写
+///
+/// 驱动线程每个时间片持有一次内存锁，在锁内启动 `threads.count` 个线程同时访问，
+/// 时间片结束后释放锁，以便控制接口替换内存。内存拆分为小对象时改为按随机顺序访问各个对象。`prefetch-train` 模式每隔 `switch_interval`
+/// 在两种步长之间切换（按时间片对齐），忽略 `stride`；`random-*` 模式同样忽略 `stride`。`latency` 非零时每次访问之后忙等该时长。
+pub fn spawn_memory_stress(
+    pattern: MemoryPattern,
+    stride: usize,
+    switch_interval: Duration,
+    threads: AccessThreads,
+    latency: Duration,
+    state: Arc<LoadState>,
+) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let count = threads.count.max(1);
+        let shared = if threads.overlap && count > 1 { "yes" } else { "no" };
+        info!(
+            "{}",
+            tr!("memstress-started", pattern = format!("{:?}", pattern), stride = stride, count = count, shared = shared)
+        );
+        let mut offsets = vec![0usize; count];
+        let mut accesses = vec![0u64; count];
+        // 报告间隔内持有内存锁访问的总时长，用于计算实际的每次访问耗时
+        let mut active = Duration::ZERO;
+        let mut last_report = Instant::now();
+        // prefetch-train 的当前阶段（false 为训练阶段）、阶段开始时间，以及两个阶段各自累计的访问次数和时间
+        let mut prime_phase = false;
+        let mut phase_start = Instant::now();
+        let mut phase_totals = [(0u64, Duration::ZERO); 2];
+        while state.is_running() {
+            let sweep = Sweep {
+                stride: match pattern {
+                    MemoryPattern::PrefetchTrain if prime_phase => PRIME_STRIDE,
+                    MemoryPattern::PrefetchTrain => TRAIN_STRIDE,
+                    _ => stride,
+                },
+                random: pattern.is_random(),
+                access: pattern.access(),
+            };
+            let before: u64 = accesses.iter().sum();
+            let slice_start = Instant::now();
+            {
+                let mut objects = state.objects.lock().unwrap_or_else(|e| e.into_inner());
+                if !objects.is_empty() {
+                    let deadline = Instant::now() + LOCK_SLICE;
+                    run_objects(&mut objects, sweep, deadline, latency, &mut offsets, &mut accesses);
+                } else {
+                    drop(objects);
+                    let mut guard = state.memory.lock().unwrap_or_else(|e| e.into_inner());
+                    let Some(buffer) = guard.as_deref_mut().filter(|b| b.len() >= count) else {
+                        drop(guard);
+                        thread::sleep(Duration::from_millis(100));
+                        continue;
+                    };
+                    let deadline = Instant::now() + LOCK_SLICE;
+                    if threads.overlap {
+                        run_overlapping(buffer, sweep, deadline, latency, &mut offsets, &mut accesses);
+                    } else {
+                        run_partitioned(buffer, sweep, deadline, latency, &mut offsets, &mut accesses);
+                    }
+                }
+            }
+            active += slice_start.elapsed();
+            let phase = &mut phase_totals[usize::from(prime_phase)];
+            phase.0 += accesses.iter().sum::<u64>() - before;
+            phase.1 += slice_start.elapsed();
+            if phase_start.elapsed() >= switch_interval {
+                prime_phase = !prime_phase;
+                phase_start = Instant::now();
+            }
+
+            let elapsed = last_report.elapsed();
+            if elapsed >= REPORT_INTERVAL {
+                report(&accesses, elapsed);
+                if !latency.is_zero() {
+                    report_latency(latency, active, &accesses);
+                }
+                if pattern == MemoryPattern::PrefetchTrain {
+                    report_prefetch(&phase_totals);
+                }
+                accesses.iter_mut().for_each(|a| *a = 0);
+                active = Duration::ZERO;
+                phase_totals = [(0, Duration::ZERO); 2];
+                last_report = Instant::now();
+            }
+        }
+    })
+}
+
+/// 每个线程访问等分的一段，互不干扰
+fn run_partitioned(
+    buffer: &mut [u8],
+    sweep: Sweep,
+    deadline: Instant,
+    latency: Duration,
+    offsets: &mut [usize],
+    accesses: &mut [u64],
+) {
+    let segment = buffer.len() / offsets.len();
+    let per_check = accesses_per_check(latency);
+    thread::scope(|scope| {
+        for ((chunk, offset), accesses) in buffer.chunks_mut(segment).zip(offsets.iter_mut()).zip(accesses.iter_mut()) {
+            scope.spawn(move || {
+                while Instant::now() < deadline {
+                    for _ in 0..per_check {
+                        let i = sweep.next(offset, chunk.len());
+                        sweep.access.apply(&mut chunk[i]);
+                        inject_latency(latency);
+                    }
+                    *accesses += per_check as u64;
+                }
+            });
+        }
+    });
+}
+
+/// 每个线程负责等分的一组对象，每次随机挑选其中一个对象，在对象内按步长推进访问位置
+fn run_objects(
+    objects: &mut [Box<[u8]>],
+    sweep: Sweep,
+    deadline: Instant,
+    latency: Duration,
+    offsets: &mut [usize],
+    accesses: &mut [u64],
+) {
+    let group = objects.len().div_ceil(offsets.len());
+    let per_check = accesses_per_check(latency);
+    thread::scope(|scope| {
+        let groups = objects.chunks_mut(group).zip(offsets.iter_mut()).zip(accesses.iter_mut()).enumerate();
+        for (i, ((group, offset), accesses)) in groups {
+            scope.spawn(move || {
+                let mut rng = seed::thread_rng(seed::MEMORY_ACCESS_STREAMS + i);
+                while Instant::now() < deadline {
+                    for _ in 0..per_check {
+                        let object = &mut group[rng.gen_range(0..group.len())];
+                        let i = if sweep.random {
+                            sweep.next(offset, object.len())
+                        } else {
+                            let i = *offset % object.len();
+                            *offset = offset.wrapping_add(sweep.stride);
+                            i
+                        };
+                        sweep.access.apply(&mut object[i]);
+                        inject_latency(latency);
+                    }
+                    *accesses += per_check as u64;
+                }
+            });
+        }
+    });
+}
+
+/// 所有线程同时访问整块内存，用原子加法使并发的读改写互不丢失
+fn run_overlapping(
+    buffer: &mut [u8],
+    sweep: Sweep,
+    deadline: Instant,
+    latency: Duration,
+    offsets: &mut [usize],
+    accesses: &mut [u64],
+) {
+    let len = buffer.len();
+    let per_check = accesses_per_check(latency);
+    // SAFETY: AtomicU8 与 u8 内存布局相同；buffer 在本函数内被独占借用，期间只通过原子操作访问
+    let cells: &[AtomicU8] = unsafe { std::slice::from_raw_parts(buffer.as_mut_ptr().cast(), len) };
+    let count = offsets.len();
+    thread::scope(|scope| {
+        for (i, (offset, accesses)) in offsets.iter_mut().zip(accesses.iter_mut()).enumerate() {
+            // 各线程从不同位置出发，避免始终步调一致地访问同一缓存行
+            let start = len / count * i;
+            scope.spawn(move || {
+                while Instant::now() < deadline {
+                    for _ in 0..per_check {
+                        let i = sweep.next(offset, len);
+                        sweep.access.apply_atomic(&cells[(start + i) % len]);
+                        inject_latency(latency);
+                    }
+                    *accesses += per_check as u64;
+                }
+            });
+        }
+    });
+}
+
+/// 输出每个线程和总计的访问速率；每次访问都按整条缓存行计入带宽
+fn report(accesses: &[u64], elapsed: Duration) {
+    let secs = elapsed.as_secs_f64();
+    let bandwidth = |n: u64| n as f64 / secs * CACHE_LINE as f64 / 1e9;
+    let total: u64 = accesses.iter().sum();
+    info!(
+        "{}",
+        tr!(
+            "memstress-rate",
+            rate = format!("{:.1}", total as f64 / secs / 1e6),
+            bandwidth = format!("{:.2}", bandwidth(total))
+        )
+    );
+    if accesses.len() > 1 {
+        let per_thread: Vec<_> = accesses.iter().map(|&n| format!("{:.2}", bandwidth(n))).collect();
+        info!("{}", tr!("memstress-per-thread", bandwidths = per_thread.join(", ")));
+    }
+}
+
+/// 校准检查：输出请求的注入延迟和实际的每次访问耗时（含内存访问本身和计时开销）
+fn report_latency(requested: Duration, active: Duration, accesses: &[u64]) {
+    let total: u64 = accesses.iter().sum();
+    if total == 0 {
+        return;
+    }
+    // 各线程并行访问，每个线程的平均访问耗时为访问时长除以该线程的平均访问次数
+    let effective = active.as_secs_f64() * 1e9 * accesses.len() as f64 / total as f64;
+    info!(
+        "{}",
+        tr!(
+            "memstress-latency",
+            requested = requested.as_nanos() as u64,
+            effective = format!("{:.0}", effective)
+        )
+    );
+}
+
+/// 输出 `prefetch-train` 两个阶段扫过内存的速度（访问次数 × 步长）及其比值，
+/// 比值越大说明带宽越依赖硬件预取
+fn report_prefetch(phase_totals: &[(u64, Duration); 2]) {
+    let bandwidth = |(accesses, time): (u64, Duration), stride: usize| {
+        accesses as f64 * stride as f64 / time.as_secs_f64().max(f64::EPSILON) / 1e9
+    };
+    let (train, prime) = (bandwidth(phase_totals[0], TRAIN_STRIDE), bandwidth(phase_totals[1], PRIME_STRIDE));
+    // 报告间隔内可能只经历了一个阶段
+    if train == 0.0 || prime == 0.0 {
+        return;
+    }
+    info!(
+        "{}",
+        tr!(
+            "memstress-prefetch",
+            train_stride = TRAIN_STRIDE,
+            train = format!("{:.2}", train),
+            prime_stride = PRIME_STRIDE,
+            prime = format!("{:.2}", prime),
+            ratio = format!("{:.2}", train / prime)
+        )
+    );
+}
@@ -0,0 +1,103 @@
+//! `--output`：状态线程每次刷新时把一条指标记录写入文件，格式为 CSV 或 JSON Lines，供事后在 Jupyter 等工具中分析
+//!
+//! 每条记录写入后立即刷新，负载中途被终止时文件中已有的记录仍然完整。
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+/// 指标文件的格式
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum MetricsFormat {
+    /// 逗号分隔，第一行为表头，每个逻辑CPU一列
+    Csv,
+    /// 每行一个JSON对象
+    Jsonl,
+}
+
+impl MetricsFormat {
+    /// 未指定格式时按扩展名选择：`.csv` 为 CSV，其他为 JSON Lines
+    pub fn from_path(path: &Path) -> MetricsFormat {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => MetricsFormat::Csv,
+            _ => MetricsFormat::Jsonl,
+        }
+    }
+}
+
+/// 一次刷新的指标，字段名即 JSON Lines 中的键名，CSV 按字段顺序逐列写出
+#[derive(Clone, Debug, Serialize)]
+pub struct MetricRecord {
+    /// Unix时间戳（毫秒）
+    pub timestamp_ms: u64,
+    /// 每个逻辑CPU的使用率（百分比），CSV 中每个CPU一列
+    pub per_core_percent: Vec<f32>,
+    pub memory_total_bytes: u64,
+    pub memory_used_bytes: u64,
+    pub memory_available_bytes: u64,
+    /// 本工具分配的内存（字节），未分配时为 None
+    pub memory_allocated_bytes: Option<u64>,
+}
+
+/// 打开的指标文件
+enum Sink {
+    /// 表头在写入第一条记录时按其CPU数生成
+    Csv { writer: Box<csv::Writer<File>>, header_written: bool },
+    Jsonl(BufWriter<File>),
+}
+
+/// 指标文件，写入失败时告警一次并停止写入
+pub struct MetricsWriter {
+    sink: Sink,
+    failed: bool,
+}
+
+impl MetricsWriter {
+    /// 创建（已存在时截断）指标文件
+    pub fn create(path: &Path, format: MetricsFormat) -> std::io::Result<MetricsWriter> {
+        let file = File::create(path)?;
+        let sink = match format {
+            // 表头由 write_header 写出，serialize 只写数据行
+            MetricsFormat::Csv => Sink::Csv {
+                writer: Box::new(csv::WriterBuilder::new().has_headers(false).from_writer(file)),
+                header_written: false,
+            },
+            MetricsFormat::Jsonl => Sink::Jsonl(BufWriter::new(file)),
+        };
+        Ok(MetricsWriter { sink, failed: false })
+    }
+
+    pub fn write(&mut self, record: &MetricRecord) {
+        if self.failed {
+            return;
+        }
+        let result = match &mut self.sink {
+            Sink::Csv { writer, header_written } => {
+                let header = if *header_written { Ok(()) } else { write_header(writer, record.per_core_percent.len()) };
+                *header_written = true;
+                header.and_then(|()| writer.serialize(record)).map_err(std::io::Error::from).and_then(|()| writer.flush())
+            }
+            Sink::Jsonl(writer) => serde_json::to_writer(&mut *writer, record)
+                .map_err(std::io::Error::from)
+                .and_then(|()| writeln!(writer))
+                .and_then(|()| writer.flush()),
+        };
+        if let Err(e) = result {
+            warn!("{}", tr!("metrics-write-failed", error = e));
+            self.failed = true;
+        }
+    }
+}
+
+/// CSV 表头：每个逻辑CPU一列 `cpu<N>_percent`，其余列与 [`MetricRecord`] 的字段同名
+fn write_header(writer: &mut csv::Writer<File>, cpus: usize) -> csv::Result<()> {
+    let mut header = vec!["timestamp_ms".to_string()];
+    header.extend((0..cpus).map(|i| format!("cpu{}_percent", i)));
+    header.extend(
+        ["memory_total_bytes", "memory_used_bytes", "memory_available_bytes", "memory_allocated_bytes"].map(String::from),
+    );
+    writer.write_record(&header)
+}
@@ -0,0 +1,60 @@
+//! 用 mlock 锁定已分配内存的一部分，防止被换出
+
+#[cfg(unix)]
+use crate::residency;
+
+/// 锁定缓冲区开头 `percent`% 的内存，返回实际锁定的字节数
+///
+/// 请求的大小锁定失败时（通常是超出 RLIMIT_MEMLOCK），二分查找能够锁定的最大页数。
+#[cfg(unix)]
+pub fn lock_prefix(buffer: &[u8], percent: u8) -> std::io::Result<usize> {
+    let page = residency::page_size();
+    let requested = buffer.len() * usize::from(percent) / 100;
+    let requested_pages = requested.div_ceil(page);
+    if requested_pages == 0 {
+        return Ok(0);
+    }
+    let len_of = |pages: usize| (pages * page).min(buffer.len());
+
+    let first_error = match try_lock(buffer, len_of(requested_pages)) {
+        Ok(()) => return Ok(len_of(requested_pages)),
+        Err(e) => e,
+    };
+
+    // 二分查找可锁定的最大页数：lo 总是可以锁定，hi 总是锁定失败
+    let (mut lo, mut hi) = (0, requested_pages);
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if try_lock(buffer, len_of(mid)).is_ok() {
+            unlock(buffer, len_of(mid));
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    if lo == 0 {
+        return Err(first_error);
+    }
+    try_lock(buffer, len_of(lo))?;
+    Ok(len_of(lo))
+}
+
+#[cfg(unix)]
+fn try_lock(buffer: &[u8], len: usize) -> std::io::Result<()> {
+    // SAFETY: 地址范围位于缓冲区内，mlock 不会修改内存内容
+    let ret = unsafe { libc::mlock(buffer.as_ptr().cast(), len) };
+    if ret == 0 { Ok(()) } else { Err(std::io::Error::last_os_error()) }
+}
+
+#[cfg(unix)]
+fn unlock(buffer: &[u8], len: usize) {
+    // SAFETY: 同上
+    unsafe {
+        libc::munlock(buffer.as_ptr().cast(), len);
+    }
+}
+
+#[cfg(not(unix))]
+pub fn lock_prefix(_buffer: &[u8], _percent: u8) -> std::io::Result<usize> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, tr!("mlock-unsupported")))
+}
@@ -0,0 +1,112 @@
+//! 网络接口统计：通过 sysinfo 的网络接口计数，得到各接口的收发速率和错误数
+//!
+//! 默认排除回环接口 `lo`；指定 `--net-filter` 时改为只统计名称匹配该正则表达式的接口。
+
+use std::time::Instant;
+
+use regex::Regex;
+use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+
+use crate::json::Json;
+
+/// 一个网络接口在一次采样间隔内的速率
+#[derive(Clone, Debug)]
+pub struct NetRates {
+    pub name: String,
+    pub rx_bytes_per_sec: f64,
+    pub tx_bytes_per_sec: f64,
+    pub rx_packets_per_sec: f64,
+    pub tx_packets_per_sec: f64,
+    /// 间隔内的收发错误数
+    pub errors: u64,
+}
+
+impl NetRates {
+    /// 收发合计吞吐量
+    pub fn throughput(&self) -> f64 {
+        self.rx_bytes_per_sec + self.tx_bytes_per_sec
+    }
+
+    /// 文本格式，例如 "eth0 rx:1.2MB/s tx:0.3MB/s"
+    pub fn describe(&self) -> String {
+        const MB: f64 = 1_000_000.0;
+        let mut out = format!("{} rx:{:.1}MB/s tx:{:.1}MB/s", self.name, self.rx_bytes_per_sec / MB, self.tx_bytes_per_sec / MB);
+        if self.errors > 0 {
+            out.push(' ');
+            out.push_str(&tr!("netstats-errors", errors = self.errors));
+        }
+        out
+    }
+
+    /// 带包速率的详细格式，用于 `status` 命令
+    pub fn describe_detailed(&self) -> String {
+        tr!(
+            "netstats-detailed",
+            rates = self.describe(),
+            rx = format!("{:.0}", self.rx_packets_per_sec),
+            tx = format!("{:.0}", self.tx_packets_per_sec)
+        )
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::object([
+            ("name", self.name.as_str().into()),
+            ("rx_bytes_per_sec", self.rx_bytes_per_sec.into()),
+            ("tx_bytes_per_sec", self.tx_bytes_per_sec.into()),
+            ("rx_packets_per_sec", self.rx_packets_per_sec.into()),
+            ("tx_packets_per_sec", self.tx_packets_per_sec.into()),
+            ("errors", self.errors.into()),
+        ])
+    }
+}
+
+/// 周期性采样网络接口
+pub struct NetMonitor {
+    sys: System,
+    filter: Option<Regex>,
+    last_sample: Instant,
+}
+
+impl NetMonitor {
+    /// 记录当前的累计计数作为基准
+    pub fn new(filter: Option<Regex>) -> Self {
+        let mut sys = System::new();
+        sys.refresh_networks_list();
+        NetMonitor { sys, filter, last_sample: Instant::now() }
+    }
+
+    fn selected(&self, name: &str) -> bool {
+        match &self.filter {
+            Some(filter) => filter.is_match(name),
+            None => name != "lo",
+        }
+    }
+
+    /// 上次采样以来各接口的速率，按吞吐量从高到低排序
+    pub fn sample(&mut self) -> Vec<NetRates> {
+        self.sys.refresh_networks();
+        let secs = self.last_sample.elapsed().as_secs_f64().max(f64::EPSILON);
+        self.last_sample = Instant::now();
+        let mut rates: Vec<NetRates> = self
+            .sys
+            .networks()
+            .iter()
+            .filter(|(name, _)| self.selected(name))
+            .map(|(name, data)| NetRates {
+                name: name.clone(),
+                rx_bytes_per_sec: data.received() as f64 / secs,
+                tx_bytes_per_sec: data.transmitted() as f64 / secs,
+                rx_packets_per_sec: data.packets_received() as f64 / secs,
+                tx_packets_per_sec: data.packets_transmitted() as f64 / secs,
+                errors: data.errors_on_received() + data.errors_on_transmitted(),
+            })
+            .collect();
+        rates.sort_by(|a, b| b.throughput().total_cmp(&a.throughput()).then_with(|| a.name.cmp(&b.name)));
+        rates
+    }
+}
+
+/// 解析 `--net-filter` 的正则表达式
+pub fn parse_net_filter(s: &str) -> Result<Regex, String> {
+    Regex::new(s).map_err(|e| tr!("parse-invalid-regex", error = e))
+}
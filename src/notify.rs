@@ -0,0 +1,82 @@
+//! 就绪通知：所有工作线程启动后向指定的文件描述符写入 `\n`，参照 systemd 的 `sd_notify`
+//!
+//! 后台模式下父进程在 fork 前创建管道，等待子进程通过写端报告就绪后才退出，
+//! 这样 `start --background && ...` 返回时负载已经真正运行。
+
+use std::fs::File;
+use std::io::{ErrorKind, Read, Write};
+use std::os::fd::{FromRawFd, RawFd};
+use std::time::{Duration, Instant};
+
+/// 创建就绪通知管道，返回 (读端, 写端)
+pub fn ready_pipe() -> std::io::Result<(RawFd, RawFd)> {
+    let mut fds = [0; 2];
+    // SAFETY: fds 是长度为2的数组，pipe 成功时写入两个新的文件描述符
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // 钩子等子进程不应继承管道，否则子进程退出后父进程仍等不到 EOF
+    for fd in fds {
+        // SAFETY: fd 是刚创建的有效文件描述符
+        unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) };
+    }
+    Ok((fds[0], fds[1]))
+}
+
+/// 关闭不再使用的管道端
+pub fn close(fd: RawFd) {
+    // SAFETY: 调用方保证 fd 属于本进程且之后不再使用
+    unsafe { libc::close(fd) };
+}
+
+/// 报告就绪：向 `fd` 写入 `\n` 并关闭它
+pub fn notify_ready(fd: RawFd) {
+    // SAFETY: fd 由父进程或 --notify-fd 传入，由这里接管并在写入后关闭
+    let mut file = unsafe { File::from_raw_fd(fd) };
+    match file.write_all(b"\n") {
+        Ok(()) => info!("{}", tr!("notify-sent", fd = fd)),
+        Err(e) => warn!("{}", tr!("notify-failed", fd = fd, error = e)),
+    }
+}
+
+/// 等待就绪通知的结果
+#[derive(Debug, PartialEq, Eq)]
+pub enum Readiness {
+    Ready,
+    /// 写端已全部关闭但没有收到通知，通常是子进程启动失败
+    Exited,
+    TimedOut,
+}
+
+/// 在 `timeout` 内等待管道读端收到就绪通知，之后关闭读端
+pub fn wait_ready(fd: RawFd, timeout: Duration) -> Readiness {
+    // SAFETY: fd 是 ready_pipe 创建的读端，由这里接管
+    let mut file = unsafe { File::from_raw_fd(fd) };
+    let deadline = Instant::now() + timeout;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Readiness::TimedOut;
+        }
+        let mut pollfd = libc::pollfd { fd, events: libc::POLLIN, revents: 0 };
+        let millis = remaining.as_millis().clamp(1, i32::MAX as u128) as i32;
+        // SAFETY: pollfd 指向一个有效的结构体，数量为1
+        match unsafe { libc::poll(&mut pollfd, 1, millis) } {
+            0 => return Readiness::TimedOut,
+            n if n < 0 => {
+                // 被信号打断时继续等待剩余时间
+                if std::io::Error::last_os_error().kind() == ErrorKind::Interrupted {
+                    continue;
+                }
+                return Readiness::Exited;
+            }
+            _ => {
+                let mut byte = [0u8; 1];
+                return match file.read(&mut byte) {
+                    Ok(1) => Readiness::Ready,
+                    _ => Readiness::Exited,
+                };
+            }
+        }
+    }
+}
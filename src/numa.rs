@@ -0,0 +1,313 @@
+//! NUMA 绑定（仅Linux）：把内存分配限制在指定节点上，`--numa-bind-all` 时同时把线程限制在该节点的CPU上
+//!
+//! 不依赖 libnuma，直接调用 `set_mempolicy(MPOL_BIND)` 和 `sched_setaffinity`，效果分别与
+//! `numa_set_membind`、`numa_run_on_node` 相同。两者都只作用于调用线程，之后创建的线程继承，
+//! 因此要在分配内存和启动任何线程之前调用。
+//!
+//! `--numa-interleave-policy weighted` 则不改变线程的策略，而是用 `mbind(MPOL_BIND)` 把内存块按权重
+//! 切成若干段，每段绑定到一个节点，使各节点按比例提供页面。`--ensure-numa-coverage` 只检查分配后的
+//! 分布，在没有页面的节点上补充少量内存，保证每个节点都参与访问。
+
+use std::io;
+use std::ops::Range;
+
+use bytesize::ByteSize;
+use clap::ValueEnum;
+
+/// 按节点分配内存的策略
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum NumaInterleavePolicy {
+    /// 按 `--numa-weights` 的比例从各节点分配页面
+    Weighted,
+}
+
+/// 节点 `node` 在 `mbind`/`set_mempolicy` 中使用的节点掩码，以及传给内核的 maxnode
+#[cfg(target_os = "linux")]
+fn node_mask(node: usize) -> (Vec<libc::c_ulong>, libc::c_ulong) {
+    const BITS: usize = libc::c_ulong::BITS as usize;
+    let mut mask: Vec<libc::c_ulong> = vec![0; node / BITS + 1];
+    mask[node / BITS] |= 1 << (node % BITS);
+    // 内核只读取 maxnode - 1 位，因此多传一位
+    let maxnode = (mask.len() * BITS + 1) as libc::c_ulong;
+    (mask, maxnode)
+}
+
+/// 节点 `node` 上的逻辑CPU，节点不存在时返回错误
+#[cfg(target_os = "linux")]
+pub fn node_cpus(node: usize) -> io::Result<Vec<usize>> {
+    let list = std::fs::read_to_string(format!("/sys/devices/system/node/node{}/cpulist", node))?;
+    crate::topology::parse_cpu_list(&list).ok_or_else(|| io::Error::from(io::ErrorKind::InvalidData))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn node_cpus(_node: usize) -> io::Result<Vec<usize>> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+/// 有内存的NUMA节点
+#[cfg(target_os = "linux")]
+pub fn memory_nodes() -> io::Result<Vec<usize>> {
+    let list = std::fs::read_to_string("/sys/devices/system/node/has_memory")?;
+    crate::topology::parse_cpu_list(&list).ok_or_else(|| io::Error::from(io::ErrorKind::InvalidData))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn memory_nodes() -> io::Result<Vec<usize>> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+/// 之后的内存分配只从节点 `node` 获取；该节点内存不足时分配失败，而不是回退到其他节点
+#[cfg(target_os = "linux")]
+pub fn bind_memory(node: usize) -> io::Result<()> {
+    let (mask, maxnode) = node_mask(node);
+    // SAFETY: mask 在调用期间有效
+    let ret = unsafe { libc::syscall(libc::SYS_set_mempolicy, libc::MPOL_BIND, mask.as_ptr(), maxnode) };
+    if ret != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn bind_memory(_node: usize) -> io::Result<()> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+/// 把调用线程限制在 `cpus` 上运行
+#[cfg(target_os = "linux")]
+pub fn run_on_cpus(cpus: &[usize]) -> io::Result<()> {
+    // SAFETY: cpu_set_t 是普通位图，全零即空集合；pid=0 表示调用线程
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        for &cpu in cpus {
+            libc::CPU_SET(cpu, &mut set);
+        }
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn run_on_cpus(_cpus: &[usize]) -> io::Result<()> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+/// 解析 `--numa-weights` 的一项，例如 "0:70"
+pub fn parse_weight_entry(s: &str) -> Result<(usize, u8), String> {
+    let (node, percent) = s.split_once(':').ok_or_else(|| tr!("numa-weights-invalid", value = s))?;
+    let node = node.trim().parse().map_err(|_| tr!("numa-weights-invalid", value = s))?;
+    let percent = percent.trim();
+    let percent = percent.strip_suffix('%').unwrap_or(percent);
+    match percent.trim().parse::<u8>() {
+        Ok(percent @ 1..=100) => Ok((node, percent)),
+        _ => Err(tr!("numa-weights-invalid", value = s)),
+    }
+}
+
+/// 节点 `node` 是否存在（包括只有内存没有CPU的节点）
+pub fn node_exists(node: usize) -> bool {
+    std::path::Path::new(&format!("/sys/devices/system/node/node{}", node)).exists()
+}
+
+/// 按权重把一块内存切成若干段并分别绑定到各个节点
+pub struct WeightedNumaAllocator {
+    /// (节点, 百分比)，百分比之和为100
+    weights: Vec<(usize, u8)>,
+}
+
+impl WeightedNumaAllocator {
+    pub fn new(weights: Vec<(usize, u8)>) -> Self {
+        WeightedNumaAllocator { weights }
+    }
+
+    /// 把 `[start, end)` 按页面边界切成每个节点一段；`start` 所在的页并入第一段，末尾不足一页的部分并入最后一段
+    fn regions(&self, start: usize, end: usize) -> Vec<(usize, Range<usize>)> {
+        let page = page_size();
+        let base = start & !(page - 1);
+        let pages = (end - base) / page;
+        let mut region_start = base;
+        let mut cumulative = 0usize;
+        self.weights
+            .iter()
+            .enumerate()
+            .map(|(i, &(node, percent))| {
+                cumulative += usize::from(percent);
+                // 按累计比例取整，避免逐段取整的误差累积到最后一段
+                let region_end = if i + 1 == self.weights.len() { end } else { base + pages * cumulative / 100 * page };
+                let range = region_start..region_end;
+                region_start = region_end;
+                (node, range)
+            })
+            .collect()
+    }
+
+    /// 在首次访问之前把 `buffer` 的各段绑定到对应节点，之后缺页时才按策略分配物理页面
+    pub fn bind(&self, buffer: &mut [u8]) -> io::Result<()> {
+        let start = buffer.as_mut_ptr() as usize;
+        for (node, range) in self.regions(start, start + buffer.len()) {
+            if !range.is_empty() {
+                mbind(range, node)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 从 `/proc/self/numa_maps` 统计 `buffer` 在各节点上实际驻留的字节数，与要求的比例一同输出
+    pub fn report(&self, buffer: &[u8]) {
+        let start = buffer.as_ptr() as usize;
+        let Some(resident) = resident_by_node(start..start + buffer.len()) else {
+            warn!("{}", tr!("numa-weights-report-unavailable"));
+            return;
+        };
+        let total: u64 = resident.iter().map(|(_, bytes)| bytes).sum();
+        info!("{}", tr!("numa-weights-header"));
+        for &(node, percent) in &self.weights {
+            let bytes = resident.iter().find(|(n, _)| *n == node).map_or(0, |(_, bytes)| *bytes);
+            let achieved = if total > 0 { bytes as f64 * 100.0 / total as f64 } else { 0.0 };
+            info!(
+                "  {}",
+                tr!(
+                    "numa-weights-entry",
+                    node = node,
+                    requested = percent,
+                    achieved = format!("{:.1}", achieved),
+                    size = ByteSize::b(bytes)
+                )
+            );
+        }
+        // 绑定的节点内存不足时分配失败而不是回退，其余节点上的页面来自绑定之前已经访问过的部分
+        for &(node, bytes) in resident.iter().filter(|(n, _)| !self.weights.iter().any(|(w, _)| w == n)) {
+            warn!("{}", tr!("numa-weights-unexpected-node", node = node, size = ByteSize::b(bytes)));
+        }
+    }
+}
+
+/// `--ensure-numa-coverage` 在没有页面的节点上补充分配的大小
+const COVERAGE_BYTES: usize = 4 * 1024 * 1024;
+
+/// 在节点 `node` 上分配 `COVERAGE_BYTES` 并写入，使页面确实来自该节点
+fn alloc_on_node(node: usize) -> io::Result<Vec<u8>> {
+    let page = page_size();
+    let mut block = crate::memory::try_alloc_zeroed(COVERAGE_BYTES + page)
+        .ok_or_else(|| io::Error::from(io::ErrorKind::OutOfMemory))?;
+    let start = (block.as_ptr() as usize).next_multiple_of(page);
+    mbind(start..start + COVERAGE_BYTES, node)?;
+    crate::memory::prefault(&mut block, false);
+    Ok(block)
+}
+
+/// 已写入页面的 `buffer` 在各节点上的驻留分布中，若有内存的节点没有任何页面，就在该节点上
+/// 补充分配4MiB并输出最终的分布；返回补充的内存块，调用方持有到负载结束
+pub fn ensure_coverage(buffer: &[u8]) -> Vec<Vec<u8>> {
+    let range = |b: &[u8]| b.as_ptr() as usize..b.as_ptr() as usize + b.len();
+    let (Ok(nodes), Some(resident)) = (memory_nodes(), resident_by_node(range(buffer))) else {
+        warn!("{}", tr!("numa-coverage-unavailable"));
+        return Vec::new();
+    };
+    let mut extra = Vec::new();
+    for &node in nodes.iter().filter(|&&node| !resident.iter().any(|&(n, bytes)| n == node && bytes > 0)) {
+        match alloc_on_node(node) {
+            Ok(block) => {
+                info!("{}", tr!("numa-coverage-added", node = node, size = ByteSize::b(COVERAGE_BYTES as u64)));
+                extra.push(block);
+            }
+            Err(e) => warn!("{}", tr!("numa-coverage-failed", node = node, error = e)),
+        }
+    }
+
+    let mut totals: Vec<(usize, u64)> = nodes.iter().map(|&node| (node, 0)).collect();
+    for block in std::iter::once(buffer).chain(extra.iter().map(Vec::as_slice)) {
+        for (node, bytes) in resident_by_node(range(block)).unwrap_or_default() {
+            match totals.iter_mut().find(|(n, _)| *n == node) {
+                Some((_, total)) => *total += bytes,
+                None => totals.push((node, bytes)),
+            }
+        }
+    }
+    let sum: u64 = totals.iter().map(|(_, bytes)| bytes).sum();
+    let distribution: Vec<String> = totals
+        .iter()
+        .map(|&(node, bytes)| {
+            let percent = if sum > 0 { bytes as f64 * 100.0 / sum as f64 } else { 0.0 };
+            tr!("numa-coverage-node", node = node, percent = format!("{:.1}", percent))
+        })
+        .collect();
+    info!("{}", tr!("numa-coverage-distribution", nodes = distribution.join(", ")));
+    extra
+}
+
+fn page_size() -> usize {
+    #[cfg(unix)]
+    {
+        // SAFETY: sysconf 没有副作用
+        let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if size > 0 {
+            return size as usize;
+        }
+    }
+    4096
+}
+
+/// 把 `range` 绑定到节点 `node`；起始地址须按页对齐，由调用方保证
+#[cfg(target_os = "linux")]
+fn mbind(range: Range<usize>, node: usize) -> io::Result<()> {
+    let (mask, maxnode) = node_mask(node);
+    // SAFETY: range 位于调用方持有的内存块内，mbind 只修改该范围的内存策略
+    let ret = unsafe {
+        libc::syscall(
+            libc::SYS_mbind,
+            range.start as *mut libc::c_void,
+            range.len() as libc::c_ulong,
+            libc::MPOL_BIND,
+            mask.as_ptr(),
+            maxnode,
+            0 as libc::c_uint,
+        )
+    };
+    if ret != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn mbind(_range: Range<usize>, _node: usize) -> io::Result<()> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+/// 起始地址落在 `range` 内的映射在各节点上驻留的字节数，按节点编号排序；不可读时为 None
+fn resident_by_node(range: Range<usize>) -> Option<Vec<(usize, u64)>> {
+    let maps = std::fs::read_to_string("/proc/self/numa_maps").ok()?;
+    let mut resident: Vec<(usize, u64)> = Vec::new();
+    for line in maps.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(address) = fields.next().and_then(|a| usize::from_str_radix(a, 16).ok()) else {
+            continue;
+        };
+        // 绑定的第一段从内存块开头所在的页开始，可能早于内存块本身
+        if address < range.start & !(page_size() - 1) || address >= range.end {
+            continue;
+        }
+        let fields: Vec<&str> = fields.collect();
+        let page_kib: u64 = fields
+            .iter()
+            .find_map(|f| f.strip_prefix("kernelpagesize_kB=")?.parse().ok())
+            .unwrap_or(4);
+        for field in &fields {
+            let Some((node, pages)) = field.strip_prefix('N').and_then(|f| f.split_once('=')) else {
+                continue;
+            };
+            let (Ok(node), Ok(pages)) = (node.parse::<usize>(), pages.parse::<u64>()) else {
+                continue;
+            };
+            match resident.iter_mut().find(|(n, _)| *n == node) {
+                Some((_, bytes)) => *bytes += pages * page_kib * 1024,
+                None => resident.push((node, pages * page_kib * 1024)),
+            }
+        }
+    }
+    resident.sort_unstable();
+    Some(resident)
+}
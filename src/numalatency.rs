@@ -0,0 +1,140 @@
+//! `benchmark numa-latency`：测量本地与远端NUMA节点的内存访问延迟和拷贝带宽（仅Linux）
+//!
+//! 在本地节点和远端节点上各分配一块内存（`mbind(MPOL_BIND)`），测量线程绑定在本地节点的CPU上。
+//! 延迟用指针追逐测量：内存按缓存行分成若干槽，每个槽存放下一个槽的序号，所有槽连成一个随机的环，
+//! 每次读取都依赖上一次的结果，硬件预取无从下手，测得的就是一次未命中缓存的访存延迟。
+//! 远端与本地延迟之比即 NUMA 系数。带宽分别测量本地到本地、远端到本地的 memcpy。
+
+use std::time::{Duration, Instant};
+
+use bytesize::ByteSize;
+use rand::Rng;
+
+use crate::error::LoadError;
+use crate::memory;
+use crate::numa::{self, WeightedNumaAllocator};
+use crate::seed;
+
+/// 每项测量的时长
+const MEASURE_TIME: Duration = Duration::from_secs(2);
+
+/// 每次检查时间之前追逐的步数
+const CHASE_BATCH: usize = 100_000;
+
+/// 缓存行大小，每个槽占一条缓存行
+const CACHE_LINE: usize = 64;
+
+/// 每个槽的字数
+const SLOT_WORDS: usize = CACHE_LINE / 8;
+
+/// 在节点 `node` 上分配 `size` 字节并预分配全部页面
+fn alloc_on_node(size: usize, node: usize) -> Result<Vec<u8>, LoadError> {
+    let mut buffer = memory::try_alloc_zeroed(size).ok_or(LoadError::MemoryAllocationFailed(size))?;
+    WeightedNumaAllocator::new(vec![(node, 100)])
+        .bind(&mut buffer)
+        .map_err(|e| std::io::Error::new(e.kind(), tr!("numa-latency-bind-failed", node = node, error = e)))?;
+    memory::prefault(&mut buffer, false);
+    Ok(buffer)
+}
+
+/// 把 `words` 按槽连成一个随机的环（Sattolo 算法保证只有一个环），每个槽的第一个字存放下一个槽的序号
+fn build_chain(words: &mut [u64]) {
+    let slots = words.len() / SLOT_WORDS;
+    let mut order: Vec<usize> = (0..slots).collect();
+    let mut rng = seed::thread_rng(seed::NUMA_LATENCY_STREAM);
+    for i in (1..slots).rev() {
+        order.swap(i, rng.gen_range(0..i));
+    }
+    for i in 0..slots {
+        words[order[i] * SLOT_WORDS] = order[(i + 1) % slots] as u64;
+    }
+}
+
+/// 沿着环追逐 `MEASURE_TIME`，返回每次访问的平均延迟（纳秒）
+fn chase(words: &[u64]) -> f64 {
+    let mut slot = 0usize;
+    let mut steps = 0u64;
+    let start = Instant::now();
+    while start.elapsed() < MEASURE_TIME {
+        for _ in 0..CHASE_BATCH {
+            slot = words[slot * SLOT_WORDS] as usize;
+        }
+        steps += CHASE_BATCH as u64;
+    }
+    std::hint::black_box(slot);
+    start.elapsed().as_nanos() as f64 / steps as f64
+}
+
+/// 在 `buffer` 上建立指针环并测量延迟（纳秒）
+fn measure_latency(buffer: &mut [u8]) -> f64 {
+    // SAFETY: 任意字节序列都是合法的 u64，align_to_mut 只取出对齐的中间部分
+    let (_, words, _) = unsafe { buffer.align_to_mut::<u64>() };
+    build_chain(words);
+    chase(words)
+}
+
+/// 反复把 `src` 拷贝到 `dst`，持续 `MEASURE_TIME`，返回带宽（GB/s）
+fn measure_copy(src: &[u8], dst: &mut [u8]) -> f64 {
+    let len = src.len().min(dst.len());
+    let mut bytes = 0u64;
+    let start = Instant::now();
+    while start.elapsed() < MEASURE_TIME {
+        dst[..len].copy_from_slice(&src[..len]);
+        std::hint::black_box(&dst[0]);
+        bytes += len as u64;
+    }
+    bytes as f64 / start.elapsed().as_secs_f64() / 1e9
+}
+
+/// 测量本地节点 `local` 与远端节点 `remote` 的延迟和带宽；未指定远端节点时取第一个其他有内存的节点
+pub fn run(local: usize, remote: Option<usize>, size: ByteSize) -> Result<(), LoadError> {
+    let nodes = numa::memory_nodes()
+        .map_err(|e| std::io::Error::new(e.kind(), tr!("numa-latency-unsupported", error = e)))?;
+    if !nodes.contains(&local) {
+        return Err(LoadError::InvalidArgument(tr!("numa-weights-node-missing", node = local)));
+    }
+    let remote = match remote {
+        Some(node) if node == local => return Err(LoadError::InvalidArgument(tr!("numa-latency-same-node"))),
+        Some(node) if !nodes.contains(&node) => {
+            return Err(LoadError::InvalidArgument(tr!("numa-weights-node-missing", node = node)));
+        }
+        Some(node) => node,
+        None => *nodes
+            .iter()
+            .find(|&&node| node != local)
+            .ok_or_else(|| LoadError::InvalidArgument(tr!("numa-latency-single-node")))?,
+    };
+    let cpus = numa::node_cpus(local)
+        .ok()
+        .filter(|cpus| !cpus.is_empty())
+        .ok_or_else(|| LoadError::InvalidArgument(tr!("numa-node-invalid", node = local)))?;
+    numa::run_on_cpus(&cpus)?;
+
+    // 缓冲区应远大于末级缓存，否则测到的是缓存延迟
+    let size = (size.as_u64() as usize).max(CACHE_LINE * 2);
+    info!("{}", tr!("numa-latency-started", local = local, remote = remote, size = ByteSize::b(size as u64)));
+    let mut local_buffer = alloc_on_node(size, local)?;
+    let mut remote_buffer = alloc_on_node(size, remote)?;
+
+    let local_ns = measure_latency(&mut local_buffer);
+    let remote_ns = measure_latency(&mut remote_buffer);
+    info!("  {}", tr!("numa-latency-local", node = local, ns = format!("{:.1}", local_ns)));
+    info!("  {}", tr!("numa-latency-remote", node = remote, ns = format!("{:.1}", remote_ns)));
+    info!("  {}", tr!("numa-latency-factor", factor = format!("{:.2}", remote_ns / local_ns.max(f64::EPSILON))));
+
+    // 本地到本地拷贝在同一块内存的两半之间进行，与远端到本地拷贝的数据量相同
+    let half = size / 2;
+    let (local_src, local_dst) = local_buffer.split_at_mut(half);
+    let local_gbps = measure_copy(local_src, local_dst);
+    let remote_gbps = measure_copy(&remote_buffer[..half], local_dst);
+    info!("  {}", tr!("numa-latency-copy-local", bandwidth = format!("{:.2}", local_gbps)));
+    info!(
+        "  {}",
+        tr!(
+            "numa-latency-copy-remote",
+            bandwidth = format!("{:.2}", remote_gbps),
+            percent = format!("{:.1}", remote_gbps * 100.0 / local_gbps.max(f64::EPSILON))
+        )
+    );
+    Ok(())
+}
@@ -0,0 +1,207 @@
+//! 小对象内存：把 `-m` 的总量拆成大量独立分配的固定大小对象，而不是一整块缓冲区
+//!
+//! 每个对象都由分配器单独记录，分配器元数据（空闲链表、arena、页表项）承受的压力
+//! 远大于一次大块分配；内存访问模式下按随机顺序访问各个对象。
+//!
+//! `--memory-dealloc-pattern` 把总量拆成 `--chunk-count` 块，退出时按指定顺序逐块释放并计时，
+//! 比较分配器在不同释放顺序下合并空闲块、归还内存的开销。
+//!
+//! `--memory-fragmentation` 把总量拆成随机大小的小块，并由后台线程不断释放、重新分配其中一部分，
+//! 让堆中持续出现大小不一的空洞。
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use bytesize::ByteSize;
+use clap::ValueEnum;
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::histogram::LatencyHistogram;
+use crate::memory;
+use crate::seed;
+use crate::state::LoadState;
+
+/// 分配器开销基准中每种大小的分配次数
+const BENCH_ALLOCATIONS: usize = 1000;
+
+/// 分配器开销基准测量的对象大小
+const BENCH_SIZES: [u64; 4] = [1 << 10, 4 << 10, 64 << 10, 1 << 20];
+
+/// `--memory-fragmentation` 小块的最小和最大字节数
+const FRAGMENT_SIZES: std::ops::RangeInclusive<usize> = 64..=4096;
+
+/// 抖动线程每轮重新分配的小块比例
+const CHURN_FRACTION: f64 = 0.1;
+
+/// 抖动线程两轮之间的间隔
+const CHURN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 检查运行标志的间隔，停止时抖动线程最多延迟这么久退出
+const CHURN_TICK: Duration = Duration::from_millis(100);
+
+/// 分配 `total / object_size` 个全零对象，任一分配失败时返回 None
+pub fn alloc_objects(total: u64, object_size: u64) -> Option<Vec<Box<[u8]>>> {
+    let count = (total / object_size.max(1)) as usize;
+    let mut objects = Vec::new();
+    objects.try_reserve_exact(count).ok()?;
+    for _ in 0..count {
+        objects.push(memory::try_alloc_zeroed(object_size as usize)?.into_boxed_slice());
+    }
+    Some(objects)
+}
+
+/// 退出时释放各块内存的顺序
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum DeallocPattern {
+    /// 按 `--random-seed` 打乱的随机顺序，考验空闲链表的管理
+    Random,
+    /// 后分配的先释放
+    Lifo,
+    /// 先分配的先释放，容易在堆中留下碎片
+    Fifo,
+}
+
+impl DeallocPattern {
+    pub fn name(self) -> &'static str {
+        match self {
+            DeallocPattern::Random => "random",
+            DeallocPattern::Lifo => "lifo",
+            DeallocPattern::Fifo => "fifo",
+        }
+    }
+}
+
+/// 把 `total` 拆成 `count` 块等大的内存分别分配，并让每块的页面都分配物理内存；
+/// 释放已写入的页面才能体现分配器归还内存的开销
+pub fn alloc_chunks(total: u64, count: u64) -> Option<Vec<Box<[u8]>>> {
+    let mut chunks = alloc_objects(total, total / count.max(1))?;
+    for chunk in &mut chunks {
+        memory::prefault(chunk, false);
+    }
+    Some(chunks)
+}
+
+/// 按 `pattern` 的顺序逐块释放，输出总耗时和单块释放耗时的分布
+pub fn dealloc_chunks(chunks: Vec<Box<[u8]>>, pattern: DeallocPattern) {
+    let mut order: Vec<usize> = (0..chunks.len()).collect();
+    match pattern {
+        DeallocPattern::Random => order.shuffle(&mut seed::thread_rng(seed::DEALLOC_STREAM)),
+        DeallocPattern::Lifo => order.reverse(),
+        DeallocPattern::Fifo => {}
+    }
+    let count = chunks.len();
+    let mut chunks: Vec<Option<Box<[u8]>>> = chunks.into_iter().map(Some).collect();
+    // 单块释放耗时（纳秒）
+    let mut latencies = LatencyHistogram::default();
+    let start = Instant::now();
+    for i in order {
+        let chunk = chunks[i].take();
+        let freed = Instant::now();
+        drop(chunk);
+        latencies.record(freed.elapsed().as_nanos() as u64);
+    }
+    let total = start.elapsed();
+    let us = |nanos: u64| format!("{:.1}", nanos as f64 / 1e3);
+    info!(
+        "{}",
+        tr!(
+            "dealloc-report",
+            pattern = pattern.name(),
+            count = count,
+            ms = format!("{:.2}", total.as_secs_f64() * 1e3),
+            mean = format!("{:.1}", latencies.mean() / 1e3),
+            p50 = us(latencies.percentile(50.0)),
+            p99 = us(latencies.percentile(99.0)),
+            max = us(latencies.max())
+        )
+    );
+}
+
+/// 随机大小的小块，写入内容使页面分配物理内存
+fn alloc_fragment(rng: &mut impl Rng) -> Option<Box<[u8]>> {
+    let mut fragment = memory::try_alloc_zeroed(rng.gen_range(FRAGMENT_SIZES))?;
+    fragment.fill(0xA5);
+    Some(fragment.into_boxed_slice())
+}
+
+/// 把 `total` 拆成64B-4KiB随机大小的小块分别分配，任一分配失败时返回 None
+pub fn alloc_fragments(total: u64) -> Option<Vec<Box<[u8]>>> {
+    let mut rng = seed::thread_rng(seed::FRAGMENT_STREAMS);
+    let mut fragments = Vec::new();
+    let mut allocated = 0;
+    while allocated < total {
+        let fragment = alloc_fragment(&mut rng)?;
+        allocated += fragment.len() as u64;
+        fragments.push(fragment);
+    }
+    Some(fragments)
+}
+
+/// 启动抖动线程：每秒先释放 `state.objects` 中随机10%的小块，再为它们重新分配随机大小的小块，
+/// 并更新已分配的字节数
+pub fn spawn_fragment_churn(state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut rng = seed::thread_rng(seed::FRAGMENT_STREAMS + 1);
+        let mut next = Instant::now() + CHURN_INTERVAL;
+        while state.is_running() {
+            if Instant::now() < next {
+                thread::sleep(CHURN_TICK);
+                continue;
+            }
+            next += CHURN_INTERVAL;
+            let mut objects = state.objects.lock().unwrap_or_else(|e| e.into_inner());
+            let count = (objects.len() as f64 * CHURN_FRACTION).ceil() as usize;
+            let chosen = rand::seq::index::sample(&mut rng, objects.len(), count.min(objects.len()));
+            // 全部释放后再分配，新的小块才有机会落入刚释放的空洞
+            for i in chosen.iter() {
+                objects[i] = Box::default();
+            }
+            for i in chosen.iter() {
+                // 分配失败时留下空块，下一轮可能再次选中
+                if let Some(fragment) = alloc_fragment(&mut rng) {
+                    objects[i] = fragment;
+                }
+            }
+            state.allocated_bytes.store(total_bytes(&objects), Ordering::Relaxed);
+        }
+    })
+}
+
+/// 对象占用的总字节数
+pub fn total_bytes(objects: &[Box<[u8]>]) -> u64 {
+    objects.iter().map(|object| object.len() as u64).sum()
+}
+
+/// 连续分配 `BENCH_ALLOCATIONS` 个 `size` 字节的对象，返回平均每次分配的耗时；
+/// 分配期间对象全部保留，测到的是分配器扩展堆的开销而不是复用刚释放的块
+fn time_allocations(size: u64) -> Option<Duration> {
+    let mut objects = Vec::with_capacity(BENCH_ALLOCATIONS);
+    let start = Instant::now();
+    for _ in 0..BENCH_ALLOCATIONS {
+        objects.push(memory::try_alloc_zeroed(size as usize)?.into_boxed_slice());
+    }
+    let elapsed = start.elapsed();
+    std::hint::black_box(&objects);
+    Some(elapsed / BENCH_ALLOCATIONS as u32)
+}
+
+/// 输出几种典型对象大小下单次分配的平均耗时，便于比较分配器开销
+pub fn report_allocator_overhead() {
+    info!("{}", tr!("objects-bench-header", count = BENCH_ALLOCATIONS));
+    for size in BENCH_SIZES {
+        match time_allocations(size) {
+            Some(per_alloc) => info!(
+                "  {}",
+                tr!(
+                    "objects-bench-size",
+                    size = ByteSize::b(size),
+                    ns = format!("{:.0}", per_alloc.as_secs_f64() * 1e9)
+                )
+            ),
+            None => warn!("{}", tr!("objects-bench-failed", size = ByteSize::b(size))),
+        }
+    }
+}
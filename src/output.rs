@@ -0,0 +1,388 @@
+//! 状态输出渲染：文本、JSON、YAML、表格
+
+use std::fmt::Write;
+
+use clap::ValueEnum;
+
+use crate::diskstats::DiskRates;
+use crate::iostress::IoRates;
+use crate::json::{self, Json};
+use crate::memorypressure::MemoryPsi;
+use crate::netstats::NetRates;
+use crate::perf::PerfMetrics;
+use crate::ramp::RampPhase;
+use crate::schedule::ScheduleStatus;
+use crate::syscalls::SyscallRates;
+use crate::timeutil;
+
+/// 1 GiB 的字节数
+pub const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// 输出格式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    Yaml,
+    Table,
+}
+
+/// 采样所处的运行阶段
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RunPhase {
+    /// 正在施加负载
+    #[default]
+    Load,
+    /// 负载结束后的空闲测量阶段
+    Measurement,
+}
+
+impl RunPhase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RunPhase::Load => "load",
+            RunPhase::Measurement => "post-load-measurement",
+        }
+    }
+}
+
+/// 状态线程每次刷新采集到的数据
+#[derive(Clone, Debug)]
+pub struct Stats {
+    /// Unix时间戳（毫秒）
+    pub timestamp_ms: u64,
+    /// 所处阶段
+    pub phase: RunPhase,
+    /// 负载核心的平均CPU使用率
+    pub cpu_usage: f32,
+    /// 本次采样间隔内的CPU窃取时间占比（百分比），非虚拟机上通常为0，不可读时为 None
+    pub cpu_steal: Option<f64>,
+    /// 目标CPU使用率，未设置时为 None
+    pub target_percent: Option<u8>,
+    /// 目标使用率的随机扰动幅度（百分点），未启用 `--cpu-noise` 时为 None
+    pub target_noise: Option<u8>,
+    /// 当前脉冲周期，未启用脉冲模式时为 None
+    pub burst_cycle: Option<u32>,
+    /// `--ramp-up`/`--ramp-down` 所处的阶段，未启用时为 None
+    pub ramp_phase: Option<RampPhase>,
+    /// `--load-schedule` 所处的阶段，未启用时为 None
+    pub schedule: Option<ScheduleStatus>,
+    /// 距 `--duration` 到期的剩余秒数，未设置持续时间时为 None
+    pub remaining_secs: Option<u64>,
+    /// 系统已用内存（字节）
+    pub memory_used: u64,
+    /// 系统总内存（字节）
+    pub memory_total: u64,
+    /// 本工具分配的内存（字节），未分配时为 None
+    pub memory_allocated: Option<u64>,
+    /// 被 mlock 锁定的内存（字节），未锁定时为 None
+    pub memory_locked: Option<u64>,
+    /// `/proc/pressure/memory` 的内存压力值，内核不支持时为 None
+    pub memory_pressure: Option<MemoryPsi>,
+    /// 透明大页覆盖率（百分比），未启用 `--memory-huge-pages-status` 或不可读时为 None
+    pub thp_coverage: Option<f64>,
+    /// 本次采样间隔内的硬件计数器指标，未启用时为 None
+    pub perf: Option<PerfMetrics>,
+    /// 本进程的系统调用和上下文切换速率，未启用时为 None
+    pub syscalls: Option<SyscallRates>,
+    /// 各磁盘的I/O速率，按吞吐量从高到低排序；无法统计时为 None
+    pub disks: Option<Vec<DiskRates>>,
+    /// 各网络接口的速率，按吞吐量从高到低排序
+    pub networks: Vec<NetRates>,
+    /// `io-stress` 本次采样间隔内的 IOPS 和吞吐量，未运行时为 None
+    pub io_stress: Option<IoRates>,
+}
+
+impl Stats {
+    /// 转换为结构化数据，供JSON、YAML和表格渲染器共用
+    pub fn to_json(&self) -> Json {
+        Json::object([
+            ("timestamp_ms", self.timestamp_ms.into()),
+            ("phase", self.phase.as_str().into()),
+            ("cpu_usage_percent", self.cpu_usage.into()),
+            ("cpu_steal_percent", self.cpu_steal.into()),
+            ("target_percent", self.target_percent.into()),
+            ("target_noise_percent", self.target_noise.into()),
+            ("burst_cycle", self.burst_cycle.into()),
+            ("ramp_phase", self.ramp_phase.map(RampPhase::as_str).into()),
+            ("schedule_phase", self.schedule.map(|s| s.phase.as_str()).into()),
+            ("schedule_phase_elapsed_secs", self.schedule.map(|s| s.since.elapsed().as_secs()).into()),
+            ("schedule_remaining_repeats", self.schedule.and_then(|s| s.remaining).into()),
+            ("remaining_secs", self.remaining_secs.into()),
+            ("memory_used_bytes", self.memory_used.into()),
+            ("memory_total_bytes", self.memory_total.into()),
+            ("memory_allocated_bytes", self.memory_allocated.into()),
+            ("memory_locked_bytes", self.memory_locked.into()),
+            ("memory_pressure", self.memory_pressure.map(MemoryPsi::to_json).into()),
+            ("thp_coverage_percent", self.thp_coverage.into()),
+            ("perf", self.perf.map(PerfMetrics::to_json).into()),
+            ("syscalls", self.syscalls.map(SyscallRates::to_json).into()),
+            (
+                "disks",
+                self.disks.as_ref().map(|disks| disks.iter().map(DiskRates::to_json).collect::<Vec<_>>()).into(),
+            ),
+            ("networks", self.networks.iter().map(NetRates::to_json).collect::<Vec<_>>().into()),
+            ("io_stress", self.io_stress.map(IoRates::to_json).into()),
+        ])
+    }
+
+    /// 吞吐量最高且有I/O的磁盘
+    fn busiest_disk(&self) -> Option<&DiskRates> {
+        self.disks.as_ref()?.first().filter(|disk| disk.throughput() > 0.0)
+    }
+
+    /// 吞吐量最高且有流量的网络接口
+    fn busiest_network(&self) -> Option<&NetRates> {
+        self.networks.first().filter(|net| net.throughput() > 0.0)
+    }
+}
+
+/// 状态渲染器
+pub trait Renderer: Send {
+    /// 将一次状态采样渲染为待输出的文本
+    fn render_status(&self, stats: &Stats) -> String;
+}
+
+/// 按输出格式创建渲染器
+pub fn renderer(format: OutputFormat) -> Box<dyn Renderer> {
+    match format {
+        OutputFormat::Text => Box::new(TextRenderer),
+        OutputFormat::Json => Box::new(JsonRenderer),
+        OutputFormat::Yaml => Box::new(YamlRenderer),
+        OutputFormat::Table => Box::new(TableRenderer),
+    }
+}
+
+/// 人类可读的文本格式（默认）
+pub struct TextRenderer;
+
+impl Renderer for TextRenderer {
+    fn render_status(&self, stats: &Stats) -> String {
+        let usage = format!("{:.1}", stats.cpu_usage);
+        let mut out = match (stats.target_percent, stats.target_noise) {
+            (Some(target), Some(noise)) => {
+                tr!("output-cpu-usage-target-noise", usage = usage, target = target, noise = noise)
+            }
+            (Some(target), None) => tr!("output-cpu-usage-target", usage = usage, target = target),
+            (None, _) => tr!("output-cpu-usage", usage = usage),
+        };
+        if let Some(cycle) = stats.burst_cycle {
+            let _ = write!(out, " [{}]", tr!("output-burst-cycle", cycle = cycle));
+        }
+        if let Some(phase) = stats.ramp_phase {
+            let _ = write!(out, " [{}]", phase.describe());
+        }
+        if let Some(schedule) = stats.schedule {
+            let _ = write!(out, " [{}]", describe_schedule(schedule));
+        }
+        if let Some(secs) = stats.remaining_secs {
+            let _ = write!(out, " [{}]", tr!("output-remaining", remaining = timeutil::format_remaining(secs)));
+        }
+        // 没有窃取时间（物理机）时不显示
+        if let Some(steal) = stats.cpu_steal.filter(|&steal| steal > 0.0) {
+            let _ = write!(out, " | {}", tr!("output-cpu-steal", percent = format!("{:.1}", steal)));
+        }
+        if let Some(perf) = &stats.perf {
+            let _ = write!(out, " | {}", perf.describe());
+        }
+        if let Some(syscalls) = &stats.syscalls {
+            let _ = write!(out, " | {}", syscalls.describe());
+        }
+        if let Some(disk) = stats.busiest_disk() {
+            let _ = write!(out, " | {} {}", tr!("output-disk"), disk.describe());
+        }
+        if let Some(net) = stats.busiest_network() {
+            let _ = write!(out, " | NET: {}", net.describe());
+        }
+        if let Some(io) = &stats.io_stress {
+            let _ = write!(out, " | IO: {}", io.describe());
+        }
+        if stats.phase == RunPhase::Measurement {
+            out.insert_str(0, "[POST-LOAD MEASUREMENT] ");
+        }
+        // 未指定内存大小时不显示内存信息
+        if let Some(allocated) = stats.memory_allocated {
+            let _ = write!(
+                out,
+                "\n{}",
+                tr!(
+                    "output-memory-usage",
+                    used = format!("{:.1}", stats.memory_used as f64 / GIB),
+                    total = format!("{:.1}", stats.memory_total as f64 / GIB),
+                    allocated = format!("{:.1}", allocated as f64 / GIB)
+                )
+            );
+            if let Some(locked) = stats.memory_locked {
+                let _ = write!(out, " {}", tr!("output-memory-locked", locked = format!("{:.1}", locked as f64 / GIB)));
+            }
+            if let Some(coverage) = stats.thp_coverage {
+                let _ = write!(out, " | {}", tr!("output-thp-coverage", coverage = format!("{:.1}", coverage)));
+            }
+            if let Some(psi) = &stats.memory_pressure {
+                let _ = write!(out, " | {}", psi.describe());
+            }
+        }
+        out
+    }
+}
+
+/// 循环负载的阶段、阶段已持续的时间和剩余周期数，例如 "ACTIVE 3s, 剩余 2 个周期"
+fn describe_schedule(schedule: ScheduleStatus) -> String {
+    let elapsed = schedule.since.elapsed().as_secs();
+    let remaining = match schedule.remaining {
+        Some(count) => tr!("output-schedule-remaining", count = count),
+        None => tr!("output-schedule-forever"),
+    };
+    format!("{} {}s, {}", schedule.phase.as_str(), elapsed, remaining)
+}
+
+/// 每次采样输出一行JSON
+pub struct JsonRenderer;
+
+impl Renderer for JsonRenderer {
+    fn render_status(&self, stats: &Stats) -> String {
+        stats.to_json().to_string()
+    }
+}
+
+/// 每次采样输出一个YAML文档
+pub struct YamlRenderer;
+
+impl Renderer for YamlRenderer {
+    fn render_status(&self, stats: &Stats) -> String {
+        let mut out = String::from("---");
+        write_yaml(&mut out, &stats.to_json(), 0);
+        out
+    }
+}
+
+fn write_yaml(out: &mut String, value: &Json, indent: usize) {
+    match value {
+        Json::Object(fields) if !fields.is_empty() => {
+            for (key, value) in fields {
+                out.push('\n');
+                out.push_str(&"  ".repeat(indent));
+                out.push_str(key);
+                out.push(':');
+                write_yaml(out, value, indent + 1);
+            }
+        }
+        Json::Array(items) if !items.is_empty() => {
+            for item in items {
+                out.push('\n');
+                out.push_str(&"  ".repeat(indent));
+                out.push('-');
+                write_yaml(out, item, indent + 1);
+            }
+        }
+        Json::String(s) => {
+            out.push(' ');
+            json::write_string(out, s);
+        }
+        other => {
+            let _ = write!(out, " {}", other);
+        }
+    }
+}
+
+/// 使用制表符绘制的表格
+pub struct TableRenderer;
+
+impl Renderer for TableRenderer {
+    fn render_status(&self, stats: &Stats) -> String {
+        let mut rows = Vec::new();
+        if stats.phase == RunPhase::Measurement {
+            rows.push((tr!("output-table-phase"), "POST-LOAD MEASUREMENT".to_string()));
+        }
+        rows.push((tr!("output-table-cpu"), format!("{:.1}%", stats.cpu_usage)));
+        if let Some(target) = stats.target_percent {
+            let value = match stats.target_noise {
+                Some(noise) => format!("{}% ±{}%", target, noise),
+                None => format!("{}%", target),
+            };
+            rows.push((tr!("output-table-target"), value));
+        }
+        if let Some(cycle) = stats.burst_cycle {
+            rows.push((tr!("output-table-burst-cycle"), cycle.to_string()));
+        }
+        if let Some(phase) = stats.ramp_phase {
+            rows.push((tr!("output-table-ramp"), phase.describe()));
+        }
+        if let Some(schedule) = stats.schedule {
+            rows.push((tr!("output-table-schedule"), describe_schedule(schedule)));
+        }
+        if let Some(secs) = stats.remaining_secs {
+            rows.push((tr!("output-table-remaining"), timeutil::format_remaining(secs)));
+        }
+        if let Some(steal) = stats.cpu_steal.filter(|&steal| steal > 0.0) {
+            rows.push((tr!("output-table-steal"), format!("{:.1}%", steal)));
+        }
+        if let Some(perf) = &stats.perf {
+            rows.push((tr!("output-table-perf"), perf.describe()));
+        }
+        if let Some(syscalls) = &stats.syscalls {
+            rows.push((tr!("output-table-syscalls"), syscalls.describe()));
+        }
+        if let Some(disk) = stats.busiest_disk() {
+            rows.push((tr!("output-table-disk"), disk.describe()));
+        }
+        if let Some(net) = stats.busiest_network() {
+            rows.push((tr!("output-table-network"), net.describe()));
+        }
+        if let Some(io) = &stats.io_stress {
+            rows.push((tr!("output-table-io-stress"), io.describe()));
+        }
+        rows.push((
+            tr!("output-table-memory"),
+            format!("{:.1}GB / {:.1}GB", stats.memory_used as f64 / GIB, stats.memory_total as f64 / GIB),
+        ));
+        if let Some(allocated) = stats.memory_allocated {
+            rows.push((tr!("output-table-allocated"), format!("{:.1}GB", allocated as f64 / GIB)));
+        }
+        if let Some(locked) = stats.memory_locked {
+            rows.push((tr!("output-table-locked"), format!("{:.1}GB", locked as f64 / GIB)));
+        }
+        if let Some(coverage) = stats.thp_coverage {
+            rows.push((tr!("output-table-thp"), format!("{:.1}%", coverage)));
+        }
+        if let Some(psi) = &stats.memory_pressure {
+            rows.push((tr!("output-table-pressure"), psi.describe()));
+        }
+        draw_table(&rows)
+    }
+}
+
+/// 绘制两列表格，按终端显示宽度对齐（中日韩字符占两列）
+fn draw_table(rows: &[(String, String)]) -> String {
+    let key_width = rows.iter().map(|(k, _)| display_width(k)).max().unwrap_or(0);
+    let value_width = rows.iter().map(|(_, v)| display_width(v)).max().unwrap_or(0);
+    let border = |left: &str, mid: &str, right: &str| {
+        format!("{}{}{}{}{}", left, "─".repeat(key_width + 2), mid, "─".repeat(value_width + 2), right)
+    };
+
+    let mut out = border("┌", "┬", "┐");
+    for (key, value) in rows {
+        let _ = write!(
+            out,
+            "\n│ {}{} │ {}{} │",
+            key,
+            " ".repeat(key_width - display_width(key)),
+            value,
+            " ".repeat(value_width - display_width(value))
+        );
+    }
+    out.push('\n');
+    out.push_str(&border("└", "┴", "┘"));
+    out
+}
+
+/// 估算字符串在终端中的显示宽度
+fn display_width(s: &str) -> usize {
+    s.chars()
+        .map(|c| match c as u32 {
+            0x1100..=0x115F | 0x2E80..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFF00..=0xFF60 => 2,
+            _ => 1,
+        })
+        .sum()
+}
@@ -0,0 +1,176 @@
+//! 页缓存压力：多个线程从一个大文件中随机 pread 4KiB 页面，把文件内容读入内核页缓存
+//!
+//! 读入的页面与应用自己的缓存争用物理内存，配合 `-m` 时同时考验物理内存和内核的页缓存回收算法。
+//! 未指定 `--file` 时在 `-p` 目录下创建 `--file-size` 大小的临时文件，写完后从页缓存中丢弃，
+//! 使读取从冷缓存开始。页缓存命中率由本进程实际从存储读取的字节数（`/proc/self/io` 的
+//! `read_bytes`）与请求读取的字节数之比得出；同时输出 `/proc/vmstat` 中 `pgmajfault` 的增量。
+
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use bytesize::ByteSize;
+use rand::{Rng, RngCore};
+
+use crate::diskio;
+use crate::seed;
+use crate::state::LoadState;
+
+/// 每次读取的页面大小
+const PAGE: usize = 4096;
+
+/// 输出统计的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 创建临时文件时每次写入的大小
+const WRITE_CHUNK: usize = 1 << 20;
+
+/// 被读取的文件；临时文件随之删除
+enum Source {
+    Existing(PathBuf),
+    Temp(tempfile::NamedTempFile),
+}
+
+impl Source {
+    fn path(&self) -> &Path {
+        match self {
+            Source::Existing(path) => path,
+            Source::Temp(file) => file.path(),
+        }
+    }
+}
+
+/// 在 `dir` 下创建 `size` 字节的随机内容临时文件，写回存储后从页缓存中丢弃
+fn create_temp_file(dir: &Path, size: u64) -> io::Result<tempfile::NamedTempFile> {
+    let mut file = tempfile::Builder::new().prefix("enhancecpu-pagecache").tempfile_in(dir)?;
+    let mut rng = seed::thread_rng(seed::PAGE_CACHE_STREAMS);
+    let mut chunk = vec![0u8; WRITE_CHUNK];
+    let mut remaining = size;
+    while remaining > 0 {
+        let len = remaining.min(WRITE_CHUNK as u64) as usize;
+        rng.fill_bytes(&mut chunk[..len]);
+        file.write_all(&chunk[..len])?;
+        remaining -= len as u64;
+    }
+    file.as_file().sync_all()?;
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::fd::AsRawFd;
+        // SAFETY: fadvise 只是给内核的提示，不访问用户内存
+        unsafe { libc::posix_fadvise(file.as_file().as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+    }
+    Ok(file)
+}
+
+/// 本进程累计从存储读取的字节数（`/proc/self/io` 的 `read_bytes`），不可读时为 None
+fn storage_read_bytes() -> Option<u64> {
+    let io = std::fs::read_to_string("/proc/self/io").ok()?;
+    io.lines().find_map(|line| line.strip_prefix("read_bytes:")?.trim().parse().ok())
+}
+
+/// 系统累计的主缺页次数（`/proc/vmstat` 的 `pgmajfault`），不可读时为 None
+fn major_faults() -> Option<u64> {
+    let vmstat = std::fs::read_to_string("/proc/vmstat").ok()?;
+    vmstat.lines().find_map(|line| line.strip_prefix("pgmajfault ")?.trim().parse().ok())
+}
+
+/// 打开 `file`（为 None 时创建临时文件），启动 `threads` 个读取线程和统计输出线程
+pub fn spawn_page_cache_load(
+    file: Option<&Path>,
+    file_size: ByteSize,
+    dir: &Path,
+    threads: usize,
+    state: Arc<LoadState>,
+) -> io::Result<Vec<JoinHandle<()>>> {
+    let source = match file {
+        Some(path) => Source::Existing(path.to_path_buf()),
+        None => Source::Temp(create_temp_file(dir, file_size.as_u64())?),
+    };
+    let reader = Arc::new(File::open(source.path())?);
+    let pages = reader.metadata()?.len() / PAGE as u64;
+    if pages == 0 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, tr!("pagecache-file-too-small", size = PAGE)));
+    }
+    info!(
+        "{}",
+        tr!(
+            "pagecache-started",
+            path = source.path().display(),
+            size = ByteSize::b(pages * PAGE as u64),
+            threads = threads
+        )
+    );
+    let reads = Arc::new(AtomicU64::new(0));
+
+    let mut handles: Vec<JoinHandle<()>> = (0..threads)
+        .map(|i| {
+            let reader = reader.clone();
+            let reads = reads.clone();
+            let state = state.clone();
+            thread::spawn(move || {
+                let mut rng = seed::thread_rng(seed::PAGE_CACHE_STREAMS + 1 + i);
+                let mut buf = vec![0u8; PAGE];
+                while state.is_running() {
+                    let offset = rng.gen_range(0..pages) * PAGE as u64;
+                    if let Err(e) = diskio::read_block(&reader, &mut buf, offset) {
+                        warn!("{}", tr!("pagecache-read-failed", error = e));
+                        return;
+                    }
+                    reads.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        })
+        .collect();
+    handles.push(thread::spawn(move || run_reporter(source, &reads, &state)));
+    Ok(handles)
+}
+
+/// 定期输出读取吞吐量、页缓存命中率和主缺页增量；`source` 在负载结束时随线程一同丢弃
+fn run_reporter(source: Source, reads: &AtomicU64, state: &LoadState) {
+    let mut last_reads = 0u64;
+    let mut last_storage = storage_read_bytes();
+    let mut last_faults = major_faults();
+    let mut last_report = Instant::now();
+    while state.is_running() {
+        thread::sleep(Duration::from_millis(100));
+        let elapsed = last_report.elapsed();
+        if elapsed < REPORT_INTERVAL {
+            continue;
+        }
+        let current = reads.load(Ordering::Relaxed);
+        let requested = (current - last_reads) * PAGE as u64;
+        let storage = storage_read_bytes();
+        let faults = major_faults();
+        // 从存储读取的字节都是未命中页缓存的部分
+        let hit_rate = match (storage, last_storage) {
+            (Some(now), Some(earlier)) if requested > 0 => {
+                let missed = now.saturating_sub(earlier).min(requested);
+                format!("{:.1}%", 100.0 - missed as f64 * 100.0 / requested as f64)
+            }
+            _ => "-".to_string(),
+        };
+        let fault_delta = match (faults, last_faults) {
+            (Some(now), Some(earlier)) => now.saturating_sub(earlier).to_string(),
+            _ => "-".to_string(),
+        };
+        info!(
+            "{}",
+            tr!(
+                "pagecache-stats",
+                throughput = format!("{:.1}", requested as f64 / elapsed.as_secs_f64() / (1 << 20) as f64),
+                iops = format!("{:.0}", (current - last_reads) as f64 / elapsed.as_secs_f64()),
+                hit_rate = hit_rate,
+                faults = fault_delta
+            )
+        );
+        last_reads = current;
+        last_storage = storage;
+        last_faults = faults;
+        last_report = Instant::now();
+    }
+    drop(source);
+}
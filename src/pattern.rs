@@ -0,0 +1,198 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::signals;
+use crate::state::LoadState;
+use crate::timeline;
+use crate::timeutil;
+
+/// 尖峰上升/回落的过渡时长
+const SPIKE_RAMP: Duration = Duration::from_secs(1);
+
+/// 驱动线程更新目标使用率的间隔
+const DRIVER_TICK: Duration = Duration::from_millis(100);
+
+/// 信号触发的尖峰进行中最多排队的尖峰数，超出的信号丢弃
+const MAX_QUEUED_SPIKES: usize = 5;
+
+/// 尖峰模式所处的阶段
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpikePhase {
+    Base,
+    RampUp,
+    Hold,
+    RampDown,
+}
+
+impl fmt::Display for SpikePhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// 周期性CPU尖峰：平时保持 `base`%，每隔 `every` 升至 `spike_to`% 并保持 `hold`
+#[derive(Clone, Debug)]
+pub struct SpikePattern {
+    pub base: u8,
+    pub spike_to: u8,
+    pub hold: Duration,
+    pub every: Duration,
+}
+
+impl SpikePattern {
+    /// 检查周期是否足够容纳上升、保持和回落三个阶段
+    pub fn validate(&self) -> Result<(), String> {
+        let spike_len = self.hold + SPIKE_RAMP * 2;
+        if self.every <= spike_len {
+            return Err(tr!(
+                "spike-period-too-short",
+                every = format!("{:?}", self.every),
+                spike = format!("{:?}", spike_len)
+            ));
+        }
+        Ok(())
+    }
+
+    /// 计算周期内某一时刻所处的阶段和目标使用率
+    fn phase_at(&self, elapsed: Duration) -> (SpikePhase, u8) {
+        let cycle = Duration::from_nanos((elapsed.as_nanos() % self.every.as_nanos()) as u64);
+        let base_len = self.every - self.hold - SPIKE_RAMP * 2;
+        let ramp_up_end = base_len + SPIKE_RAMP;
+        let hold_end = ramp_up_end + self.hold;
+
+        if cycle < base_len {
+            (SpikePhase::Base, self.base)
+        } else if cycle < ramp_up_end {
+            (SpikePhase::RampUp, self.interpolate(cycle - base_len))
+        } else if cycle < hold_end {
+            (SpikePhase::Hold, self.spike_to)
+        } else {
+            (SpikePhase::RampDown, self.interpolate(SPIKE_RAMP.saturating_sub(cycle - hold_end)))
+        }
+    }
+
+    /// 在基础值与尖峰值之间线性插值，`progress` 为过渡阶段已进行的时长
+    fn interpolate(&self, progress: Duration) -> u8 {
+        ramp(self.base, self.spike_to, progress)
+    }
+}
+
+/// 从 `from` 到 `to` 的线性过渡中，经过 `progress` 时的使用率
+fn ramp(from: u8, to: u8, progress: Duration) -> u8 {
+    let ratio = (progress.as_secs_f64() / SPIKE_RAMP.as_secs_f64()).min(1.0);
+    let delta = f64::from(to) - f64::from(from);
+    (f64::from(from) + delta * ratio).round().clamp(0.0, 100.0) as u8
+}
+
+/// 启动尖峰模式驱动线程，持续更新共享的目标使用率，并记录每次阶段切换
+pub fn spawn_spike_driver(spike: SpikePattern, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let start = Instant::now();
+        let mut current = SpikePhase::Base;
+        state.target_percent.store(spike.base, Ordering::Relaxed);
+        info!("[{}] {}", timeutil::now_rfc3339(), tr!("spike-started", phase = current, percent = spike.base));
+
+        while state.is_running() {
+            let (phase, percent) = spike.phase_at(start.elapsed());
+            state.target_percent.store(percent, Ordering::Relaxed);
+            if phase != current {
+                match phase {
+                    SpikePhase::RampUp => timeline::begin(timeline::CONTROL_ROW, "cpu_spike"),
+                    SpikePhase::Base => timeline::end(timeline::CONTROL_ROW, "cpu_spike"),
+                    _ => {}
+                }
+                info!(
+                    "[{}] {}",
+                    timeutil::now_rfc3339(),
+                    tr!("spike-phase-changed", from = current, to = phase, percent = percent)
+                );
+                current = phase;
+            }
+            thread::sleep(DRIVER_TICK);
+        }
+    })
+}
+
+/// 收到 SIGUSR1 时触发的一次性尖峰：用1秒升至 `spike_to`%，保持 `hold` 后再用1秒回落到触发时的使用率
+#[derive(Clone, Copy, Debug)]
+pub struct SignalSpike {
+    pub spike_to: u8,
+    pub hold: Duration,
+}
+
+/// 取走新收到的信号，把收到的时间加入队列，超出上限的丢弃；`busy` 表示有尖峰正在进行
+fn receive_signals(queued: &mut VecDeque<String>, busy: bool) {
+    for _ in 0..signals::take_usr1() {
+        let received = timeutil::now_rfc3339();
+        if queued.len() >= MAX_QUEUED_SPIKES {
+            warn!("[{}] {}", received, tr!("spike-signal-discarded", max = MAX_QUEUED_SPIKES));
+            continue;
+        }
+        if busy {
+            info!("[{}] {}", received, tr!("spike-signal-queued", queued = queued.len() + 1));
+        }
+        queued.push_back(received);
+    }
+}
+
+/// 在 `duration` 内按 `percent_at(已进行时长)` 持续更新目标使用率，期间接收新的信号；负载停止时返回 false
+fn drive_for(
+    duration: Duration,
+    state: &LoadState,
+    queued: &mut VecDeque<String>,
+    percent_at: impl Fn(Duration) -> u8,
+) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < duration {
+        if !state.is_running() {
+            return false;
+        }
+        state.target_percent.store(percent_at(start.elapsed()), Ordering::Relaxed);
+        receive_signals(queued, true);
+        thread::sleep(DRIVER_TICK);
+    }
+    true
+}
+
+/// 启动信号尖峰驱动线程，每收到一次 SIGUSR1 执行一次尖峰
+pub fn spawn_signal_spike_driver(spike: SignalSpike, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        info!("{}", tr!("spike-signal-ready", pid = std::process::id()));
+        let mut queued = VecDeque::new();
+        let mut count = 0u32;
+        while state.is_running() {
+            receive_signals(&mut queued, false);
+            let Some(received) = queued.pop_front() else {
+                thread::sleep(DRIVER_TICK);
+                continue;
+            };
+            count += 1;
+            let base = state.target_percent.load(Ordering::Relaxed);
+            info!(
+                "[{}] {}",
+                timeutil::now_rfc3339(),
+                tr!(
+                    "spike-signal-triggered",
+                    count = count,
+                    received = received,
+                    percent = spike.spike_to,
+                    hold = format!("{:?}", spike.hold)
+                )
+            );
+            timeline::begin(timeline::CONTROL_ROW, "cpu_spike");
+            let finished = drive_for(SPIKE_RAMP, &state, &mut queued, |t| ramp(base, spike.spike_to, t))
+                && drive_for(spike.hold, &state, &mut queued, |_| spike.spike_to)
+                && drive_for(SPIKE_RAMP, &state, &mut queued, |t| ramp(spike.spike_to, base, t));
+            state.target_percent.store(base, Ordering::Relaxed);
+            timeline::end(timeline::CONTROL_ROW, "cpu_spike");
+            if !finished {
+                return;
+            }
+            info!("[{}] {}", timeutil::now_rfc3339(), tr!("spike-signal-finished", count = count, percent = base));
+        }
+    })
+}
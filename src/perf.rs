@@ -0,0 +1,348 @@
+//! 硬件性能计数器：通过 perf_event_open(2) 统计工作线程的指令数、周期数、缓存和分支未命中
+//!
+//! 需要以 `perf` 特性编译，且仅支持 Linux。`kernel.perf_event_paranoid` 大于2时，
+//! 普通用户需要 CAP_PERFMON（Linux 5.8+，更早的内核为 CAP_SYS_ADMIN）才能打开计数器。
+
+use crate::json::Json;
+
+/// 可统计的硬件事件
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PerfEvent {
+    Instructions,
+    Cycles,
+    CacheReferences,
+    CacheMisses,
+    Branches,
+    BranchMisses,
+}
+
+impl PerfEvent {
+    const ALL: [PerfEvent; 6] = [
+        PerfEvent::Instructions,
+        PerfEvent::Cycles,
+        PerfEvent::CacheReferences,
+        PerfEvent::CacheMisses,
+        PerfEvent::Branches,
+        PerfEvent::BranchMisses,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            PerfEvent::Instructions => "instructions",
+            PerfEvent::Cycles => "cycles",
+            PerfEvent::CacheReferences => "cache-references",
+            PerfEvent::CacheMisses => "cache-misses",
+            PerfEvent::Branches => "branches",
+            PerfEvent::BranchMisses => "branch-misses",
+        }
+    }
+
+    /// 计算比率时需要一同统计的事件
+    fn companion(self) -> Option<PerfEvent> {
+        match self {
+            PerfEvent::Instructions => Some(PerfEvent::Cycles),
+            PerfEvent::CacheMisses => Some(PerfEvent::CacheReferences),
+            PerfEvent::BranchMisses => Some(PerfEvent::Branches),
+            _ => None,
+        }
+    }
+}
+
+/// 解析单个事件名，例如 "instructions"、"cache-misses"
+pub fn parse_perf_event(name: &str) -> Result<PerfEvent, String> {
+    let name = name.trim();
+    PerfEvent::ALL
+        .into_iter()
+        .find(|e| e.name() == name || (name == "branch-instructions" && *e == PerfEvent::Branches))
+        .ok_or_else(|| {
+            let names: Vec<_> = PerfEvent::ALL.iter().map(|e| e.name()).collect();
+            tr!("perf-unknown-event", name = name, supported = names.join(", "))
+        })
+}
+
+/// 补上计算比率所需的事件并去重
+pub fn with_companions(events: &[PerfEvent]) -> Vec<PerfEvent> {
+    let mut all = Vec::new();
+    for &event in events {
+        for e in std::iter::once(event).chain(event.companion()) {
+            if !all.contains(&e) {
+                all.push(e);
+            }
+        }
+    }
+    all
+}
+
+/// 由计数器换算出的指标，缺少所需事件时为 None
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PerfMetrics {
+    /// 每周期指令数
+    pub ipc: Option<f64>,
+    /// 末级缓存未命中率（百分比）
+    pub llc_miss_rate: Option<f64>,
+    /// 分支预测失败率（百分比）
+    pub branch_miss_rate: Option<f64>,
+}
+
+impl PerfMetrics {
+    #[cfg_attr(not(all(feature = "perf", target_os = "linux")), allow(dead_code))]
+    fn from_counts(count: impl Fn(PerfEvent) -> Option<u64>) -> Self {
+        let ratio = |num: PerfEvent, den: PerfEvent, scale: f64| match (count(num), count(den)) {
+            (Some(n), Some(d)) if d > 0 => Some(n as f64 / d as f64 * scale),
+            _ => None,
+        };
+        PerfMetrics {
+            ipc: ratio(PerfEvent::Instructions, PerfEvent::Cycles, 1.0),
+            llc_miss_rate: ratio(PerfEvent::CacheMisses, PerfEvent::CacheReferences, 100.0),
+            branch_miss_rate: ratio(PerfEvent::BranchMisses, PerfEvent::Branches, 100.0),
+        }
+    }
+
+    /// 文本格式，例如 "IPC 1.85, LLC未命中率 3.2%, 分支预测失败率 0.10%"
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(ipc) = self.ipc {
+            parts.push(format!("IPC {:.2}", ipc));
+        }
+        if let Some(rate) = self.llc_miss_rate {
+            parts.push(tr!("perf-llc-miss-rate", rate = format!("{:.1}", rate)));
+        }
+        if let Some(rate) = self.branch_miss_rate {
+            parts.push(tr!("perf-branch-miss-rate", rate = format!("{:.2}", rate)));
+        }
+        parts.join(", ")
+    }
+
+    pub fn to_json(self) -> Json {
+        Json::object([
+            ("ipc", self.ipc.into()),
+            ("llc_miss_rate_percent", self.llc_miss_rate.into()),
+            ("branch_miss_rate_percent", self.branch_miss_rate.into()),
+        ])
+    }
+}
+
+/// 检查 `kernel.perf_event_paranoid` 并输出其含义，返回本进程是否能打开所需的 perf 事件
+///
+/// 硬件计数器只统计用户态，系统调用和上下文切换为进程级计数，级别不高于2即可；
+/// 更高的级别（Debian、Android 等发行版的补丁）禁止普通用户使用 perf，除非具有
+/// CAP_PERFMON 或 CAP_SYS_ADMIN。无法读取时假定可用，由打开事件时的错误说明原因。
+#[cfg(target_os = "linux")]
+pub fn check_paranoia() -> bool {
+    let Some(level) = std::fs::read_to_string("/proc/sys/kernel/perf_event_paranoid")
+        .ok()
+        .and_then(|s| s.trim().parse::<i32>().ok())
+    else {
+        warn!("{}", tr!("perf-paranoid-unreadable"));
+        return true;
+    };
+    let meaning = match level {
+        i32::MIN..=-1 => tr!("perf-paranoid-unrestricted"),
+        0 => tr!("perf-paranoid-0"),
+        1 => tr!("perf-paranoid-1"),
+        2 => tr!("perf-paranoid-2"),
+        _ => tr!("perf-paranoid-restricted"),
+    };
+    info!("{}", tr!("perf-paranoid-level", level = level, meaning = meaning));
+    if level <= 2 || has_perf_capability() {
+        return true;
+    }
+    error!("{}", tr!("perf-paranoid-denied"));
+    false
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn check_paranoia() -> bool {
+    true
+}
+
+/// 本进程的有效能力集是否包含 CAP_PERFMON 或 CAP_SYS_ADMIN
+#[cfg(target_os = "linux")]
+fn has_perf_capability() -> bool {
+    const CAP_SYS_ADMIN: u32 = 21;
+    const CAP_PERFMON: u32 = 38;
+    let status = std::fs::read_to_string("/proc/self/status").unwrap_or_default();
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("CapEff:"))
+        .and_then(|caps| u64::from_str_radix(caps.trim(), 16).ok())
+        .is_some_and(|caps| caps & (1 << CAP_SYS_ADMIN | 1 << CAP_PERFMON) != 0)
+}
+
+/// perf_event_open(2) 的底层封装，硬件计数器和系统调用统计共用
+#[cfg(target_os = "linux")]
+pub mod sys {
+    use std::fs::File;
+    use std::io::Read;
+    use std::os::fd::FromRawFd;
+
+    #[cfg(feature = "perf")]
+    pub const PERF_TYPE_HARDWARE: u32 = 0;
+    pub const PERF_TYPE_SOFTWARE: u32 = 1;
+    pub const PERF_TYPE_TRACEPOINT: u32 = 2;
+    const PERF_FLAG_FD_CLOEXEC: libc::c_ulong = 1 << 3;
+
+    /// perf_event_attr 的 inherit 位：同时统计之后创建的子线程
+    pub const FLAG_INHERIT: u64 = 1 << 1;
+    #[cfg(feature = "perf")]
+    /// perf_event_attr 的 exclude_kernel 和 exclude_hv 位，只统计用户态，降低权限要求
+    pub const FLAG_EXCLUDE_KERNEL_HV: u64 = (1 << 5) | (1 << 6);
+
+    /// perf_event_attr 的第一个版本（PERF_ATTR_SIZE_VER0，64字节），足以打开计数型事件
+    #[repr(C)]
+    #[derive(Default)]
+    struct PerfEventAttr {
+        type_: u32,
+        size: u32,
+        config: u64,
+        sample_period: u64,
+        sample_type: u64,
+        read_format: u64,
+        flags: u64,
+        wakeup_events: u32,
+        bp_type: u32,
+        config1: u64,
+    }
+
+    /// 为调用线程打开一个计数器
+    pub fn open(type_: u32, config: u64, flags: u64) -> std::io::Result<File> {
+        let attr = PerfEventAttr {
+            type_,
+            size: std::mem::size_of::<PerfEventAttr>() as u32,
+            config,
+            flags,
+            ..Default::default()
+        };
+        // SAFETY: attr 在调用期间有效；pid=0、cpu=-1 表示统计调用线程在任意CPU上的事件
+        let fd = unsafe {
+            libc::syscall(libc::SYS_perf_event_open, &attr as *const PerfEventAttr, 0, -1, -1, PERF_FLAG_FD_CLOEXEC)
+        };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        // SAFETY: fd 是刚打开的有效描述符，所有权交给 File
+        Ok(unsafe { File::from_raw_fd(fd as i32) })
+    }
+
+    /// 读取计数器的当前值，失败时返回0
+    pub fn read(file: &File) -> u64 {
+        let mut buf = [0u8; 8];
+        match (&*file).read_exact(&mut buf) {
+            Ok(()) => u64::from_ne_bytes(buf),
+            Err(_) => 0,
+        }
+    }
+}
+
+#[cfg(all(feature = "perf", target_os = "linux"))]
+mod imp {
+    use std::collections::HashMap;
+    use std::fs::File;
+    use std::sync::Mutex;
+
+    use super::sys::{self, read};
+    use super::{PerfEvent, PerfMetrics};
+
+    fn config(event: PerfEvent) -> u64 {
+        match event {
+            PerfEvent::Cycles => 0,
+            PerfEvent::Instructions => 1,
+            PerfEvent::CacheReferences => 2,
+            PerfEvent::CacheMisses => 3,
+            PerfEvent::Branches => 4,
+            PerfEvent::BranchMisses => 5,
+        }
+    }
+
+    fn open(event: PerfEvent) -> std::io::Result<File> {
+        sys::open(sys::PERF_TYPE_HARDWARE, config(event), sys::FLAG_EXCLUDE_KERNEL_HV)
+    }
+
+    /// 所有工作线程的计数器
+    pub struct PerfCounters {
+        events: Vec<PerfEvent>,
+        counters: Mutex<Vec<(PerfEvent, File)>>,
+        last: Mutex<HashMap<PerfEvent, u64>>,
+    }
+
+    impl PerfCounters {
+        pub fn new(events: Vec<PerfEvent>) -> Option<Self> {
+            Some(PerfCounters { events, counters: Mutex::new(Vec::new()), last: Mutex::new(HashMap::new()) })
+        }
+
+        /// 为调用线程打开所有计数器
+        pub fn attach_current_thread(&self) -> std::io::Result<()> {
+            let files = self
+                .events
+                .iter()
+                .map(|&event| open(event).map(|file| (event, file)))
+                .collect::<std::io::Result<Vec<_>>>()?;
+            self.counters.lock().unwrap_or_else(|e| e.into_inner()).extend(files);
+            Ok(())
+        }
+
+        fn totals(&self) -> HashMap<PerfEvent, u64> {
+            let mut totals = HashMap::new();
+            for (event, file) in self.counters.lock().unwrap_or_else(|e| e.into_inner()).iter() {
+                *totals.entry(*event).or_insert(0) += read(file);
+            }
+            totals
+        }
+
+        /// 上次采样以来的指标，没有任何线程成功打开计数器时为 None
+        pub fn sample(&self) -> Option<PerfMetrics> {
+            let totals = self.totals();
+            if totals.is_empty() {
+                return None;
+            }
+            let mut last = self.last.lock().unwrap_or_else(|e| e.into_inner());
+            let metrics = PerfMetrics::from_counts(|event| {
+                totals.get(&event).map(|total| total.saturating_sub(last.get(&event).copied().unwrap_or(0)))
+            });
+            *last = totals;
+            Some(metrics)
+        }
+
+        /// 整个运行期间的指标
+        pub fn total(&self) -> Option<PerfMetrics> {
+            let totals = self.totals();
+            if totals.is_empty() {
+                return None;
+            }
+            Some(PerfMetrics::from_counts(|event| totals.get(&event).copied()))
+        }
+    }
+}
+
+#[cfg(not(all(feature = "perf", target_os = "linux")))]
+mod imp {
+    use super::{PerfEvent, PerfMetrics};
+
+    /// 未启用 `perf` 特性或非 Linux 平台时的占位实现
+    pub struct PerfCounters;
+
+    impl PerfCounters {
+        pub fn new(_events: Vec<PerfEvent>) -> Option<Self> {
+            if cfg!(target_os = "linux") {
+                warn!("{}", tr!("perf-feature-missing"));
+            } else {
+                warn!("{}", tr!("perf-unsupported"));
+            }
+            None
+        }
+
+        pub fn attach_current_thread(&self) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        pub fn sample(&self) -> Option<PerfMetrics> {
+            None
+        }
+
+        pub fn total(&self) -> Option<PerfMetrics> {
+            None
+        }
+    }
+}
+
+pub use imp::PerfCounters;
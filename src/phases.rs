@@ -0,0 +1,111 @@
+//! 分阶段核心数：按顺序在每个阶段启用固定数量的工作线程，全部阶段完成后自动退出；
+//! `--profile` 的各阶段还可以设置目标使用率和占用的内存
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use bytesize::ByteSize;
+
+use crate::memory;
+use crate::state::LoadState;
+use crate::timeline;
+use crate::timeutil;
+
+/// 检查运行标志的间隔
+const DRIVER_TICK: Duration = Duration::from_millis(100);
+
+/// 各阶段启用的工作线程数及其持续时间
+#[derive(Clone, Debug)]
+pub struct PhasePlan {
+    pub cores: Vec<usize>,
+    pub durations: Vec<Duration>,
+    /// 各阶段的目标使用率，为空或 None 时不修改
+    pub targets: Vec<Option<u8>>,
+    /// 各阶段占用的内存字节数，None 表示不占用；为空时不管理内存
+    pub memory: Vec<Option<u64>>,
+}
+
+impl PhasePlan {
+    /// 检查核心数与持续时间一一对应，且至少有一个阶段启用工作线程
+    pub fn validate(&self) -> Result<(), String> {
+        if self.cores.len() != self.durations.len() {
+            return Err(tr!("phases-length-mismatch", cores = self.cores.len(), durations = self.durations.len()));
+        }
+        if self.max_cores() == 0 {
+            return Err(tr!("phases-all-idle"));
+        }
+        Ok(())
+    }
+
+    /// 所有阶段中最大的核心数，即需要预先启动的工作线程数
+    pub fn max_cores(&self) -> usize {
+        self.cores.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// 启动阶段驱动线程：第 i 个阶段只启用序号小于 Ni 的工作线程，其余线程在条件变量上等待；
+/// 最后一个阶段结束后停止负载。`workers` 为实际启动的工作线程数，超出的核心数按该值处理。
+pub fn spawn_phase_driver(plan: PhasePlan, workers: usize, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let total = plan.cores.len();
+        for (i, (&cores, &duration)) in plan.cores.iter().zip(&plan.durations).enumerate() {
+            if !state.is_running() {
+                return;
+            }
+            let active = cores.min(workers);
+            state.set_active_workers(active);
+            if let Some(target) = plan.targets.get(i).copied().flatten() {
+                state.target_percent.store(target, Ordering::Relaxed);
+            }
+            // 第一阶段的内存已在启动时分配，之后只在大小变化时先释放旧内存再分配新内存，避免峰值翻倍
+            if i > 0
+                && let Some(&bytes) = plan.memory.get(i)
+                && plan.memory.get(i - 1) != Some(&bytes) {
+                resize_memory(bytes, &state);
+            }
+            info!(
+                "[{}] {}",
+                timeutil::now_rfc3339(),
+                tr!(
+                    "phases-transition",
+                    phase = i + 1,
+                    total = total,
+                    cores = active,
+                    duration = format!("{:?}", duration)
+                )
+            );
+            if !plan.targets.is_empty() || !plan.memory.is_empty() {
+                let memory = plan.memory.get(i).copied().flatten().map_or_else(|| "0".to_string(), |b| ByteSize::b(b).to_string());
+                info!("  {}", tr!("phases-settings", target = state.target_percent.load(Ordering::Relaxed), memory = memory));
+            }
+            let label = format!("phase {} ({} cores)", i + 1, active);
+            timeline::begin(timeline::CONTROL_ROW, &label);
+            let end = Instant::now() + duration;
+            while state.is_running() && Instant::now() < end {
+                thread::sleep(DRIVER_TICK.min(end.saturating_duration_since(Instant::now())));
+            }
+            timeline::end(timeline::CONTROL_ROW, &label);
+        }
+        if state.is_running() {
+            info!("{}", tr!("phases-completed", total = total));
+            state.finish();
+        }
+    })
+}
+
+/// 把占用的内存换成 `bytes` 字节并预填充，None 时只释放
+fn resize_memory(bytes: Option<u64>, state: &LoadState) {
+    state.replace_memory(None);
+    let Some(bytes) = bytes else {
+        return;
+    };
+    match memory::try_alloc_zeroed(bytes as usize) {
+        Some(mut buffer) => {
+            memory::prefault(&mut buffer, false);
+            state.replace_memory(Some(buffer));
+        }
+        None => warn!("{}", tr!("phases-memory-failed", size = ByteSize::b(bytes))),
+    }
+}
@@ -0,0 +1,240 @@
+//! 负载配置文件（TOML）：按顺序执行的多个阶段，`--profile` 运行，`validate-profile` 在使用前检查文件
+//!
+//! ```toml
+//! name = "nightly"
+//!
+//! [[phases]]
+//! duration_secs = 120
+//! cores = 4
+//! target_percent = 50
+//! memory = "1G"
+//! workload = "spin"
+//! ```
+//!
+//! 除 `duration_secs` 和 `target_percent` 外的字段都可以省略：`cores` 省略时使用 `-c`，
+//! `memory` 省略时该阶段不占用内存，`workload` 省略时使用 `--workload`。
+//! 运行中不能切换负载类型，各阶段写出的 `workload` 必须相同。
+
+use std::path::Path;
+use std::time::Duration;
+
+use bytesize::ByteSize;
+use clap::ValueEnum;
+use serde::Deserialize;
+use sysinfo::{System, SystemExt};
+use toml::Spanned;
+
+use crate::error::LoadError;
+use crate::memory;
+use crate::phases::PhasePlan;
+use crate::workload::Workload;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub name: Option<String>,
+    #[serde(default)]
+    pub phases: Vec<Phase>,
+}
+
+/// 一个阶段；字段带有在文件中的位置，校验失败时可以指出行号
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Phase {
+    pub duration_secs: Spanned<u64>,
+    pub cores: Option<Spanned<i64>>,
+    pub target_percent: Spanned<i64>,
+    pub memory: Option<Spanned<String>>,
+    pub workload: Option<Spanned<String>>,
+}
+
+/// 一条校验错误，`line` 从1开始，无法定位时为 None
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// 字节偏移所在的行号
+fn line_of(text: &str, offset: usize) -> usize {
+    text[..offset.min(text.len())].matches('\n').count() + 1
+}
+
+impl Profile {
+    /// 解析配置文件，语法或类型错误时给出出错字段的路径和行号
+    pub fn parse(text: &str) -> Result<Profile, Diagnostic> {
+        serde_path_to_error::deserialize(toml::Deserializer::new(text)).map_err(|e| {
+            let line = e.inner().span().map(|span| line_of(text, span.start));
+            // Spanned 在内部包了一层结构体，路径中去掉它的私有字段名
+            let mut path = String::new();
+            for segment in e.path().iter() {
+                match segment {
+                    serde_path_to_error::Segment::Seq { index } => path.push_str(&format!("[{}]", index)),
+                    serde_path_to_error::Segment::Map { key } | serde_path_to_error::Segment::Enum { variant: key }
+                        if !key.starts_with("$__") => {
+                        if !path.is_empty() {
+                            path.push('.');
+                        }
+                        path.push_str(key);
+                    }
+                    _ => {}
+                }
+            }
+            let message = match path.as_str() {
+                "" => e.inner().message().to_string(),
+                path => format!("{}: {}", path, e.inner().message()),
+            };
+            Diagnostic { line, message }
+        })
+    }
+
+    /// 检查每个阶段的取值，返回全部错误
+    pub fn validate(&self, text: &str) -> Vec<Diagnostic> {
+        let mut errors = Vec::new();
+        let mut report = |span: std::ops::Range<usize>, message: String| {
+            errors.push(Diagnostic { line: Some(line_of(text, span.start)), message });
+        };
+        for (i, phase) in self.phases.iter().enumerate() {
+            let index = i + 1;
+            if *phase.duration_secs.get_ref() == 0 {
+                report(phase.duration_secs.span(), tr!("profile-duration-zero", phase = index));
+            }
+            let percent = *phase.target_percent.get_ref();
+            if !(0..=100).contains(&percent) {
+                report(phase.target_percent.span(), tr!("profile-cpu-out-of-range", phase = index, value = percent));
+            }
+            if let Some(cores) = &phase.cores
+                && *cores.get_ref() < 1 {
+                report(cores.span(), tr!("profile-cores-invalid", phase = index, value = cores.get_ref()));
+            }
+            if let Some(size) = &phase.memory
+                && let Err(e) = memory::parse_memory_spec(size.get_ref()) {
+                report(size.span(), tr!("profile-memory-invalid", phase = index, error = e));
+            }
+            if let Some(workload) = &phase.workload
+                && Workload::from_str(workload.get_ref(), true).is_err() {
+                report(
+                    workload.span(),
+                    tr!("profile-workload-invalid", phase = index, value = workload.get_ref(), choices = workload_names()),
+                );
+            }
+        }
+        // 每个阶段的时长都已检查为正，没有阶段时总时长为0
+        if self.phases.is_empty() {
+            errors.push(Diagnostic { line: None, message: tr!("profile-no-phases") });
+        }
+        errors.sort_by_key(|e| e.line);
+        errors
+    }
+
+    /// 各阶段写出的负载类型，都省略时为 None；在 `validate` 通过后调用
+    pub fn workload(&self) -> Result<Option<Workload>, LoadError> {
+        let mut chosen: Option<Workload> = None;
+        for phase in &self.phases {
+            let Some(name) = &phase.workload else {
+                continue;
+            };
+            let workload = Workload::from_str(name.get_ref(), true).map_err(LoadError::InvalidArgument)?;
+            match chosen {
+                Some(previous) if previous != workload => {
+                    return Err(LoadError::InvalidArgument(tr!("profile-workload-mixed")));
+                }
+                _ => chosen = Some(workload),
+            }
+        }
+        Ok(chosen)
+    }
+
+    /// 转换为阶段计划；省略核心数的阶段使用 `default_cores`，百分比形式的内存按 `total_memory` 换算。
+    /// 在 `validate` 通过后调用
+    pub fn plan(&self, default_cores: usize, total_memory: u64) -> PhasePlan {
+        PhasePlan {
+            cores: self
+                .phases
+                .iter()
+                .map(|p| p.cores.as_ref().map_or(default_cores, |cores| *cores.get_ref() as usize))
+                .collect(),
+            durations: self.phases.iter().map(|p| Duration::from_secs(*p.duration_secs.get_ref())).collect(),
+            targets: self.phases.iter().map(|p| Some(*p.target_percent.get_ref() as u8)).collect(),
+            memory: self
+                .phases
+                .iter()
+                .map(|p| {
+                    let spec = memory::parse_memory_spec(p.memory.as_ref()?.get_ref()).ok()?;
+                    Some(spec.to_bytes(total_memory))
+                })
+                .collect(),
+        }
+    }
+
+    /// 所有阶段的总时长
+    pub fn total_duration(&self) -> Duration {
+        Duration::from_secs(self.phases.iter().map(|p| *p.duration_secs.get_ref()).sum())
+    }
+
+    /// 摘要的各行：阶段数、总时长、峰值CPU使用率和峰值内存；百分比形式的内存按系统总内存换算
+    pub fn summary_lines(&self, total_memory: u64) -> Vec<String> {
+        let peak_cpu = self.phases.iter().map(|p| *p.target_percent.get_ref()).max().unwrap_or(0);
+        let peak_memory = self
+            .phases
+            .iter()
+            .filter_map(|p| memory::parse_memory_spec(p.memory.as_ref()?.get_ref()).ok())
+            .map(|spec| spec.to_bytes(total_memory))
+            .max()
+            .unwrap_or(0);
+        vec![
+            tr!("profile-summary-phases", count = self.phases.len()),
+            tr!("profile-summary-duration", duration = format!("{:?}", self.total_duration())),
+            tr!("profile-summary-cpu", percent = peak_cpu),
+            tr!("profile-summary-memory", memory = ByteSize::b(peak_memory)),
+        ]
+    }
+}
+
+/// 可用的负载类型名，例如 "spin, udp-flood, zstd-compress"
+fn workload_names() -> String {
+    Workload::value_variants()
+        .iter()
+        .filter_map(|w| w.to_possible_value())
+        .map(|v| v.get_name().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// 读取并校验配置文件，有错误时输出每条错误及其行号
+pub fn load(path: &Path) -> Result<Profile, LoadError> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| std::io::Error::new(e.kind(), tr!("profile-read-failed", file = path.display(), error = e)))?;
+    let errors = match Profile::parse(&text) {
+        Ok(profile) => {
+            let errors = profile.validate(&text);
+            if errors.is_empty() {
+                return Ok(profile);
+            }
+            errors
+        }
+        Err(e) => vec![e],
+    };
+    for e in &errors {
+        match e.line {
+            Some(line) => error!("{}:{}: {}", path.display(), line, e.message),
+            None => error!("{}: {}", path.display(), e.message),
+        }
+    }
+    Err(LoadError::InvalidArgument(tr!("profile-invalid", file = path.display(), count = errors.len())))
+}
+
+/// `validate-profile` 命令：全部有效时输出摘要
+pub fn validate_file(path: &Path) -> Result<(), LoadError> {
+    let profile = load(path)?;
+    let mut sys = System::new();
+    sys.refresh_memory();
+    match &profile.name {
+        Some(name) => info!("{}", tr!("profile-valid-named", file = path.display(), name = name)),
+        None => info!("{}", tr!("profile-valid", file = path.display())),
+    }
+    for line in profile.summary_lines(sys.total_memory()) {
+        info!("  {}", line);
+    }
+    Ok(())
+}
@@ -0,0 +1,129 @@
+//! `--ramp-up`/`--ramp-down`：开始时逐步加大负载、到期前逐步减小负载，避免负载突变触发监控告警
+//!
+//! 所有工作线程一开始就启动，驱动线程定时调整启用的工作线程数；指定了 `--cpu-base` 时
+//! 改为调整各线程的占空比。逐步减小在 `--duration` 的最后一段进行，收到 Ctrl+C 或 `stop`
+//! 时仍立即停止。
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::state::LoadState;
+use crate::timeline;
+use crate::timeutil;
+
+/// 调整负载的间隔
+const DRIVER_TICK: Duration = Duration::from_millis(100);
+
+/// 逐步调整的对象
+#[derive(Clone, Copy, Debug)]
+pub enum RampTarget {
+    /// 启用的工作线程数从0到该值
+    Workers(usize),
+    /// 占空比从0到该百分比
+    Percent(u8),
+}
+
+/// 当前所处的阶段，保存在 `LoadState::ramp_phase` 中，0 表示未启用
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RampPhase {
+    Up = 1,
+    Steady = 2,
+    Down = 3,
+}
+
+impl RampPhase {
+    pub fn load(state: &LoadState) -> Option<RampPhase> {
+        match state.ramp_phase.load(Ordering::Relaxed) {
+            1 => Some(RampPhase::Up),
+            2 => Some(RampPhase::Steady),
+            3 => Some(RampPhase::Down),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            RampPhase::Up => "ramping-up",
+            RampPhase::Steady => "steady",
+            RampPhase::Down => "ramping-down",
+        }
+    }
+
+    pub fn describe(self) -> String {
+        match self {
+            RampPhase::Up => tr!("ramp-phase-up"),
+            RampPhase::Steady => tr!("ramp-phase-steady"),
+            RampPhase::Down => tr!("ramp-phase-down"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RampSettings {
+    pub target: RampTarget,
+    pub up: Option<Duration>,
+    pub down: Option<Duration>,
+}
+
+impl RampSettings {
+    /// 在工作线程启动前把负载降到起点，启用了 `--ramp-up` 时为0
+    pub fn prepare(&self, state: &LoadState) {
+        if self.up.is_none() {
+            return;
+        }
+        match self.target {
+            RampTarget::Workers(_) => state.set_active_workers(0),
+            RampTarget::Percent(_) => state.target_percent.store(0, Ordering::Relaxed),
+        }
+    }
+
+    /// 按进度（0.0-1.0）设置负载
+    fn apply(&self, progress: f64, state: &LoadState) {
+        match self.target {
+            RampTarget::Workers(workers) => state.set_active_workers((workers as f64 * progress).ceil() as usize),
+            RampTarget::Percent(percent) => {
+                state.target_percent.store((f64::from(percent) * progress).round() as u8, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+/// 启动驱动线程：`--ramp-up` 期间线性增加负载，之后保持，距 `--duration` 到期不足 `--ramp-down` 时线性减小负载
+pub fn spawn_ramp_driver(settings: RampSettings, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let start = Instant::now();
+        let mut phase = None;
+        while state.is_running() {
+            let now = Instant::now();
+            let down_progress = settings.down.zip(state.deadline.get()).and_then(|(down, &deadline)| {
+                let remaining = deadline.saturating_duration_since(now);
+                (remaining < down).then(|| remaining.as_secs_f64() / down.as_secs_f64())
+            });
+            let (current, progress) = match (down_progress, settings.up) {
+                (Some(progress), _) => (RampPhase::Down, progress),
+                (None, Some(up)) if now - start < up => (RampPhase::Up, (now - start).as_secs_f64() / up.as_secs_f64()),
+                _ => (RampPhase::Steady, 1.0),
+            };
+            let changed = phase != Some(current);
+            if changed {
+                if let Some(previous) = phase {
+                    timeline::end(timeline::CONTROL_ROW, previous.as_str());
+                }
+                timeline::begin(timeline::CONTROL_ROW, current.as_str());
+                info!("[{}] {}", timeutil::now_rfc3339(), tr!("ramp-phase-changed", phase = current.describe()));
+                state.ramp_phase.store(current as u8, Ordering::Relaxed);
+                phase = Some(current);
+            }
+            // 稳定阶段只设置一次，之后控制接口或目标文件对占空比的修改不会被覆盖
+            if current != RampPhase::Steady || changed {
+                settings.apply(progress, &state);
+            }
+            thread::sleep(DRIVER_TICK);
+        }
+        if let Some(phase) = phase {
+            timeline::end(timeline::CONTROL_ROW, phase.as_str());
+        }
+    })
+}
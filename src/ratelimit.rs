@@ -0,0 +1,38 @@
+//! 令牌桶限速：按固定速率发放迭代令牌，使负载的工作量与机器快慢无关
+
+use std::time::{Duration, Instant};
+
+/// 单个工作线程的令牌桶
+pub struct RateLimiter {
+    /// 每秒发放的令牌数
+    rate: f64,
+    /// 桶容量，限制空闲后一次性积累的突发量
+    capacity: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// 每秒发放 `rate` 个令牌，最多积累0.1秒的量（至少1个）
+    pub fn new(rate: f64) -> Self {
+        let capacity = (rate / 10.0).max(1.0);
+        RateLimiter { rate, capacity, tokens: 0.0, last_refill: Instant::now() }
+    }
+
+    fn refill(&mut self) {
+        let now = Instant::now();
+        self.tokens = (self.tokens + now.duration_since(self.last_refill).as_secs_f64() * self.rate).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// 最多取走 `want` 个令牌并返回取到的数量；没有可用令牌时返回到下一个令牌的等待时间
+    pub fn take(&mut self, want: u64) -> Result<u64, Duration> {
+        self.refill();
+        if self.tokens < 1.0 {
+            return Err(Duration::from_secs_f64((1.0 - self.tokens) / self.rate));
+        }
+        let taken = (self.tokens.floor() as u64).min(want);
+        self.tokens -= taken as f64;
+        Ok(taken)
+    }
+}
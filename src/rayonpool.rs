@@ -0,0 +1,93 @@
+//! Rayon 负载：在自建的 Rayon 线程池中并行执行空转迭代，测试工作窃取调度器的开销
+//!
+//! 每个工作单元执行一轮与 spin 负载相同的空转迭代，并按目标使用率休眠；
+//! 与相同 `-c` 的 spin 负载对比吞吐量，即可看出工作窃取相对直接使用线程的开销，
+//! 这也是数据并行 Rust 程序的典型写法。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+
+use crate::selfmonitor;
+use crate::state::LoadState;
+use crate::workload::{BusyLoop, BusyLooper};
+
+/// 输出统计的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 启动 Rayon 线程池和吞吐量输出线程
+pub fn spawn_rayon_load(
+    threads: usize,
+    busy_loop: BusyLoop,
+    unit_iterations: u64,
+    state: Arc<LoadState>,
+) -> std::io::Result<Vec<JoinHandle<()>>> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .thread_name(|i| format!("rayon-{}", i))
+        .build()
+        .map_err(std::io::Error::other)?;
+    info!("{}", tr!("rayon-started", threads = pool.current_num_threads(), iterations = unit_iterations));
+    let iterations = Arc::new(AtomicU64::new(0));
+
+    let driver = {
+        let state = state.clone();
+        let iterations = iterations.clone();
+        thread::spawn(move || {
+            pool.install(|| {
+                // repeat 产生无限的工作单元，运行标志清除后返回 None 使 try_for_each 提前结束
+                rayon::iter::repeat(()).try_for_each(|()| {
+                    if !state.is_running() {
+                        return None;
+                    }
+                    let index = rayon::current_thread_index().unwrap_or(0);
+                    state.wait_until_active(index);
+                    let percent = state.thread_percent(index, None);
+                    if percent == 0 {
+                        thread::sleep(crate::spin::IDLE_POLL_INTERVAL);
+                        return Some(());
+                    }
+                    let percent = state.with_noise(percent, &mut rand::thread_rng());
+                    let start = Instant::now();
+                    BusyLooper::new(busy_loop).run(unit_iterations);
+                    iterations.fetch_add(unit_iterations, Ordering::Relaxed);
+                    selfmonitor::duty_cycle_sleep(None, percent, unit_iterations, start.elapsed());
+                    Some(())
+                })
+            });
+        })
+    };
+
+    let reporter = thread::spawn(move || {
+        let started = Instant::now();
+        let mut last = 0u64;
+        let mut last_report = Instant::now();
+        while state.is_running() {
+            thread::sleep(Duration::from_millis(100));
+            let elapsed = last_report.elapsed();
+            if elapsed < REPORT_INTERVAL {
+                continue;
+            }
+            let current = iterations.load(Ordering::Relaxed);
+            info!(
+                "{}",
+                tr!("rayon-stats", rate = format!("{:.1}", (current - last) as f64 / elapsed.as_secs_f64() / 1e6))
+            );
+            last = current;
+            last_report = Instant::now();
+        }
+        let total = iterations.load(Ordering::Relaxed);
+        info!(
+            "{}",
+            tr!(
+                "rayon-summary",
+                total = total,
+                rate = format!("{:.1}", total as f64 / started.elapsed().as_secs_f64() / 1e6)
+            )
+        );
+    });
+    Ok(vec![driver, reporter])
+}
@@ -0,0 +1,78 @@
+//! 逐秒CPU使用率日志：采样线程把测量值放入无锁环形缓冲区，写入线程每秒取出并追加到文件
+//!
+//! 每行格式为 `<Unix毫秒时间戳>,<平均CPU使用率>`。写入跟不上时缓冲区只保留最近的N条，
+//! 采样线程不会因为磁盘写入而阻塞。
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crossbeam_queue::ArrayQueue;
+use sysinfo::{CpuExt, System, SystemExt};
+
+use crate::state::LoadState;
+use crate::timeutil;
+
+/// 采样和写入的间隔
+const INTERVAL: Duration = Duration::from_secs(1);
+
+/// 检查运行标志的间隔
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 打开日志文件并启动采样线程和写入线程；负载停止后写入缓冲区中剩余的测量值
+///
+/// `cores` 为参与负载的核心数，只统计这些核心的平均使用率。
+pub fn spawn(path: &Path, capacity: usize, cores: usize, state: Arc<LoadState>) -> std::io::Result<JoinHandle<()>> {
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    let queue = Arc::new(ArrayQueue::new(capacity.max(1)));
+
+    let sampler = {
+        let queue = queue.clone();
+        let state = state.clone();
+        thread::spawn(move || {
+            let mut sys = System::new();
+            sys.refresh_cpu();
+            let cores = cores.max(1);
+            let mut next = Instant::now() + INTERVAL;
+            while state.is_running() {
+                if Instant::now() < next {
+                    thread::sleep(POLL_INTERVAL.min(next - Instant::now()));
+                    continue;
+                }
+                next += INTERVAL;
+                sys.refresh_cpu();
+                let usage = sys.cpus().iter().take(cores).map(|cpu| cpu.cpu_usage()).sum::<f32>() / cores as f32;
+                // 缓冲区满时丢弃最旧的测量值
+                queue.force_push((timeutil::unix_millis(), usage));
+            }
+        })
+    };
+
+    let path = path.to_path_buf();
+    Ok(thread::spawn(move || {
+        let mut writer = BufWriter::new(file);
+        while state.is_running() {
+            let start = Instant::now();
+            while state.is_running() && start.elapsed() < INTERVAL {
+                thread::sleep(POLL_INTERVAL);
+            }
+            drain(&queue, &mut writer, &path);
+        }
+        let _ = sampler.join();
+        drain(&queue, &mut writer, &path);
+    }))
+}
+
+/// 取出缓冲区中的全部测量值并写入文件
+fn drain(queue: &ArrayQueue<(u64, f32)>, writer: &mut BufWriter<File>, path: &Path) {
+    let mut result = Ok(());
+    while let Some((timestamp, usage)) = queue.pop() {
+        result = result.and_then(|_| writeln!(writer, "{},{:.1}", timestamp, usage));
+    }
+    if let Err(e) = result.and_then(|_| writer.flush()) {
+        warn!("{}", tr!("realtime-log-write-failed", path = path.display(), error = e));
+    }
+}
@@ -0,0 +1,127 @@
+//! `reset` 子命令：终止所有实例并清理PID文件，用于恢复到干净的状态
+
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::instances::{is_alive, is_instance_process};
+#[cfg(unix)]
+use crate::instances::signal;
+
+/// 发送 SIGTERM 后等待进程退出的时间，超时后发送 SIGKILL
+const GRACE_PERIOD: Duration = Duration::from_secs(2);
+
+/// 锁文件名（与PID文件位于同一目录）
+const LOCK_FILE: &str = "enhancecpu.lock";
+
+/// 单个进程的处理结果
+#[derive(PartialEq, Eq)]
+enum Outcome {
+    /// 收到 SIGTERM 后正常退出
+    Stopped,
+    /// 需要 SIGKILL 才能终止
+    ForceKilled,
+    /// 进程已不存在，或PID文件无法解析
+    Stale,
+}
+
+/// 目录中所有 `enhancecpu*.pid` 文件
+fn pid_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("enhancecpu") && name.ends_with(".pid"))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// 进程已不存在时返回 false；PID被其他程序占用（或为0、1、本进程）时告警并返回 false
+fn is_target(pid: u32) -> bool {
+    if !is_alive(pid) {
+        return false;
+    }
+    if !is_instance_process(pid) {
+        warn!("{}", tr!("reset-skip-foreign", pid = pid));
+        return false;
+    }
+    true
+}
+
+#[cfg(unix)]
+fn terminate(pid: u32) -> Outcome {
+    if !is_target(pid) || signal(pid, libc::SIGTERM).is_err() {
+        return Outcome::Stale;
+    }
+    let start = Instant::now();
+    while start.elapsed() < GRACE_PERIOD {
+        if !is_alive(pid) {
+            return Outcome::Stopped;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    warn!("{}", tr!("reset-force-kill", pid = pid, secs = GRACE_PERIOD.as_secs()));
+    let _ = signal(pid, libc::SIGKILL);
+    Outcome::ForceKilled
+}
+
+#[cfg(windows)]
+fn terminate(pid: u32) -> Outcome {
+    use std::process::{Command, Stdio};
+    if !is_target(pid) {
+        return Outcome::Stale;
+    }
+    // Windows 没有 SIGTERM，taskkill 不带 /F 时请求进程退出
+    let pid = pid.to_string();
+    let quiet = |args: &[&str]| {
+        Command::new("taskkill").args(args).stdout(Stdio::null()).stderr(Stdio::null()).status()
+    };
+    if !quiet(&["/PID", &pid]).is_ok_and(|s| s.success()) {
+        return Outcome::Stale;
+    }
+    thread::sleep(GRACE_PERIOD);
+    if quiet(&["/PID", &pid, "/F"]).is_ok_and(|s| s.success()) {
+        Outcome::ForceKilled
+    } else {
+        Outcome::Stopped
+    }
+}
+
+/// 终止 `dir` 中PID文件记录的所有实例并删除PID文件和锁文件，全部正常停止时返回 true
+pub fn reset(dir: &Path) -> bool {
+    let (mut stopped, mut killed, mut removed) = (0, 0, 0);
+    for file in pid_files(dir) {
+        let pid = std::fs::read_to_string(&file).ok().and_then(|s| s.trim().parse::<u32>().ok());
+        match pid.map_or(Outcome::Stale, terminate) {
+            Outcome::Stopped => stopped += 1,
+            Outcome::ForceKilled => killed += 1,
+            Outcome::Stale => {}
+        }
+        match std::fs::remove_file(&file) {
+            Ok(()) => removed += 1,
+            // 被终止的实例可能已自行删除PID文件
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => removed += 1,
+            Err(e) => warn!("{}", tr!("reset-remove-failed", path = file.display(), error = e)),
+        }
+        // --compress-pid-file 写入的状态文件与PID文件同名
+        let state_file = file.with_extension("state");
+        if state_file.exists()
+            && let Err(e) = std::fs::remove_file(&state_file) {
+            warn!("{}", tr!("reset-remove-failed", path = state_file.display(), error = e));
+        }
+    }
+    let lock = dir.join(LOCK_FILE);
+    if lock.exists()
+        && let Err(e) = std::fs::remove_file(&lock) {
+        warn!("{}", tr!("reset-remove-failed", path = lock.display(), error = e));
+    }
+    info!("{}", tr!("reset-done", stopped = stopped, killed = killed, removed = removed));
+    killed == 0
+}
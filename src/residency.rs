@@ -0,0 +1,139 @@
+//! 定期检查已分配内存的常驻情况（mincore），发现页面被换出时给出提示
+
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::state::LoadState;
+
+/// 内存常驻检查配置
+#[derive(Clone, Debug)]
+pub struct ResidencyCheck {
+    pub interval: Duration,
+    /// 常驻比例低于该百分比时告警
+    pub min_resident_percent: f64,
+    /// 告警时是否调用 madvise(MADV_WILLNEED) 预取页面
+    pub willneed: bool,
+}
+
+/// 一次检查的结果
+struct Residency {
+    resident_pages: usize,
+    total_pages: usize,
+}
+
+impl Residency {
+    fn percent(&self) -> f64 {
+        if self.total_pages == 0 {
+            100.0
+        } else {
+            self.resident_pages as f64 * 100.0 / self.total_pages as f64
+        }
+    }
+}
+
+/// 启动检查线程
+pub fn spawn_checker(check: ResidencyCheck, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut last_check = Instant::now();
+        while state.is_running() {
+            thread::sleep(Duration::from_millis(100));
+            if last_check.elapsed() < check.interval {
+                continue;
+            }
+            last_check = Instant::now();
+
+            let guard = state.memory.lock().unwrap_or_else(|e| e.into_inner());
+            let Some(buffer) = guard.as_deref() else {
+                continue;
+            };
+            match query(buffer) {
+                Ok(residency) => {
+                    let percent = residency.percent();
+                    info!(
+                        "{}",
+                        tr!(
+                            "residency-checked",
+                            resident = residency.resident_pages,
+                            total = residency.total_pages,
+                            percent = format!("{:.1}", percent)
+                        )
+                    );
+                    if percent < check.min_resident_percent {
+                        warn!(
+                            "{}",
+                            tr!(
+                                "residency-low",
+                                percent = format!("{:.1}", percent),
+                                min = format!("{:.1}", check.min_resident_percent)
+                            )
+                        );
+                        if check.willneed {
+                            willneed(buffer);
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!("{}", tr!("residency-failed", error = e));
+                    return;
+                }
+            }
+        }
+    })
+}
+
+/// 系统页面大小
+pub fn page_size() -> usize {
+    #[cfg(unix)]
+    {
+        let size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if size > 0 {
+            return size as usize;
+        }
+    }
+    4096
+}
+
+/// 返回按页对齐后的起始地址和长度
+fn page_range(buffer: &[u8]) -> (usize, usize) {
+    let page = page_size();
+    let start = buffer.as_ptr() as usize & !(page - 1);
+    let end = buffer.as_ptr() as usize + buffer.len();
+    (start, end - start)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos"))]
+fn query(buffer: &[u8]) -> std::io::Result<Residency> {
+    let page = page_size();
+    let (start, len) = page_range(buffer);
+    let total_pages = len.div_ceil(page);
+    let mut vec = vec![0u8; total_pages];
+    // SAFETY: 地址范围完全落在已分配的缓冲区所在页内，vec 长度等于页数
+    let ret = unsafe { libc::mincore(start as *mut libc::c_void, len, vec.as_mut_ptr().cast()) };
+    if ret != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // 每个字节的最低位表示对应页面是否常驻
+    let resident_pages = vec.iter().filter(|b| *b & 1 == 1).count();
+    Ok(Residency { resident_pages, total_pages })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+fn query(_buffer: &[u8]) -> std::io::Result<Residency> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, tr!("residency-unsupported")))
+}
+
+#[cfg(unix)]
+fn willneed(buffer: &[u8]) {
+    let (start, len) = page_range(buffer);
+    // SAFETY: MADV_WILLNEED 只是提示，不会改变内存内容
+    let ret = unsafe { libc::madvise(start as *mut libc::c_void, len, libc::MADV_WILLNEED) };
+    if ret == 0 {
+        info!("{}", tr!("residency-willneed"));
+    } else {
+        warn!("{}", tr!("residency-willneed-failed", error = std::io::Error::last_os_error()));
+    }
+}
+
+#[cfg(not(unix))]
+fn willneed(_buffer: &[u8]) {}
@@ -0,0 +1,48 @@
+//! 工作线程的实时调度策略（仅Linux）
+
+use clap::ValueEnum;
+
+/// 实时调度策略
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum RtPolicy {
+    /// SCHED_FIFO：同优先级线程之间不分时间片
+    Fifo,
+    /// SCHED_RR：同优先级线程之间轮转
+    Rr,
+}
+
+/// 启动前检查：非Linux平台或非root用户时给出警告
+pub fn check_support(policy: RtPolicy, priority: u8) {
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: geteuid 没有副作用
+        if unsafe { libc::geteuid() } != 0 {
+            warn!("{}", tr!("sched-needs-root", policy = format!("{:?}", policy)));
+        }
+        info!("{}", tr!("sched-policy", policy = format!("{:?}", policy), priority = priority));
+        warn!("{}", tr!("sched-starvation"));
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (policy, priority);
+        warn!("{}", tr!("sched-unsupported"));
+    }
+}
+
+/// 为当前线程设置实时调度策略
+#[cfg(target_os = "linux")]
+pub fn apply_to_current_thread(policy: RtPolicy, priority: u8) -> std::io::Result<()> {
+    let policy = match policy {
+        RtPolicy::Fifo => libc::SCHED_FIFO,
+        RtPolicy::Rr => libc::SCHED_RR,
+    };
+    let param = libc::sched_param { sched_priority: i32::from(priority) };
+    // SAFETY: pid 为0表示调用线程，param 在调用期间有效
+    let ret = unsafe { libc::sched_setscheduler(0, policy, &param) };
+    if ret == 0 { Ok(()) } else { Err(std::io::Error::last_os_error()) }
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn apply_to_current_thread(_policy: RtPolicy, _priority: u8) -> std::io::Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "RT scheduling not supported"))
+}
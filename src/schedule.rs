@@ -0,0 +1,85 @@
+//! `--load-schedule`：按 "work=10s,pause=5s,repeat=4" 循环负载与停顿，用于测试根据负载变化伸缩的系统
+//!
+//! 停顿阶段只把目标使用率置为0，工作线程保持运行，没有反复创建线程的开销。与 `--duration`
+//! 同时使用时，到期即停止，不论还剩几个周期。
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::state::LoadState;
+use crate::timeutil;
+
+/// 检查运行标志的间隔
+const DRIVER_TICK: Duration = Duration::from_millis(100);
+
+/// 循环负载的配置
+#[derive(Clone, Copy, Debug)]
+pub struct Schedule {
+    /// 每个周期的负载时长
+    pub work: Duration,
+    /// 每个周期的停顿时长
+    pub pause: Duration,
+    /// 周期数，0 表示一直循环
+    pub repeat: u32,
+}
+
+/// 当前所处的阶段
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SchedulePhase {
+    Active,
+    Pause,
+}
+
+impl SchedulePhase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SchedulePhase::Active => "ACTIVE",
+            SchedulePhase::Pause => "PAUSE",
+        }
+    }
+}
+
+/// 驱动线程保存在 `LoadState::schedule` 中的进度，供状态线程显示
+#[derive(Clone, Copy, Debug)]
+pub struct ScheduleStatus {
+    pub phase: SchedulePhase,
+    /// 当前阶段开始的时刻
+    pub since: Instant,
+    /// 包括当前周期在内还剩的周期数，一直循环时为 None
+    pub remaining: Option<u32>,
+}
+
+/// 启动驱动线程：每个周期先按 `percent` 负载 `work`，再停顿 `pause`；完成全部周期后停止负载
+pub fn spawn_schedule_driver(schedule: Schedule, percent: u8, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut cycle = 0u32;
+        'cycles: while schedule.repeat == 0 || cycle < schedule.repeat {
+            cycle += 1;
+            let remaining = (schedule.repeat > 0).then(|| schedule.repeat - cycle + 1);
+            for (phase, len) in [(SchedulePhase::Active, schedule.work), (SchedulePhase::Pause, schedule.pause)] {
+                let target = if phase == SchedulePhase::Active { percent } else { 0 };
+                state.target_percent.store(target, Ordering::Relaxed);
+                let since = Instant::now();
+                *state.schedule.lock().unwrap_or_else(|e| e.into_inner()) =
+                    Some(ScheduleStatus { phase, since, remaining });
+                info!(
+                    "[{}] {}",
+                    timeutil::now_rfc3339(),
+                    tr!("schedule-phase-changed", phase = phase.as_str(), cycle = cycle, target = target)
+                );
+                while since.elapsed() < len {
+                    if !state.is_running() {
+                        break 'cycles;
+                    }
+                    thread::sleep(DRIVER_TICK);
+                }
+            }
+        }
+        if state.is_running() {
+            info!("{}", tr!("schedule-finished", count = cycle));
+            state.finish();
+        }
+    })
+}
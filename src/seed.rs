@@ -0,0 +1,55 @@
+//! 伪随机数种子：所有随机负载的随机数流都由同一个种子派生，指定 `--random-seed` 即可复现一次运行
+//!
+//! 未指定种子时随机生成一个并在启动时输出，同样可以用它复现。每个线程的种子为
+//! `seed XOR (流序号 * 0x9e3779b97f4a7c15)`，工作线程的流序号即线程序号。
+
+use std::sync::OnceLock;
+
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+
+/// 黄金分割比例对应的64位常数，使相邻序号派生出的种子差异足够大
+const STREAM_MULTIPLIER: u64 = 0x9e3779b97f4a7c15;
+
+/// 内存访问线程的流序号起点，与工作线程的序号不重叠
+pub const MEMORY_ACCESS_STREAMS: usize = 1 << 16;
+
+/// 硬盘I/O线程的流序号起点
+pub const DISK_IO_STREAMS: usize = 2 << 16;
+
+/// 内存规整探测线程的流序号
+pub const COMPACTION_STREAM: usize = 3 << 16;
+
+/// page-cache 负载的流序号起点：起点本身用于生成临时文件内容，之后依次为各读取线程
+pub const PAGE_CACHE_STREAMS: usize = 4 << 16;
+
+/// `benchmark numa-latency` 生成指针环的流序号
+pub const NUMA_LATENCY_STREAM: usize = 5 << 16;
+
+/// `--memory-dealloc-pattern random` 打乱释放顺序的流序号
+pub const DEALLOC_STREAM: usize = 6 << 16;
+
+/// `--memory-fragmentation` 的流序号：首个用于初始分配，下一个用于抖动线程
+pub const FRAGMENT_STREAMS: usize = 7 << 16;
+
+/// `--swap-pressure` 随机访问页面的流序号
+pub const SWAP_STREAM: usize = 8 << 16;
+
+/// memory-bound 和 branch 空转循环生成指针环和随机数组的流序号
+pub const BUSY_LOOP_STREAM: usize = 9 << 16;
+
+/// `io-stress` 的流序号：每个I/O线程一个，用于填充文件、选择随机位置和生成写入的数据
+pub const IO_STRESS_STREAMS: usize = 10 << 16;
+
+static SEED: OnceLock<u64> = OnceLock::new();
+
+/// 设置本次运行的种子，`seed` 为 None 时随机生成；返回实际使用的种子
+pub fn init(seed: Option<u64>) -> u64 {
+    *SEED.get_or_init(|| seed.unwrap_or_else(rand::random))
+}
+
+/// 第 `stream` 个随机数流的生成器
+pub fn thread_rng(stream: usize) -> SmallRng {
+    let seed = init(None);
+    SmallRng::seed_from_u64(seed ^ (stream as u64).wrapping_mul(STREAM_MULTIPLIER))
+}
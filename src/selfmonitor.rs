@@ -0,0 +1,109 @@
+//! 工作线程自监控：每个线程记录实际的忙碌和休眠时间，状态线程据此输出实际占空比与目标的偏差
+//!
+//! 偏差超过10个百分点时告警，用于排查在某台机器上达不到目标使用率的原因（调度延迟、休眠精度等）。
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// 实际占空比偏离目标超过该值（百分点）时告警
+const DEVIATION_WARNING: f64 = 10.0;
+
+/// 单个工作线程的累计量
+#[derive(Default)]
+pub struct ThreadStats {
+    busy_nanos: AtomicU64,
+    idle_nanos: AtomicU64,
+    iterations: AtomicU64,
+    /// 每个工作周期的目标使用率之和，除以周期数得到平均目标
+    target_sum: AtomicU64,
+    cycles: AtomicU64,
+}
+
+impl ThreadStats {
+    /// 累计的计算时间
+    pub fn busy_time(&self) -> Duration {
+        Duration::from_nanos(self.busy_nanos.load(Ordering::Relaxed))
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            busy_nanos: self.busy_nanos.load(Ordering::Relaxed),
+            idle_nanos: self.idle_nanos.load(Ordering::Relaxed),
+            iterations: self.iterations.load(Ordering::Relaxed),
+            target_sum: self.target_sum.load(Ordering::Relaxed),
+            cycles: self.cycles.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Snapshot {
+    busy_nanos: u64,
+    idle_nanos: u64,
+    iterations: u64,
+    target_sum: u64,
+    cycles: u64,
+}
+
+/// 按比例休眠：busy / (busy + idle) = percent / 100；启用自监控时记录本周期的实际时间
+pub fn duty_cycle_sleep(stats: Option<&ThreadStats>, percent: u8, iterations: u64, busy: Duration) {
+    let idle = if percent < 100 {
+        let start = Instant::now();
+        thread::sleep(busy.mul_f64(f64::from(100 - percent) / f64::from(percent)));
+        start.elapsed()
+    } else {
+        Duration::ZERO
+    };
+    if let Some(stats) = stats {
+        stats.busy_nanos.fetch_add(busy.as_nanos() as u64, Ordering::Relaxed);
+        stats.idle_nanos.fetch_add(idle.as_nanos() as u64, Ordering::Relaxed);
+        stats.iterations.fetch_add(iterations, Ordering::Relaxed);
+        stats.target_sum.fetch_add(u64::from(percent), Ordering::Relaxed);
+        stats.cycles.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// 状态线程持有的读取端，每次输出自上次以来的变化量
+pub struct SelfMonitor {
+    threads: Vec<Arc<ThreadStats>>,
+    last: Vec<Snapshot>,
+}
+
+impl SelfMonitor {
+    pub fn new(threads: Vec<Arc<ThreadStats>>) -> Self {
+        let last = vec![Snapshot::default(); threads.len()];
+        SelfMonitor { threads, last }
+    }
+
+    /// 输出每个线程在上一个间隔内的目标和实际占空比；没有完成任何工作周期的线程跳过
+    pub fn report(&mut self) {
+        info!("{}", tr!("self-monitor-header"));
+        for (i, (stats, last)) in self.threads.iter().zip(self.last.iter_mut()).enumerate() {
+            let now = stats.snapshot();
+            let cycles = now.cycles - last.cycles;
+            let total = (now.busy_nanos - last.busy_nanos) + (now.idle_nanos - last.idle_nanos);
+            if cycles > 0 && total > 0 {
+                let target = (now.target_sum - last.target_sum) as f64 / cycles as f64;
+                let actual = (now.busy_nanos - last.busy_nanos) as f64 * 100.0 / total as f64;
+                let deviation = actual - target;
+                info!(
+                    "  {}",
+                    tr!(
+                        "self-monitor-thread",
+                        thread = i,
+                        target = format!("{:.1}", target),
+                        actual = format!("{:.1}", actual),
+                        deviation = format!("{:+.1}", deviation),
+                        iterations = now.iterations
+                    )
+                );
+                if deviation.abs() > DEVIATION_WARNING {
+                    warn!("{}", tr!("self-monitor-deviation", thread = i, deviation = format!("{:+.1}", deviation)));
+                }
+            }
+            *last = now;
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! `--signal-adjust`：运行中收到 SIGUSR1 时加大负载、收到 SIGUSR2 时减小负载，后台运行的负载无需重启即可调整（仅Unix）
+//!
+//! 调整的是工作线程已在读取的 `target_percent` 或 `active_workers`，每个信号改变 `--signal-step`。
+//! 调整后的取值输出到标准错误，标准输出被重定向时仍能看到。
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+use crate::signals;
+use crate::state::LoadState;
+use crate::timeutil;
+
+/// 检查新信号的间隔
+const DRIVER_TICK: Duration = Duration::from_millis(100);
+
+/// 信号调整的对象
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum AdjustTarget {
+    /// 目标CPU使用率，范围0-100%
+    Percent,
+    /// 启用的工作线程数，范围0到 --cores
+    Cores,
+}
+
+impl AdjustTarget {
+    /// 未指定 `--signal-step` 时每个信号的调整量
+    pub fn default_step(self) -> u32 {
+        match self {
+            AdjustTarget::Percent => 10,
+            AdjustTarget::Cores => 1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SignalAdjust {
+    pub target: AdjustTarget,
+    pub step: u32,
+    /// 已启动的工作线程数，`cores` 的上限
+    pub workers: usize,
+}
+
+impl SignalAdjust {
+    /// 按 `delta` 个调整量（正数加大、负数减小）修改负载，返回调整后的取值
+    fn apply(&self, delta: i64, state: &LoadState) -> i64 {
+        let change = delta * i64::from(self.step);
+        match self.target {
+            AdjustTarget::Percent => {
+                let current = i64::from(state.target_percent.load(Ordering::Relaxed));
+                let percent = (current + change).clamp(0, 100);
+                state.target_percent.store(percent as u8, Ordering::Relaxed);
+                percent
+            }
+            AdjustTarget::Cores => {
+                let current = state.active_workers.load(Ordering::Relaxed).min(self.workers) as i64;
+                let cores = (current + change).clamp(0, self.workers as i64);
+                state.set_active_workers(cores as usize);
+                cores
+            }
+        }
+    }
+}
+
+/// 启动信号调整线程；调用前须已安装 SIGUSR1 和 SIGUSR2 的计数处理函数
+pub fn spawn_signal_adjuster(adjust: SignalAdjust, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        info!("{}", tr!("signal-adjust-ready", pid = std::process::id(), step = adjust.step));
+        while state.is_running() {
+            let delta = i64::from(signals::take_usr1()) - i64::from(signals::take_usr2());
+            if delta != 0 {
+                let value = adjust.apply(delta, &state);
+                let message = match adjust.target {
+                    AdjustTarget::Percent => tr!("signal-adjust-percent", percent = value),
+                    AdjustTarget::Cores => tr!("signal-adjust-cores", cores = value, max = adjust.workers),
+                };
+                notice!("[{}] {}", timeutil::now_rfc3339(), message);
+            }
+            thread::sleep(DRIVER_TICK);
+        }
+    })
+}
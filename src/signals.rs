@@ -0,0 +1,118 @@
+//! `stop --signal` 接受的信号名和编号，以及 `--spike-on-signal`、`--signal-adjust` 的 SIGUSR1/SIGUSR2 计数
+//!
+//! 信号处理函数统一通过 [`register`]（signal-hook）注册，不直接调用 `sigaction`。
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// 收到但尚未取走的 SIGUSR1 次数
+static USR1_RECEIVED: AtomicU32 = AtomicU32::new(0);
+
+/// 收到但尚未取走的 SIGUSR2 次数
+static USR2_RECEIVED: AtomicU32 = AtomicU32::new(0);
+
+/// 可以按名称指定的信号；名称不带 `SIG` 前缀
+fn table() -> HashMap<&'static str, i32> {
+    #[cfg(unix)]
+    {
+        HashMap::from([
+            ("INT", libc::SIGINT),
+            ("KILL", libc::SIGKILL),
+            ("TERM", libc::SIGTERM),
+            ("USR1", libc::SIGUSR1),
+            ("USR2", libc::SIGUSR2),
+        ])
+    }
+    #[cfg(not(unix))]
+    {
+        HashMap::new()
+    }
+}
+
+/// 解析信号名（`SIGUSR1`、`usr1`）或编号（1-64）
+pub fn parse_signal(s: &str) -> Result<i32, String> {
+    let s = s.trim();
+    if let Ok(number) = s.parse::<i32>() {
+        return match number {
+            1..=64 => Ok(number),
+            _ => Err(tr!("signal-out-of-range", value = number)),
+        };
+    }
+    let upper = s.to_ascii_uppercase();
+    let name = upper.strip_prefix("SIG").unwrap_or(&upper);
+    let table = table();
+    table.get(name).copied().ok_or_else(|| {
+        let mut names: Vec<String> = table.keys().map(|name| format!("SIG{}", name)).collect();
+        names.sort_unstable();
+        tr!("signal-unknown", value = s, names = names.join(", "))
+    })
+}
+
+/// 信号的显示名，例如 "SIGUSR1"；不在表中的编号原样显示
+pub fn name(signal: i32) -> String {
+    table()
+        .into_iter()
+        .find(|&(_, number)| number == signal)
+        .map_or_else(|| signal.to_string(), |(name, _)| format!("SIG{}", name))
+}
+
+/// 注册信号处理函数，同一信号的多个处理函数按注册顺序执行；被打断的系统调用自动重试（SA_RESTART）
+///
+/// # Safety
+///
+/// `action` 在信号处理上下文中运行，只能执行异步信号安全的操作，例如原子操作或读写
+/// 常量初始化的线程局部变量；不能加锁、分配内存或输出日志。
+#[cfg(unix)]
+pub unsafe fn register(signal: libc::c_int, action: impl Fn() + Send + Sync + 'static) -> std::io::Result<()> {
+    // SAFETY: 由调用方保证 action 是异步信号安全的
+    unsafe { signal_hook::low_level::register(signal, action) }.map(drop)
+}
+
+/// 为 `signal` 注册一次计数处理函数；重复调用不会重复计数
+#[cfg(unix)]
+fn install_counter(
+    signal: libc::c_int,
+    counter: &'static AtomicU32,
+    installed: &'static AtomicBool,
+) -> std::io::Result<()> {
+    if installed.swap(true, Ordering::SeqCst) {
+        return Ok(());
+    }
+    // SAFETY: 处理函数只执行一次原子加法
+    unsafe {
+        register(signal, move || {
+            counter.fetch_add(1, Ordering::Relaxed);
+        })
+    }
+    .inspect_err(|_| installed.store(false, Ordering::SeqCst))
+}
+
+/// 安装 SIGUSR1 处理函数，收到信号时只计数，由驱动线程轮询 [`take_usr1`]
+#[cfg(unix)]
+pub fn install_usr1_counter() -> std::io::Result<()> {
+    static INSTALLED: AtomicBool = AtomicBool::new(false);
+    install_counter(libc::SIGUSR1, &USR1_RECEIVED, &INSTALLED)
+}
+
+/// 安装 SIGUSR2 处理函数，收到信号时只计数，由驱动线程轮询 [`take_usr2`]
+#[cfg(unix)]
+pub fn install_usr2_counter() -> std::io::Result<()> {
+    static INSTALLED: AtomicBool = AtomicBool::new(false);
+    install_counter(libc::SIGUSR2, &USR2_RECEIVED, &INSTALLED)
+}
+
+/// 取走上次调用以来收到的 SIGUSR1 次数
+pub fn take_usr1() -> u32 {
+    USR1_RECEIVED.swap(0, Ordering::Relaxed)
+}
+
+/// 取走上次调用以来收到的 SIGUSR2 次数
+pub fn take_usr2() -> u32 {
+    USR2_RECEIVED.swap(0, Ordering::Relaxed)
+}
+
+/// 是否为用于结束进程的信号，发送后 `stop` 删除PID文件
+pub fn terminates(signal: i32) -> bool {
+    let table = table();
+    ["INT", "KILL", "TERM"].iter().any(|name| table.get(name) == Some(&signal))
+}
@@ -0,0 +1,258 @@
+//! TCP 回环连接压力（仅Unix）：连接线程按目标速率反复 connect 再 close，接受线程 accept 后立即关闭
+//!
+//! 多个接受线程时每个线程各自监听同一端口（`SO_REUSEPORT`），由内核在监听套接字之间分发连接，
+//! 这是高性能服务器 accept 循环的常见写法。连接端关闭时发送 RST（`SO_LINGER` 为0），
+//! 不留下 TIME_WAIT，避免高速率下很快耗尽临时端口。
+
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, TcpListener, TcpStream};
+use std::os::fd::{AsRawFd, FromRawFd};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::state::LoadState;
+
+/// 输出统计的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 接受线程等待新连接的超时（毫秒），用于定期检查运行标志
+const POLL_TIMEOUT_MS: i32 = 200;
+
+/// 连接超时；监听队列满时 SYN 会被丢弃，不设超时的 connect 要重传很久才返回
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// 限速时每批发起的连接数，避免每个连接都检查时间
+const PACING_BATCH: u64 = 16;
+
+/// 接受和连接的计数
+#[derive(Default)]
+struct Counters {
+    accepted: AtomicU64,
+    connected: AtomicU64,
+    failed: AtomicU64,
+}
+
+/// 设置套接字选项
+fn set_option<T>(fd: i32, level: i32, name: i32, value: &T) -> io::Result<()> {
+    // SAFETY: value 指向的内存在调用期间有效，长度与其类型一致
+    let ret = unsafe {
+        libc::setsockopt(fd, level, name, (value as *const T).cast(), std::mem::size_of_val(value) as libc::socklen_t)
+    };
+    if ret != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// 创建开启 `SO_REUSEPORT` 的监听套接字；标准库的 TcpListener::bind 无法在 bind 之前设置选项
+fn reuseport_listener(addr: SocketAddrV4) -> io::Result<TcpListener> {
+    // SAFETY: socket 不访问用户内存
+    let fd = unsafe { libc::socket(libc::AF_INET, libc::SOCK_STREAM, 0) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // SAFETY: fd 刚由 socket 创建，所有权转交给 TcpListener，出错时随之关闭
+    let listener = unsafe { TcpListener::from_raw_fd(fd) };
+    // 不让 --post-start-cmd 等钩子启动的子进程继承监听套接字
+    // SAFETY: fcntl 只修改 fd 的标志
+    unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) };
+    set_option(fd, libc::SOL_SOCKET, libc::SO_REUSEPORT, &1i32)?;
+    let sockaddr = libc::sockaddr_in {
+        #[cfg(any(target_os = "macos", target_os = "freebsd"))]
+        sin_len: std::mem::size_of::<libc::sockaddr_in>() as u8,
+        sin_family: libc::AF_INET as libc::sa_family_t,
+        sin_port: addr.port().to_be(),
+        sin_addr: libc::in_addr { s_addr: u32::from(*addr.ip()).to_be() },
+        sin_zero: Default::default(),
+    };
+    // SAFETY: sockaddr 是有效的 sockaddr_in，长度与之一致
+    let ret = unsafe {
+        libc::bind(
+            fd,
+            (&sockaddr as *const libc::sockaddr_in).cast(),
+            std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+        )
+    };
+    // SAFETY: listen 不访问用户内存
+    if ret != 0 || unsafe { libc::listen(fd, libc::SOMAXCONN) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(listener)
+}
+
+/// 创建 `acceptors` 个监听同一端口的套接字；第一个绑定随机端口，其余复用该端口
+fn bind_listeners(acceptors: usize) -> io::Result<Vec<TcpListener>> {
+    let first = reuseport_listener(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 0))?;
+    let SocketAddr::V4(addr) = first.local_addr()? else {
+        unreachable!("监听地址是 IPv4");
+    };
+    let mut listeners = vec![first];
+    for _ in 1..acceptors {
+        listeners.push(reuseport_listener(addr)?);
+    }
+    for listener in &listeners {
+        listener.set_nonblocking(true)?;
+    }
+    Ok(listeners)
+}
+
+/// 启动接受线程、连接线程和统计输出线程，`rate` 为 None 时尽可能快地发起连接
+pub fn spawn_socket_stress(
+    rate: Option<u64>,
+    acceptors: usize,
+    state: Arc<LoadState>,
+) -> io::Result<Vec<JoinHandle<()>>> {
+    let listeners = bind_listeners(acceptors.max(1))?;
+    let addr = listeners[0].local_addr()?;
+    let target = rate.map_or_else(|| tr!("socket-rate-unlimited"), |rate| rate.to_string());
+    info!("{}", tr!("socket-started", addr = addr, acceptors = listeners.len(), rate = target.clone()));
+    let counters = Arc::new(Counters::default());
+
+    let mut handles: Vec<JoinHandle<()>> = listeners
+        .into_iter()
+        .map(|listener| {
+            let state = state.clone();
+            let counters = counters.clone();
+            thread::spawn(move || run_acceptor(&listener, &counters, &state))
+        })
+        .collect();
+    handles.push({
+        let state = state.clone();
+        let counters = counters.clone();
+        thread::spawn(move || run_connector(addr, rate, &counters, &state))
+    });
+    handles.push(thread::spawn(move || run_reporter(&target, &counters, &state)));
+    Ok(handles)
+}
+
+fn run_acceptor(listener: &TcpListener, counters: &Counters, state: &LoadState) {
+    let mut poll_fd = libc::pollfd { fd: listener.as_raw_fd(), events: libc::POLLIN, revents: 0 };
+    while state.is_running() {
+        // SAFETY: poll_fd 是有效的 pollfd，数量为1
+        if unsafe { libc::poll(&mut poll_fd, 1, POLL_TIMEOUT_MS) } <= 0 {
+            continue;
+        }
+        // 一次取完所有已完成握手的连接，接受后立即关闭
+        let mut accepted = 0;
+        while let Ok((stream, _)) = listener.accept() {
+            drop(stream);
+            accepted += 1;
+        }
+        counters.accepted.fetch_add(accepted, Ordering::Relaxed);
+    }
+}
+
+fn run_connector(addr: SocketAddr, rate: Option<u64>, counters: &Counters, state: &LoadState) {
+    let linger = libc::linger { l_onoff: 1, l_linger: 0 };
+    let mut attempts = 0u64;
+    let start = Instant::now();
+    while state.is_running() {
+        if let Some(rate) = rate {
+            // 超前于目标速率时等待
+            let due = start + Duration::from_secs_f64(attempts as f64 / rate as f64);
+            let now = Instant::now();
+            if due > now {
+                thread::sleep(due - now);
+            }
+        }
+        let batch = rate.map_or(PACING_BATCH, |rate| rate.clamp(1, PACING_BATCH));
+        let (mut connected, mut failed) = (0, 0);
+        for _ in 0..batch {
+            match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+                Ok(stream) => {
+                    // 关闭时发送 RST，不进入 TIME_WAIT
+                    let _ = set_option(stream.as_raw_fd(), libc::SOL_SOCKET, libc::SO_LINGER, &linger);
+                    connected += 1;
+                }
+                // 连接超时（监听队列已满）或临时端口耗尽
+                Err(_) => failed += 1,
+            }
+        }
+        attempts += batch;
+        counters.connected.fetch_add(connected, Ordering::Relaxed);
+        counters.failed.fetch_add(failed, Ordering::Relaxed);
+    }
+}
+
+/// 网络软中断的累计计数：softirq 占用的CPU时间（jiffies）、总CPU时间，以及 softnet 处理和丢弃的包数
+#[derive(Clone, Copy, Debug, Default)]
+struct SoftnetSample {
+    softirq: u64,
+    total: u64,
+    processed: u64,
+    dropped: u64,
+}
+
+impl SoftnetSample {
+    /// 读取 /proc/stat 的 softirq 时间和 /proc/net/softnet_stat 各CPU的前两列（十六进制）
+    fn read() -> Option<Self> {
+        let stat = std::fs::read_to_string("/proc/stat").ok()?;
+        let times: Vec<u64> = stat.lines().next()?.split_whitespace().skip(1).filter_map(|v| v.parse().ok()).collect();
+        let softnet = std::fs::read_to_string("/proc/net/softnet_stat").ok()?;
+        let mut sample = SoftnetSample { softirq: *times.get(6)?, total: times.iter().sum(), ..Default::default() };
+        for line in softnet.lines() {
+            let mut columns = line.split_whitespace().map(|v| u64::from_str_radix(v, 16).unwrap_or(0));
+            sample.processed += columns.next().unwrap_or(0);
+            sample.dropped += columns.next().unwrap_or(0);
+        }
+        Some(sample)
+    }
+
+    /// 相对于 `earlier` 的软中断CPU占比（%）、每秒处理的包数和丢弃的包数
+    fn since(&self, earlier: &Self, secs: f64) -> (f64, f64, u64) {
+        let total = self.total.saturating_sub(earlier.total).max(1);
+        (
+            self.softirq.saturating_sub(earlier.softirq) as f64 * 100.0 / total as f64,
+            self.processed.saturating_sub(earlier.processed) as f64 / secs,
+            self.dropped.saturating_sub(earlier.dropped),
+        )
+    }
+}
+
+/// 定期输出实际接受速率与目标速率、连接失败数，以及网络软中断的开销
+fn run_reporter(target: &str, counters: &Counters, state: &LoadState) {
+    let mut last = (0u64, 0u64, 0u64);
+    let mut last_softnet = SoftnetSample::read();
+    let mut last_report = Instant::now();
+    while state.is_running() {
+        thread::sleep(Duration::from_millis(100));
+        let elapsed = last_report.elapsed();
+        if elapsed < REPORT_INTERVAL {
+            continue;
+        }
+        let secs = elapsed.as_secs_f64();
+        let current = (
+            counters.accepted.load(Ordering::Relaxed),
+            counters.connected.load(Ordering::Relaxed),
+            counters.failed.load(Ordering::Relaxed),
+        );
+        info!(
+            "{}",
+            tr!(
+                "socket-stats",
+                accepted = format!("{:.0}", (current.0 - last.0) as f64 / secs),
+                connected = format!("{:.0}", (current.1 - last.1) as f64 / secs),
+                target = target,
+                failed = current.2 - last.2
+            )
+        );
+        let softnet = SoftnetSample::read();
+        if let (Some(now), Some(earlier)) = (&softnet, &last_softnet) {
+            let (softirq, processed, dropped) = now.since(earlier, secs);
+            info!(
+                "  {}",
+                tr!(
+                    "socket-softnet",
+                    softirq = format!("{:.1}", softirq),
+                    processed = format!("{:.0}", processed),
+                    dropped = dropped
+                )
+            );
+        }
+        last = current;
+        last_softnet = softnet;
+        last_report = Instant::now();
+    }
+}
@@ -0,0 +1,125 @@
+//! `benchmark fork-spawn`：测量创建进程或线程的耗时，以JSON输出延迟分布便于在CI中跟踪基线
+
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+
+use crate::error::LoadError;
+use crate::histogram::LatencyHistogram;
+use crate::json::Json;
+
+/// 创建方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum SpawnMethod {
+    /// fork 子进程，计时到子进程通过管道报到为止（仅Unix）
+    #[default]
+    Fork,
+    /// 启动 /bin/true 并等待其退出
+    Exec,
+    /// 创建一个空线程并等待其结束
+    Thread,
+}
+
+impl SpawnMethod {
+    fn name(self) -> String {
+        self.to_possible_value().map_or_else(String::new, |v| v.get_name().to_string())
+    }
+}
+
+/// fork 一个子进程，返回从 fork 前到读到子进程写入的字节的耗时；子进程回收不计入
+#[cfg(unix)]
+fn fork_once() -> std::io::Result<Duration> {
+    let mut fds = [0; 2];
+    // SAFETY: fds 有两个元素，pipe 成功时写入读写两端
+    if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    let [read_fd, write_fd] = fds;
+    let start = Instant::now();
+    // SAFETY: 子进程只调用 async-signal-safe 的 write 和 _exit
+    let pid = unsafe { libc::fork() };
+    if pid == 0 {
+        // SAFETY: 写入一个字节后立即退出，不运行析构和 atexit 处理
+        unsafe {
+            libc::write(write_fd, [1u8].as_ptr().cast(), 1);
+            libc::_exit(0);
+        }
+    }
+    let fork_error = (pid < 0).then(std::io::Error::last_os_error);
+    // SAFETY: 父进程不再需要写端；关闭后子进程异常退出时 read 返回0而不是一直阻塞
+    unsafe { libc::close(write_fd) };
+    let result = if let Some(e) = fork_error {
+        Err(e)
+    } else {
+        let mut byte = 0u8;
+        // SAFETY: 读入一个字节到栈上的缓冲区
+        let n = unsafe { libc::read(read_fd, (&mut byte as *mut u8).cast(), 1) };
+        let elapsed = start.elapsed();
+        // SAFETY: pid 是刚 fork 出的子进程
+        unsafe { libc::waitpid(pid, std::ptr::null_mut(), 0) };
+        match n {
+            1 => Ok(elapsed),
+            0 => Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof)),
+            _ => Err(std::io::Error::last_os_error()),
+        }
+    };
+    // SAFETY: 读端由本函数创建，关闭后不再使用
+    unsafe { libc::close(read_fd) };
+    result
+}
+
+fn exec_once() -> std::io::Result<Duration> {
+    let start = Instant::now();
+    #[cfg(unix)]
+    let status = Command::new("/bin/true").status()?;
+    #[cfg(not(unix))]
+    let status = Command::new("cmd").args(["/C", "exit", "0"]).status()?;
+    let elapsed = start.elapsed();
+    if !status.success() {
+        return Err(std::io::Error::other(status.to_string()));
+    }
+    Ok(elapsed)
+}
+
+fn thread_once() -> std::io::Result<Duration> {
+    let start = Instant::now();
+    thread::Builder::new().spawn(|| {})?.join().map_err(|_| std::io::Error::other("thread panicked"))?;
+    Ok(start.elapsed())
+}
+
+/// 执行 `count` 次创建，输出总耗时、平均值和 p50/p95/p99（微秒）
+pub fn run(method: SpawnMethod, count: u32) -> Result<(), LoadError> {
+    let once: fn() -> std::io::Result<Duration> = match method {
+        #[cfg(unix)]
+        SpawnMethod::Fork => fork_once,
+        #[cfg(not(unix))]
+        SpawnMethod::Fork => return Err(LoadError::PlatformUnsupported(tr!("spawn-fork-unsupported"))),
+        SpawnMethod::Exec => exec_once,
+        SpawnMethod::Thread => thread_once,
+    };
+    let mut histogram = LatencyHistogram::default();
+    let start = Instant::now();
+    for _ in 0..count {
+        let elapsed = once()
+            .map_err(|e| std::io::Error::new(e.kind(), tr!("spawn-failed", method = method.name(), error = e)))?;
+        histogram.record(elapsed.as_nanos() as u64);
+    }
+    let total = start.elapsed();
+
+    let micros = |nanos: f64| Json::Number((nanos / 1000.0 * 100.0).round() / 100.0);
+    let report = Json::object([
+        ("benchmark", Json::from("fork-spawn")),
+        ("method", method.name().into()),
+        ("count", count.into()),
+        ("total_ms", Json::Number((total.as_secs_f64() * 1e6).round() / 1e3)),
+        ("mean_us", micros(histogram.mean())),
+        ("p50_us", micros(histogram.percentile(50.0) as f64)),
+        ("p95_us", micros(histogram.percentile(95.0) as f64)),
+        ("p99_us", micros(histogram.percentile(99.0) as f64)),
+        ("max_us", micros(histogram.max() as f64)),
+    ]);
+    info!("{}", report);
+    Ok(())
+}
@@ -0,0 +1,109 @@
+//! spin 负载的工作线程：按目标使用率在计算与休眠之间交替，命令行和 [`crate::LoadHandle`] 共用
+
+use std::thread;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use crate::ratelimit::RateLimiter;
+use crate::seed;
+use crate::selfmonitor;
+use crate::state::LoadState;
+use crate::workload::{BusyLoop, BusyLooper, WorkVerifier};
+
+/// 每轮计算的迭代次数，占空比按每轮的实际耗时计算空闲时间
+pub const WORK_BURST_ITERATIONS: u32 = 20_000;
+
+/// 目标使用率为0%时的空闲轮询间隔
+pub const IDLE_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 未指定 `--work-batch` 时两次检查运行标志之间的迭代次数
+pub const DEFAULT_WORK_BATCH: u64 = 100;
+
+/// spin 负载的计算参数
+#[derive(Clone, Copy, Debug)]
+pub struct SpinSettings {
+    pub busy_loop: BusyLoop,
+    /// 每个线程每秒的迭代次数上限
+    pub rate: Option<f64>,
+    /// `--cpu-work-verify` 测得的基准迭代速率
+    pub verify_baseline: Option<f64>,
+    /// 每轮计算内两次检查运行标志之间的迭代次数
+    pub work_batch: u64,
+    /// memory-bound 每个线程的缓冲区大小
+    pub cache_size: usize,
+}
+
+/// CPU密集型任务，按目标使用率在计算与休眠之间交替，返回完成的迭代次数
+///
+/// `index` 为线程序号；`fixed_percent` 为该线程的覆盖使用率，不随全局目标变化；
+/// `limit` 为迭代次数上限，达到后线程退出。
+pub fn cpu_intensive_task(
+    state: &LoadState,
+    index: usize,
+    fixed_percent: Option<u8>,
+    limit: Option<u64>,
+    settings: SpinSettings,
+) -> u64 {
+    let mut looper = BusyLooper::new(settings.busy_loop);
+    looper.set_cache_size(settings.cache_size);
+    let mut verifier = settings.verify_baseline.map(WorkVerifier::new);
+    let mut limiter = settings.rate.map(RateLimiter::new);
+    let mut rng = seed::thread_rng(index);
+    let mut done: u64 = 0;
+    while state.is_running() {
+        let remaining = limit.map_or(u64::MAX, |limit| limit - done);
+        if remaining == 0 {
+            break;
+        }
+        state.wait_until_active(index);
+        let percent = state.thread_percent(index, fixed_percent);
+        if percent == 0 {
+            thread::sleep(IDLE_POLL_INTERVAL);
+            continue;
+        }
+        let percent = state.with_noise(percent, &mut rng);
+
+        let mut iterations = remaining.min(u64::from(WORK_BURST_ITERATIONS));
+        if let Some(limiter) = &mut limiter {
+            match limiter.take(iterations) {
+                Ok(granted) => iterations = granted,
+                Err(wait) => {
+                    thread::sleep(wait.min(IDLE_POLL_INTERVAL));
+                    continue;
+                }
+            }
+        }
+
+        // 每批之间只做 Relaxed 读取，停止后最多再执行一批；每轮开始时由 is_running 做 Acquire 读取
+        let burst_start = Instant::now();
+        let mut ran = 0;
+        while ran < iterations {
+            let batch = settings.work_batch.min(iterations - ran);
+            looper.run(batch);
+            ran += batch;
+            if !state.running.load(Ordering::Relaxed) {
+                break;
+            }
+        }
+        let iterations = ran;
+        done += iterations;
+        let busy = burst_start.elapsed();
+
+        if let Some(rate) = verifier.as_mut().and_then(|v| v.record(iterations, busy)) {
+            let baseline = verifier.take().map_or(0.0, |v| v.baseline());
+            warn!(
+                "{}",
+                tr!(
+                    "work-verify-optimized",
+                    thread = index,
+                    rate = format!("{:.0}", rate),
+                    baseline = format!("{:.0}", baseline)
+                )
+            );
+            looper.harden();
+        }
+
+        selfmonitor::duty_cycle_sleep(state.thread_stats(index), percent, iterations, busy);
+    }
+    done
+}
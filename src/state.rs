@@ -0,0 +1,207 @@
+//! 负载运行时的共享状态，由工作线程、驱动线程和控制接口共同访问
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::time::Instant;
+
+use rand::Rng;
+
+use crate::schedule::ScheduleStatus;
+use crate::selfmonitor::ThreadStats;
+
+/// 窃取时间补偿后的占空比上限
+const MAX_COMPENSATED_PERCENT: u8 = 99;
+
+pub struct LoadState {
+    /// 负载是否仍在运行，置为 false 后工作线程退出
+    pub running: AtomicBool,
+    /// 是否收到了外部停止请求（Ctrl-C、stop 命令等），而不是正常到期
+    pub interrupted: AtomicBool,
+    /// 暂停期间工作线程不做计算
+    pub paused: AtomicBool,
+    /// 目标CPU使用率（0-100）
+    pub target_percent: AtomicU8,
+    /// 每个工作周期在目标使用率上叠加的随机扰动幅度（百分点），0 表示不扰动
+    pub cpu_noise: AtomicU8,
+    /// 启用的工作线程数，序号不小于该值的线程保持空闲；修改时应调用 [`LoadState::set_active_workers`]
+    pub active_workers: AtomicUsize,
+    /// 未启用的工作线程在此等待，启用的线程数变化或负载停止时唤醒
+    worker_gate: Mutex<()>,
+    workers_changed: Condvar,
+    /// `--cpu-steal-compensation` 最近测得的窃取时间（万分比），工作线程据此提高占空比；0 表示不补偿
+    pub steal_basis_points: AtomicU32,
+    /// `--ramp-up`/`--ramp-down` 当前所处的阶段（见 [`crate::ramp::RampPhase`]），未启用时为0
+    pub ramp_phase: AtomicU8,
+    /// 当前所处的脉冲周期（从1开始），未启用脉冲模式时为0
+    pub burst_cycle: AtomicU32,
+    /// `--load-schedule` 当前所处的阶段和剩余周期，未启用时为 None
+    pub schedule: Mutex<Option<ScheduleStatus>>,
+    /// 当前占用的内存块
+    pub memory: Mutex<Option<Vec<u8>>>,
+    /// `--memory-object-size` 时代替 `memory` 占用内存的小对象
+    pub objects: Mutex<Vec<Box<[u8]>>>,
+    /// 当前已分配的内存字节数，供状态线程无锁读取
+    pub allocated_bytes: AtomicU64,
+    /// 被 mlock 锁定的字节数
+    pub locked_bytes: AtomicU64,
+    /// 内存增长模式已额外分配的字节数
+    pub grown_bytes: AtomicU64,
+    /// 内存气球当前占用的字节数
+    pub balloon_bytes: AtomicU64,
+    /// `--self-monitor` 时每个工作线程的统计，启动工作线程前设置一次
+    pub self_monitor: OnceLock<Vec<Arc<ThreadStats>>>,
+    /// `--duration` 到期的时刻，开始计时时设置一次，状态线程据此显示剩余时间
+    pub deadline: OnceLock<Instant>,
+    /// `io-stress` 创建的临时文件，Ctrl+C 时由中断处理函数删除
+    temp_files: Mutex<Vec<PathBuf>>,
+}
+
+impl LoadState {
+    pub fn new(target_percent: u8, memory: Option<Vec<u8>>) -> Self {
+        let allocated = memory.as_ref().map_or(0, |m| m.len() as u64);
+        LoadState {
+            running: AtomicBool::new(true),
+            interrupted: AtomicBool::new(false),
+            paused: AtomicBool::new(false),
+            target_percent: AtomicU8::new(target_percent),
+            cpu_noise: AtomicU8::new(0),
+            active_workers: AtomicUsize::new(usize::MAX),
+            worker_gate: Mutex::new(()),
+            workers_changed: Condvar::new(),
+            steal_basis_points: AtomicU32::new(0),
+            ramp_phase: AtomicU8::new(0),
+            burst_cycle: AtomicU32::new(0),
+            schedule: Mutex::new(None),
+            memory: Mutex::new(memory),
+            objects: Mutex::new(Vec::new()),
+            allocated_bytes: AtomicU64::new(allocated),
+            locked_bytes: AtomicU64::new(0),
+            grown_bytes: AtomicU64::new(0),
+            balloon_bytes: AtomicU64::new(0),
+            self_monitor: OnceLock::new(),
+            deadline: OnceLock::new(),
+            temp_files: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::Acquire)
+    }
+
+    /// 第 `index` 个工作线程的自监控统计，未启用 `--self-monitor` 时为 None
+    pub fn thread_stats(&self, index: usize) -> Option<&ThreadStats> {
+        self.self_monitor.get()?.get(index).map(|stats| stats.as_ref())
+    }
+
+    /// 外部请求停止
+    pub fn request_stop(&self) {
+        self.interrupted.store(true, Ordering::SeqCst);
+        self.finish();
+    }
+
+    /// 负载正常结束（到达持续时间、完成全部周期等）
+    pub fn finish(&self) {
+        self.running.store(false, Ordering::SeqCst);
+        self.wake_workers();
+    }
+
+    /// 登记需要在中断时删除的临时文件
+    pub fn register_temp_file(&self, path: &Path) {
+        self.temp_files.lock().unwrap_or_else(|e| e.into_inner()).push(path.to_path_buf());
+    }
+
+    /// 删除已登记的临时文件；线程仍持有的句柄在Unix上继续有效，文件在句柄关闭后释放
+    pub fn remove_temp_files(&self) {
+        for path in self.temp_files.lock().unwrap_or_else(|e| e.into_inner()).drain(..) {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    /// 修改启用的工作线程数并唤醒等待中的线程
+    pub fn set_active_workers(&self, count: usize) {
+        self.active_workers.store(count, Ordering::Relaxed);
+        self.wake_workers();
+    }
+
+    fn wake_workers(&self) {
+        // 持锁通知，避免线程在检查条件之后、开始等待之前错过唤醒
+        let _guard = self.worker_gate.lock().unwrap_or_else(|e| e.into_inner());
+        self.workers_changed.notify_all();
+    }
+
+    /// 第 `index` 个工作线程未启用时阻塞，直到被启用或负载停止
+    pub fn wait_until_active(&self, index: usize) {
+        if index < self.active_workers.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut guard = self.worker_gate.lock().unwrap_or_else(|e| e.into_inner());
+        while self.is_running() && index >= self.active_workers.load(Ordering::Relaxed) {
+            guard = self.workers_changed.wait(guard).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// 工作线程当前应达到的使用率，暂停时为0
+    pub fn effective_percent(&self) -> u8 {
+        if self.paused.load(Ordering::Relaxed) {
+            0
+        } else {
+            self.target_percent.load(Ordering::Relaxed).min(100)
+        }
+    }
+
+    /// 第 `index` 个工作线程应达到的使用率：未启用的线程为0，有覆盖值时使用覆盖值，暂停时同样为0
+    pub fn thread_percent(&self, index: usize, fixed_percent: Option<u8>) -> u8 {
+        if index >= self.active_workers.load(Ordering::Relaxed) {
+            return 0;
+        }
+        let percent = match fixed_percent {
+            Some(_) if self.paused.load(Ordering::Relaxed) => 0,
+            Some(percent) => percent,
+            None => self.effective_percent(),
+        };
+        self.compensate_steal(percent)
+    }
+
+    /// 按窃取时间提高占空比，使虚拟机内实际得到的CPU时间达到 `percent`：`percent / (1 - 窃取比例)`，
+    /// 最高99%，避免其他线程饿死；`percent` 为0或不低于99时不变
+    pub fn compensate_steal(&self, percent: u8) -> u8 {
+        let steal = self.steal_basis_points.load(Ordering::Relaxed);
+        if steal == 0 || percent == 0 || percent >= MAX_COMPENSATED_PERCENT {
+            return percent;
+        }
+        let compensated = f64::from(percent) * 10_000.0 / f64::from(10_000u32.saturating_sub(steal).max(1));
+        (compensated.round() as u8).clamp(percent, MAX_COMPENSATED_PERCENT)
+    }
+
+    /// 在 `percent` 上叠加 [-cpu_noise, +cpu_noise] 内均匀分布的扰动，结果限制在 1-100；为0时保持空闲
+    pub fn with_noise(&self, percent: u8, rng: &mut impl Rng) -> u8 {
+        let noise = i16::from(self.cpu_noise.load(Ordering::Relaxed));
+        if noise == 0 || percent == 0 {
+            return percent;
+        }
+        (i16::from(percent) + rng.gen_range(-noise..=noise)).clamp(1, 100) as u8
+    }
+
+    /// 替换占用的内存块，旧内存块上的锁定随之失效
+    pub fn replace_memory(&self, memory: Option<Vec<u8>>) {
+        let mut guard = self.memory.lock().unwrap_or_else(|e| e.into_inner());
+        self.locked_bytes.store(0, Ordering::Relaxed);
+        self.allocated_bytes.store(memory.as_ref().map_or(0, |m| m.len() as u64), Ordering::Relaxed);
+        *guard = memory;
+    }
+
+    /// 替换占用内存的小对象
+    pub fn replace_objects(&self, objects: Vec<Box<[u8]>>) {
+        let mut guard = self.objects.lock().unwrap_or_else(|e| e.into_inner());
+        self.allocated_bytes.store(crate::objects::total_bytes(&objects), Ordering::Relaxed);
+        *guard = objects;
+    }
+
+    /// 取出内存块以便释放
+    pub fn take_memory(&self) -> Option<Vec<u8>> {
+        self.allocated_bytes.store(0, Ordering::Relaxed);
+        self.locked_bytes.store(0, Ordering::Relaxed);
+        self.memory.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
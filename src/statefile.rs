@@ -0,0 +1,155 @@
+//! 二进制状态文件：与文本PID文件并存，记录实例的负载参数，`status` 无需连接进程即可显示
+//!
+//! 格式（小端）：魔数 `ECMS`、版本(u8)、PID(u32)、启动时间(i64，Unix秒)、目标CPU使用率(u8)、
+//! 目标内存字节数(u64)、核心数(u32)、负载类型名（u32长度 + UTF-8）。版本不匹配或内容
+//! 损坏时读取失败，调用方回退到文本PID文件。
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, UNIX_EPOCH};
+
+use bytesize::ByteSize;
+
+use crate::json::Json;
+use crate::state::LoadState;
+use crate::timeutil;
+
+const MAGIC: &[u8; 4] = b"ECMS";
+const VERSION: u8 = 1;
+
+/// 检查负载参数是否变化的间隔
+const UPDATE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 状态文件记录的实例信息
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstanceState {
+    pub pid: u32,
+    /// 启动时间（Unix秒）
+    pub started_at: i64,
+    pub target_percent: u8,
+    pub memory_bytes: u64,
+    pub cores: u32,
+    /// 负载类型名，例如 "spin"
+    pub profile: String,
+}
+
+impl InstanceState {
+    fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(34 + self.profile.len());
+        out.extend_from_slice(MAGIC);
+        out.push(VERSION);
+        out.extend_from_slice(&self.pid.to_le_bytes());
+        out.extend_from_slice(&self.started_at.to_le_bytes());
+        out.push(self.target_percent);
+        out.extend_from_slice(&self.memory_bytes.to_le_bytes());
+        out.extend_from_slice(&self.cores.to_le_bytes());
+        out.extend_from_slice(&(self.profile.len() as u32).to_le_bytes());
+        out.extend_from_slice(self.profile.as_bytes());
+        out
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader(bytes);
+        if reader.take(4)? != MAGIC || reader.take(1)? != [VERSION] {
+            return None;
+        }
+        let pid = u32::from_le_bytes(reader.array()?);
+        let started_at = i64::from_le_bytes(reader.array()?);
+        let [target_percent] = reader.array()?;
+        let memory_bytes = u64::from_le_bytes(reader.array()?);
+        let cores = u32::from_le_bytes(reader.array()?);
+        let len = u32::from_le_bytes(reader.array()?) as usize;
+        let profile = String::from_utf8(reader.take(len)?.to_vec()).ok()?;
+        Some(InstanceState { pid, started_at, target_percent, memory_bytes, cores, profile })
+    }
+
+    /// 文本格式，用于 `status` 命令
+    pub fn describe(&self) -> String {
+        let started = UNIX_EPOCH + Duration::from_secs(self.started_at.max(0) as u64);
+        tr!(
+            "statefile-instance",
+            pid = self.pid,
+            started = timeutil::format_rfc3339(started),
+            profile = self.profile,
+            cores = self.cores,
+            target = self.target_percent,
+            memory = ByteSize::b(self.memory_bytes)
+        )
+    }
+
+    pub fn to_json(&self) -> Json {
+        Json::object([
+            ("pid", self.pid.into()),
+            ("started_at", self.started_at.into()),
+            ("target_percent", self.target_percent.into()),
+            ("memory_bytes", self.memory_bytes.into()),
+            ("cores", self.cores.into()),
+            ("profile", self.profile.as_str().into()),
+        ])
+    }
+
+    /// 先写临时文件再重命名，读取方不会看到写了一半的内容
+    pub fn write(&self, path: &Path) -> std::io::Result<()> {
+        let tmp = path.with_extension("state.tmp");
+        std::fs::File::create(&tmp)?.write_all(&self.encode())?;
+        std::fs::rename(tmp, path)
+    }
+
+    /// 读取状态文件，不存在、版本不匹配或内容损坏时返回 None
+    pub fn read(path: &Path) -> Option<Self> {
+        Self::decode(&std::fs::read(path).ok()?)
+    }
+}
+
+/// 按顺序读取定长字段
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (head, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+}
+
+/// 与PID文件同名、扩展名为 `.state` 的状态文件路径
+pub fn state_path(pid_file: &Path) -> PathBuf {
+    pid_file.with_extension("state")
+}
+
+/// 写入状态文件，并在目标使用率、内存或启用的线程数变化时（例如通过控制套接字调整）更新；
+/// 负载停止后删除状态文件
+pub fn spawn_updater(path: PathBuf, mut current: InstanceState, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        if let Err(e) = current.write(&path) {
+            warn!("{}", tr!("statefile-write-failed", path = path.display(), error = e));
+            return;
+        }
+        // 未启用自动核心调整时 active_workers 为 usize::MAX
+        let max_cores = current.cores as usize;
+        while state.is_running() {
+            thread::sleep(UPDATE_INTERVAL);
+            let mut next = current.clone();
+            next.target_percent = state.target_percent.load(Ordering::Relaxed);
+            next.memory_bytes = state.allocated_bytes.load(Ordering::Relaxed);
+            next.cores = state.active_workers.load(Ordering::Relaxed).min(max_cores) as u32;
+            if next != current {
+                if let Err(e) = next.write(&path) {
+                    warn!("{}", tr!("statefile-write-failed", path = path.display(), error = e));
+                }
+                current = next;
+            }
+        }
+        let _ = std::fs::remove_file(&path);
+    })
+}
@@ -0,0 +1,291 @@
+//! `status --diff`：比较间隔一段时间的两次采样，显示系统状态的变化趋势；
+//! `status --watch`：像 top 一样定期清屏并重新输出各核心使用率、内存和交换分区；
+//! 加 `--json` 时改为每次刷新输出一行JSON（JSON Lines），便于脚本逐行读取
+
+use std::io::IsTerminal;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use sysinfo::{CpuExt, System, SystemExt};
+
+use crate::json::Json;
+use crate::timeutil;
+
+/// 1 MiB 的字节数
+const MIB: f64 = 1024.0 * 1024.0;
+
+/// 1 GiB 的字节数
+const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// 清屏并把光标移到左上角
+const CLEAR_SCREEN: &str = "\x1B[2J\x1B[H";
+
+/// 无法获取终端宽度时使用的宽度
+const DEFAULT_COLUMNS: usize = 80;
+
+/// 单个核心的变化小于该值（百分点）时视为不变
+const CORE_STEADY_THRESHOLD: f32 = 1.0;
+
+/// 一次采样
+struct Snapshot {
+    at: Instant,
+    per_core: Vec<f32>,
+    cpu_usage: f32,
+    memory_used: u64,
+    memory_total: u64,
+}
+
+impl Snapshot {
+    fn take(sys: &mut System) -> Self {
+        sys.refresh_cpu();
+        sys.refresh_memory();
+        let per_core: Vec<f32> = sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
+        let cpu_usage = per_core.iter().sum::<f32>() / per_core.len().max(1) as f32;
+        Snapshot {
+            at: Instant::now(),
+            per_core,
+            cpu_usage,
+            memory_used: sys.used_memory(),
+            memory_total: sys.total_memory(),
+        }
+    }
+}
+
+/// 两次采样之间的变化
+struct Delta {
+    secs: f64,
+    per_core: Vec<f32>,
+    cpu_usage: f32,
+    memory_used: i64,
+}
+
+impl Delta {
+    fn between(before: &Snapshot, after: &Snapshot) -> Self {
+        Delta {
+            secs: after.at.duration_since(before.at).as_secs_f64(),
+            per_core: after.per_core.iter().zip(&before.per_core).map(|(a, b)| a - b).collect(),
+            cpu_usage: after.cpu_usage - before.cpu_usage,
+            memory_used: after.memory_used as i64 - before.memory_used as i64,
+        }
+    }
+
+    /// 内存变化速度（字节/秒）
+    fn memory_rate(&self) -> f64 {
+        self.memory_used as f64 / self.secs.max(f64::EPSILON)
+    }
+
+    /// 每个核心一个字符：▲ 上升、▼ 下降、= 基本不变
+    fn sparkline(&self) -> String {
+        self.per_core
+            .iter()
+            .map(|&d| {
+                if d >= CORE_STEADY_THRESHOLD {
+                    '▲'
+                } else if d <= -CORE_STEADY_THRESHOLD {
+                    '▼'
+                } else {
+                    '='
+                }
+            })
+            .collect()
+    }
+}
+
+/// `status --json` 的基本字段；`--watch` 时每次刷新输出一行
+#[derive(Clone, Debug, Serialize)]
+pub struct StatusReport {
+    pub cpu_count: usize,
+    /// 每个逻辑CPU的使用率（百分比）
+    pub per_core_usage: Vec<f32>,
+    pub avg_cpu_usage: f32,
+    pub total_memory_bytes: u64,
+    pub used_memory_bytes: u64,
+    pub available_memory_bytes: u64,
+    pub swap_total_bytes: u64,
+    pub swap_used_bytes: u64,
+    /// Unix时间戳（毫秒）
+    pub timestamp_ms: u64,
+}
+
+impl StatusReport {
+    /// 从已刷新CPU和内存的 `sys` 读取
+    pub fn read(sys: &System) -> Self {
+        let per_core_usage: Vec<f32> = sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
+        StatusReport {
+            cpu_count: per_core_usage.len(),
+            avg_cpu_usage: per_core_usage.iter().sum::<f32>() / per_core_usage.len().max(1) as f32,
+            per_core_usage,
+            total_memory_bytes: sys.total_memory(),
+            used_memory_bytes: sys.used_memory(),
+            available_memory_bytes: sys.available_memory(),
+            swap_total_bytes: sys.total_swap(),
+            swap_used_bytes: sys.used_swap(),
+            timestamp_ms: timeutil::unix_millis(),
+        }
+    }
+}
+
+/// 间隔 `interval` 采样两次并输出变化量
+pub fn show_diff(interval: Duration, json: bool) {
+    let mut sys = System::new();
+    // CPU使用率需要两次刷新才有意义，先建立基准
+    sys.refresh_cpu();
+    thread::sleep(System::MINIMUM_CPU_UPDATE_INTERVAL.max(Duration::from_millis(500)));
+    let before = Snapshot::take(&mut sys);
+    thread::sleep(interval);
+    let after = Snapshot::take(&mut sys);
+    let delta = Delta::between(&before, &after);
+
+    if json {
+        info!("{}", to_json(&after, &delta));
+    } else {
+        render_text(&delta);
+    }
+}
+
+fn render_text(delta: &Delta) {
+    info!("{}", tr!("status-diff-header", secs = format!("{:.1}", delta.secs)));
+    info!("CPU: {:+.1}%", delta.cpu_usage);
+    info!("{}", tr!("status-diff-cores", sparkline = delta.sparkline()));
+    info!("MEM: {:+.0} MiB", delta.memory_used as f64 / MIB);
+    let rate = delta.memory_rate() / MIB;
+    if rate.abs() < 0.05 {
+        info!("{}", tr!("status-diff-memory-stable"));
+    } else if rate > 0.0 {
+        info!("{}", tr!("status-diff-memory-growing", rate = format!("{:.1}", rate)));
+    } else {
+        info!("{}", tr!("status-diff-memory-shrinking", rate = format!("{:.1}", -rate)));
+    }
+}
+
+fn to_json(current: &Snapshot, delta: &Delta) -> Json {
+    Json::object([
+        ("interval_secs", delta.secs.into()),
+        (
+            "current",
+            Json::object([
+                ("cpu_usage_percent", current.cpu_usage.into()),
+                ("per_core_percent", current.per_core.clone().into()),
+                ("memory_used_bytes", current.memory_used.into()),
+                ("memory_total_bytes", current.memory_total.into()),
+            ]),
+        ),
+        (
+            "delta",
+            Json::object([
+                ("cpu_usage_percent", delta.cpu_usage.into()),
+                ("per_core_percent", delta.per_core.clone().into()),
+                ("memory_used_bytes", delta.memory_used.into()),
+                ("memory_bytes_per_sec", delta.memory_rate().into()),
+            ]),
+        ),
+    ])
+}
+
+/// 终端宽度：优先读取标准输出所在终端的窗口大小，其次为 `COLUMNS` 环境变量
+fn terminal_width() -> usize {
+    #[cfg(unix)]
+    {
+        // SAFETY: winsize 是普通结构体，全零是合法值；TIOCGWINSZ 只写入 ws
+        let mut ws: libc::winsize = unsafe { std::mem::zeroed() };
+        if unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut ws) } == 0 && ws.ws_col > 0 {
+            return usize::from(ws.ws_col);
+        }
+    }
+    std::env::var("COLUMNS").ok().and_then(|columns| columns.trim().parse().ok()).unwrap_or(DEFAULT_COLUMNS)
+}
+
+/// 宽度为 `width` 的使用率条，例如 `[████░░░░]`
+fn usage_bar(percent: f64, width: usize) -> String {
+    let filled = ((percent.clamp(0.0, 100.0) / 100.0) * width as f64).round() as usize;
+    format!("[{}{}]", "█".repeat(filled), "░".repeat(width - filled))
+}
+
+/// 一帧的全部行：标题、各核心和平均使用率、内存和交换分区
+fn render_frame(sys: &System, sample: u64, interval: Duration) -> Vec<String> {
+    // 标签和百分比约占16列，其余留给使用率条
+    let width = terminal_width().saturating_sub(18).clamp(10, 100);
+    let per_core: Vec<f32> = sys.cpus().iter().map(|cpu| cpu.cpu_usage()).collect();
+    let average = per_core.iter().sum::<f32>() / per_core.len().max(1) as f32;
+    let mut lines = vec![
+        tr!("status-watch-header", time = timeutil::now_rfc3339(), sample = sample, secs = interval.as_secs()),
+        String::new(),
+    ];
+    for (i, &usage) in per_core.iter().enumerate() {
+        lines.push(format!("{:<7}{:>6.1}% {}", format!("cpu{}", i), usage, usage_bar(f64::from(usage), width)));
+    }
+    lines.push(format!("{:<7}{:>6.1}% {}", "avg", average, usage_bar(f64::from(average), width)));
+    lines.push(String::new());
+    let (used, total) = (sys.used_memory(), sys.total_memory());
+    let percent = used as f64 * 100.0 / total.max(1) as f64;
+    lines.push(tr!(
+        "status-watch-memory",
+        used = format!("{:.1}", used as f64 / GIB),
+        total = format!("{:.1}", total as f64 / GIB),
+        percent = format!("{:.1}", percent)
+    ));
+    lines.push(format!("{:>14} {}", "", usage_bar(percent, width)));
+    let (used, total) = (sys.used_swap(), sys.total_swap());
+    if total == 0 {
+        lines.push(tr!("status-watch-no-swap"));
+    } else {
+        let percent = used as f64 * 100.0 / total as f64;
+        lines.push(tr!(
+            "status-watch-swap",
+            used = format!("{:.1}", used as f64 / GIB),
+            total = format!("{:.1}", total as f64 / GIB),
+            percent = format!("{:.1}", percent)
+        ));
+        lines.push(format!("{:>14} {}", "", usage_bar(percent, width)));
+    }
+    lines
+}
+
+/// 每隔 `interval` 刷新一次，输出到终端时先清屏，`json` 时每次输出一行JSON；`count` 次后或收到 Ctrl+C 时退出
+pub fn watch(interval: Duration, count: Option<u64>, json: bool) {
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let stop = stop.clone();
+        if let Err(e) = ctrlc::set_handler(move || stop.store(true, Ordering::SeqCst)) {
+            warn!("{}", tr!("status-watch-handler-failed", error = e));
+        }
+    }
+    let clear = !json && std::io::stdout().is_terminal();
+    let mut sys = System::new();
+    // CPU使用率需要两次刷新才有意义，第一帧只等待建立基准所需的时间
+    sys.refresh_cpu();
+    let mut wait = System::MINIMUM_CPU_UPDATE_INTERVAL.max(Duration::from_millis(500)).min(interval);
+    let mut sample = 0u64;
+    loop {
+        let deadline = Instant::now() + wait;
+        while !stop.load(Ordering::SeqCst) && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(50).min(deadline.saturating_duration_since(Instant::now())));
+        }
+        if stop.load(Ordering::SeqCst) {
+            break;
+        }
+        sys.refresh_cpu();
+        sys.refresh_memory();
+        sample += 1;
+        if json {
+            match serde_json::to_string(&StatusReport::read(&sys)) {
+                Ok(line) => info!("{}", line),
+                Err(e) => warn!("{}", tr!("status-json-failed", error = e)),
+            }
+        } else {
+            let frame = render_frame(&sys, sample, interval).join("\n");
+            if clear {
+                info!("{}{}", CLEAR_SCREEN, frame);
+            } else {
+                info!("{}\n", frame);
+            }
+        }
+        if count.is_some_and(|count| sample >= count) {
+            break;
+        }
+        wait = interval;
+    }
+}
@@ -0,0 +1,104 @@
+//! CPU 窃取时间（仅Linux）：虚拟机想要运行但被虚拟机监控器分给其他虚拟机的时间占比
+//!
+//! 数据来自 `/proc/stat` 第一行 `cpu` 的第8列 `steal`。窃取时间较高时工作线程的实际运行时间少于
+//! 按占空比计算的时间，测得的CPU使用率和基准结果都不可信。`--cpu-steal-compensation` 据此
+//! 提高工作线程的占空比，使虚拟机内实际得到的CPU时间达到目标。
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::state::LoadState;
+use crate::timeutil;
+
+/// 重新测量窃取时间、调整补偿的间隔
+const COMPENSATION_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 默认的可接受窃取时间上限（百分比），超过时告警并在汇总中标记
+pub const DEFAULT_MAX_STEAL: f64 = 5.0;
+
+/// `/proc/stat` 中所有CPU累计的窃取时间和总时间（jiffies）
+fn read_steal() -> Option<(u64, u64)> {
+    let stat = std::fs::read_to_string("/proc/stat").ok()?;
+    let times: Vec<u64> = stat
+        .lines()
+        .next()?
+        .strip_prefix("cpu ")?
+        .split_whitespace()
+        .filter_map(|v| v.parse().ok())
+        .collect();
+    // guest、guest_nice 已计入 user、nice，不重复累加
+    let steal = *times.get(7)?;
+    Some((steal, times.iter().take(8).sum()))
+}
+
+/// 每次状态刷新时计算上次刷新以来的窃取时间占比，超过 `max_percent` 时告警
+pub struct StealMonitor {
+    last: Option<(u64, u64)>,
+    max_percent: f64,
+}
+
+impl StealMonitor {
+    pub fn new(max_percent: f64) -> Self {
+        StealMonitor { last: read_steal(), max_percent }
+    }
+
+    /// 上次采样以来的窃取时间占比（百分比），不可读或间隔内没有时间流逝时为 None
+    pub fn sample(&mut self) -> Option<f64> {
+        let current = read_steal()?;
+        let (steal, total) = self.last.replace(current)?;
+        let elapsed = current.1.checked_sub(total).filter(|&t| t > 0)?;
+        let percent = current.0.saturating_sub(steal) as f64 * 100.0 / elapsed as f64;
+        if percent > self.max_percent {
+            warn!("{}", tr!("steal-high", percent = format!("{:.1}", percent)));
+        }
+        Some(percent)
+    }
+}
+
+/// 启动窃取时间补偿线程：每个间隔测量一次窃取时间比例写入共享状态，补偿后的占空比变化时输出
+pub fn spawn_compensation(state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let Some(mut last) = read_steal() else {
+            warn!("{}", tr!("steal-compensation-unavailable"));
+            return;
+        };
+        info!("{}", tr!("steal-compensation-started", secs = COMPENSATION_INTERVAL.as_secs()));
+        let mut last_check = Instant::now();
+        // 最近一次输出时的目标和补偿后的占空比
+        let mut logged = None;
+        while state.is_running() {
+            thread::sleep(Duration::from_millis(100));
+            if last_check.elapsed() < COMPENSATION_INTERVAL {
+                continue;
+            }
+            last_check = Instant::now();
+            let Some(current) = read_steal() else {
+                continue;
+            };
+            let (steal, total) = std::mem::replace(&mut last, current);
+            let Some(elapsed) = current.1.checked_sub(total).filter(|&t| t > 0) else {
+                continue;
+            };
+            let basis_points = current.0.saturating_sub(steal) * 10_000 / elapsed;
+            state.steal_basis_points.store(basis_points as u32, Ordering::Relaxed);
+
+            let target = state.effective_percent();
+            let compensated = state.compensate_steal(target);
+            if logged != Some((target, compensated)) && (logged.is_some() || compensated != target) {
+                info!(
+                    "[{}] {}",
+                    timeutil::now_rfc3339(),
+                    tr!(
+                        "steal-compensation-adjusted",
+                        steal = format!("{:.1}", basis_points as f64 / 100.0),
+                        target = target,
+                        percent = compensated
+                    )
+                );
+                logged = Some((target, compensated));
+            }
+        }
+    })
+}
@@ -0,0 +1,129 @@
+//! 阶梯负载：每隔固定时间增加启用的核心数或分配的内存，到达上限后保持
+//!
+//! 每一级在扩缩容曲线上对应一个清晰的台阶，用于刻画自动扩缩容的行为。
+
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use bytesize::ByteSize;
+
+use crate::growth;
+use crate::memory;
+use crate::state::LoadState;
+use crate::timeutil;
+
+/// 核心数阶梯：从 `start` 开始每级增加 `size` 个核心，直到 `end`
+#[derive(Clone, Copy, Debug)]
+pub struct CoreStepper {
+    pub start: usize,
+    pub end: usize,
+    pub size: usize,
+    pub interval: Duration,
+}
+
+impl CoreStepper {
+    /// 检查起止值
+    pub fn validate(&self) -> Result<(), String> {
+        if self.start == 0 || self.start > self.end {
+            return Err(tr!("step-range-invalid", start = self.start, end = self.end));
+        }
+        Ok(())
+    }
+
+    /// 每一级的核心数，最后一级为 `end`
+    fn steps(&self) -> Vec<usize> {
+        let mut steps: Vec<usize> = (self.start..self.end).step_by(self.size.max(1)).collect();
+        steps.push(self.end);
+        steps
+    }
+
+    /// 启动阶梯驱动线程；`workers` 为实际启动的工作线程数，超出的核心数按该值处理
+    pub fn spawn(self, workers: usize, state: Arc<LoadState>) -> JoinHandle<()> {
+        thread::spawn(move || {
+            let steps = self.steps();
+            for (i, &cores) in steps.iter().enumerate() {
+                if !state.is_running() {
+                    return;
+                }
+                let active = cores.min(workers);
+                state.set_active_workers(active);
+                info!(
+                    "[{}] {}",
+                    timeutil::now_rfc3339(),
+                    tr!("step-cores", step = i + 1, total = steps.len(), cores = active)
+                );
+                if i + 1 < steps.len() {
+                    growth::sleep_while_running(&state, self.interval);
+                }
+            }
+            info!("{}", tr!("step-holding"));
+        })
+    }
+}
+
+/// 内存阶梯：从 `start` 开始每级乘以 `multiplier`，直到 `end`
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryStepper {
+    pub start: u64,
+    pub end: u64,
+    pub multiplier: f64,
+    pub interval: Duration,
+}
+
+impl MemoryStepper {
+    /// 检查起止值和倍数
+    pub fn validate(&self) -> Result<(), String> {
+        if self.start == 0 || self.start > self.end {
+            return Err(tr!("step-range-invalid", start = ByteSize::b(self.start), end = ByteSize::b(self.end)));
+        }
+        if self.multiplier <= 1.0 {
+            return Err(tr!("step-multiplier-invalid"));
+        }
+        Ok(())
+    }
+
+    /// 每一级的内存大小，最后一级为 `end`
+    fn steps(&self) -> Vec<u64> {
+        let mut steps = Vec::new();
+        let mut size = self.start as f64;
+        while size < self.end as f64 {
+            steps.push(size as u64);
+            size *= self.multiplier;
+        }
+        steps.push(self.end);
+        steps
+    }
+
+    /// 启动阶梯驱动线程，每级先释放旧内存再分配并预填充新内存，避免峰值翻倍
+    pub fn spawn(self, state: Arc<LoadState>) -> JoinHandle<()> {
+        thread::spawn(move || {
+            let steps = self.steps();
+            for (i, &bytes) in steps.iter().enumerate() {
+                if !state.is_running() {
+                    return;
+                }
+                state.replace_memory(None);
+                match memory::try_alloc_zeroed(bytes as usize) {
+                    Some(mut buffer) => {
+                        memory::prefault(&mut buffer, false);
+                        state.replace_memory(Some(buffer));
+                        info!(
+                            "[{}] {}",
+                            timeutil::now_rfc3339(),
+                            tr!("step-memory", step = i + 1, total = steps.len(), size = ByteSize::b(bytes))
+                        );
+                    }
+                    None => {
+                        warn!("{}", tr!("step-memory-failed", size = ByteSize::b(bytes)));
+                        return;
+                    }
+                }
+                if i + 1 < steps.len() {
+                    growth::sleep_while_running(&state, self.interval);
+                }
+            }
+            info!("{}", tr!("step-holding"));
+        })
+    }
+}
@@ -0,0 +1,151 @@
+//! 运行结束时的统计汇总
+
+use std::fmt::Write;
+
+use crate::output::{GIB, RunPhase, Stats};
+
+/// 直方图中最长的柱的宽度（字符）
+const HISTOGRAM_WIDTH: usize = 40;
+
+/// 单个阶段内的采样统计
+#[derive(Clone, Debug, Default)]
+pub struct PhaseSummary {
+    samples: u32,
+    cpu_sum: f64,
+    cpu_min: f32,
+    cpu_max: f32,
+    memory_used_sum: f64,
+    /// 有窃取时间数据的采样次数及其窃取时间占比之和
+    steal_samples: u32,
+    steal_sum: f64,
+    /// 每次采样的CPU使用率，用于绘制直方图
+    cpu_samples: Vec<f32>,
+}
+
+impl PhaseSummary {
+    /// 记录一次采样
+    pub fn record(&mut self, stats: &Stats) {
+        if self.samples == 0 {
+            self.cpu_min = stats.cpu_usage;
+            self.cpu_max = stats.cpu_usage;
+        } else {
+            self.cpu_min = self.cpu_min.min(stats.cpu_usage);
+            self.cpu_max = self.cpu_max.max(stats.cpu_usage);
+        }
+        self.samples += 1;
+        self.cpu_sum += f64::from(stats.cpu_usage);
+        self.memory_used_sum += stats.memory_used as f64;
+        self.cpu_samples.push(stats.cpu_usage);
+        if let Some(steal) = stats.cpu_steal {
+            self.steal_samples += 1;
+            self.steal_sum += steal;
+        }
+    }
+
+    /// 平均CPU窃取时间占比，没有窃取时间数据时为 None
+    fn mean_steal(&self) -> Option<f64> {
+        (self.steal_samples > 0).then(|| self.steal_sum / f64::from(self.steal_samples))
+    }
+
+    /// 把CPU使用率按 `bins` 个等宽区间绘制成直方图，并给出变异系数（标准差 / 平均值）
+    fn render_histogram(&self, bins: usize, out: &mut String) {
+        if self.cpu_samples.is_empty() {
+            return;
+        }
+        let bins = bins.max(1);
+        let width = 100.0 / bins as f64;
+        let mut counts = vec![0u32; bins];
+        for &usage in &self.cpu_samples {
+            // 100% 落在最后一个区间
+            let bin = (f64::from(usage).clamp(0.0, 100.0) / width) as usize;
+            counts[bin.min(bins - 1)] += 1;
+        }
+        let max = counts.iter().copied().max().unwrap_or(0).max(1);
+        let _ = write!(out, "\n  {}", tr!("summary-histogram-header"));
+        let label = |value: f64| format!("{}", (value * 100.0).round() / 100.0);
+        for (i, &count) in counts.iter().enumerate() {
+            // 有采样的区间至少画一格
+            let bar = (count as usize * HISTOGRAM_WIDTH).div_ceil(max as usize);
+            let _ = write!(
+                out,
+                "\n    [{}-{}%]: {} ({})",
+                label(i as f64 * width),
+                label((i + 1) as f64 * width),
+                "█".repeat(bar),
+                count
+            );
+        }
+
+        let n = self.cpu_samples.len() as f64;
+        let mean = self.cpu_samples.iter().map(|&v| f64::from(v)).sum::<f64>() / n;
+        let variance = self.cpu_samples.iter().map(|&v| (f64::from(v) - mean).powi(2)).sum::<f64>() / n;
+        let line = if mean > 0.0 {
+            tr!("summary-cpu-cv", cv = format!("{:.3}", variance.sqrt() / mean), stddev = format!("{:.1}", variance.sqrt()))
+        } else {
+            tr!("summary-cpu-cv-idle")
+        };
+        let _ = write!(out, "\n  {}", line);
+    }
+
+    fn render(&self, title: &str, out: &mut String) {
+        let _ = write!(out, "\n{}", title);
+        if self.samples == 0 {
+            let _ = write!(out, "\n  {}", tr!("summary-no-samples"));
+            return;
+        }
+        let samples = f64::from(self.samples);
+        let lines = [
+            tr!("summary-samples", count = self.samples),
+            tr!("summary-avg-cpu", usage = format!("{:.1}", self.cpu_sum / samples)),
+            tr!(
+                "summary-cpu-range",
+                min = format!("{:.1}", self.cpu_min),
+                max = format!("{:.1}", self.cpu_max)
+            ),
+            tr!("summary-avg-memory", size = format!("{:.1}", self.memory_used_sum / samples / GIB)),
+        ];
+        for line in lines {
+            let _ = write!(out, "\n  {}", line);
+        }
+        if let Some(steal) = self.mean_steal().filter(|&steal| steal > 0.0) {
+            let _ = write!(out, "\n  {}", tr!("summary-avg-steal", percent = format!("{:.1}", steal)));
+        }
+    }
+}
+
+/// 整个运行过程的统计，按阶段分开
+#[derive(Clone, Debug, Default)]
+pub struct RunSummary {
+    pub load: PhaseSummary,
+    pub measurement: Option<PhaseSummary>,
+    /// 负载阶段CPU使用率直方图的区间数，未启用 `--cpu-utilization-histogram` 时为 None
+    pub histogram_bins: Option<usize>,
+    /// 可接受的平均窃取时间（百分比），负载阶段超过时把本次运行标记为可能不准确
+    pub max_steal: f64,
+}
+
+impl RunSummary {
+    /// 将采样计入所属阶段
+    pub fn record(&mut self, stats: &Stats) {
+        match stats.phase {
+            RunPhase::Load => self.load.record(stats),
+            RunPhase::Measurement => self.measurement.get_or_insert_with(Default::default).record(stats),
+        }
+    }
+
+    /// 渲染为多行文本
+    pub fn render(&self) -> String {
+        let mut out = tr!("summary-header");
+        self.load.render(&tr!("summary-load-phase"), &mut out);
+        if let Some(bins) = self.histogram_bins {
+            self.load.render_histogram(bins, &mut out);
+        }
+        if let Some(steal) = self.load.mean_steal().filter(|&steal| steal > self.max_steal) {
+            let _ = write!(out, "\n  {}", tr!("summary-steal-inaccurate", percent = format!("{:.1}", steal)));
+        }
+        if let Some(measurement) = &self.measurement {
+            measurement.render("[POST-LOAD MEASUREMENT]", &mut out);
+        }
+        out
+    }
+}
@@ -0,0 +1,188 @@
+//! 交换压力：按256MiB分块分配 `-m` 指定的内存（可以超出物理内存），强制换出后反复随机访问各页面，测量换入带宽
+//!
+//! 随机访问使预取失效、TLB 频繁未命中，每次访问都尽可能触发真实的换入缺页。
+
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use bytesize::ByteSize;
+use rand::Rng;
+use sysinfo::{System, SystemExt};
+
+use crate::memory;
+use crate::objects;
+use crate::residency;
+use crate::seed;
+use crate::state::LoadState;
+
+/// 输出统计的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 分配内存的块大小
+const CHUNK_SIZE: u64 = 256 << 20;
+
+/// 换出页面所用的 madvise 建议
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Advice {
+    /// MADV_PAGEOUT（Linux 5.4+），页面被写入交换分区
+    PageOut,
+    /// MADV_DONTNEED，页面直接被丢弃，再次访问时重新分配零页
+    DontNeed,
+}
+
+/// /proc/vmstat 中的交换计数（页）
+#[derive(Clone, Copy, Default)]
+struct SwapCounters {
+    pswpin: u64,
+    pswpout: u64,
+}
+
+/// 检查交换分区是否足以容纳要换出的内存
+pub fn check_swap(bytes: u64) {
+    let mut sys = System::new();
+    sys.refresh_memory();
+    let capacity = sys.total_memory() + sys.total_swap();
+    if bytes > capacity {
+        warn!("{}", tr!("swap-exceeds-capacity", size = ByteSize::b(bytes), capacity = ByteSize::b(capacity)));
+    }
+    if sys.total_swap() == 0 {
+        warn!("{}", tr!("swap-disabled"));
+    } else if sys.free_swap() < bytes {
+        warn!(
+            "{}",
+            tr!(
+                "swap-too-small",
+                free = format!("{:.1}", sys.free_swap() as f64 / 1e6),
+                size = format!("{:.1}", bytes as f64 / 1e6)
+            )
+        );
+    }
+}
+
+/// 以 `CHUNK_SIZE` 为单位分配并写入 `total` 字节，超出物理内存时内核随即开始换出，每块完成后输出进度；
+/// 某块分配失败时停止分配，返回已分配的块
+pub fn alloc_chunks(total: u64) -> Vec<Box<[u8]>> {
+    let mut chunks = Vec::new();
+    let mut allocated = 0;
+    while allocated < total {
+        let size = CHUNK_SIZE.min(total - allocated);
+        let Some(mut chunk) = memory::try_alloc_zeroed(size as usize) else {
+            warn!("{}", tr!("swap-alloc-stopped", allocated = ByteSize::b(allocated), total = ByteSize::b(total)));
+            break;
+        };
+        memory::prefault(&mut chunk, false);
+        allocated += size;
+        chunks.push(chunk.into_boxed_slice());
+        info!("{}", tr!("swap-alloc-progress", allocated = ByteSize::b(allocated), total = ByteSize::b(total)));
+    }
+    chunks
+}
+
+/// 启动交换压力线程，对 `state.objects` 中 [`alloc_chunks`] 分配的各块反复换出并随机访问
+pub fn spawn_swap_pressure(state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let page = residency::page_size();
+        let mut advice = Advice::PageOut;
+        let mut rng = seed::thread_rng(seed::SWAP_STREAM);
+        let pages = objects::total_bytes(&state.objects.lock().unwrap_or_else(|e| e.into_inner())) as usize / page;
+        info!("{}", tr!("swap-started", pages = pages));
+
+        let mut last_report = Instant::now();
+        let mut counters = read_vmstat();
+        let mut touched_bytes = 0u64;
+        let mut touch_time = Duration::ZERO;
+        while state.is_running() {
+            {
+                let chunks = state.objects.lock().unwrap_or_else(|e| e.into_inner());
+                if chunks.is_empty() {
+                    break;
+                }
+                if let Err(e) = chunks.iter().try_for_each(|chunk| page_out(chunk, advice)) {
+                    if advice == Advice::PageOut {
+                        warn!("{}", tr!("swap-pageout-failed", error = e));
+                        advice = Advice::DontNeed;
+                        continue;
+                    }
+                    warn!("{}", tr!("swap-dontneed-failed", error = e));
+                    return;
+                }
+
+                // 随机访问与页面总数相同次数，触发换入缺页
+                let start = Instant::now();
+                let mut sum = 0u8;
+                for _ in 0..pages {
+                    let chunk = &chunks[rng.gen_range(0..chunks.len())];
+                    let offset = rng.gen_range(0..chunk.len().div_ceil(page)) * page;
+                    // SAFETY: offset 小于块的长度
+                    sum = sum.wrapping_add(unsafe { std::ptr::read_volatile(chunk.as_ptr().add(offset)) });
+                }
+                std::hint::black_box(sum);
+                touch_time += start.elapsed();
+                touched_bytes += (pages * page) as u64;
+            }
+
+            let elapsed = last_report.elapsed();
+            if elapsed >= REPORT_INTERVAL {
+                let current = read_vmstat();
+                let mut line = tr!(
+                    "swap-bandwidth",
+                    rate = format!("{:.1}", touched_bytes as f64 / 1e6 / touch_time.as_secs_f64().max(f64::EPSILON))
+                );
+                if let (Some(before), Some(after)) = (counters, current) {
+                    let secs = elapsed.as_secs_f64();
+                    line.push_str(&tr!(
+                        "swap-vmstat",
+                        si = format!("{:.0}", after.pswpin.saturating_sub(before.pswpin) as f64 / secs),
+                        so = format!("{:.0}", after.pswpout.saturating_sub(before.pswpout) as f64 / secs)
+                    ));
+                }
+                info!("{}", line);
+                counters = current;
+                touched_bytes = 0;
+                touch_time = Duration::ZERO;
+                last_report = Instant::now();
+            }
+        }
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn page_out(buffer: &[u8], advice: Advice) -> std::io::Result<()> {
+    // 只处理完全落在缓冲区内的页面：MADV_DONTNEED 会清零整页，首尾页可能还存放着分配器的元数据
+    let page = residency::page_size();
+    let start = (buffer.as_ptr() as usize).next_multiple_of(page);
+    let end = (buffer.as_ptr() as usize + buffer.len()) & !(page - 1);
+    if end <= start {
+        return Ok(());
+    }
+    let len = end - start;
+    let advice = match advice {
+        Advice::PageOut => libc::MADV_PAGEOUT,
+        Advice::DontNeed => libc::MADV_DONTNEED,
+    };
+    // SAFETY: 地址范围完全位于缓冲区内；换出或丢弃后再次访问会重新缺页，
+    // 被丢弃的页面读到零，缓冲区内容本身无关紧要
+    let ret = unsafe { libc::madvise(start as *mut libc::c_void, len, advice) };
+    if ret == 0 { Ok(()) } else { Err(std::io::Error::last_os_error()) }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn page_out(_buffer: &[u8], _advice: Advice) -> std::io::Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, tr!("swap-unsupported")))
+}
+
+/// 读取 /proc/vmstat 中的 pswpin/pswpout，非 Linux 平台返回 None
+fn read_vmstat() -> Option<SwapCounters> {
+    let content = std::fs::read_to_string("/proc/vmstat").ok()?;
+    let mut counters = SwapCounters::default();
+    for line in content.lines() {
+        let mut parts = line.split_whitespace();
+        match (parts.next(), parts.next().and_then(|v| v.parse().ok())) {
+            (Some("pswpin"), Some(value)) => counters.pswpin = value,
+            (Some("pswpout"), Some(value)) => counters.pswpout = value,
+            _ => {}
+        }
+    }
+    Some(counters)
+}
@@ -0,0 +1,160 @@
+//! 跨机器同步启动：简单的TCP集合点，协议为按行传输的JSON
+//!
+//! 客户端发送 `{"msg":"ready"}`，服务端在N个客户端全部就绪后广播
+//! `{"msg":"go","epoch_ms":...}`。
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::time::{Duration, Instant};
+
+use crate::json::Json;
+use crate::state::LoadState;
+use crate::timeutil;
+
+/// 连接服务端失败后的重试间隔
+const CONNECT_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 服务端等待客户端发送就绪消息的时间
+const READY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 服务端接受的就绪消息最大长度（字节）
+const MAX_MESSAGE_LEN: u64 = 1024;
+
+/// 等待开始信号时检查运行标志的间隔
+const READ_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 运行集合点服务端：等待 `count` 个客户端就绪后同时发送开始信号
+///
+/// 单个客户端超时未发送就绪消息、发送无效内容或连接出错时只告警并忽略该连接；
+/// 广播前剔除已断开的客户端，并继续等待新的客户端补齐。
+pub fn run_server(listen: &str, count: usize) -> std::io::Result<()> {
+    let listener = TcpListener::bind(listen)?;
+    info!("{}", tr!("sync-server-started", addr = listener.local_addr()?, count = count));
+
+    let mut clients: Vec<(TcpStream, SocketAddr)> = Vec::with_capacity(count);
+    loop {
+        while clients.len() < count {
+            let (stream, addr) = match listener.accept() {
+                Ok(client) => client,
+                Err(e) => {
+                    warn!("{}", tr!("sync-accept-failed", error = e));
+                    continue;
+                }
+            };
+            let line = match read_ready(&stream) {
+                Ok(line) => line,
+                Err(e) => {
+                    warn!("{}", tr!("sync-client-read-failed", addr = addr, error = e));
+                    continue;
+                }
+            };
+            match Json::parse(line.trim()) {
+                Ok(msg) if msg.get("msg").and_then(Json::as_str) == Some("ready") => {
+                    clients.push((stream, addr));
+                    info!("{}", tr!("sync-client-ready", addr = addr, ready = clients.len(), count = count));
+                }
+                _ => warn!("{}", tr!("sync-invalid-client-message", addr = addr, message = format!("{:?}", line.trim()))),
+            }
+        }
+        clients.retain(|(stream, addr)| {
+            let connected = is_connected(stream);
+            if !connected {
+                warn!("{}", tr!("sync-client-disconnected", addr = addr));
+            }
+            connected
+        });
+        if clients.len() == count {
+            break;
+        }
+    }
+
+    let go = Json::object([("msg", "go".into()), ("epoch_ms", timeutil::unix_millis().into())]);
+    let mut sent = 0;
+    for (mut client, addr) in clients {
+        match writeln!(client, "{}", go) {
+            Ok(()) => sent += 1,
+            Err(e) => warn!("{}", tr!("sync-broadcast-failed", addr = addr, error = e)),
+        }
+    }
+    info!("{}", tr!("sync-broadcast", count = sent));
+    Ok(())
+}
+
+/// 在 `READY_TIMEOUT` 内读取客户端的就绪消息，最多读取 `MAX_MESSAGE_LEN` 字节
+fn read_ready(stream: &TcpStream) -> std::io::Result<String> {
+    stream.set_read_timeout(Some(READY_TIMEOUT))?;
+    let mut line = String::new();
+    BufReader::new(stream.take(MAX_MESSAGE_LEN)).read_line(&mut line)?;
+    Ok(line)
+}
+
+/// 客户端是否仍保持连接：对端关闭时非阻塞 peek 立即返回0字节
+fn is_connected(stream: &TcpStream) -> bool {
+    if stream.set_nonblocking(true).is_err() {
+        return false;
+    }
+    let connected = match stream.peek(&mut [0u8; 1]) {
+        Ok(0) => false,
+        Ok(_) => true,
+        Err(e) => e.kind() == std::io::ErrorKind::WouldBlock,
+    };
+    connected && stream.set_nonblocking(false).is_ok()
+}
+
+/// 连接集合点并阻塞等待开始信号，负载在等待期间被停止时返回 Ok(false)
+pub fn wait_for_go(addr: &str, state: &LoadState) -> std::io::Result<bool> {
+    let stream = loop {
+        match TcpStream::connect(addr) {
+            Ok(stream) => break stream,
+            Err(e) => {
+                if !state.is_running() {
+                    return Ok(false);
+                }
+                warn!("{}", tr!("sync-connect-retry", addr = addr, error = e));
+                std::thread::sleep(CONNECT_RETRY_INTERVAL);
+            }
+        }
+    };
+
+    let mut writer = stream.try_clone()?;
+    writeln!(writer, "{}", Json::object([("msg", "ready".into())]))?;
+    info!("{}", tr!("sync-connected", addr = addr));
+    let ready_at = Instant::now();
+
+    stream.set_read_timeout(Some(READ_POLL_INTERVAL))?;
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    loop {
+        match reader.read_line(&mut line) {
+            Ok(0) => {
+                return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, tr!("sync-server-closed")));
+            }
+            Ok(_) => break,
+            Err(e) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => {
+                if !state.is_running() {
+                    return Ok(false);
+                }
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    let received_ms = timeutil::unix_millis();
+    let msg = Json::parse(line.trim()).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    if msg.get("msg").and_then(Json::as_str) != Some("go") {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, tr!("sync-unexpected-message", message = msg)));
+    }
+    match msg.get("epoch_ms").and_then(Json::as_f64) {
+        // 延迟包含两台机器之间的时钟偏差
+        Some(epoch_ms) => info!(
+            "{}",
+            tr!(
+                "sync-go-received-latency",
+                latency = received_ms as i64 - epoch_ms as i64,
+                waited = format!("{:.1}", ready_at.elapsed().as_secs_f64())
+            )
+        ),
+        None => info!("{}", tr!("sync-go-received", waited = format!("{:.1}", ready_at.elapsed().as_secs_f64()))),
+    }
+    Ok(true)
+}
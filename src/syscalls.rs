@@ -0,0 +1,176 @@
+//! 统计本进程的系统调用和上下文切换速率，确认负载本身的开销足够低（仅Linux）
+//!
+//! 系统调用数通过 raw_syscalls:sys_enter 跟踪点计数，需要 tracefs 可读且有足够的
+//! perf 权限（CAP_PERFMON 或较低的 `kernel.perf_event_paranoid`）；上下文切换通过
+//! PERF_COUNT_SW_CONTEXT_SWITCHES 软件事件计数，打不开时改为汇总 /proc/self/task/*/status。
+
+use std::time::Instant;
+
+use crate::json::Json;
+
+/// 一次采样间隔内的速率，无法统计的项为 None
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SyscallRates {
+    pub syscalls_per_sec: Option<f64>,
+    pub context_switches_per_sec: Option<f64>,
+}
+
+impl SyscallRates {
+    /// 文本格式，例如 "系统调用/s: 1234, 上下文切换/s: 56"
+    pub fn describe(&self) -> String {
+        let format = |v: Option<f64>| v.map_or_else(|| "-".to_string(), |v| format!("{:.0}", v));
+        tr!(
+            "syscalls-rates",
+            syscalls = format(self.syscalls_per_sec),
+            switches = format(self.context_switches_per_sec)
+        )
+    }
+
+    pub fn to_json(self) -> Json {
+        Json::object([
+            ("syscalls_per_sec", self.syscalls_per_sec.into()),
+            ("context_switches_per_sec", self.context_switches_per_sec.into()),
+        ])
+    }
+}
+
+/// 计数来源
+enum Source {
+    #[cfg(target_os = "linux")]
+    Perf(std::fs::File),
+    /// 汇总 /proc/self/task/*/status 中的上下文切换次数
+    ProcStatus,
+}
+
+impl Source {
+    fn read(&self) -> Option<u64> {
+        match self {
+            #[cfg(target_os = "linux")]
+            Source::Perf(file) => Some(crate::perf::sys::read(file)),
+            Source::ProcStatus => read_task_context_switches(),
+        }
+    }
+}
+
+struct Counter {
+    source: Source,
+    last: u64,
+}
+
+impl Counter {
+    fn new(source: Source) -> Self {
+        let last = source.read().unwrap_or(0);
+        Counter { source, last }
+    }
+
+    /// 自上次调用以来的增量
+    fn delta(&mut self) -> Option<u64> {
+        let current = self.source.read()?;
+        let delta = current.saturating_sub(self.last);
+        self.last = current;
+        Some(delta)
+    }
+}
+
+/// 系统调用和上下文切换计数器
+pub struct SyscallTracer {
+    syscalls: Option<Counter>,
+    context_switches: Option<Counter>,
+    last_sample: Instant,
+}
+
+impl SyscallTracer {
+    /// 打开计数器；必须在创建工作线程之前调用，之后创建的线程才会被一并统计
+    ///
+    /// `use_perf` 为 false 时（例如 `kernel.perf_event_paranoid` 不允许）不尝试 perf 事件，
+    /// 只通过 /proc/self/task 统计上下文切换。
+    pub fn new(use_perf: bool) -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            use crate::perf::sys;
+
+            /// PERF_COUNT_SW_CONTEXT_SWITCHES
+            const CONTEXT_SWITCHES: u64 = 3;
+
+            if !use_perf {
+                warn!("{}", tr!("syscalls-perf-disabled"));
+                return SyscallTracer {
+                    syscalls: None,
+                    context_switches: Some(Counter::new(Source::ProcStatus)),
+                    last_sample: Instant::now(),
+                };
+            }
+            let syscalls = match tracepoint_id("raw_syscalls/sys_enter")
+                .and_then(|id| sys::open(sys::PERF_TYPE_TRACEPOINT, id, sys::FLAG_INHERIT))
+            {
+                Ok(file) => Some(Counter::new(Source::Perf(file))),
+                Err(e) => {
+                    warn!("{}", tr!("syscalls-trace-failed", error = e));
+                    None
+                }
+            };
+            let context_switches = match sys::open(sys::PERF_TYPE_SOFTWARE, CONTEXT_SWITCHES, sys::FLAG_INHERIT) {
+                Ok(file) => Counter::new(Source::Perf(file)),
+                Err(e) => {
+                    warn!("{}", tr!("syscalls-switches-failed", error = e));
+                    Counter::new(Source::ProcStatus)
+                }
+            };
+            SyscallTracer { syscalls, context_switches: Some(context_switches), last_sample: Instant::now() }
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = use_perf;
+            warn!("{}", tr!("syscalls-unsupported"));
+            SyscallTracer { syscalls: None, context_switches: None, last_sample: Instant::now() }
+        }
+    }
+
+    /// 上次采样以来的速率
+    pub fn sample(&mut self) -> SyscallRates {
+        let secs = self.last_sample.elapsed().as_secs_f64().max(f64::EPSILON);
+        self.last_sample = Instant::now();
+        let rate = |counter: &mut Option<Counter>| counter.as_mut()?.delta().map(|d| d as f64 / secs);
+        SyscallRates {
+            syscalls_per_sec: rate(&mut self.syscalls),
+            context_switches_per_sec: rate(&mut self.context_switches),
+        }
+    }
+}
+
+/// 读取跟踪点的事件ID，依次尝试 tracefs 的两个常见挂载位置
+#[cfg(target_os = "linux")]
+fn tracepoint_id(name: &str) -> std::io::Result<u64> {
+    let mut last_error = None;
+    for root in ["/sys/kernel/tracing", "/sys/kernel/debug/tracing"] {
+        match std::fs::read_to_string(format!("{}/events/{}/id", root, name)) {
+            Ok(id) => {
+                return id
+                    .trim()
+                    .parse()
+                    .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidData, tr!("syscalls-invalid-tracepoint")));
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| std::io::ErrorKind::NotFound.into()))
+}
+
+/// 汇总当前所有线程的自愿和非自愿上下文切换次数，已退出的线程不计入
+fn read_task_context_switches() -> Option<u64> {
+    let mut total = 0;
+    for entry in std::fs::read_dir("/proc/self/task").ok()?.flatten() {
+        let Ok(status) = std::fs::read_to_string(entry.path().join("status")) else {
+            continue;
+        };
+        for line in status.lines() {
+            if let Some(value) = line
+                .strip_prefix("voluntary_ctxt_switches:")
+                .or_else(|| line.strip_prefix("nonvoluntary_ctxt_switches:"))
+            {
+                total += value.trim().parse::<u64>().unwrap_or(0);
+            }
+        }
+    }
+    Some(total)
+}
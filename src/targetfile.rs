@@ -0,0 +1,124 @@
+//! `--target-file`：从文件读取CPU目标使用率，外部脚本写入文件即可调整负载，例如 `echo 80 > /tmp/cpu_target`
+//!
+//! Linux 上用 inotify 监视文件所在的目录（编辑器常以重命名的方式替换文件，直接监视文件会失效），
+//! 其他平台或 inotify 不可用时每500ms读取一次。只有内容变化时才解析，解析失败时保留当前目标。
+//! 比控制套接字简单，也可以通过卷挂载跨容器使用。
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::state::LoadState;
+use crate::timeutil;
+
+/// 轮询的间隔，也是 inotify 等待事件的超时
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// 解析文件内容为目标使用率（0-100 的小数，四舍五入到整数）
+fn parse_target(text: &str) -> Result<u8, String> {
+    let text = text.trim();
+    match text.parse::<f64>() {
+        Ok(percent) if (0.0..=100.0).contains(&percent) => Ok(percent.round() as u8),
+        _ => Err(tr!("target-file-invalid", value = format!("{:?}", text))),
+    }
+}
+
+/// 监视文件所在目录的 inotify 描述符，drop 时关闭
+#[cfg(target_os = "linux")]
+struct Watch(i32);
+
+#[cfg(target_os = "linux")]
+impl Watch {
+    fn new(path: &std::path::Path) -> std::io::Result<Watch> {
+        use std::os::unix::ffi::OsStrExt;
+        use std::path::Path;
+
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let dir = std::ffi::CString::new(dir.as_os_str().as_bytes())
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        // SAFETY: inotify_init1 不访问用户内存
+        let fd = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+        if fd < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let watch = Watch(fd);
+        let mask = libc::IN_CLOSE_WRITE | libc::IN_MODIFY | libc::IN_MOVED_TO | libc::IN_CREATE;
+        // SAFETY: dir 是以 NUL 结尾的有效路径
+        if unsafe { libc::inotify_add_watch(fd, dir.as_ptr(), mask) } < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(watch)
+    }
+
+    /// 等待目录中的事件，最多等待 `POLL_INTERVAL`，并取走所有已到达的事件
+    fn wait(&self) {
+        let mut pollfd = libc::pollfd { fd: self.0, events: libc::POLLIN, revents: 0 };
+        // SAFETY: pollfd 指向一个有效的结构体，数量为1
+        if unsafe { libc::poll(&mut pollfd, 1, POLL_INTERVAL.as_millis() as i32) } <= 0 {
+            return;
+        }
+        let mut buf = [0u8; 4096];
+        // 目录中其他文件的事件同样会唤醒，读取后比较内容即可区分，这里不解析事件
+        // SAFETY: buf 在调用期间有效，长度与传入的一致；描述符为非阻塞，读完后返回 EAGAIN
+        while unsafe { libc::read(self.0, buf.as_mut_ptr().cast(), buf.len()) } > 0 {}
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for Watch {
+    fn drop(&mut self) {
+        // SAFETY: fd 由 inotify_init1 创建，只在这里关闭一次
+        unsafe { libc::close(self.0) };
+    }
+}
+
+/// 启动目标文件监视线程，文件内容变化并解析成功时更新 `state.target_percent`
+pub fn spawn_target_file_watcher(path: PathBuf, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        #[cfg(target_os = "linux")]
+        let watch = match Watch::new(&path) {
+            Ok(watch) => Some(watch),
+            Err(e) => {
+                warn!("{}", tr!("target-file-inotify-failed", error = e));
+                None
+            }
+        };
+        info!("{}", tr!("target-file-watching", path = path.display()));
+        // 上次读到的内容，文件不存在时为 None
+        let mut last: Option<String> = None;
+        while state.is_running() {
+            let contents = std::fs::read_to_string(&path).ok();
+            // `echo 80 > 文件` 先截断再写入，两步之间可能读到空文件，不当作错误
+            if let Some(text) = contents.as_deref().filter(|text| !text.trim().is_empty())
+                && contents != last
+            {
+                match parse_target(text) {
+                    Ok(percent) => {
+                        let previous = state.target_percent.swap(percent, Ordering::Relaxed);
+                        if previous != percent {
+                            info!(
+                                "[{}] {}",
+                                timeutil::now_rfc3339(),
+                                tr!("target-file-updated", from = previous, to = percent)
+                            );
+                        }
+                    }
+                    Err(e) => warn!("[{}] {}", timeutil::now_rfc3339(), e),
+                }
+            }
+            last = contents;
+
+            #[cfg(target_os = "linux")]
+            if let Some(watch) = &watch {
+                watch.wait();
+                continue;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    })
+}
@@ -0,0 +1,96 @@
+//! 温度保护：定期读取CPU温度，过热时降低占空比目标，温度回落后恢复
+//!
+//! 温度优先取 sysinfo 报告的CPU传感器（coretemp、k10temp 等）中的最高值，没有时回退到
+//! `/sys/class/thermal/thermal_zone0/temp`。超过上限时每次检查把共享的目标使用率降低10个百分点
+//! （不低于5%）；降到上限以下5°C后恢复为最初的目标，避免在上限附近反复切换。
+
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use sysinfo::{ComponentExt, System, SystemExt};
+
+use crate::state::LoadState;
+use crate::timeutil;
+
+/// 读取温度的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 每次过热时降低的目标使用率（百分点）
+const THROTTLE_STEP: u8 = 10;
+
+/// 降温后目标使用率的下限
+const MIN_PERCENT: u8 = 5;
+
+/// 恢复目标前需要低于上限的温差（°C）
+const HYSTERESIS: f32 = 5.0;
+
+/// sysinfo 中看起来属于CPU的传感器标签
+const CPU_LABELS: [&str; 6] = ["cpu", "core", "package", "tctl", "tdie", "k10temp"];
+
+/// 当前CPU温度（°C），没有可用的传感器时为 None
+fn read_temperature(sys: &mut System) -> Option<f32> {
+    sys.refresh_components();
+    let cpu = sys
+        .components()
+        .iter()
+        .filter(|c| {
+            let label = c.label().to_lowercase();
+            CPU_LABELS.iter().any(|l| label.contains(l))
+        })
+        .map(|c| c.temperature())
+        .filter(|t| t.is_finite() && *t > 0.0)
+        .reduce(f32::max);
+    cpu.or_else(|| {
+        let millis: f32 = std::fs::read_to_string("/sys/class/thermal/thermal_zone0/temp").ok()?.trim().parse().ok()?;
+        Some(millis / 1000.0)
+    })
+}
+
+/// 启动温度保护线程，温度超过 `max_temp` 时降低 `state.target_percent`
+pub fn spawn_thermal_guard(max_temp: u8, state: Arc<LoadState>) -> JoinHandle<()> {
+    thread::spawn(move || {
+        let mut sys = System::new();
+        sys.refresh_components_list();
+        let max_temp = f32::from(max_temp);
+        if read_temperature(&mut sys).is_none() {
+            warn!("{}", tr!("thermal-unavailable"));
+            return;
+        }
+        info!("{}", tr!("thermal-started", max = max_temp, resume = max_temp - HYSTERESIS));
+        // 降温前的目标使用率，未降温时为 None
+        let mut original: Option<u8> = None;
+        let mut last_check = Instant::now();
+        while state.is_running() {
+            thread::sleep(Duration::from_millis(100));
+            if last_check.elapsed() < CHECK_INTERVAL {
+                continue;
+            }
+            last_check = Instant::now();
+            let Some(temp) = read_temperature(&mut sys) else {
+                continue;
+            };
+            let current = state.target_percent.load(Ordering::Relaxed);
+            if temp > max_temp {
+                let target = current.saturating_sub(THROTTLE_STEP).max(MIN_PERCENT.min(current));
+                original.get_or_insert(current);
+                state.target_percent.store(target, Ordering::Relaxed);
+                warn!(
+                    "[{}] {}",
+                    timeutil::now_rfc3339(),
+                    tr!("thermal-throttled", temp = format!("{:.1}", temp), percent = target)
+                );
+            } else if temp < max_temp - HYSTERESIS
+                && let Some(percent) = original.take()
+            {
+                state.target_percent.store(percent, Ordering::Relaxed);
+                info!(
+                    "[{}] {}",
+                    timeutil::now_rfc3339(),
+                    tr!("thermal-restored", temp = format!("{:.1}", temp), percent = percent)
+                );
+            }
+        }
+    })
+}
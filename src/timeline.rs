@@ -0,0 +1,101 @@
+//! `--timeline-export`：记录负载执行过程中的事件，结束后写成 Chrome Trace Event 格式的JSON，
+//! 可以在 chrome://tracing 或 Perfetto UI（ui.perfetto.dev）中按时间线查看
+//!
+//! 每个工作线程一行，内存分配与释放、阶段切换和尖峰各占一行；`ts` 为从开始记录起的微秒数。
+//! 未启用时各记录函数直接返回，不产生开销。
+
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use crate::json::Json;
+
+/// 内存分配与释放所在的行
+pub const MEMORY_ROW: u64 = 1;
+
+/// 阶段切换和CPU尖峰所在的行
+pub const CONTROL_ROW: u64 = 2;
+
+/// 第一个工作线程所在的行，第 i 个工作线程为 `WORKER_ROWS + i`
+const WORKER_ROWS: u64 = 10;
+
+/// 一个事件：`phase` 为 B（开始）、E（结束）或 M（行名称）
+struct Event {
+    name: String,
+    phase: char,
+    ts: u64,
+    row: u64,
+}
+
+struct Timeline {
+    start: Instant,
+    events: Mutex<Vec<Event>>,
+}
+
+static TIMELINE: OnceLock<Timeline> = OnceLock::new();
+
+/// 开始记录，时间从此刻起算，并为内存行和控制行命名
+pub fn enable() {
+    TIMELINE.get_or_init(|| Timeline { start: Instant::now(), events: Mutex::new(Vec::new()) });
+    name_row(MEMORY_ROW, "memory");
+    name_row(CONTROL_ROW, "control");
+}
+
+/// 第 `index` 个工作线程所在的行，并为它命名
+pub fn worker_row(index: usize) -> u64 {
+    let row = WORKER_ROWS + index as u64;
+    name_row(row, &format!("cpu_worker {}", index));
+    row
+}
+
+fn record(row: u64, name: &str, phase: char) {
+    let Some(timeline) = TIMELINE.get() else {
+        return;
+    };
+    let ts = timeline.start.elapsed().as_micros() as u64;
+    let event = Event { name: name.to_string(), phase, ts, row };
+    timeline.events.lock().unwrap_or_else(|e| e.into_inner()).push(event);
+}
+
+fn name_row(row: u64, name: &str) {
+    record(row, name, 'M');
+}
+
+/// 在 `row` 上开始一段名为 `name` 的区间
+pub fn begin(row: u64, name: &str) {
+    record(row, name, 'B');
+}
+
+/// 结束 `row` 上最近开始的区间
+pub fn end(row: u64, name: &str) {
+    record(row, name, 'E');
+}
+
+/// 把记录的事件写入 `path`，返回事件数；未启用时不写入，返回 None
+pub fn export(path: &Path) -> Option<std::io::Result<usize>> {
+    let timeline = TIMELINE.get()?;
+    let events = timeline.events.lock().unwrap_or_else(|e| e.into_inner());
+    let pid = std::process::id();
+    let trace: Vec<Json> = events
+        .iter()
+        .map(|event| match event.phase {
+            'M' => Json::object([
+                ("name", Json::from("thread_name")),
+                ("ph", "M".into()),
+                ("pid", pid.into()),
+                ("tid", event.row.into()),
+                ("args", Json::object([("name", Json::from(event.name.as_str()))])),
+            ]),
+            phase => Json::object([
+                ("name", Json::from(event.name.as_str())),
+                ("ph", phase.to_string().into()),
+                ("ts", event.ts.into()),
+                ("pid", pid.into()),
+                ("tid", event.row.into()),
+            ]),
+        })
+        .collect();
+    let count = trace.len();
+    let document = Json::object([("traceEvents", Json::Array(trace)), ("displayTimeUnit", "ms".into())]);
+    Some(std::fs::write(path, format!("{}\n", document)).map(|()| count))
+}
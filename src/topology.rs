@@ -0,0 +1,198 @@
+//! CPU拓扑：逻辑CPU所属的物理插槽和物理核心，以及工作线程的绑核
+//!
+//! Linux 上从 `/sys/devices/system/cpu/cpuN/topology/` 读取；其他平台或读取失败时，
+//! 把每个逻辑CPU视为插槽0上独立的物理核心。
+
+use crate::json::Json;
+
+/// 一个逻辑CPU的位置
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CpuLocation {
+    /// 逻辑CPU编号
+    pub cpu: usize,
+    /// 物理插槽（physical_package_id）
+    pub package: usize,
+    /// 插槽内的物理核心（core_id）
+    pub core: usize,
+}
+
+impl CpuLocation {
+    pub fn to_json(self) -> Json {
+        Json::object([
+            ("cpu", self.cpu.into()),
+            ("package", self.package.into()),
+            ("core", self.core.into()),
+        ])
+    }
+}
+
+/// 读取所有逻辑CPU的位置，按逻辑CPU编号排序；无法读取的CPU视为独立的物理核心
+pub fn cpu_locations() -> Vec<CpuLocation> {
+    (0..num_cpus::get())
+        .map(|cpu| {
+            let (package, core) = read_location(cpu).unwrap_or((0, cpu));
+            CpuLocation { cpu, package, core }
+        })
+        .collect()
+}
+
+/// 读取 `count` 个逻辑CPU的真实位置，任意一个无法读取时返回 None
+pub fn physical_layout(count: usize) -> Option<Vec<CpuLocation>> {
+    (0..count)
+        .map(|cpu| read_location(cpu).map(|(package, core)| CpuLocation { cpu, package, core }))
+        .collect()
+}
+
+/// 按物理核心分组，组内为共享该核心的逻辑CPU（超线程兄弟），按插槽和核心排序
+pub fn group_by_core(layout: &[CpuLocation]) -> Vec<((usize, usize), Vec<usize>)> {
+    let mut groups: Vec<((usize, usize), Vec<usize>)> = Vec::new();
+    let mut sorted = layout.to_vec();
+    sorted.sort_by_key(|l| (l.package, l.core, l.cpu));
+    for location in sorted {
+        let key = (location.package, location.core);
+        match groups.last_mut() {
+            Some((last, cpus)) if *last == key => cpus.push(location.cpu),
+            _ => groups.push((key, vec![location.cpu])),
+        }
+    }
+    groups
+}
+
+#[cfg(target_os = "linux")]
+fn read_location(cpu: usize) -> Option<(usize, usize)> {
+    let read = |name: &str| -> Option<usize> {
+        let path = format!("/sys/devices/system/cpu/cpu{}/topology/{}", cpu, name);
+        std::fs::read_to_string(path).ok()?.trim().parse().ok()
+    };
+    Some((read("physical_package_id")?, read("core_id")?))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_location(_cpu: usize) -> Option<(usize, usize)> {
+    None
+}
+
+/// 每个物理核心上除第一个兄弟之外的一个逻辑CPU（超线程），按CPU编号排序
+///
+/// Linux 上读取 `/sys/devices/system/cpu/cpuN/topology/thread_siblings_list`，编号最小的兄弟
+/// 视为主线程；未开启超线程或其他平台上返回空列表。
+#[cfg(target_os = "linux")]
+pub fn get_ht_siblings() -> Vec<usize> {
+    let mut siblings: Vec<usize> = (0..num_cpus::get())
+        .filter_map(|cpu| {
+            let path = format!("/sys/devices/system/cpu/cpu{}/topology/thread_siblings_list", cpu);
+            let mut list = parse_cpu_list(&std::fs::read_to_string(path).ok()?)?;
+            list.sort_unstable();
+            // 同一物理核心的兄弟读到相同的列表，只在第二个兄弟自己身上计入一次
+            (list.get(1) == Some(&cpu)).then_some(cpu)
+        })
+        .collect();
+    siblings.sort_unstable();
+    siblings
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_ht_siblings() -> Vec<usize> {
+    Vec::new()
+}
+
+/// 按插槽交错排列逻辑CPU：插槽0的第1个CPU、插槽1的第1个CPU、插槽0的第2个CPU……
+///
+/// 插槽之间CPU数不同时，CPU较少的插槽排完后只轮换剩余的插槽。
+pub fn interleaved(locations: &[CpuLocation]) -> Vec<CpuLocation> {
+    let mut packages: Vec<usize> = locations.iter().map(|l| l.package).collect();
+    packages.sort_unstable();
+    packages.dedup();
+    let per_package: Vec<Vec<CpuLocation>> = packages
+        .iter()
+        .map(|&package| locations.iter().filter(|l| l.package == package).copied().collect())
+        .collect();
+    let rounds = per_package.iter().map(Vec::len).max().unwrap_or(0);
+    (0..rounds)
+        .flat_map(|round| per_package.iter().filter_map(move |cpus| cpus.get(round).copied()))
+        .collect()
+}
+
+/// 把调用线程绑定到指定的逻辑CPU
+#[cfg(target_os = "linux")]
+pub fn pin_current_thread(cpu: usize) -> std::io::Result<()> {
+    // SAFETY: cpu_set_t 是普通位图，全零即空集合；pid=0 表示调用线程
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_SET(cpu, &mut set);
+        if libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// 混合架构CPU（如 Intel Alder Lake 的性能核与能效核、ARM big.LITTLE）的核心分类
+#[derive(Clone, Debug, Default)]
+pub struct HybridInfo {
+    /// 性能核上的逻辑CPU
+    pub performance: Vec<usize>,
+    /// 能效核上的逻辑CPU
+    pub efficiency: Vec<usize>,
+}
+
+impl HybridInfo {
+    pub fn is_hybrid(&self) -> bool {
+        !self.performance.is_empty() && !self.efficiency.is_empty()
+    }
+}
+
+/// 检测混合架构CPU，不是混合架构或无法检测时返回 None
+///
+/// Intel 混合架构下内核为两类核心分别注册 perf PMU，`/sys/devices/cpu_core/cpus` 和
+/// `/sys/devices/cpu_atom/cpus` 列出各自的逻辑CPU；ARM 平台则比较各CPU的 `cpu_capacity`，
+/// 容量最大的为性能核。
+#[cfg(target_os = "linux")]
+pub fn check_hybrid_cores() -> Option<HybridInfo> {
+    let read = |path: &str| std::fs::read_to_string(path).ok();
+    if let (Some(core), Some(atom)) = (read("/sys/devices/cpu_core/cpus"), read("/sys/devices/cpu_atom/cpus")) {
+        let info = HybridInfo { performance: parse_cpu_list(&core)?, efficiency: parse_cpu_list(&atom)? };
+        return info.is_hybrid().then_some(info);
+    }
+
+    let capacities: Vec<u64> = (0..num_cpus::get())
+        .map(|cpu| read(&format!("/sys/devices/system/cpu/cpu{}/cpu_capacity", cpu))?.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    let max = *capacities.iter().max()?;
+    let (performance, efficiency): (Vec<usize>, Vec<usize>) = (0..capacities.len()).partition(|&cpu| capacities[cpu] == max);
+    let info = HybridInfo { performance, efficiency };
+    info.is_hybrid().then_some(info)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn check_hybrid_cores() -> Option<HybridInfo> {
+    None
+}
+
+/// 解析内核的CPU列表格式，例如 "0-7,16"
+pub fn parse_cpu_list(list: &str) -> Option<Vec<usize>> {
+    let mut cpus = Vec::new();
+    for part in list.trim().split(',').filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => cpus.extend(start.parse::<usize>().ok()?..=end.parse::<usize>().ok()?),
+            None => cpus.push(part.parse().ok()?),
+        }
+    }
+    Some(cpus)
+}
+
+/// 把CPU编号格式化为区间，例如 [8, 9, 10, 12] -> "8–10, 12"
+pub fn format_cpu_ranges(cpus: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &cpu in cpus {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == cpu => *end = cpu,
+            _ => ranges.push((cpu, cpu)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|&(start, end)| if start == end { start.to_string() } else { format!("{}–{}", start, end) })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
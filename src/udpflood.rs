@@ -0,0 +1,117 @@
+//! UDP回环压力：一个线程向 127.0.0.1 上的随机端口持续发送64字节的小包，另一个线程接收并丢弃
+//!
+//! 每个包携带序号和发送时间，接收端据此统计收包速率、丢包率和单向延迟。
+
+use std::net::UdpSocket;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::state::LoadState;
+
+/// 包大小
+const PACKET_SIZE: usize = 64;
+
+/// 输出统计的间隔
+const REPORT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 接收超时，用于定期检查运行标志
+const RECV_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// 限速时每批发送的包数，避免每个包都检查时间
+const PACING_BATCH: u64 = 64;
+
+/// 启动发送和接收线程，`pps` 为 None 时尽可能快地发送
+pub fn spawn_udp_flood(pps: Option<u64>, state: Arc<LoadState>) -> std::io::Result<Vec<JoinHandle<()>>> {
+    let receiver = UdpSocket::bind("127.0.0.1:0")?;
+    receiver.set_read_timeout(Some(RECV_TIMEOUT))?;
+    let sender = UdpSocket::bind("127.0.0.1:0")?;
+    sender.connect(receiver.local_addr()?)?;
+    match pps {
+        Some(pps) => info!(
+            "{}",
+            tr!("udp-started-rate", from = sender.local_addr()?, to = receiver.local_addr()?, pps = pps)
+        ),
+        None => info!("{}", tr!("udp-started", from = sender.local_addr()?, to = receiver.local_addr()?)),
+    }
+
+    // 发送和接收时间都相对于同一个起点计算
+    let epoch = Instant::now();
+    let sent = Arc::new(AtomicU64::new(0));
+
+    let send_thread = {
+        let state = state.clone();
+        let sent = sent.clone();
+        thread::spawn(move || run_sender(&sender, pps, epoch, &sent, &state))
+    };
+    let recv_thread = thread::spawn(move || run_receiver(&receiver, epoch, &sent, &state));
+    Ok(vec![send_thread, recv_thread])
+}
+
+fn run_sender(socket: &UdpSocket, pps: Option<u64>, epoch: Instant, sent: &AtomicU64, state: &LoadState) {
+    let mut packet = [0u8; PACKET_SIZE];
+    let mut seq = 0u64;
+    let start = Instant::now();
+    while state.is_running() {
+        if let Some(pps) = pps {
+            // 超前于目标速率时等待
+            let due = start + Duration::from_secs_f64(seq as f64 / pps as f64);
+            let now = Instant::now();
+            if due > now {
+                thread::sleep(due - now);
+            }
+        }
+        for _ in 0..pps.map_or(PACING_BATCH, |pps| pps.clamp(1, PACING_BATCH)) {
+            packet[..8].copy_from_slice(&seq.to_le_bytes());
+            packet[8..16].copy_from_slice(&(epoch.elapsed().as_nanos() as u64).to_le_bytes());
+            // 接收缓冲区满时内核可能返回 ENOBUFS 等错误，计为已发送、由丢包率体现
+            let _ = socket.send(&packet);
+            seq += 1;
+        }
+        sent.store(seq, Ordering::Relaxed);
+    }
+}
+
+fn run_receiver(socket: &UdpSocket, epoch: Instant, sent: &AtomicU64, state: &LoadState) {
+    let mut packet = [0u8; PACKET_SIZE];
+    let mut received = 0u64;
+    let mut latency_nanos = 0u64;
+    let mut last_sent = 0u64;
+    let mut last_report = Instant::now();
+    while state.is_running() {
+        if let Ok(len) = socket.recv(&mut packet)
+            && len >= 16 {
+            let sent_at = u64::from_le_bytes(packet[8..16].try_into().unwrap());
+            latency_nanos += (epoch.elapsed().as_nanos() as u64).saturating_sub(sent_at);
+            received += 1;
+        }
+
+        let elapsed = last_report.elapsed();
+        if elapsed >= REPORT_INTERVAL {
+            let total_sent = sent.load(Ordering::Relaxed);
+            let sent_delta = total_sent - last_sent;
+            let secs = elapsed.as_secs_f64();
+            let loss = if sent_delta > 0 {
+                sent_delta.saturating_sub(received) as f64 / sent_delta as f64 * 100.0
+            } else {
+                0.0
+            };
+            let latency = if received > 0 { latency_nanos as f64 / received as f64 / 1000.0 } else { 0.0 };
+            info!(
+                "{}",
+                tr!(
+                    "udp-stats",
+                    sent = format!("{:.0}", sent_delta as f64 / secs),
+                    received = format!("{:.0}", received as f64 / secs),
+                    loss = format!("{:.2}", loss),
+                    latency = format!("{:.1}", latency)
+                )
+            );
+            last_sent = total_sent;
+            received = 0;
+            latency_nanos = 0;
+            last_report = Instant::now();
+        }
+    }
+}
@@ -0,0 +1,424 @@
+//! 工作线程执行的负载类型
+
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::seed;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Workload {
+    /// 按目标使用率执行数学计算（默认）
+    #[default]
+    Spin,
+    /// 通过本机回环地址收发小UDP包，测试内核网络栈的开销
+    UdpFlood,
+    /// 反复用 zstd 压缩并解压类似日志的数据
+    ZstdCompress,
+    /// 通过管道、Unix 域套接字或共享内存在线程对之间传递消息，测试内核IPC的开销（仅Unix）
+    IpcStress,
+    /// 所有工作线程在共享的 AtomicU64 数组上执行原子操作，考验缓存一致性协议
+    AtomicStress,
+    /// 不断分配随机大小的堆块并释放最旧的块，模拟带GC语言的分配模式
+    AllocChurn,
+    /// 在 Rayon 线程池中并行执行空转迭代，测试工作窃取调度器的开销
+    Rayon,
+    /// 反复打开再关闭一组文件描述符，测试内核文件描述符表的性能
+    FdStress,
+    /// 所有工作线程向共享数组中随机的缓存行写入，产生跨核心的缓存行失效流量
+    CacheThrash,
+    /// 在本机回环地址上反复建立并关闭TCP连接，测试内核 accept/connect 的吞吐量（仅Unix）
+    SocketStress,
+    /// 多个线程从大文件中随机读取4KiB页面，填满内核页缓存并与应用缓存争用内存
+    PageCache,
+    /// 按时间在放不进L3的64MiB缓冲区和放得进L2的256KiB缓冲区之间交替顺序读取，测量缓存未命中的性能落差
+    CacheAlternating,
+    /// 计算过程中按 `--interrupt-rate` 向工作线程发送 SIGUSR2，测量每次信号打断的开销（仅Unix）
+    InterruptionTest,
+}
+
+impl Workload {
+    /// 是否按 `-c` 启动计算线程；其余负载自带收发线程
+    pub fn uses_cpu_workers(self) -> bool {
+        match self {
+            Workload::Spin
+            | Workload::ZstdCompress
+            | Workload::AtomicStress
+            | Workload::AllocChurn
+            | Workload::CacheThrash
+            | Workload::CacheAlternating
+            | Workload::InterruptionTest => true,
+            Workload::UdpFlood
+            | Workload::IpcStress
+            | Workload::Rayon
+            | Workload::FdStress
+            | Workload::SocketStress
+            | Workload::PageCache => false,
+        }
+    }
+}
+
+/// ipc-stress 负载的通道类型
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum IpcType {
+    /// 匿名管道
+    #[default]
+    Pipe,
+    /// Unix 域套接字对
+    UnixSocket,
+    /// 内存映射文件上带自旋锁的环形缓冲区
+    SharedMem,
+}
+
+impl IpcType {
+    /// 命令行中的名称，例如 "unix-socket"
+    pub fn name(self) -> String {
+        self.to_possible_value().map_or_else(String::new, |v| v.get_name().to_string())
+    }
+}
+
+/// spin 负载每次迭代执行的指令类型，用于观察电源管理对不同指令的反应
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum BusyLoop {
+    /// 单精度三角函数链（默认），电源管理无法识别为空转
+    #[default]
+    #[value(aliases = ["trig", "fp32"])]
+    Math,
+    /// 双精度三角、指数和对数函数链
+    Fp64,
+    /// 64位取模运算加循环移位、异或和位计数，集中使用整数除法和位运算单元
+    Integer,
+    /// 在每个线程自己的 `--cache-size` 大小的缓冲区中沿随机指针环追逐，受限于缓存和内存延迟
+    MemoryBound,
+    /// 遍历随机打乱的数组，按每个元素的取值走不同的分支，分支预测器无法预测
+    Branch,
+    /// 连续的 nop 指令，部分CPU会识别并降低电压或频率
+    Nop,
+    /// 反复 volatile 读取同一个静态变量，单周期的加载指令且不会被优化掉
+    Volatile,
+    /// 4x4 单精度矩阵乘法，集中使用浮点乘加单元
+    Matmul,
+    /// SHA-256 压缩函数，集中使用整数移位、逻辑和加法单元
+    Sha256,
+}
+
+impl BusyLoop {
+    /// 命令行中的名称，例如 "matmul"
+    pub fn name(self) -> String {
+        self.to_possible_value().map_or_else(String::new, |v| v.get_name().to_string())
+    }
+}
+
+/// nop 和 volatile 每次迭代执行的指令数，使单次迭代的耗时与 math 处于同一量级
+const SLIDE_LEN: usize = 64;
+
+static VOLATILE_SOURCE: u64 = 1;
+
+/// memory-bound 未指定 `--cache-size` 时每个线程的缓冲区大小
+pub const DEFAULT_CACHE_SIZE: usize = 8 << 20;
+
+/// memory-bound 指针环中每个槽的长度（一个64字节缓存行）
+const SLOT_WORDS: usize = 8;
+
+/// branch 遍历的打乱数组长度
+const BRANCH_DATA_LEN: usize = 4096;
+
+/// integer 取模使用的素数（2^61-1）
+const INTEGER_MODULUS: u64 = (1 << 61) - 1;
+
+/// matmul 每次迭代右乘的正交矩阵（两个 3-4-5 旋转块），反复相乘时数值不会发散
+const MATMUL_ROTATION: [[f32; 4]; 4] = [
+    [0.6, -0.8, 0.0, 0.0],
+    [0.8, 0.6, 0.0, 0.0],
+    [0.0, 0.0, 0.6, -0.8],
+    [0.0, 0.0, 0.8, 0.6],
+];
+
+/// SHA-256 的轮常量
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// SHA-256 的初始哈希值
+const SHA256_INIT: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// 对一个64字节的数据块执行 SHA-256 压缩，结果累加到 `hash`
+fn sha256_compress(hash: &mut [u32; 8], block: &[u32; 16]) {
+    let mut w = [0u32; 64];
+    w[..16].copy_from_slice(block);
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *hash;
+    for i in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(SHA256_K[i]).wrapping_add(w[i]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+        (h, g, f, e, d, c, b, a) = (g, f, e, d.wrapping_add(t1), c, b, a, t1.wrapping_add(t2));
+    }
+    for (state, value) in hash.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *state = state.wrapping_add(value);
+    }
+}
+
+/// 把 `bytes` 字节的缓冲区按缓存行连成一个随机的环（Sattolo 算法保证只有一个环），
+/// 每个槽的第一个字存放下一个槽的序号
+fn build_chain(bytes: usize) -> Vec<u64> {
+    let slots = (bytes / (SLOT_WORDS * 8)).max(2);
+    let mut order: Vec<usize> = (0..slots).collect();
+    let mut rng = seed::thread_rng(seed::BUSY_LOOP_STREAM);
+    for i in (1..slots).rev() {
+        order.swap(i, rng.gen_range(0..i));
+    }
+    let mut words = vec![0u64; slots * SLOT_WORDS];
+    for i in 0..slots {
+        words[order[i] * SLOT_WORDS] = order[(i + 1) % slots] as u64;
+    }
+    words
+}
+
+/// 执行指定类型的空转迭代
+pub struct BusyLooper {
+    kind: BusyLoop,
+    x: f32,
+    y: f64,
+    sum: u64,
+    matrix: [[f32; 4]; 4],
+    hash: [u32; 8],
+    /// memory-bound 的指针环，第一次迭代时才分配
+    chain: Vec<u64>,
+    /// memory-bound 当前所在的槽，或 branch 当前遍历到的位置
+    slot: usize,
+    cache_size: usize,
+    /// branch 遍历的随机数组，第一次迭代时才生成
+    branch_data: Vec<u8>,
+    hardened: bool,
+}
+
+impl BusyLooper {
+    pub fn new(kind: BusyLoop) -> Self {
+        let mut matrix = [[0.0; 4]; 4];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        BusyLooper {
+            kind,
+            x: 0.0001,
+            y: 0.0001,
+            sum: 0,
+            matrix,
+            hash: SHA256_INIT,
+            chain: Vec::new(),
+            slot: 0,
+            cache_size: DEFAULT_CACHE_SIZE,
+            branch_data: Vec::new(),
+            hardened: false,
+        }
+    }
+
+    /// 设置 memory-bound 缓冲区的大小，须在第一次迭代前调用
+    pub fn set_cache_size(&mut self, bytes: usize) {
+        self.cache_size = bytes;
+    }
+
+    /// 改用每次迭代都经过 volatile 读写的三角函数链，编译器和CPU都无法省略
+    pub fn harden(&mut self) {
+        self.hardened = true;
+    }
+
+    /// 执行 `iterations` 次迭代
+    pub fn run(&mut self, iterations: u64) {
+        if self.hardened {
+            for _ in 0..iterations {
+                // SAFETY: self.x 是有效且对齐的字段
+                unsafe {
+                    let x = std::ptr::read_volatile(&self.x);
+                    std::ptr::write_volatile(&mut self.x, x.sin().cos().sin().cos());
+                }
+            }
+            return;
+        }
+        match self.kind {
+            BusyLoop::Math => {
+                for _ in 0..iterations {
+                    // 执行一些计算密集型操作，black_box 防止编译器优化掉这个计算
+                    self.x = std::hint::black_box(self.x.sin().cos().sin().cos());
+                }
+            }
+            BusyLoop::Nop => {
+                for _ in 0..iterations {
+                    for _ in 0..SLIDE_LEN {
+                        // SAFETY: nop 不访问内存也不修改寄存器
+                        unsafe { std::arch::asm!("nop", options(nomem, nostack, preserves_flags)) };
+                    }
+                }
+            }
+            BusyLoop::Volatile => {
+                for _ in 0..iterations {
+                    for _ in 0..SLIDE_LEN {
+                        // SAFETY: VOLATILE_SOURCE 是只读的静态变量，引用始终有效且对齐
+                        self.sum = self.sum.wrapping_add(unsafe { std::ptr::read_volatile(&VOLATILE_SOURCE) });
+                    }
+                }
+                std::hint::black_box(self.sum);
+            }
+            BusyLoop::Matmul => {
+                for _ in 0..iterations {
+                    let mut product = [[0.0f32; 4]; 4];
+                    for (row, lhs) in product.iter_mut().zip(&self.matrix) {
+                        for (j, cell) in row.iter_mut().enumerate() {
+                            *cell = (0..4).map(|k| lhs[k] * MATMUL_ROTATION[k][j]).sum();
+                        }
+                    }
+                    self.matrix = std::hint::black_box(product);
+                }
+            }
+            BusyLoop::Sha256 => {
+                for _ in 0..iterations {
+                    // 数据块由上一轮的哈希值和计数构成，每一轮都依赖上一轮的结果
+                    self.sum = self.sum.wrapping_add(1);
+                    let mut block = [0u32; 16];
+                    block[..8].copy_from_slice(&self.hash);
+                    block[8] = self.sum as u32;
+                    sha256_compress(&mut self.hash, &block);
+                }
+                std::hint::black_box(self.hash);
+            }
+            BusyLoop::Fp64 => {
+                for _ in 0..iterations {
+                    // exp 和 ln 互为反函数，atan 把结果限制在 (-π/2, π/2)，反复迭代数值不会发散
+                    self.y = std::hint::black_box(self.y.sin().exp().ln().cos().tan().atan());
+                }
+            }
+            BusyLoop::Integer => {
+                // 模数经过 black_box，编译器无法把取模换成乘法
+                let modulus = std::hint::black_box(INTEGER_MODULUS);
+                for _ in 0..iterations {
+                    let x = self.sum.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407) % modulus;
+                    self.sum = std::hint::black_box((x ^ x.rotate_left(17)).wrapping_add(u64::from(x.count_ones())));
+                }
+            }
+            BusyLoop::MemoryBound => {
+                if self.chain.is_empty() {
+                    self.chain = build_chain(self.cache_size);
+                }
+                // 每次迭代只访问一次：缓存未命中时单次访问的耗时已与 math 的一次迭代相当
+                for _ in 0..iterations {
+                    self.slot = self.chain[self.slot * SLOT_WORDS] as usize;
+                }
+                std::hint::black_box(self.slot);
+            }
+            BusyLoop::Branch => {
+                if self.branch_data.is_empty() {
+                    let mut rng = seed::thread_rng(seed::BUSY_LOOP_STREAM);
+                    self.branch_data = (0..BRANCH_DATA_LEN).map(|_| rng.gen_range(0..=u8::MAX)).collect();
+                }
+                for _ in 0..iterations {
+                    let start = self.slot;
+                    for &value in &self.branch_data[start..start + SLIDE_LEN] {
+                        // black_box 阻止编译器把分支改写成条件传送
+                        if std::hint::black_box(value) < 128 {
+                            self.sum = self.sum.wrapping_add(u64::from(value));
+                        } else {
+                            self.sum = self.sum.rotate_left(3) ^ u64::from(value);
+                        }
+                    }
+                    self.slot = (start + SLIDE_LEN) % BRANCH_DATA_LEN;
+                }
+                std::hint::black_box(self.sum);
+            }
+        }
+    }
+}
+
+/// 解析 `--cpu-work-mix` 的一项，例如 "matmul:50%"（百分号可省略）
+pub fn parse_mix_entry(s: &str) -> Result<(BusyLoop, u8), String> {
+    let (kind, percent) = s.split_once(':').ok_or_else(|| tr!("work-mix-invalid", value = s))?;
+    let kind = BusyLoop::from_str(kind.trim(), true).map_err(|_| tr!("work-mix-unknown", value = kind.trim()))?;
+    let percent = percent.trim();
+    let percent = percent.strip_suffix('%').unwrap_or(percent);
+    match percent.trim().parse::<u8>() {
+        Ok(percent @ 1..=100) => Ok((kind, percent)),
+        _ => Err(tr!("work-mix-invalid", value = s)),
+    }
+}
+
+/// 按比例把 `threads` 个线程分配给各个计算类型，返回每个线程的类型
+///
+/// 各项按比例向下取整，余下的线程全部分给比例最高的一项（并列时取第一项）。
+/// 调用前应检查比例之和为100。
+pub fn distribute_mix(mix: &[(BusyLoop, u8)], threads: usize) -> Vec<BusyLoop> {
+    let mut counts: Vec<usize> = mix.iter().map(|&(_, percent)| threads * usize::from(percent) / 100).collect();
+    let remainder = threads - counts.iter().sum::<usize>();
+    if let Some(top) = (0..mix.len()).rev().max_by_key(|&i| mix[i].1) {
+        counts[top] += remainder;
+    }
+    mix.iter().zip(counts).flat_map(|(&(kind, _), count)| std::iter::repeat_n(kind, count)).collect()
+}
+
+/// 迭代速率超过基准的多少倍时认为空转循环已被优化掉
+const VERIFY_FACTOR: f64 = 5.0;
+
+/// 检查迭代速率的间隔
+const VERIFY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 在当前线程上执行 `window` 时长的空转迭代，返回每秒迭代次数作为基准
+pub fn calibrate(kind: BusyLoop, window: Duration, burst: u64) -> f64 {
+    let mut looper = BusyLooper::new(kind);
+    let start = Instant::now();
+    let mut iterations = 0u64;
+    while start.elapsed() < window {
+        looper.run(burst);
+        iterations += burst;
+    }
+    iterations as f64 / start.elapsed().as_secs_f64()
+}
+
+/// `--cpu-work-verify`：累计工作线程实际忙碌的时间和迭代次数，定期与启动时的基准比较
+pub struct WorkVerifier {
+    baseline: f64,
+    iterations: u64,
+    busy: Duration,
+    since: Instant,
+}
+
+impl WorkVerifier {
+    pub fn new(baseline: f64) -> Self {
+        WorkVerifier { baseline, iterations: 0, busy: Duration::ZERO, since: Instant::now() }
+    }
+
+    /// 记录一轮迭代；每个检查间隔结束时，若速率超过基准的5倍则返回该速率
+    pub fn record(&mut self, iterations: u64, busy: Duration) -> Option<f64> {
+        self.iterations += iterations;
+        self.busy += busy;
+        if self.since.elapsed() < VERIFY_INTERVAL || self.busy.is_zero() {
+            return None;
+        }
+        let rate = self.iterations as f64 / self.busy.as_secs_f64();
+        self.iterations = 0;
+        self.busy = Duration::ZERO;
+        self.since = Instant::now();
+        (rate > self.baseline * VERIFY_FACTOR).then_some(rate)
+    }
+
+    pub fn baseline(&self) -> f64 {
+        self.baseline
+    }
+}
@@ -0,0 +1,112 @@
+//! 为重要进程让路（仅Linux）：发现名称匹配且优先级高于下限的进程时临时调高本进程的 nice 值
+//!
+//! Linux 的 nice 值是线程属性，这里逐个修改 `/proc/self/task` 下的全部线程。
+//! 匹配的进程全部退出后恢复原来的 nice 值；恢复到更高的优先级需要root权限或 `CAP_SYS_NICE`。
+
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use regex::Regex;
+
+use crate::growth;
+use crate::state::LoadState;
+use crate::timeutil;
+
+/// 检查进程列表的间隔
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 让路期间使用的 nice 值（最低优先级）
+const YIELD_NICE: i32 = 19;
+
+#[derive(Clone, Debug)]
+pub struct YieldSettings {
+    /// 匹配进程名的正则表达式
+    pub pattern: Regex,
+    /// nice 值低于该值（优先级更高）的匹配进程才触发让路
+    pub floor: i32,
+}
+
+/// 读取 `/proc/<pid>/stat` 中的进程名和 nice 值
+fn name_and_nice(pid: &str) -> Option<(String, i32)> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // 进程名在括号中且可能含空格，从最后一个右括号之后开始数：state 为第3个字段，nice 为第19个
+    let (head, tail) = stat.rsplit_once(')')?;
+    let name = head.split_once('(')?.1.to_string();
+    Some((name, tail.split_whitespace().nth(16)?.parse().ok()?))
+}
+
+/// 把本进程全部线程的 nice 值设为 `nice`
+fn renice_all_threads(nice: i32) -> std::io::Result<()> {
+    for entry in std::fs::read_dir("/proc/self/task")? {
+        let Some(tid) = entry?.file_name().to_str().and_then(|name| name.parse::<libc::id_t>().ok()) else {
+            continue;
+        };
+        // SAFETY: setpriority 只修改目标线程的调度属性；线程可能已经退出，此时返回 ESRCH
+        if unsafe { libc::setpriority(libc::PRIO_PROCESS, tid, nice) } != 0 {
+            let e = std::io::Error::last_os_error();
+            if e.raw_os_error() != Some(libc::ESRCH) {
+                return Err(e);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 第一个名称匹配且 nice 值低于下限的其他进程
+///
+/// 每次都重新读取 `/proc`：进程 exec 后PID不变但名称改变，缓存的进程表会给出旧名称。
+fn find_priority_process(settings: &YieldSettings) -> Option<(String, u32, i32)> {
+    let own = std::process::id();
+    std::fs::read_dir("/proc").ok()?.flatten().find_map(|entry| {
+        let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+        if pid == own {
+            return None;
+        }
+        let (name, nice) = name_and_nice(&pid.to_string())?;
+        (nice < settings.floor && settings.pattern.is_match(&name)).then_some((name, pid, nice))
+    })
+}
+
+/// 启动看门狗线程，负载停止时恢复原来的 nice 值
+pub fn spawn_yield_watchdog(settings: YieldSettings, state: Arc<LoadState>) -> JoinHandle<()> {
+    let original = name_and_nice("self").map_or(0, |(_, nice)| nice);
+    info!("{}", tr!("yield-watching", pattern = settings.pattern.as_str(), floor = settings.floor));
+    thread::spawn(move || {
+        let mut yielded = false;
+        while state.is_running() {
+            match (find_priority_process(&settings), yielded) {
+                (Some((name, pid, nice)), false) => match renice_all_threads(YIELD_NICE) {
+                    Ok(()) => {
+                        info!(
+                            "[{}] {}",
+                            timeutil::now_rfc3339(),
+                            tr!("yield-started", name = name, pid = pid, nice = nice, own = YIELD_NICE)
+                        );
+                        yielded = true;
+                    }
+                    Err(e) => {
+                        warn!("{}", tr!("yield-renice-failed", nice = YIELD_NICE, error = e));
+                        return;
+                    }
+                },
+                (None, true) => {
+                    restore(original);
+                    yielded = false;
+                }
+                _ => {}
+            }
+            growth::sleep_while_running(&state, CHECK_INTERVAL);
+        }
+        if yielded {
+            restore(original);
+        }
+    })
+}
+
+fn restore(original: i32) {
+    match renice_all_threads(original) {
+        Ok(()) => info!("[{}] {}", timeutil::now_rfc3339(), tr!("yield-restored", nice = original)),
+        Err(e) => warn!("{}", tr!("yield-renice-failed", nice = original, error = e)),
+    }
+}
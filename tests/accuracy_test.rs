@@ -0,0 +1,112 @@
+//! CPU负载精度测试：以指定的目标使用率在1个核心上运行负载，检查实际使用率是否接近目标
+//!
+//! 除命令行进程外，也通过库接口在测试进程内启动负载。每个用例需要实际满载运行十余秒，默认忽略，通过 `cargo test -- --ignored` 运行。
+
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+use sysinfo::{Pid, PidExt, ProcessExt, System, SystemExt};
+
+/// 允许的误差（百分点）
+const TOLERANCE: f32 = 15.0;
+
+/// 等待负载进入稳定状态的时间
+const WARMUP: Duration = Duration::from_secs(2);
+
+/// 测量窗口
+const MEASURE: Duration = Duration::from_secs(10);
+
+/// 各用例依次运行，避免同时运行的负载互相争抢CPU
+static SERIAL: Mutex<()> = Mutex::new(());
+
+/// 负载进程，离开作用域时终止
+struct Load {
+    child: Child,
+    _pid_dir: tempfile::TempDir,
+}
+
+impl Load {
+    fn start(percent: u8) -> Self {
+        // 每个进程使用独立的临时目录存放PID文件，不受本机已运行实例的影响
+        let pid_dir = tempfile::tempdir().expect("无法创建临时目录");
+        let child = Command::new(env!("CARGO_BIN_EXE_enhance-cpu-memory"))
+            .args(["-c", "1", "--cpu-base", &percent.to_string(), "--quiet"])
+            .env("TMPDIR", pid_dir.path())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("无法启动负载进程");
+        Load { child, _pid_dir: pid_dir }
+    }
+
+    /// 测量窗口内进程的CPU使用率（100 表示占满一个核心）
+    fn measure_cpu_usage(&self) -> f32 {
+        let pid = Pid::from_u32(self.child.id());
+        let mut sys = System::new();
+        thread::sleep(WARMUP);
+        sys.refresh_process(pid);
+        thread::sleep(MEASURE);
+        assert!(sys.refresh_process(pid), "负载进程已提前退出");
+        sys.process(pid).expect("找不到负载进程").cpu_usage()
+    }
+}
+
+impl Drop for Load {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn assert_accuracy(percent: u8) {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let load = Load::start(percent);
+    let usage = load.measure_cpu_usage();
+    assert!(
+        (usage - f32::from(percent)).abs() <= TOLERANCE,
+        "目标 {}%，实际 {:.1}%，超出 ±{} 个百分点",
+        percent,
+        usage,
+        TOLERANCE
+    );
+}
+
+#[test]
+#[ignore = "需要实际运行CPU负载"]
+fn duty_cycle_50_percent() {
+    assert_accuracy(50);
+}
+
+#[test]
+#[ignore = "需要实际运行CPU负载"]
+fn duty_cycle_80_percent() {
+    assert_accuracy(80);
+}
+
+#[test]
+#[ignore = "需要实际运行CPU负载"]
+fn duty_cycle_20_percent() {
+    assert_accuracy(20);
+}
+
+/// 通过库接口在测试进程内启动负载，测量本进程的使用率
+#[test]
+#[ignore = "需要实际运行CPU负载"]
+fn library_duty_cycle_50_percent() {
+    let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    let config = enhance_cpu_memory::LoadConfig::new().cores(1).unwrap().cpu_percent(50.0).unwrap().build().unwrap();
+    let handle = config.start().expect("无法启动负载");
+    let pid = Pid::from_u32(std::process::id());
+    let mut sys = System::new();
+    thread::sleep(WARMUP);
+    sys.refresh_process(pid);
+    thread::sleep(MEASURE);
+    sys.refresh_process(pid);
+    let usage = sys.process(pid).expect("找不到测试进程").cpu_usage();
+    assert!(handle.is_running());
+    let stats = handle.stop();
+    assert!((usage - 50.0).abs() <= TOLERANCE, "目标 50%，实际 {:.1}%，超出 ±{} 个百分点", usage, TOLERANCE);
+    assert!(stats.total_cpu_seconds > 0.0, "{:?}", stats);
+}
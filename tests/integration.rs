@@ -0,0 +1,426 @@
+//! 端到端测试：以子进程方式运行程序，检查启动、停止、参数解析和退出码
+//!
+//! 每个测试使用独立的临时目录作为 TMPDIR，PID 文件互不干扰，可以并行运行。
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use assert_cmd::cargo::CommandCargoExt;
+use tempfile::TempDir;
+
+/// 以 `tmp` 为 PID 文件目录的命令，固定使用中文输出
+fn command(tmp: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("enhance-cpu-memory").expect("找不到可执行文件");
+    cmd.env("TMPDIR", tmp.path()).env("LANG", "zh_CN.UTF-8").env_remove("LC_ALL").env_remove("LC_MESSAGES");
+    cmd
+}
+
+fn pid_file(tmp: &TempDir) -> PathBuf {
+    tmp.path().join("enhancecpu-default.pid")
+}
+
+/// 在 `timeout` 内等待条件成立
+fn wait_until(timeout: Duration, mut condition: impl FnMut() -> bool) -> bool {
+    let start = Instant::now();
+    while start.elapsed() < timeout {
+        if condition() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    condition()
+}
+
+/// 测试结束时确保子进程被回收
+struct Guard(Child);
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+#[test]
+fn start_then_stop() {
+    let tmp = tempfile::tempdir().unwrap();
+    let child = command(&tmp)
+        .args(["-c", "1", "--cpu-base", "10"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let mut child = Guard(child);
+
+    let pid_file = pid_file(&tmp);
+    assert!(wait_until(Duration::from_secs(1), || pid_file.exists()), "1秒内未生成PID文件");
+    let pid: u32 = std::fs::read_to_string(&pid_file).unwrap().trim().parse().unwrap();
+    assert_eq!(pid, child.0.id());
+
+    let output = command(&tmp).arg("stop").output().unwrap();
+    assert!(output.status.success(), "stop 失败: {:?}", output);
+
+    let mut status = None;
+    assert!(
+        wait_until(Duration::from_secs(10), || {
+            status = child.0.try_wait().unwrap();
+            status.is_some()
+        }),
+        "收到停止信号后进程未退出"
+    );
+    assert!(status.unwrap().success(), "进程未正常退出: {:?}", status);
+    assert!(!pid_file.exists(), "进程退出后PID文件仍然存在");
+}
+
+#[test]
+fn invalid_memory_exits_with_1() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = command(&tmp).args(["-c", "1", "-m", "abc"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    assert!(!pid_file(&tmp).exists(), "参数错误时不应留下PID文件");
+}
+
+#[test]
+fn cpu_base_rejects_zero_and_clamps_above_100() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = command(&tmp).args(["-c", "1", "--cpu-base", "0"]).output().unwrap();
+    assert!(!output.status.success(), "{:?}", output);
+    let output = command(&tmp).args(["-c", "1", "--cpu-base", "150", "--duration", "200ms"]).output().unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("按100处理"), "{:?}", output);
+}
+
+#[test]
+fn status_prints_usage() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = command(&tmp).arg("status").output().unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).expect("输出不是有效的 UTF-8");
+    assert!(stdout.contains("CPU"), "缺少CPU信息: {}", stdout);
+    assert!(stdout.contains("内存"), "缺少内存信息: {}", stdout);
+    assert!(stdout.contains("主机名"), "缺少主机信息: {}", stdout);
+}
+
+#[test]
+fn stop_without_instance_exits_with_4() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = command(&tmp).arg("stop").output().unwrap();
+    assert_eq!(output.status.code(), Some(4), "{:?}", output);
+}
+
+#[test]
+fn start_with_running_instance_exits_with_3() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(pid_file(&tmp), std::process::id().to_string()).unwrap();
+    let output = command(&tmp).args(["-c", "1"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(3), "{:?}", output);
+    // 不能删除另一个实例的PID文件
+    assert!(pid_file(&tmp).exists());
+}
+
+#[test]
+fn stop_with_corrupted_pid_file_exits_with_1() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(pid_file(&tmp), "not a pid").unwrap();
+    let output = command(&tmp).arg("stop").output().unwrap();
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("已损坏"), "{}", stderr);
+}
+
+#[test]
+fn named_instances_run_side_by_side() {
+    let tmp = tempfile::tempdir().unwrap();
+    let spawn = |args: &[&str]| {
+        Guard(command(&tmp).args(args).stdout(Stdio::null()).stderr(Stdio::null()).spawn().unwrap())
+    };
+    let _default = spawn(&["-c", "1", "--cpu-base", "10"]);
+    let mut other = spawn(&["-c", "1", "--cpu-base", "10", "--instance-name", "other"]);
+    let other_pid_file = tmp.path().join("enhancecpu-other.pid");
+    assert!(
+        wait_until(Duration::from_secs(1), || pid_file(&tmp).exists() && other_pid_file.exists()),
+        "1秒内未生成两个实例的PID文件"
+    );
+
+    let output = command(&tmp).arg("list").output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.matches("运行中").count(), 2, "{}", stdout);
+    assert!(stdout.contains("default") && stdout.contains("other"), "{}", stdout);
+
+    let output = command(&tmp).args(["stop", "--instance-name", "other"]).output().unwrap();
+    assert!(output.status.success(), "stop 失败: {:?}", output);
+    assert!(
+        wait_until(Duration::from_secs(10), || other.0.try_wait().unwrap().is_some()),
+        "收到停止信号后进程未退出"
+    );
+    assert!(!other_pid_file.exists());
+    assert!(pid_file(&tmp).exists(), "不应停止其他实例");
+}
+
+#[test]
+fn reset_stops_instances_and_removes_pid_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    let child = command(&tmp)
+        .args(["-c", "1", "--cpu-base", "10"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let mut child = Guard(child);
+    assert!(wait_until(Duration::from_secs(1), || pid_file(&tmp).exists()), "1秒内未生成PID文件");
+    let stale = tmp.path().join("enhancecpu-stale.pid");
+    std::fs::write(&stale, "not-a-pid").unwrap();
+
+    let output = command(&tmp).arg("reset").output().unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    assert!(wait_until(Duration::from_secs(5), || child.0.try_wait().unwrap().is_some()), "reset 后进程未退出");
+    assert!(!pid_file(&tmp).exists());
+    assert!(!stale.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn background_start_returns_after_ready() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = command(&tmp).args(["start", "-b", "-c", "1", "--cpu-base", "10"]).output().unwrap();
+    assert!(output.status.success(), "{:?}", output);
+
+    // 父进程返回时后台进程已经就绪，PID文件指向仍在运行的后台进程
+    let pid: i32 = std::fs::read_to_string(pid_file(&tmp)).unwrap().trim().parse().unwrap();
+    // SAFETY: 信号0只检查进程是否存在
+    assert_eq!(unsafe { libc::kill(pid, 0) }, 0, "后台进程 {} 不存在", pid);
+
+    // 后台进程的输出写入默认的日志文件，父进程告知其路径
+    let log = tmp.path().join("enhancecpu-default.log");
+    assert!(String::from_utf8_lossy(&output.stdout).contains(&log.display().to_string()), "{:?}", output);
+
+    // stop 成功时已确认后台进程退出
+    let output = command(&tmp).arg("stop").output().unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    assert!(!pid_file(&tmp).exists());
+    let log = std::fs::read_to_string(&log).unwrap();
+    assert!(log.contains("启动工作线程 0"), "{}", log);
+}
+
+#[test]
+fn stop_with_stale_pid_file_warns_and_cleans_up() {
+    let tmp = tempfile::tempdir().unwrap();
+    // 超出 pid_max 的PID不可能存在
+    std::fs::write(pid_file(&tmp), "999999999").unwrap();
+    let output = command(&tmp).arg("stop").output().unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    assert!(String::from_utf8(output.stdout).unwrap().contains("已不存在"));
+    assert!(!pid_file(&tmp).exists());
+}
+
+#[test]
+fn abort_on_oom_exits_with_2() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = command(&tmp)
+        .args(["-c", "1", "-m", "64M", "--abort-on-oom", "--alloc-fail-rate", "1.0", "--min-fail-size", "1M"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2), "{:?}", output);
+    assert!(!pid_file(&tmp).exists());
+
+    // 未指定时直接 abort，同样不留下PID文件
+    let output = command(&tmp)
+        .args(["-c", "1", "-m", "64M", "--alloc-fail-rate", "1.0", "--min-fail-size", "1M"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success() && output.status.code() != Some(2), "{:?}", output);
+    assert!(!pid_file(&tmp).exists());
+}
+
+#[test]
+fn status_respects_lang() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = command(&tmp).args(["status", "--lang", "en-US"]).output().unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).expect("输出不是有效的 UTF-8");
+    assert!(stdout.contains("Memory:"), "缺少英文内存信息: {}", stdout);
+
+    let output = command(&tmp).arg("stop").env("LANG", "en_US.UTF-8").output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("No running CPU load process found"), "未按 LANG 选择英文: {}", stdout);
+}
+
+/// `.ftl` 文件中定义的消息ID
+fn message_ids(locale: &str) -> BTreeSet<String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("locales").join(format!("{}.ftl", locale));
+    std::fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .filter(|line| line.starts_with(|c: char| c.is_ascii_lowercase()))
+        .filter_map(|line| line.split_once(" =").map(|(id, _)| id.to_string()))
+        .collect()
+}
+
+#[test]
+fn locales_define_every_message() {
+    let zh = message_ids("zh-CN");
+    let en = message_ids("en-US");
+    assert_eq!(zh, en, "zh-CN.ftl 与 en-US.ftl 的消息ID不一致");
+
+    let pattern = regex::Regex::new(r#"tr!\(\s*"([a-z0-9-]+)""#).unwrap();
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    for entry in std::fs::read_dir(src).unwrap() {
+        let path = entry.unwrap().path();
+        let code = std::fs::read_to_string(&path).unwrap();
+        for id in pattern.captures_iter(&code).map(|c| c[1].to_string()) {
+            assert!(zh.contains(&id), "{} 使用了未定义的消息ID {}", path.display(), id);
+        }
+    }
+}
+
+#[test]
+fn compress_pid_file_writes_state() {
+    let tmp = tempfile::tempdir().unwrap();
+    let child = command(&tmp)
+        .args(["-c", "1", "--cpu-base", "10", "--compress-pid-file"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let mut child = Guard(child);
+    let state_file = tmp.path().join("enhancecpu-default.state");
+    assert!(wait_until(Duration::from_secs(2), || state_file.exists()), "2秒内未生成状态文件");
+
+    let output = command(&tmp).args(["status", "--json"]).output().unwrap();
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(status["instance"]["pid"], child.0.id(), "状态中缺少实例信息: {}", status);
+    assert_eq!(status["instance"]["profile"], "spin", "{}", status);
+
+    let output = command(&tmp).arg("list").output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("目标CPU使用率 10%"), "list 未显示状态文件中的参数: {}", stdout);
+
+    let output = command(&tmp).arg("stop").output().unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    assert!(wait_until(Duration::from_secs(10), || child.0.try_wait().unwrap().is_some()), "进程未退出");
+    assert!(!state_file.exists(), "进程退出后状态文件仍然存在");
+}
+
+#[test]
+fn phase_cores_run_every_phase_then_exit() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = command(&tmp)
+        .args(["--cpu-base", "10", "--phase-cores", "1,0,1", "--phase-durations", "300ms,300ms,300ms"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let log = String::from_utf8_lossy(&output.stdout) + String::from_utf8_lossy(&output.stderr);
+    for phase in ["阶段 1/3: 启用 1 个", "阶段 2/3: 启用 0 个", "阶段 3/3: 启用 1 个", "已完成全部 3 个阶段"] {
+        assert!(log.contains(phase), "缺少阶段日志 {}: {}", phase, log);
+    }
+    assert!(!pid_file(&tmp).exists());
+}
+
+#[test]
+fn validate_profile_reports_lines_and_exit_code() {
+    let tmp = tempfile::tempdir().unwrap();
+    let good = tmp.path().join("good.toml");
+    std::fs::write(&good, "[[phases]]\nduration_secs = 60\ntarget_percent = 80\nmemory = \"1G\"\n").unwrap();
+    let output = command(&tmp).arg("validate-profile").arg(&good).output().unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("峰值CPU使用率: 80%"), "{}", stdout);
+
+    let bad = tmp.path().join("bad.toml");
+    std::fs::write(&bad, "[[phases]]\nduration_secs = 60\ntarget_percent = 150\nworkload = \"nope\"\n").unwrap();
+    let output = command(&tmp).arg("validate-profile").arg(&bad).output().unwrap();
+    assert_eq!(output.status.code(), Some(1), "{:?}", output);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("bad.toml:3:"), "{}", stderr);
+    assert!(stderr.contains("bad.toml:4:"), "{}", stderr);
+}
+
+#[test]
+fn profile_runs_every_phase_then_exit() {
+    let tmp = tempfile::tempdir().unwrap();
+    let profile = tmp.path().join("profile.toml");
+    std::fs::write(
+        &profile,
+        "[[phases]]\nduration_secs = 1\ncores = 1\ntarget_percent = 10\nmemory = \"16M\"\n\n\
+         [[phases]]\nduration_secs = 1\ncores = 1\ntarget_percent = 20\n",
+    )
+    .unwrap();
+    let output = command(&tmp).arg("--profile").arg(&profile).output().unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let log = String::from_utf8_lossy(&output.stdout) + String::from_utf8_lossy(&output.stderr);
+    for line in ["目标CPU使用率 10%，占用内存 16.0 MB", "目标CPU使用率 20%，占用内存 0", "已完成全部 2 个阶段"] {
+        assert!(log.contains(line), "缺少阶段日志 {}: {}", line, log);
+    }
+    assert!(!pid_file(&tmp).exists());
+}
+
+#[test]
+fn cpu_realtime_log_writes_one_line_per_second() {
+    let tmp = tempfile::tempdir().unwrap();
+    let log = tmp.path().join("cpu.csv");
+    let output = command(&tmp)
+        .args(["-c", "1", "--cpu-base", "10", "--duration", "2500ms", "--cpu-realtime-log"])
+        .arg(&log)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let lines: Vec<String> = std::fs::read_to_string(&log).unwrap().lines().map(str::to_string).collect();
+    assert!((2..=3).contains(&lines.len()), "{:?}", lines);
+    for line in &lines {
+        let (timestamp, usage) = line.split_once(',').expect("缺少逗号");
+        timestamp.parse::<u64>().unwrap();
+        usage.parse::<f32>().unwrap();
+    }
+}
+
+#[test]
+fn benchmark_fork_spawn_prints_json() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = command(&tmp)
+        .args(["benchmark", "fork-spawn", "--count", "20", "--method", "thread"])
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("\"method\":\"thread\""), "{}", stdout);
+    assert!(stdout.contains("\"count\":20"), "{}", stdout);
+    assert!(stdout.contains("\"p99_us\":"), "{}", stdout);
+}
+
+#[test]
+fn io_stress_removes_temp_files_on_exit() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let output = command(&tmp)
+        .args(["io-stress", "--threads", "2", "--file-size", "1M", "--pattern", "rand-read", "-c", "1", "--cpu-base", "10", "--duration", "1"])
+        .arg("-p")
+        .arg(dir.path())
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{:?}", output);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("I/O压力汇总"), "{}", stdout);
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn library_load_handle_stops_and_reports_stats() {
+    let config = enhance_cpu_memory::LoadConfig::new()
+        .cores(1)
+        .unwrap()
+        .cpu_percent(50.0)
+        .unwrap()
+        .memory(bytesize::ByteSize::mib(8))
+        .unwrap()
+        .build()
+        .unwrap();
+    let handle = config.start().unwrap();
+    thread::sleep(Duration::from_millis(500));
+    assert!(handle.is_running());
+    let stats = handle.stop();
+    assert!(stats.total_cpu_seconds > 0.0, "{:?}", stats);
+    assert_eq!(stats.peak_memory_bytes, 8 << 20);
+}